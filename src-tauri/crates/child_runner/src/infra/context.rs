@@ -1,4 +1,5 @@
 pub(crate) mod child_process;
+pub(crate) mod debug_control;
 mod init_error;
 mod policy_execution;
 mod running_status;
@@ -0,0 +1,185 @@
+//! Step-through debugger state shared by the IPC handler and the script executor.
+
+use ad_kernel::ids::StepId;
+use runner_protocol::message::{DebugPauseReason, DebugResumeMode};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+use tokio::sync::watch;
+
+/// 暂停判定方式，`depth` 为发出单步命令时暂停所在的步骤深度
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DebugStepMode {
+    Run,
+    Pause,
+    StepInto,
+    StepOver { depth: u32 },
+    StepOut { depth: u32 },
+}
+
+#[derive(Debug)]
+struct DebugState {
+    breakpoints: HashSet<StepId>,
+    mode: DebugStepMode,
+    paused_depth: Option<u32>,
+}
+
+static DEBUG_STATE: LazyLock<Mutex<DebugState>> = LazyLock::new(|| {
+    Mutex::new(DebugState {
+        breakpoints: HashSet::new(),
+        mode: DebugStepMode::Run,
+        paused_depth: None,
+    })
+});
+/// 没有断点且处于 Run 模式时为 false，执行器据此跳过加锁
+static DEBUG_ACTIVE: AtomicBool = AtomicBool::new(false);
+static DEBUG_RESUME: LazyLock<watch::Sender<u64>> = LazyLock::new(|| watch::channel(0).0);
+
+fn refresh_active(state: &DebugState) {
+    let active = !state.breakpoints.is_empty() || state.mode != DebugStepMode::Run;
+    DEBUG_ACTIVE.store(active, Ordering::Release);
+}
+
+fn wake_paused() {
+    DEBUG_RESUME.send_modify(|generation| *generation = generation.wrapping_add(1));
+}
+
+pub(crate) fn debug_active() -> bool {
+    DEBUG_ACTIVE.load(Ordering::Acquire)
+}
+
+pub(crate) fn set_breakpoints(step_ids: Vec<StepId>) {
+    let mut state = DEBUG_STATE.lock().unwrap();
+    state.breakpoints = step_ids.into_iter().collect();
+    refresh_active(&state);
+}
+
+pub(crate) fn request_debug_pause() {
+    let mut state = DEBUG_STATE.lock().unwrap();
+    if state.paused_depth.is_none() {
+        state.mode = DebugStepMode::Pause;
+    }
+    refresh_active(&state);
+}
+
+/// 按单步方式继续；当前未暂停时忽略
+pub(crate) fn resume_debug(mode: DebugResumeMode) -> bool {
+    let mut state = DEBUG_STATE.lock().unwrap();
+    let Some(depth) = state.paused_depth.take() else {
+        return false;
+    };
+    state.mode = match mode {
+        DebugResumeMode::Continue => DebugStepMode::Run,
+        DebugResumeMode::StepInto => DebugStepMode::StepInto,
+        DebugResumeMode::StepOver => DebugStepMode::StepOver { depth },
+        DebugResumeMode::StepOut => DebugStepMode::StepOut { depth },
+    };
+    refresh_active(&state);
+    drop(state);
+    wake_paused();
+    true
+}
+
+/// 清空断点与单步状态，并放行正在等待的步骤
+pub(crate) fn reset_debug_session() {
+    let mut state = DEBUG_STATE.lock().unwrap();
+    state.breakpoints.clear();
+    state.mode = DebugStepMode::Run;
+    state.paused_depth = None;
+    refresh_active(&state);
+    drop(state);
+    wake_paused();
+}
+
+/// 判断即将进入的步骤是否需要暂停；需要时记录暂停深度并返回等待恢复的接收端
+pub(crate) fn check_debug_pause(
+    step_id: Option<StepId>,
+    depth: u32,
+) -> Option<(DebugPauseReason, watch::Receiver<u64>)> {
+    if !debug_active() {
+        return None;
+    }
+    let mut state = DEBUG_STATE.lock().unwrap();
+    let reason = pause_reason(&state, step_id, depth)?;
+    state.mode = DebugStepMode::Run;
+    state.paused_depth = Some(depth);
+    refresh_active(&state);
+    Some((reason, DEBUG_RESUME.subscribe()))
+}
+
+/// 判断同一深度的一批步骤中是否有步骤需要暂停，只读不改变单步状态
+pub(crate) fn any_debug_pause(
+    step_ids: impl IntoIterator<Item = Option<StepId>>,
+    depth: u32,
+) -> bool {
+    if !debug_active() {
+        return false;
+    }
+    let state = DEBUG_STATE.lock().unwrap();
+    step_ids
+        .into_iter()
+        .any(|step_id| pause_reason(&state, step_id, depth).is_some())
+}
+
+fn pause_reason(
+    state: &DebugState,
+    step_id: Option<StepId>,
+    depth: u32,
+) -> Option<DebugPauseReason> {
+    if state.paused_depth.is_some() {
+        return None;
+    }
+    let stepped = match state.mode {
+        DebugStepMode::Run => false,
+        DebugStepMode::Pause => return Some(DebugPauseReason::Pause),
+        DebugStepMode::StepInto => true,
+        DebugStepMode::StepOver { depth: paused } => depth <= paused,
+        DebugStepMode::StepOut { depth: paused } => depth < paused,
+    };
+    if stepped {
+        Some(DebugPauseReason::Step)
+    } else if step_id.is_some_and(|id| state.breakpoints.contains(&id)) {
+        Some(DebugPauseReason::Breakpoint)
+    } else {
+        None
+    }
+}
+
+/// 停止执行时放弃暂停，不改变断点
+pub(crate) fn abandon_debug_pause() {
+    let mut state = DEBUG_STATE.lock().unwrap();
+    state.paused_depth = None;
+    refresh_active(&state);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(mode: DebugStepMode, breakpoints: &[StepId]) -> DebugState {
+        DebugState {
+            breakpoints: breakpoints.iter().copied().collect(),
+            mode,
+            paused_depth: None,
+        }
+    }
+
+    #[test]
+    fn step_modes_pause_by_depth() {
+        let step = StepId::new_v7();
+        assert_eq!(
+            pause_reason(&state(DebugStepMode::StepInto, &[]), None, 3),
+            Some(DebugPauseReason::Step)
+        );
+        let over = state(DebugStepMode::StepOver { depth: 2 }, &[]);
+        assert_eq!(pause_reason(&over, None, 3), None);
+        assert_eq!(pause_reason(&over, None, 2), Some(DebugPauseReason::Step));
+        let out = state(DebugStepMode::StepOut { depth: 2 }, &[step]);
+        assert_eq!(pause_reason(&out, None, 2), None);
+        assert_eq!(
+            pause_reason(&out, Some(step), 3),
+            Some(DebugPauseReason::Breakpoint)
+        );
+        assert_eq!(pause_reason(&out, None, 1), Some(DebugPauseReason::Step));
+    }
+}
@@ -7,7 +7,7 @@ use ad_kernel::ids::{
     AccountId, AssignmentId, DeviceId, ExecutionId, PolicyGroupId, PolicyId, PolicySetId, ScriptId,
     StepId, TaskId, TemplateId,
 };
-use domain_script::{PolicyProfile, ScriptInfo, ScriptTaskProfile};
+use domain_script::{PolicyProfile, RuntimeStoreEntry, ScriptInfo, ScriptTaskProfile};
 use domain_vision::SearchHit;
 use domain_vision::VisionSnapshot;
 use domain_vision::VisionTextCacheRuntimeConfig;
@@ -66,6 +66,9 @@ pub(crate) struct ExecutionState {
     /// 当前执行使用的模板变量快照。运行时自动切换 UI 变量时会更新它，供后续任务 hydrate 使用。
     pub template_values_json: Option<String>,
 
+    /// 跨运行持久化存储在本次执行作用域（设备、账号、脚本）内的缓存，供 Rhai 同步读取。
+    pub runtime_store: HashMap<String, RuntimeStoreEntry>,
+
     /// 策略状态
     pub policy_states: HashMap<PolicyId, PolicyState>,
 
@@ -104,6 +107,7 @@ impl ExecutionState {
             current_step_name: None,
             var_map: HashMap::new(),
             template_values_json: None,
+            runtime_store: HashMap::new(),
             policy_states: HashMap::new(),
             task_states: HashMap::new(),
             action_states: HashMap::new(),
@@ -1,3 +1,6 @@
+use crate::infra::context::debug_control::{
+    request_debug_pause, reset_debug_session, resume_debug, set_breakpoints,
+};
 use crate::infra::context::runtime_control::{
     RunningStatus, clear_stop_request, get_ipc_client, get_scheduler, request_stop_execution,
     set_running_status, trigger_cancel,
@@ -300,6 +303,7 @@ async fn handle_session_control(control: SessionControlMessage) {
         }
         SessionControlMessage::ClearSession => {
             clear_stop_request();
+            reset_debug_session();
             let cleared = clear_runtime_session().await;
             Log::info("[ child ] 清空当前 session");
             if let Some(scheduler) = get_scheduler() {
@@ -331,6 +335,21 @@ async fn handle_session_control(control: SessionControlMessage) {
             )
             .await;
         }
+        SessionControlMessage::SetBreakpoints { step_ids } => {
+            Log::info(&format!("[ child ] 更新调试断点，共 {} 个", step_ids.len()));
+            set_breakpoints(step_ids);
+        }
+        SessionControlMessage::DebugPause => {
+            Log::info("[ child ] 收到调试暂停命令，将在下一个步骤入口暂停");
+            request_debug_pause();
+        }
+        SessionControlMessage::DebugResume { mode } => {
+            if resume_debug(mode) {
+                Log::info(&format!("[ child ] 调试继续: {:?}", mode));
+            } else {
+                Log::warn("[ child ] 当前没有处于调试暂停的步骤，忽略继续命令");
+            }
+        }
     }
 }
 
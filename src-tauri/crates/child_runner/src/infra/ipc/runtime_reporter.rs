@@ -8,9 +8,9 @@ use ad_kernel::ids::{
 };
 use runner_protocol::message::{
     CaptureResultEvent, ConnectionStatusEvent, ConnectionStatusKind, IpcMessage, MessagePayload,
-    MessageType, RuntimeDispatchEvent, RuntimeDispatchPhase, RuntimeEventMessage,
-    RuntimeLifecycleEvent, RuntimeLifecyclePhase, RuntimeProgressEvent, RuntimeProgressPhase,
-    RuntimeScheduleEvent, RuntimeScheduleStatus,
+    MessageType, RuntimeDebugPausedEvent, RuntimeDispatchEvent, RuntimeDispatchPhase,
+    RuntimeEventMessage, RuntimeLifecycleEvent, RuntimeLifecyclePhase, RuntimeProgressEvent,
+    RuntimeProgressPhase, RuntimeScheduleEvent, RuntimeScheduleStatus,
 };
 
 fn current_session_id() -> Option<SessionId> {
@@ -51,6 +51,7 @@ fn runtime_event_level(event: &RuntimeEventMessage) -> LogLevel {
             RuntimeDispatchPhase::Failed => LogLevel::Warn,
             _ => LogLevel::Info,
         },
+        RuntimeEventMessage::DebugPaused(_) => LogLevel::Info,
     }
 }
 
@@ -252,3 +253,20 @@ pub(crate) fn emit_dispatch_event(
         "dispatch事件",
     );
 }
+
+/// 调试暂停事件不受日志级别过滤，否则主进程收不到暂停现场
+pub(crate) async fn emit_debug_paused_event_now(event: RuntimeDebugPausedEvent) -> bool {
+    let Some(client) = get_ipc_client() else {
+        return false;
+    };
+    let msg = IpcMessage::new(
+        *client.device_id,
+        MessageType::Status,
+        MessagePayload::RuntimeEvent(RuntimeEventMessage::DebugPaused(event)),
+    );
+    if let Err(error) = client.send_ensure(msg).await {
+        Log::warn(&format!("[ child ] 发送调试暂停事件失败: {}", error));
+        return false;
+    }
+    true
+}
@@ -6,7 +6,9 @@ use crate::infra::context::{
     PolicyActionKind, PolicyActionSource, PolicyActionTarget, PolicyActionTargetRole,
     PolicyActionTrace, PolicyExecutionResult, PolicyExecutionRound,
 };
-use crate::infra::ipc::runtime_reporter::{emit_lifecycle_event, emit_progress_event};
+use crate::infra::ipc::runtime_reporter::{
+    emit_debug_paused_event_now, emit_lifecycle_event, emit_progress_event,
+};
 use crate::infra::logging::LogLevel;
 use crate::infra::logging::log_trait::Log;
 use crate::infra::session::runtime_session::{
@@ -19,13 +21,16 @@ use ad_kernel::{
         ScriptId, StepId, TaskId, TemplateId,
     },
 };
-use domain_device::{DeviceOperation, TimeoutAction};
+use domain_device::{DeviceOperation, HumanizeProfile, HumanizeRng, TimeoutAction, TouchWaypoint};
+use domain_script::extract_number;
 use domain_script::{
     Action, ClickMode, ColorCompareMethod, ColorRgb, CompareOp, ConditionNode,
-    CurrentTaskCondition, DataHanding, ExecCountValue, ExecuteResult, FilterMode, FlowControl,
-    OcrTextMatchMode, PointF32, PointU16, PolicySetResultCompareOp, PolicySetResultField,
-    PrintSource, RegionPoint, ScriptError, StateStatus, StateTarget, Step, StepKind, SwipeMode,
-    SwipeTarget, TaskControl, VarValue, VisionCountTarget, VisionNode,
+    CurrentTaskCondition, DataHanding, ExecCountValue, ExecuteResult, ExtractedNumber, FilterMode,
+    FlowControl, GestureWaypoint, NumberField, NumberValue, OcrNumberSource, OcrTextMatchMode,
+    PointF32, PointU16, PolicySetResultCompareOp, PolicySetResultField, PrintSource, RegionPoint,
+    RhaiModule, RuntimeStoreEntry, ScriptError, StateStatus, StateTarget, Step, StepKind,
+    StoreExpiry, SwipeMode, SwipeTarget, TaskControl, VarValue, VisionCountTarget, VisionNode,
+    WaitUntilTarget,
 };
 use domain_script::{
     PolicyGroupPolicyLink, PolicyGroupProfile, PolicyProfile, PolicySetGroupLink, PolicySetProfile,
@@ -41,10 +46,14 @@ use domain_vision::{
 use image::RgbaImage;
 use infra_device_runtime::get_device_ctx;
 use infra_vision::OcrService;
+use infra_vision::{
+    TemplateMatchOptions, TemplateSearchRegion, load_img_from_base64, match_template_rgba,
+};
 use rhai::serde::{from_dynamic, to_dynamic};
 use rhai::{AST, Array, Dynamic, Engine, EvalAltResult, FLOAT, INT, Map, Scope};
 use runner_protocol::message::{
-    RunTarget, RuntimeLifecyclePhase, RuntimeProgressPhase, ScriptBundleSnapshot,
+    DebugPauseReason, RunTarget, RuntimeDebugPausedEvent, RuntimeLifecyclePhase,
+    RuntimeProgressPhase, ScriptBundleSnapshot,
 };
 use serde::Deserialize;
 use serde::Serialize;
@@ -67,6 +76,8 @@ include!("executor/action_target_resolver.rs");
 include!("executor/action_planner.rs");
 include!("executor/action_plan_click.rs");
 include!("executor/action_plan_swipe.rs");
+include!("executor/action_plan_gesture.rs");
+include!("executor/action_humanize.rs");
 include!("executor/sequence_operation_compiler.rs");
 include!("executor/action_trace.rs");
 include!("executor/action_dispatcher.rs");
@@ -86,10 +97,14 @@ include!("executor/flow_data_region.rs");
 include!("executor/flow_data.rs");
 include!("executor/flow_data_relative.rs");
 include!("executor/flow_data_color.rs");
+include!("executor/flow_data_number.rs");
+include!("executor/flow_data_store.rs");
 include!("executor/flow_task_vision.rs");
 include!("executor/flow.rs");
 include!("executor/runtime.rs");
 include!("executor/rhai_bridge.rs");
+include!("executor/rhai_modules.rs");
+include!("executor/debugger.rs");
 
 #[cfg(test)]
 mod tests;
@@ -100,6 +115,8 @@ const ITEM_VAR: &str = "item";
 const ITEM_INDEX_VAR: &str = "item_index";
 const MAX_LOOP_ITERATIONS: usize = 10_000;
 const WAIT_TIMEOUT_CHECK_SLICE_MS: u64 = 500;
+const WAIT_UNTIL_MIN_INTERVAL_MS: u64 = 50;
+const WAIT_UNTIL_PROGRESS_REPORT_MS: u64 = 1_000;
 
 #[derive(Debug)]
 pub(crate) enum ControlFlow {
@@ -263,6 +280,10 @@ pub(crate) struct ScriptExecutor {
     active_policy_round: Option<ActivePolicyRoundTrace>,
     active_policy_context: Option<ActivePolicyContext>,
     last_progress_probe: Option<ProgressProbe>,
+    /// 当前步骤的嵌套深度，供调试单步判断
+    step_depth: u32,
+    /// 拟人化输入的随机序列，连同播种时使用的种子一起保存
+    humanize_rng: StdMutex<Option<(Option<u64>, HumanizeRng)>>,
     #[cfg(feature = "testkit")]
     test_hooks: Option<Arc<crate::testkit::TestRuntimeHooks>>,
 }
@@ -280,6 +301,8 @@ impl ScriptExecutor {
             active_policy_round: None,
             active_policy_context: None,
             last_progress_probe: None,
+            step_depth: 0,
+            humanize_rng: StdMutex::new(None),
             #[cfg(feature = "testkit")]
             test_hooks: None,
         };
@@ -298,6 +321,15 @@ impl ScriptExecutor {
     }
 
     async fn execute_device_operation(&self, operation: DeviceOperation) -> Result<(), String> {
+        #[cfg(feature = "testkit")]
+        if let Some(test_hooks) = self
+            .test_hooks
+            .as_ref()
+            .filter(|hooks| hooks.is_replaying())
+        {
+            test_hooks.record_operation(&operation, None).await;
+            return Ok(());
+        }
         let result = get_device_ctx().execute_operation(operation.clone()).await;
         #[cfg(feature = "testkit")]
         if let Some(test_hooks) = self.test_hooks.as_ref() {
@@ -312,6 +344,15 @@ impl ScriptExecutor {
         &self,
         operations: &[DeviceOperation],
     ) -> Result<(), String> {
+        #[cfg(feature = "testkit")]
+        if let Some(test_hooks) = self
+            .test_hooks
+            .as_ref()
+            .filter(|hooks| hooks.is_replaying())
+        {
+            test_hooks.record_operations(operations, None).await;
+            return Ok(());
+        }
         let result = get_device_ctx().execute_operations(operations).await;
         #[cfg(feature = "testkit")]
         if let Some(test_hooks) = self.test_hooks.as_ref() {
@@ -323,6 +364,15 @@ impl ScriptExecutor {
     }
 
     async fn execute_device_sequence(&self, operations: &[DeviceOperation]) -> Result<(), String> {
+        #[cfg(feature = "testkit")]
+        if let Some(test_hooks) = self
+            .test_hooks
+            .as_ref()
+            .filter(|hooks| hooks.is_replaying())
+        {
+            test_hooks.record_operations(operations, None).await;
+            return Ok(());
+        }
         let result = get_device_ctx().execute_sequence(operations).await;
         #[cfg(feature = "testkit")]
         if let Some(test_hooks) = self.test_hooks.as_ref() {
@@ -389,7 +439,12 @@ impl ScriptExecutor {
                 test_hooks.record_step("enter", step, None, None).await;
             }
             let frame = self.enter_step(step).await;
-            let result = self.execute_step_inner(step).await;
+            self.step_depth += 1;
+            let result = match self.pause_for_debugger(step, &step_name).await {
+                Some(flow) => Ok(flow),
+                None => self.execute_step_inner(step).await,
+            };
+            self.step_depth -= 1;
             self.leave_step(frame).await;
             match &result {
                 Ok(flow) => self.log_step_debug(
@@ -487,7 +542,15 @@ impl ScriptExecutor {
         if let Some(flow) = Self::stop_requested_flow() {
             return Ok(flow);
         }
-        if let Some(flow) = self.try_execute_action_sequence(steps).await? {
+        // 调试时仍整批执行，只在批次边界检查断点和单步；子步骤会暂停时才逐个进入
+        let child_pauses = crate::infra::context::debug_control::any_debug_pause(
+            steps
+                .iter()
+                .filter(|step| !step.skip_flag)
+                .map(|step| step.id),
+            self.step_depth + 1,
+        );
+        if !child_pauses && let Some(flow) = self.try_execute_action_sequence(steps).await? {
             return Ok(flow);
         }
 
@@ -28,13 +28,13 @@ impl ScriptExecutor {
         }
 
         let det_results = if has_img_det_model {
-            self.run_img_det_pipeline("action.capture", Arc::clone(&image))
+            self.run_img_det_pipeline("action.capture", Arc::clone(&image), None)
                 .await?
         } else {
             Vec::new()
         };
         let ocr_results = if has_txt_det_model {
-            self.run_ocr_pipeline("action.capture", Arc::clone(&image))
+            self.run_ocr_pipeline("action.capture", Arc::clone(&image), None)
                 .await?
                 .1
         } else {
@@ -124,9 +124,19 @@ impl ScriptExecutor {
         step_type: &str,
         input_var: &str,
         out_var: &str,
+        region_top_left: &RegionPoint,
+        region_bottom_right: &RegionPoint,
     ) -> ExecuteResult<Vec<DetResult>> {
         let image = self.read_runtime_image_var(input_var, step_type).await?;
-        let det_results = self.run_img_det_pipeline(step_type, image.clone()).await?;
+        let region = Self::resolve_image_region(
+            step_type,
+            region_top_left,
+            region_bottom_right,
+            (image.width(), image.height()),
+        )?;
+        let det_results = self
+            .run_img_det_pipeline(step_type, image.clone(), region)
+            .await?;
         self.store_explicit_vision_results(step_type, image, Some(det_results.clone()), None)
             .await?;
         self.set_runtime_var(
@@ -147,9 +157,19 @@ impl ScriptExecutor {
         step_type: &str,
         input_var: &str,
         out_var: &str,
+        region_top_left: &RegionPoint,
+        region_bottom_right: &RegionPoint,
     ) -> ExecuteResult<Vec<OcrResult>> {
         let image = self.read_runtime_image_var(input_var, step_type).await?;
-        let (_, ocr_results) = self.run_ocr_pipeline(step_type, image.clone()).await?;
+        let region = Self::resolve_image_region(
+            step_type,
+            region_top_left,
+            region_bottom_right,
+            (image.width(), image.height()),
+        )?;
+        let (_, ocr_results) = self
+            .run_ocr_pipeline(step_type, image.clone(), region)
+            .await?;
         self.store_explicit_vision_results(step_type, image, None, Some(ocr_results.clone()))
             .await?;
         self.set_runtime_var(
@@ -216,8 +236,9 @@ impl ScriptExecutor {
         &self,
         step_type: &str,
         image: Arc<RgbaImage>,
+        region: Option<RegionRect>,
     ) -> ExecuteResult<Vec<DetResult>> {
-        let service = {
+        let (service, grid_size) = {
             let ctx = self.runtime_ctx.read().await;
             let Some(script_info) = ctx.execution.script_info.as_ref() else {
                 return Err(Self::execute_error(
@@ -231,10 +252,15 @@ impl ScriptExecutor {
                     "当前脚本未配置图像检测模型".to_string(),
                 ));
             }
-            ctx.img_det_service.clone()
+            (
+                ctx.img_det_service.clone(),
+                ctx.observation.vision_signature_grid_size,
+            )
         };
 
-        let det_results = Self::run_ocr_service_with_timeout(
+        // 只对识别区域做推理，结果再平移回原图坐标
+        let (image, offset) = Self::crop_image_to_region(image, region);
+        let mut det_results = Self::run_ocr_service_with_timeout(
             step_type,
             "目标检测",
             VISION_INFERENCE_TIMEOUT_MS,
@@ -246,6 +272,12 @@ impl ScriptExecutor {
             },
         )
         .await?;
+        if let Some((dx, dy)) = offset {
+            det_results = det_results
+                .iter()
+                .map(|item| item.translated(dx, dy, grid_size))
+                .collect();
+        }
 
         Log::debug_lazy(|| {
             let mut lines = vec![format!(
@@ -277,8 +309,9 @@ impl ScriptExecutor {
         &self,
         step_type: &str,
         image: Arc<RgbaImage>,
+        region: Option<RegionRect>,
     ) -> ExecuteResult<(Vec<DetResult>, Vec<OcrResult>)> {
-        let (service, use_cache, rec_model_signature, cached_ocr_results) = {
+        let (service, use_cache, rec_model_signature, cached_ocr_results, grid_size) = {
             let ctx = self.runtime_ctx.read().await;
             let Some(script_info) = ctx.execution.script_info.as_ref() else {
                 return Err(Self::execute_error(
@@ -307,9 +340,11 @@ impl ScriptExecutor {
                 use_cache,
                 rec_model_signature,
                 cached_ocr_results,
+                ctx.observation.vision_signature_grid_size,
             )
         };
 
+        let (image, offset) = Self::crop_image_to_region(image, region);
         let detect_image = Arc::clone(&image);
         let det_results = Self::run_ocr_service_with_timeout(
             step_type,
@@ -334,8 +369,10 @@ impl ScriptExecutor {
             for (idx, crop_image) in ocr_crop_entries {
                 let cache_key =
                     Self::build_ocr_text_cache_key(&crop_image, rec_model_signature.as_str());
-                if let Some(cached) = cached_ocr_results.get(cache_key.as_str()).cloned() {
-                    merged_results[idx] = Some(cached);
+                if let (Some(cached), Some(det_result)) =
+                    (cached_ocr_results.get(cache_key.as_str()), det_results.get(idx))
+                {
+                    merged_results[idx] = Some(Self::reuse_cached_ocr_result(cached, det_result));
                     continue;
                 }
                 if let Some(det_result) = det_results.get(idx).cloned() {
@@ -415,6 +452,18 @@ impl ScriptExecutor {
             .await?
         };
 
+        if let Some((dx, dy)) = offset {
+            return Ok((
+                det_results
+                    .iter()
+                    .map(|item| item.translated(dx, dy, grid_size))
+                    .collect(),
+                ocr_results
+                    .iter()
+                    .map(|item| item.translated(dx, dy, grid_size))
+                    .collect(),
+            ));
+        }
         Ok((det_results, ocr_results))
     }
 
@@ -454,6 +503,14 @@ impl ScriptExecutor {
             ctx.observation.screen_size = screen_size;
             return Ok(screen_size);
         }
+        #[cfg(feature = "testkit")]
+        if let Some(test_hooks) = self.test_hooks.as_ref()
+            && let Some(screen_size) = test_hooks.replay_screen_size().await
+        {
+            let mut ctx = self.runtime_ctx.write().await;
+            ctx.observation.screen_size = screen_size;
+            return Ok(screen_size);
+        }
         let image = self.capture_device_screenshot("action.screenSize").await?;
         let screen_size = (image.width(), image.height());
         let mut ctx = self.runtime_ctx.write().await;
@@ -488,6 +545,19 @@ impl ScriptExecutor {
         format!("1:{:016x}", hasher.finish())
     }
 
+    /// 缓存只按裁图像素命中，同一块文字可能来自整屏或某个区域，
+    /// 因此只复用识别出的文字，坐标始终取本次检测框
+    fn reuse_cached_ocr_result(cached: &OcrResult, det_result: &DetResult) -> OcrResult {
+        OcrResult {
+            bounding_box: det_result.bounding_box.clone(),
+            stable_box: det_result.stable_box.clone(),
+            stable_center: det_result.stable_center.clone(),
+            txt: cached.txt.clone(),
+            score: cached.score.clone(),
+            index: cached.index.clone(),
+        }
+    }
+
     fn collect_ocr_crop_entries(
         image: &RgbaImage,
         det_results: &[DetResult],
@@ -543,11 +613,11 @@ impl ScriptExecutor {
             return "none".to_string();
         };
 
-        match model {
-            domain_vision::RecognizerType::PaddleCrnn(cfg) => {
-                Self::base_model_asset_signature(&cfg.base_model)
-            }
-        }
+        format!(
+            "{}:{}",
+            model.kind_name(),
+            Self::base_model_asset_signature(model.base_model())
+        )
     }
 
     fn write_hash_segment(hasher: &mut XxHash3_64, bytes: &[u8]) {
@@ -582,17 +652,16 @@ impl ScriptExecutor {
             return "none".to_string();
         };
 
-        match model {
-            domain_vision::RecognizerType::PaddleCrnn(cfg) => format!(
-                "model={};dict={}",
-                Self::base_model_asset_signature(&cfg.base_model),
-                Self::runtime_recognizer_dict_signature(cfg)
-            ),
-        }
+        format!(
+            "kind={};model={};dict={}",
+            model.kind_name(),
+            Self::base_model_asset_signature(model.base_model()),
+            Self::runtime_recognizer_dict_signature(model)
+        )
     }
 
     fn runtime_recognizer_dict_signature(
-        model: &domain_vision::PaddleRecCrnn,
+        model: &domain_vision::RecognizerType,
     ) -> String {
         Self::resolved_path_asset_signature(
             infra_vision::resolve_recognizer_dict_path(model),
@@ -652,6 +721,12 @@ impl ScriptExecutor {
     }
 
     async fn capture_device_screenshot(&self, step_type: &str) -> ExecuteResult<RgbaImage> {
+        #[cfg(feature = "testkit")]
+        if let Some(test_hooks) = self.test_hooks.as_ref()
+            && let Some(frame) = test_hooks.next_replay_frame().await
+        {
+            return Ok(frame);
+        }
         let timeout_ms = self.resolve_capture_timeout_ms().await;
         Self::await_device_result_with_timeout(
             step_type,
@@ -145,6 +145,16 @@ impl<'a> ActionRunner<'a> {
             Action::Swipe { duration, mode } => {
                 format!("滑动(duration={}ms, {})", duration, Self::describe_swipe_mode(mode))
             }
+            Action::Path { points } => format!("轨迹滑动({} 个点)", points.len()),
+            Action::Pinch {
+                start_distance,
+                end_distance,
+                duration,
+                ..
+            } => format!(
+                "双指缩放({} -> {}, duration={}ms)",
+                start_distance, end_distance, duration
+            ),
             Action::LongClick {
                 mode,
                 offset_x,
@@ -159,6 +169,11 @@ impl<'a> ActionRunner<'a> {
             Action::Back => "返回".to_string(),
             Action::Home => "主页".to_string(),
             Action::InputText { text } => format!("输入文本({})", text),
+            Action::KeyEvent {
+                code,
+                long_press,
+                repeat,
+            } => format!("按键({}, long_press={}, repeat={})", code, long_press, repeat),
             Action::PosAdd { target } => format!("策略点击位置+1(target={})", target),
             Action::PosMinus { target } => format!("策略点击位置-1(target={})", target),
             Action::DropSetNext {
@@ -292,8 +307,12 @@ impl<'a> ActionRunner<'a> {
         if let Some(flow) = ScriptExecutor::stop_requested_flow() {
             return Ok(Some(flow));
         }
-        if runtime_policy.action_wait_ms > 0 {
-            tokio::time::sleep(Duration::from_millis(runtime_policy.action_wait_ms)).await;
+        let action_wait_ms = self
+            .executor
+            .humanized_action_wait_ms(runtime_policy.action_wait_ms)
+            .await;
+        if action_wait_ms > 0 {
+            tokio::time::sleep(Duration::from_millis(action_wait_ms)).await;
         }
         if let Some(flow) = ScriptExecutor::stop_requested_flow() {
             return Ok(Some(flow));
@@ -408,6 +427,26 @@ impl<'a> ActionRunner<'a> {
                 offset_y,
             } => self.execute_click(mode, *offset_x, *offset_y).await,
             Action::Swipe { duration, mode } => self.execute_swipe(mode, *duration).await,
+            Action::Path { points } => {
+                let plan = self.executor.plan_path_action(points).await?;
+                self.executor
+                    .execute_planned_device_action("action.path", "轨迹滑动", plan)
+                    .await
+            }
+            Action::Pinch {
+                center,
+                start_distance,
+                end_distance,
+                duration,
+            } => {
+                let plan = self
+                    .executor
+                    .plan_pinch_action(center, *start_distance, *end_distance, *duration)
+                    .await?;
+                self.executor
+                    .execute_planned_device_action("action.pinch", "双指缩放", plan)
+                    .await
+            }
             Action::LongClick {
                 mode,
                 offset_x,
@@ -449,6 +488,26 @@ impl<'a> ActionRunner<'a> {
                     DEVICE_EXTERNAL_TIMEOUT_MS,
                 )
                 .await,
+            Action::KeyEvent {
+                code,
+                long_press,
+                repeat,
+            } => {
+                // 长按每次约 1.5 秒，超时随重复次数放宽
+                let press_ms = if *long_press { 1_500 } else { 100 };
+                self.execute_simple_device_operation(
+                    "action.keyEvent",
+                    "按键",
+                    DeviceOperation::KeyEvent {
+                        code: code.clone(),
+                        long_press: *long_press,
+                        repeat: *repeat,
+                    },
+                    PolicyActionKind::Press,
+                    DEVICE_EXTERNAL_TIMEOUT_MS + u64::from(*repeat) * press_ms,
+                )
+                .await
+            }
             Action::PosAdd { target } => {
                 self.executor.adjust_policy_click_pos(*target, 1).await?;
                 Ok((ControlFlow::Next, None))
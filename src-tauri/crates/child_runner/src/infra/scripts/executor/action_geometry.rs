@@ -85,11 +85,14 @@ impl ScriptExecutor {
             action,
             Action::Click { .. }
                 | Action::Swipe { .. }
+                | Action::Path { .. }
+                | Action::Pinch { .. }
                 | Action::LongClick { .. }
                 | Action::Reboot
                 | Action::Back
                 | Action::Home
                 | Action::InputText { .. }
+                | Action::KeyEvent { .. }
                 | Action::LaunchApp { .. }
                 | Action::StopApp { .. }
         )
@@ -0,0 +1,127 @@
+impl ScriptExecutor {
+    /// 脚本运行设置中的配置优先，其次是设备执行策略；未启用时返回 None
+    async fn active_humanize_profile(&self) -> Option<HumanizeProfile> {
+        let script_profile = {
+            let ctx = self.runtime_ctx.read().await;
+            ctx.execution
+                .script_info
+                .as_ref()
+                .and_then(|script| script.runtime_settings.humanize.clone())
+        };
+        let profile = match script_profile {
+            Some(profile) => profile,
+            None => get_runtime_execution_policy().await?.humanize,
+        };
+        profile.enabled.then_some(profile)
+    }
+
+    /// 同一种子在整个运行期间共用一条随机序列，种子变化时重新播种
+    fn with_humanize_rng<T>(
+        &self,
+        profile: &HumanizeProfile,
+        sample: impl FnOnce(&mut HumanizeRng) -> T,
+    ) -> T {
+        let mut guard = self
+            .humanize_rng
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let (seed, mut rng) = match guard.take() {
+            Some((seed, rng)) if seed == profile.seed => (seed, rng),
+            _ => {
+                let initial = profile.seed.unwrap_or_else(|| {
+                    std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .map(|value| value.as_nanos() as u64)
+                        .unwrap_or(0)
+                });
+                (profile.seed, HumanizeRng::new(initial))
+            }
+        };
+        let value = sample(&mut rng);
+        *guard = Some((seed, rng));
+        value
+    }
+
+    fn bounding_box_half_extent(bounding_box: &BoundingBox) -> (f64, f64) {
+        (
+            f64::from((bounding_box.x2 - bounding_box.x1).max(0)) / 2.0,
+            f64::from((bounding_box.y2 - bounding_box.y1).max(0)) / 2.0,
+        )
+    }
+
+    /// 点击：启用拟人化时在目标框内取落点，并用原地滑动模拟随机的按压时长
+    async fn click_operation(
+        &self,
+        point: Point<u16>,
+        box_area: Option<&BoundingBox>,
+    ) -> ExecuteResult<DeviceOperation> {
+        let Some(profile) = self.active_humanize_profile().await else {
+            return Ok(DeviceOperation::Click(
+                self.apply_click_random_offset(point).await?,
+            ));
+        };
+        let screen_size = self.ensure_screen_size().await?;
+        let half_extent = box_area.map(Self::bounding_box_half_extent);
+        Ok(self.with_humanize_rng(&profile, |rng| {
+            let point = profile.jitter_point(rng, point, half_extent, screen_size);
+            DeviceOperation::Swipe {
+                from: point,
+                to: point,
+                duration: profile.press_ms(rng),
+            }
+        }))
+    }
+
+    async fn long_click_operation(
+        &self,
+        point: Point<u16>,
+        box_area: Option<&BoundingBox>,
+    ) -> ExecuteResult<DeviceOperation> {
+        let Some(profile) = self.active_humanize_profile().await else {
+            return Ok(DeviceOperation::LongClick(
+                self.apply_click_random_offset(point).await?,
+            ));
+        };
+        let screen_size = self.ensure_screen_size().await?;
+        let half_extent = box_area.map(Self::bounding_box_half_extent);
+        Ok(DeviceOperation::LongClick(
+            self.with_humanize_rng(&profile, |rng| {
+                profile.jitter_point(rng, point, half_extent, screen_size)
+            }),
+        ))
+    }
+
+    /// 滑动：启用拟人化时起止点各自抖动、时长随机伸缩，轨迹换成带缓动的曲线。
+    /// ADB 端按 sendevent 回放轨迹，未探测到触控设备时退化为一次首尾相连的 input swipe
+    async fn swipe_operation(
+        &self,
+        from: Point<u16>,
+        to: Point<u16>,
+        duration: u64,
+    ) -> ExecuteResult<DeviceOperation> {
+        let Some(profile) = self.active_humanize_profile().await else {
+            return Ok(DeviceOperation::Swipe { from, to, duration });
+        };
+        let screen_size = self.ensure_screen_size().await?;
+        Ok(self.with_humanize_rng(&profile, |rng| {
+            let from = profile.jitter_point(rng, from, None, screen_size);
+            let to = profile.jitter_point(rng, to, None, screen_size);
+            DeviceOperation::Gesture(vec![profile.swipe_stroke(
+                rng,
+                from,
+                to,
+                duration,
+                screen_size,
+            )])
+        }))
+    }
+
+    async fn humanized_action_wait_ms(&self, base_ms: u64) -> u64 {
+        match self.active_humanize_profile().await {
+            Some(profile) => {
+                self.with_humanize_rng(&profile, |rng| profile.action_delay_ms(rng, base_ms))
+            }
+            None => base_ms,
+        }
+    }
+}
@@ -30,6 +30,7 @@ impl ScriptExecutor {
             || evidence_signature.starts_with("flow.handlePolicy")
             || evidence_signature.starts_with("data.relativeFilter")
             || evidence_signature.starts_with("data.colorCompare")
+            || evidence_signature.starts_with("data.extractNumber")
             || evidence_signature.starts_with("debug.policy")
     }
 
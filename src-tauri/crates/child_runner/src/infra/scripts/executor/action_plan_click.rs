@@ -46,11 +46,11 @@ impl ScriptExecutor {
 
         let mut fixed_points = Vec::with_capacity(points.len());
         let mut operations = Vec::with_capacity(points.len());
-        for point in points {
+        for (index, point) in points.into_iter().enumerate() {
             let fixed_point = self.apply_click_fixed_offset(point, offset_x, offset_y).await?;
-            let click_point = self.apply_click_random_offset(fixed_point).await?;
+            let box_area = targets.get(index).and_then(|target| target.box_area.as_ref());
             fixed_points.push(fixed_point);
-            operations.push(DeviceOperation::Click(click_point));
+            operations.push(self.click_operation(fixed_point, box_area).await?);
         }
 
         for (target, point) in targets.iter_mut().zip(fixed_points) {
@@ -92,10 +92,10 @@ impl ScriptExecutor {
         }
 
         let mut operations = Vec::with_capacity(points.len());
-        for point in points {
+        for (index, point) in points.into_iter().enumerate() {
             let fixed_point = self.apply_click_fixed_offset(point, offset_x, offset_y).await?;
-            let click_point = self.apply_click_random_offset(fixed_point).await?;
-            operations.push(DeviceOperation::LongClick(click_point));
+            let box_area = targets.get(index).and_then(|target| target.box_area.as_ref());
+            operations.push(self.long_click_operation(fixed_point, box_area).await?);
         }
 
         Ok(PlannedDeviceAction {
@@ -0,0 +1,94 @@
+impl ScriptExecutor {
+    async fn resolve_gesture_point(&self, point: &RegionPoint) -> ExecuteResult<Point<u16>> {
+        match point {
+            RegionPoint::Point { p } => Ok(Self::point_to_absolute(p)),
+            RegionPoint::Percent { p } => {
+                let screen_size = self.ensure_screen_size().await?;
+                Self::percent_point_to_absolute(p, screen_size)
+            }
+        }
+    }
+
+    async fn plan_path_action(
+        &self,
+        points: &[GestureWaypoint],
+    ) -> ExecuteResult<PlannedDeviceAction> {
+        if points.len() < 2 {
+            return Err(Self::execute_error(
+                "action.path",
+                "轨迹至少需要两个点".to_string(),
+            ));
+        }
+
+        let mut stroke = Vec::with_capacity(points.len());
+        let mut targets = Vec::with_capacity(points.len());
+        for (index, waypoint) in points.iter().enumerate() {
+            let point = self.resolve_gesture_point(&waypoint.point).await?;
+            let role = if index == 0 {
+                PolicyActionTargetRole::Start
+            } else if index + 1 == points.len() {
+                PolicyActionTargetRole::End
+            } else {
+                PolicyActionTargetRole::Path
+            };
+            stroke.push(TouchWaypoint::new(point, waypoint.duration));
+            targets.push(ActionTraceBuilder::build_point_target(role, point));
+        }
+
+        Ok(PlannedDeviceAction {
+            operations: vec![DeviceOperation::Gesture(vec![stroke])],
+            trace: Some(ActionTraceBuilder::build_action_trace(
+                PolicyActionKind::Swipe,
+                PolicyActionSource::Fixed,
+                targets,
+            )),
+        })
+    }
+
+    async fn plan_pinch_action(
+        &self,
+        center: &RegionPoint,
+        start_distance: u16,
+        end_distance: u16,
+        duration: u64,
+    ) -> ExecuteResult<PlannedDeviceAction> {
+        let center = self.resolve_gesture_point(center).await?;
+        Ok(PlannedDeviceAction {
+            operations: vec![DeviceOperation::Pinch {
+                center,
+                start_distance,
+                end_distance,
+                duration,
+            }],
+            trace: Some(ActionTraceBuilder::build_action_trace(
+                PolicyActionKind::Swipe,
+                PolicyActionSource::Fixed,
+                vec![ActionTraceBuilder::build_point_target(
+                    PolicyActionTargetRole::Primary,
+                    center,
+                )],
+            )),
+        })
+    }
+
+    /// 手势类操作本身会持续一段时间，等待结果时需要在基础超时上加上动作耗时
+    fn operation_motion_ms(operation: &DeviceOperation) -> u64 {
+        match operation {
+            DeviceOperation::Swipe { duration, .. } | DeviceOperation::Pinch { duration, .. } => {
+                *duration
+            }
+            DeviceOperation::Gesture(strokes) => strokes
+                .iter()
+                .map(|stroke| {
+                    stroke
+                        .iter()
+                        .skip(1)
+                        .map(|waypoint| waypoint.duration)
+                        .sum()
+                })
+                .max()
+                .unwrap_or(0),
+            _ => 0,
+        }
+    }
+}
@@ -159,7 +159,7 @@ impl ScriptExecutor {
         };
 
         Ok(PlannedDeviceAction {
-            operations: vec![DeviceOperation::Swipe { from, to, duration }],
+            operations: vec![self.swipe_operation(from, to, duration).await?],
             trace: Some(trace),
         })
     }
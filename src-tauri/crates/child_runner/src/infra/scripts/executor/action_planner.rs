@@ -20,10 +20,11 @@ impl ScriptExecutor {
             return Ok((ControlFlow::Next, plan.trace));
         }
 
+        let motion_ms: u64 = plan.operations.iter().map(Self::operation_motion_ms).sum();
         Self::await_device_result_with_timeout(
             step_type,
             label,
-            DEVICE_EXTERNAL_TIMEOUT_MS,
+            DEVICE_EXTERNAL_TIMEOUT_MS + motion_ms,
             self.execute_device_operations(&plan.operations),
         )
         .await?;
@@ -0,0 +1,79 @@
+const DEBUG_STOP_POLL_MS: u64 = 200;
+
+impl ScriptExecutor {
+    /// 命中断点或单步条件时在步骤入口暂停，直到收到继续命令或停止请求
+    async fn pause_for_debugger(&mut self, step: &Step, step_name: &str) -> Option<ControlFlow> {
+        let (reason, mut resume_rx) =
+            crate::infra::context::debug_control::check_debug_pause(step.id, self.step_depth)?;
+        Log::info(&format!(
+            "[ debugger ] 暂停于步骤[{}] depth={} reason={:?}",
+            step_name, self.step_depth, reason
+        ));
+        let event = self.build_debug_paused_event(step, step_name, reason).await;
+        emit_progress_event(
+            RuntimeProgressPhase::Paused,
+            None,
+            event.script_id,
+            event.task_id,
+            step.id,
+            Some(format!("调试暂停: {}", step_name)),
+        );
+        emit_debug_paused_event_now(event).await;
+
+        loop {
+            if let Some(flow) = Self::stop_requested_flow() {
+                crate::infra::context::debug_control::abandon_debug_pause();
+                return Some(flow);
+            }
+            tokio::select! {
+                changed = resume_rx.changed() => {
+                    if changed.is_err() {
+                        crate::infra::context::debug_control::abandon_debug_pause();
+                    }
+                    // 暂停期间不算无进展，恢复后重新开始计时
+                    self.reset_progress_probe();
+                    return None;
+                }
+                _ = tokio::time::sleep(Duration::from_millis(DEBUG_STOP_POLL_MS)) => {}
+            }
+        }
+    }
+
+    async fn build_debug_paused_event(
+        &self,
+        step: &Step,
+        step_name: &str,
+        reason: DebugPauseReason,
+    ) -> RuntimeDebugPausedEvent {
+        let ctx = self.runtime_ctx.read().await;
+        let variables = ctx
+            .execution
+            .var_map
+            .iter()
+            .map(|(name, value)| (name.clone(), value))
+            .collect::<std::collections::BTreeMap<_, _>>();
+        let vision_json = ctx.observation.last_snapshot.as_ref().map(|snapshot| {
+            json!({
+                "detItems": snapshot.det_items,
+                "ocrItems": snapshot.ocr_items,
+            })
+            .to_string()
+        });
+        RuntimeDebugPausedEvent {
+            session_id: crate::infra::session::runtime_session::try_current_session_summary()
+                .map(|summary| summary.session_id),
+            script_id: Some(ctx.execution.script_id),
+            task_id: ctx.execution.current_task.as_ref().map(|task| task.id),
+            step_id: step.id,
+            step_name: Some(step_name.to_string()),
+            depth: self.step_depth,
+            reason,
+            variables_json: serde_json::to_string(&variables).unwrap_or_else(|error| {
+                Log::warn(&format!("[ debugger ] 运行时变量序列化失败: {}", error));
+                "{}".to_string()
+            }),
+            vision_json,
+            at: ad_kernel::ids::now_millis_string(),
+        }
+    }
+}
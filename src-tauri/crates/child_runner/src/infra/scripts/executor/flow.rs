@@ -183,6 +183,15 @@ impl ScriptExecutor {
                 }
                 Ok(ControlFlow::Next)
             }
+            FlowControl::WaitUntil {
+                until,
+                timeout_ms,
+                interval_ms,
+                else_steps,
+            } => {
+                self.execute_wait_until_step(until, *timeout_ms, *interval_ms, else_steps)
+                    .await
+            }
             FlowControl::Link { target } => Ok(ControlFlow::Link(*target)),
             FlowControl::AddPolicies {
                 source,
@@ -57,6 +57,15 @@ impl ScriptExecutor {
                     )
                     .await
                 }
+                ConditionNode::OcrNumberCompare {
+                    source,
+                    field,
+                    op,
+                    value,
+                } => {
+                    self.match_ocr_number_compare(source, field, op, *value)
+                        .await
+                }
                 ConditionNode::TaskStatus { a } => self.match_state_status(a).await,
                 ConditionNode::CurrentTaskIn { current } => {
                     Ok(self.match_current_task_condition(current).await)
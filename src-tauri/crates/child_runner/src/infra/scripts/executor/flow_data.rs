@@ -232,6 +232,29 @@ impl ScriptExecutor {
                 )
                 .await
             }
+            DataHanding::ExtractNumber {
+                source,
+                field,
+                out_var,
+            } => self.execute_extract_number_step(source, field, out_var).await,
+            DataHanding::StoreGet {
+                key,
+                out_var,
+                default_val,
+            } => {
+                self.execute_store_get_step(key, out_var, default_val.as_ref())
+                    .await
+            }
+            DataHanding::StoreSet {
+                key,
+                json_val,
+                expr,
+                expiry,
+            } => {
+                self.execute_store_set_step(key, json_val.as_ref(), expr.as_deref(), expiry)
+                    .await
+            }
+            DataHanding::StoreRemove { keys } => self.execute_store_remove_step(keys).await,
             DataHanding::Rhai { code, out_var } => {
                 if let Some(timeout_flow) = self
                     .record_progress_evidence("data.rhai", "执行 Rhai 代码块".to_string())
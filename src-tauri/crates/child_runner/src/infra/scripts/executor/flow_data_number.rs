@@ -0,0 +1,111 @@
+impl ScriptExecutor {
+    async fn execute_extract_number_step(
+        &mut self,
+        source: &OcrNumberSource,
+        field: &NumberField,
+        out_var: &str,
+    ) -> ExecuteResult<ControlFlow> {
+        if let Some(timeout_flow) = self
+            .record_progress_evidence(
+                "data.extractNumber",
+                format!("ExtractNumber 提取数值到 {}", out_var),
+            )
+            .await?
+        {
+            return Ok(timeout_flow);
+        }
+        let value = self
+            .extract_ocr_number("data.extractNumber", source)
+            .await?
+            .and_then(|number| number.field_value(*field));
+        let value = match value {
+            Some(NumberValue::Int(value)) => Dynamic::from_int(value),
+            Some(NumberValue::Float(value)) => Dynamic::from_float(value as FLOAT),
+            None => Dynamic::UNIT,
+        };
+        self.set_runtime_var(out_var, value).await?;
+        Ok(ControlFlow::Next)
+    }
+
+    async fn match_ocr_number_compare(
+        &self,
+        source: &OcrNumberSource,
+        field: &NumberField,
+        op: &CompareOp,
+        value: f64,
+    ) -> ExecuteResult<bool> {
+        let Some(actual) = self
+            .extract_ocr_number("condition.ocrNumberCompare", source)
+            .await?
+            .and_then(|number| number.field_value(*field))
+        else {
+            return Ok(false);
+        };
+        Ok(Self::compare_dynamic(
+            &Dynamic::from_float(actual.as_f64() as FLOAT),
+            op,
+            &Dynamic::from_float(value as FLOAT),
+        ))
+    }
+
+    /// 按区域与文字锚点挑选 OCR 文本并提取数值；指定锚点方向时只取该方向上最近的一条文本
+    async fn extract_ocr_number(
+        &self,
+        step_type: &str,
+        source: &OcrNumberSource,
+    ) -> ExecuteResult<Option<ExtractedNumber>> {
+        let input_var = source.input_var.trim();
+        let mut items = if input_var.is_empty() {
+            let ctx = self.runtime_ctx.read().await;
+            ctx.observation
+                .last_snapshot
+                .as_ref()
+                .map(|snapshot| snapshot.ocr_items.clone())
+                .unwrap_or_default()
+        } else {
+            self.read_runtime_result_vec::<OcrResult>(input_var, step_type, "OCR")
+                .await?
+        };
+        if let Some(region) = self
+            .resolve_region_rect(&source.region_top_left, &source.region_bottom_right)
+            .await?
+        {
+            items.retain(|item| Self::bounding_box_center_in_region(&item.bounding_box, &region));
+        }
+
+        let anchor = source.anchor_text.trim();
+        if anchor.is_empty() {
+            return Ok(items
+                .iter()
+                .find_map(|item| extract_number(&item.txt, source.kind)));
+        }
+
+        let Some(direction) = source.anchor_direction.as_ref() else {
+            // 锚点与数值在同一条文本中：优先解析锚点之后的部分，再退回整条文本
+            return Ok(items.iter().find_map(|item| {
+                let (_, after) = item.txt.split_once(anchor)?;
+                extract_number(after, source.kind)
+                    .or_else(|| extract_number(&item.txt.replace(anchor, " "), source.kind))
+            }));
+        };
+
+        let ctx = self.runtime_ctx.read().await;
+        let Some(snapshot) = ctx.observation.last_snapshot.as_ref() else {
+            return Ok(None);
+        };
+        let candidates = Self::relative_filter_ocr_items(
+            snapshot,
+            &items,
+            &RelativeAnchorType::OcrText,
+            anchor,
+            0,
+            direction,
+            None,
+            None,
+            None,
+        );
+        Ok(candidates
+            .iter()
+            .find_map(|item| extract_number(&item.txt, source.kind)))
+    }
+}
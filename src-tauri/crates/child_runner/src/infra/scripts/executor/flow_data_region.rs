@@ -61,6 +61,55 @@ impl ScriptExecutor {
         Ok(Self::bounding_box_center_in_region(&bounding_box, region))
     }
 
+    /// 按输入图尺寸解析识别区域并裁到图像范围内，两个角都为默认值时返回 None 表示整张图
+    fn resolve_image_region(
+        step_type: &str,
+        top_left: &RegionPoint,
+        bottom_right: &RegionPoint,
+        image_size: (u32, u32),
+    ) -> ExecuteResult<Option<RegionRect>> {
+        if Self::region_point_is_default(top_left) && Self::region_point_is_default(bottom_right) {
+            return Ok(None);
+        }
+
+        let start = Self::region_point_to_absolute(top_left, image_size)?;
+        let end = Self::region_point_to_absolute(bottom_right, image_size)?;
+        let (width, height) = (image_size.0 as i32, image_size.1 as i32);
+        let x1 = start.x.min(end.x).clamp(0, width);
+        let y1 = start.y.min(end.y).clamp(0, height);
+        let x2 = start.x.max(end.x).clamp(0, width);
+        let y2 = start.y.max(end.y).clamp(0, height);
+        if x2 <= x1 || y2 <= y1 {
+            return Err(Self::execute_error(
+                step_type,
+                format!(
+                    "识别区域为空：({}, {}) - ({}, {})，图像尺寸 {}x{}",
+                    x1, y1, x2, y2, width, height
+                ),
+            ));
+        }
+        Ok(Some(RegionRect { x1, y1, x2, y2 }))
+    }
+
+    /// 裁剪出识别区域，同时返回区域左上角在原图中的偏移
+    fn crop_image_to_region(
+        image: Arc<RgbaImage>,
+        region: Option<RegionRect>,
+    ) -> (Arc<RgbaImage>, Option<(i32, i32)>) {
+        let Some(region) = region else {
+            return (image, None);
+        };
+        let cropped = image::imageops::crop_imm(
+            image.as_ref(),
+            region.x1 as u32,
+            region.y1 as u32,
+            (region.x2 - region.x1) as u32,
+            (region.y2 - region.y1) as u32,
+        )
+        .to_image();
+        (Arc::new(cropped), Some((region.x1, region.y1)))
+    }
+
     fn bounding_box_center_in_region(bounding_box: &BoundingBox, region: &RegionRect) -> bool {
         let center = bounding_box.center();
         center.x >= region.x1
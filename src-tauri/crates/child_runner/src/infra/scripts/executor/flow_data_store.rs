@@ -0,0 +1,138 @@
+impl ScriptExecutor {
+    async fn execute_store_get_step(
+        &mut self,
+        key: &str,
+        out_var: &str,
+        default_val: Option<&VarValue>,
+    ) -> ExecuteResult<ControlFlow> {
+        if let Some(timeout_flow) = self
+            .record_progress_evidence("data.storeGet", format!("StoreGet 读取存储键 {}", key))
+            .await?
+        {
+            return Ok(timeout_flow);
+        }
+        let stored = {
+            let ctx = self.runtime_ctx.read().await;
+            Self::runtime_store_value(&ctx.execution.runtime_store, key.trim())
+        };
+        let value = match stored {
+            Some(value) => {
+                Self::json_value_to_dynamic("data.storeGet", &format!("存储键[{}]", key), &value)?
+            }
+            None => default_val
+                .map(Self::var_value_to_dynamic)
+                .unwrap_or(Dynamic::UNIT),
+        };
+        self.set_runtime_var(out_var, value).await?;
+        Ok(ControlFlow::Next)
+    }
+
+    async fn execute_store_set_step(
+        &mut self,
+        key: &str,
+        json_val: Option<&Value>,
+        expr: Option<&str>,
+        expiry: &StoreExpiry,
+    ) -> ExecuteResult<ControlFlow> {
+        if let Some(timeout_flow) = self
+            .record_progress_evidence("data.storeSet", format!("StoreSet 写入存储键 {}", key))
+            .await?
+        {
+            return Ok(timeout_flow);
+        }
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(Self::execute_error(
+                "data.storeSet",
+                "存储键不能为空".to_string(),
+            ));
+        }
+        let value = if let Some(json_val) = json_val {
+            json_val.clone()
+        } else if let Some(expr) = expr.filter(|value| !value.trim().is_empty()) {
+            let value = self.eval_dynamic(expr, "data.storeSet")?;
+            from_dynamic::<Value>(&value).map_err(|error| {
+                Self::execute_error(
+                    "data.storeSet",
+                    format!("存储键[{}]的值无法转换为 JSON: {}", key, error),
+                )
+            })?
+        } else {
+            Value::Null
+        };
+
+        let (device_id, account_id, script_id) = self.runtime_store_scope("data.storeSet").await?;
+        let entry = RuntimeStoreEntry::new(key.to_string(), value, *expiry, chrono::Local::now());
+        infra_sqlite::upsert_runtime_store_entry(device_id, account_id.as_ref(), script_id, &entry)
+            .await
+            .map_err(|error| Self::execute_error("data.storeSet", error))?;
+        self.runtime_ctx
+            .write()
+            .await
+            .execution
+            .runtime_store
+            .insert(entry.key.clone(), entry);
+        Ok(ControlFlow::Next)
+    }
+
+    async fn execute_store_remove_step(&mut self, keys: &[String]) -> ExecuteResult<ControlFlow> {
+        if let Some(timeout_flow) = self
+            .record_progress_evidence(
+                "data.storeRemove",
+                format!("StoreRemove 删除 {} 个存储键", keys.len()),
+            )
+            .await?
+        {
+            return Ok(timeout_flow);
+        }
+        let keys = keys
+            .iter()
+            .map(|key| key.trim())
+            .filter(|key| !key.is_empty())
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        if keys.is_empty() {
+            return Ok(ControlFlow::Next);
+        }
+
+        let (device_id, account_id, script_id) =
+            self.runtime_store_scope("data.storeRemove").await?;
+        infra_sqlite::delete_runtime_store_entries(device_id, account_id.as_ref(), script_id, &keys)
+            .await
+            .map_err(|error| Self::execute_error("data.storeRemove", error))?;
+        let mut ctx = self.runtime_ctx.write().await;
+        for key in &keys {
+            ctx.execution.runtime_store.remove(key);
+        }
+        Ok(ControlFlow::Next)
+    }
+
+    /// 持久化存储按当前设备、账号与脚本隔离
+    async fn runtime_store_scope(
+        &self,
+        step_type: &str,
+    ) -> ExecuteResult<(DeviceId, Option<AccountId>, ScriptId)> {
+        let ctx = self.runtime_ctx.read().await;
+        let device_id = ctx.execution.current_device_id.ok_or_else(|| {
+            Self::execute_error(
+                step_type,
+                "当前运行时缺少设备 ID，无法访问持久化存储".to_string(),
+            )
+        })?;
+        Ok((
+            device_id,
+            ctx.execution.current_account_id.clone(),
+            ctx.execution.script_id,
+        ))
+    }
+
+    fn runtime_store_value(
+        store: &HashMap<String, RuntimeStoreEntry>,
+        key: &str,
+    ) -> Option<Value> {
+        store
+            .get(key)
+            .filter(|entry| !entry.is_expired(chrono::Local::now()))
+            .map(|entry| entry.value.clone())
+    }
+}
@@ -29,7 +29,12 @@ impl ScriptExecutor {
 
     async fn execute_vision_step(&mut self, vision: &VisionNode) -> ExecuteResult<ControlFlow> {
         match vision {
-            VisionNode::Detect { input_var, out_var } => {
+            VisionNode::Detect {
+                input_var,
+                out_var,
+                region_top_left,
+                region_bottom_right,
+            } => {
                 if let Some(timeout_flow) = self
                     .record_progress_evidence(
                         "vision.inference",
@@ -39,11 +44,22 @@ impl ScriptExecutor {
                 {
                     return Ok(timeout_flow);
                 }
-                self.execute_detect_step("vision.detect", input_var, out_var)
-                    .await?;
+                self.execute_detect_step(
+                    "vision.detect",
+                    input_var,
+                    out_var,
+                    region_top_left,
+                    region_bottom_right,
+                )
+                .await?;
                 Ok(ControlFlow::Next)
             }
-            VisionNode::Ocr { input_var, out_var } => {
+            VisionNode::Ocr {
+                input_var,
+                out_var,
+                region_top_left,
+                region_bottom_right,
+            } => {
                 if let Some(timeout_flow) = self
                     .record_progress_evidence(
                         "vision.inference",
@@ -53,7 +69,14 @@ impl ScriptExecutor {
                 {
                     return Ok(timeout_flow);
                 }
-                self.execute_ocr_step("vision.ocr", input_var, out_var).await?;
+                self.execute_ocr_step(
+                    "vision.ocr",
+                    input_var,
+                    out_var,
+                    region_top_left,
+                    region_bottom_right,
+                )
+                .await?;
                 Ok(ControlFlow::Next)
             }
             VisionNode::VisionSearch {
@@ -119,7 +142,116 @@ impl ScriptExecutor {
 
                 Ok(ControlFlow::Next)
             }
+            VisionNode::TemplateMatch {
+                input_var,
+                template,
+                label,
+                idx,
+                threshold,
+                scales,
+                max_results,
+                region_top_left,
+                region_bottom_right,
+                out_var,
+            } => {
+                if let Some(timeout_flow) = self
+                    .record_progress_evidence(
+                        "vision.inference",
+                        format!("Vision TemplateMatch {} -> {}", input_var, out_var),
+                    )
+                    .await?
+                {
+                    return Ok(timeout_flow);
+                }
+                let region = self
+                    .resolve_region_rect(region_top_left, region_bottom_right)
+                    .await?;
+                let grid_size = {
+                    let ctx = self.runtime_ctx.read().await;
+                    ctx.observation.vision_signature_grid_size
+                };
+                let options = TemplateMatchOptions {
+                    threshold: *threshold,
+                    scales: scales.clone(),
+                    max_results: *max_results as usize,
+                    index: *idx,
+                    label: label.clone(),
+                    grid_size,
+                };
+                let results = self
+                    .execute_template_match_step(
+                        "vision.templateMatch",
+                        input_var,
+                        template,
+                        region,
+                        options,
+                    )
+                    .await?;
+                self.set_runtime_var(
+                    out_var,
+                    Self::results_to_dynamic("vision.templateMatch", "模板匹配", &results)?,
+                )
+                .await?;
+                Ok(ControlFlow::Next)
+            }
+        }
+    }
+
+    async fn execute_template_match_step(
+        &self,
+        step_type: &str,
+        input_var: &str,
+        template: &str,
+        region: Option<RegionRect>,
+        options: TemplateMatchOptions,
+    ) -> ExecuteResult<Vec<DetResult>> {
+        if template.trim().is_empty() {
+            return Err(Self::execute_error(
+                step_type,
+                "模板匹配缺少参考图".to_string(),
+            ));
         }
+        let image = self.read_runtime_image_var(input_var, step_type).await?;
+        let template_image = load_img_from_base64(template)
+            .map_err(|error| Self::execute_error(step_type, error.to_string()))?
+            .to_rgba8();
+        let region = region.map(|rect| TemplateSearchRegion {
+            x: rect.x1.max(0) as u32,
+            y: rect.y1.max(0) as u32,
+            width: (rect.x2 - rect.x1).max(0) as u32,
+            height: (rect.y2 - rect.y1).max(0) as u32,
+        });
+        let results = Self::await_device_result_with_timeout(
+            step_type,
+            "模板匹配",
+            VISION_INFERENCE_TIMEOUT_MS,
+            async move {
+                tokio::task::spawn_blocking(move || {
+                    match_template_rgba(image.as_ref(), &template_image, region, &options)
+                        .map_err(|error| error.to_string())
+                })
+                .await
+                .map_err(|error| format!("模板匹配任务执行失败: {}", error))?
+            },
+        )
+        .await?;
+
+        Log::debug_lazy(|| {
+            let mut lines = vec![format!(
+                "[ executor ] {}模板匹配完成: count={}",
+                step_type,
+                results.len()
+            )];
+            for (index, item) in results.iter().take(10).enumerate() {
+                let center = item.bounding_box.center();
+                lines.push(format!(
+                    "[ executor ] {} TPL[{}]: score={:.3} center=({}, {})",
+                    step_type, index, item.score, center.x, center.y
+                ));
+            }
+            lines.join("\n")
+        });
+        Ok(results)
     }
 
     async fn match_vision_count_compare(
@@ -1,6 +1,3 @@
-use regex::Regex;
-use std::sync::OnceLock;
-
 impl ScriptExecutor {
     async fn resolve_wait_duration_ms(
         &self,
@@ -49,6 +46,233 @@ impl ScriptExecutor {
         fallback_ms
     }
 
+    async fn execute_wait_until_step(
+        &mut self,
+        until: &WaitUntilTarget,
+        timeout_ms: u64,
+        interval_ms: u64,
+        else_steps: &[Step],
+    ) -> ExecuteResult<ControlFlow> {
+        let step_type = "flow.waitUntil";
+        let interval = Duration::from_millis(interval_ms.max(WAIT_UNTIL_MIN_INTERVAL_MS));
+        let timeout = Duration::from_millis(timeout_ms);
+        let (region, diff_threshold, mut reference) = match until {
+            WaitUntilTarget::Stable {
+                diff_threshold,
+                region_top_left,
+                region_bottom_right,
+                ..
+            } => (
+                self.resolve_region_rect(region_top_left, region_bottom_right)
+                    .await?,
+                *diff_threshold,
+                None,
+            ),
+            WaitUntilTarget::Changed {
+                reference_var,
+                diff_threshold,
+                region_top_left,
+                region_bottom_right,
+            } => {
+                let reference = match reference_var
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty())
+                {
+                    Some(reference_var) => Some(
+                        self.read_runtime_image_var(reference_var, step_type)
+                            .await?,
+                    ),
+                    None => None,
+                };
+                (
+                    self.resolve_region_rect(region_top_left, region_bottom_right)
+                        .await?,
+                    *diff_threshold,
+                    reference,
+                )
+            }
+            WaitUntilTarget::Condition { .. } => (None, 0.0, None),
+        };
+
+        let started = Instant::now();
+        let mut stable_since: Option<Instant> = None;
+        let mut last_report: Option<Instant> = None;
+        loop {
+            if let Some(flow) = Self::stop_requested_flow() {
+                return Ok(flow);
+            }
+            let description =
+                Self::describe_wait_until(until, started.elapsed(), timeout, stable_since);
+            if let Some(timeout_flow) = self
+                .record_progress_evidence(step_type, description.clone())
+                .await?
+            {
+                return Ok(timeout_flow);
+            }
+            if last_report.is_none_or(|reported| {
+                reported.elapsed() >= Duration::from_millis(WAIT_UNTIL_PROGRESS_REPORT_MS)
+            }) {
+                self.emit_wait_progress(description).await;
+                last_report = Some(Instant::now());
+            }
+
+            let image = Arc::new(self.capture_device_screenshot(step_type).await?);
+            let satisfied = match until {
+                WaitUntilTarget::Condition { con } => {
+                    self.activate_image_context(step_type, image.clone(), None)
+                        .await?;
+                    self.evaluate_condition(con).await?
+                }
+                WaitUntilTarget::Stable { stable_ms, .. } => {
+                    let now = Instant::now();
+                    let unchanged = match reference.as_ref() {
+                        Some(previous) => {
+                            Self::frame_diff_ratio(previous, &image, region.as_ref())
+                                .map_err(|error| Self::execute_error(step_type, error))?
+                                <= diff_threshold
+                        }
+                        None => false,
+                    };
+                    if !unchanged {
+                        stable_since = Some(now);
+                    }
+                    reference = Some(image.clone());
+                    stable_since.is_some_and(|since| {
+                        now.duration_since(since) >= Duration::from_millis(*stable_ms)
+                    })
+                }
+                WaitUntilTarget::Changed { .. } => match reference.as_ref() {
+                    Some(reference) => {
+                        Self::frame_diff_ratio(reference, &image, region.as_ref())
+                            .map_err(|error| Self::execute_error(step_type, error))?
+                            > diff_threshold
+                    }
+                    None => {
+                        reference = Some(image.clone());
+                        false
+                    }
+                },
+            };
+
+            if satisfied {
+                if !matches!(until, WaitUntilTarget::Condition { .. }) {
+                    self.store_capture_image(image, None).await?;
+                }
+                self.emit_wait_progress(format!(
+                    "WaitUntil 已满足，耗时 {}ms",
+                    started.elapsed().as_millis()
+                ))
+                .await;
+                return Ok(ControlFlow::Next);
+            }
+
+            let elapsed = started.elapsed();
+            if elapsed >= timeout {
+                let message = format!(
+                    "WaitUntil 等待超时({}ms)，{}",
+                    timeout_ms,
+                    if else_steps.is_empty() {
+                        "继续执行后续步骤"
+                    } else {
+                        "执行 else 分支"
+                    }
+                );
+                Log::warn(&format!("[ runtime ] {}", message));
+                self.emit_wait_progress(message).await;
+                if else_steps.is_empty() {
+                    return Ok(ControlFlow::Next);
+                }
+                return self.execute(else_steps).await;
+            }
+            tokio::time::sleep(interval.min(timeout - elapsed)).await;
+        }
+    }
+
+    async fn emit_wait_progress(&self, message: String) {
+        let (_execution_id, assignment_id, script_id, task_id, step_id) =
+            self.current_execution_locator().await;
+        emit_progress_event(
+            RuntimeProgressPhase::Executing,
+            assignment_id,
+            script_id,
+            task_id,
+            step_id,
+            Some(message),
+        );
+    }
+
+    fn describe_wait_until(
+        until: &WaitUntilTarget,
+        elapsed: Duration,
+        timeout: Duration,
+        stable_since: Option<Instant>,
+    ) -> String {
+        let progress = format!("{}/{}ms", elapsed.as_millis(), timeout.as_millis());
+        match until {
+            WaitUntilTarget::Stable { stable_ms, .. } => format!(
+                "WaitUntil 等待画面稳定 {}ms（已稳定 {}ms），{}",
+                stable_ms,
+                stable_since.map_or(0, |since| since.elapsed().as_millis()),
+                progress
+            ),
+            WaitUntilTarget::Changed { reference_var, .. } => format!(
+                "WaitUntil 等待画面变化（参考: {}），{}",
+                reference_var.as_deref().unwrap_or("首帧"),
+                progress
+            ),
+            WaitUntilTarget::Condition { .. } => format!("WaitUntil 等待条件成立，{}", progress),
+        }
+    }
+
+    /// 两帧在区域内的平均亮度差（0~1），尺寸不一致视为完全不同；
+    /// 区域裁剪到截图范围后为空时返回错误，避免变化等待永远无法满足
+    fn frame_diff_ratio(
+        previous: &RgbaImage,
+        current: &RgbaImage,
+        region: Option<&RegionRect>,
+    ) -> Result<f32, String> {
+        if previous.dimensions() != current.dimensions() {
+            return Ok(1.0);
+        }
+        let (width, height) = current.dimensions();
+        let (x1, y1, x2, y2) = match region {
+            Some(region) => (
+                region.x1.clamp(0, width as i32) as u32,
+                region.y1.clamp(0, height as i32) as u32,
+                region.x2.clamp(0, width as i32) as u32,
+                region.y2.clamp(0, height as i32) as u32,
+            ),
+            None => (0, 0, width, height),
+        };
+        if x2 <= x1 || y2 <= y1 {
+            return Err(format!(
+                "比较区域裁剪到 {}x{} 截图后为空: ({}, {}) - ({}, {})",
+                width,
+                height,
+                region.map_or(0, |region| region.x1),
+                region.map_or(0, |region| region.y1),
+                region.map_or(0, |region| region.x2),
+                region.map_or(0, |region| region.y2),
+            ));
+        }
+        let luma = |pixel: &image::Rgba<u8>| {
+            let [r, g, b, _] = pixel.0;
+            (u32::from(r) * 299 + u32::from(g) * 587 + u32::from(b) * 114) / 1000
+        };
+        let mut total = 0u64;
+        let mut count = 0u64;
+        for y in (y1..y2).step_by(2) {
+            for x in (x1..x2).step_by(2) {
+                let left = luma(previous.get_pixel(x, y));
+                let right = luma(current.get_pixel(x, y));
+                total += u64::from(left.abs_diff(right));
+                count += 1;
+            }
+        }
+        Ok((total as f64 / (count as f64 * 255.0)) as f32)
+    }
+
     fn fallback_wait_ms(configured_ms: u64) -> u64 {
         if configured_ms == 0 { 1_000 } else { configured_ms }
     }
@@ -101,42 +325,6 @@ impl ScriptExecutor {
     }
 
     fn extract_wait_ms_from_text(text: &str) -> Option<u64> {
-        let normalized = text.replace('：', ":");
-        for captures in Self::wait_time_regex().captures_iter(&normalized) {
-            let matched = captures.get(0)?;
-            let prev_is_digit = normalized[..matched.start()]
-                .chars()
-                .next_back()
-                .is_some_and(|ch| ch.is_ascii_digit());
-            let next_is_digit = normalized[matched.end()..]
-                .chars()
-                .next()
-                .is_some_and(|ch| ch.is_ascii_digit());
-            if prev_is_digit || next_is_digit {
-                continue;
-            }
-
-            let trailing_seconds = captures.get(3)?.as_str().parse::<u64>().ok()?;
-            let middle = captures.get(2)?.as_str().parse::<u64>().ok()?;
-            if let Some(hours) = captures.get(1) {
-                let hours = hours.as_str().parse::<u64>().ok()?;
-                if middle >= 60 || trailing_seconds >= 60 {
-                    continue;
-                }
-                return Some(((hours * 60 * 60) + (middle * 60) + trailing_seconds) * 1_000);
-            }
-
-            return Some(((middle * 60) + trailing_seconds) * 1_000);
-        }
-
-        None
-    }
-
-    fn wait_time_regex() -> &'static Regex {
-        static WAIT_TIME_REGEX: OnceLock<Regex> = OnceLock::new();
-        WAIT_TIME_REGEX.get_or_init(|| {
-            Regex::new(r"(?:(\d{1,2}):)?(\d{1,3}):(\d{2})")
-                .expect("wait time regex should be valid")
-        })
+        domain_script::parse_clock_duration_ms(text)
     }
 }
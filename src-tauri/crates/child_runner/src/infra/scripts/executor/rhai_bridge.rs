@@ -712,6 +712,72 @@ impl ScriptExecutor {
             },
         );
 
+        let queue = self.rhai_step_queue.clone();
+        self.engine.register_fn(
+            "path",
+            move |points: Array, duration_ms: INT| -> Result<(), Box<EvalAltResult>> {
+                let duration = ScriptExecutor::int_to_u64("path", "duration_ms", duration_ms)?;
+                let segments = points.len().saturating_sub(1).max(1) as u64;
+                let durations = (0..segments)
+                    .map(|index| {
+                        // 余数补到最后一段，保证总耗时与传入值一致
+                        duration / segments + if index + 1 == segments { duration % segments } else { 0 }
+                    })
+                    .collect::<Vec<_>>();
+                ScriptExecutor::enqueue_rhai_step(
+                    &queue,
+                    "path",
+                    ScriptExecutor::build_action_step(Action::Path {
+                        points: ScriptExecutor::gesture_waypoints_from_rhai("path", &points, &durations)?,
+                    }),
+                )
+            },
+        );
+
+        let queue = self.rhai_step_queue.clone();
+        self.engine.register_fn(
+            "path_timed",
+            move |points: Array, durations_ms: Array| -> Result<(), Box<EvalAltResult>> {
+                let durations = durations_ms
+                    .iter()
+                    .map(|value| {
+                        let value = value.as_int().map_err(|_| {
+                            ScriptExecutor::rhai_helper_error("path_timed() 的 durations_ms 必须是整数数组".to_string())
+                        })?;
+                        ScriptExecutor::int_to_u64("path_timed", "durations_ms", value)
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                ScriptExecutor::enqueue_rhai_step(
+                    &queue,
+                    "path_timed",
+                    ScriptExecutor::build_action_step(Action::Path {
+                        points: ScriptExecutor::gesture_waypoints_from_rhai("path_timed", &points, &durations)?,
+                    }),
+                )
+            },
+        );
+
+        let queue = self.rhai_step_queue.clone();
+        self.engine.register_fn(
+            "pinch",
+            move |center: Dynamic,
+                  start_distance: INT,
+                  end_distance: INT,
+                  duration_ms: INT|
+                  -> Result<(), Box<EvalAltResult>> {
+                ScriptExecutor::enqueue_rhai_step(
+                    &queue,
+                    "pinch",
+                    ScriptExecutor::build_action_step(Action::Pinch {
+                        center: ScriptExecutor::deserialize_rhai_helper::<RegionPoint>("pinch", &center)?,
+                        start_distance: ScriptExecutor::int_to_u16("pinch", "start_distance", start_distance)?,
+                        end_distance: ScriptExecutor::int_to_u16("pinch", "end_distance", end_distance)?,
+                        duration: ScriptExecutor::int_to_u64("pinch", "duration_ms", duration_ms)?,
+                    }),
+                )
+            },
+        );
+
         let queue = self.rhai_step_queue.clone();
         self.engine.register_fn(
             "capture",
@@ -736,6 +802,54 @@ impl ScriptExecutor {
             },
         );
 
+        let queue = self.rhai_step_queue.clone();
+        self.engine.register_fn(
+            "key",
+            move |code: String| -> Result<(), Box<EvalAltResult>> {
+                ScriptExecutor::enqueue_rhai_step(
+                    &queue,
+                    "key",
+                    ScriptExecutor::build_action_step(Action::KeyEvent {
+                        code,
+                        long_press: false,
+                        repeat: 1,
+                    }),
+                )
+            },
+        );
+
+        let queue = self.rhai_step_queue.clone();
+        self.engine.register_fn(
+            "key",
+            move |code: String, repeat: INT| -> Result<(), Box<EvalAltResult>> {
+                ScriptExecutor::enqueue_rhai_step(
+                    &queue,
+                    "key",
+                    ScriptExecutor::build_action_step(Action::KeyEvent {
+                        code,
+                        long_press: false,
+                        repeat: ScriptExecutor::int_to_u32("key", "repeat", repeat)?,
+                    }),
+                )
+            },
+        );
+
+        let queue = self.rhai_step_queue.clone();
+        self.engine.register_fn(
+            "long_key",
+            move |code: String| -> Result<(), Box<EvalAltResult>> {
+                ScriptExecutor::enqueue_rhai_step(
+                    &queue,
+                    "long_key",
+                    ScriptExecutor::build_action_step(Action::KeyEvent {
+                        code,
+                        long_press: true,
+                        repeat: 1,
+                    }),
+                )
+            },
+        );
+
         let queue = self.rhai_step_queue.clone();
         self.engine
             .register_fn("back", move || -> Result<(), Box<EvalAltResult>> {
@@ -822,7 +936,38 @@ impl ScriptExecutor {
                 ScriptExecutor::enqueue_rhai_step(
                     &queue,
                     "detect",
-                    ScriptExecutor::build_vision_step(VisionNode::Detect { input_var, out_var }),
+                    ScriptExecutor::build_vision_step(VisionNode::Detect {
+                        input_var,
+                        out_var,
+                        region_top_left: RegionPoint::default(),
+                        region_bottom_right: RegionPoint::default(),
+                    }),
+                )
+            },
+        );
+
+        let queue = self.rhai_step_queue.clone();
+        self.engine.register_fn(
+            "detect",
+            move |input_var: String,
+                  out_var: String,
+                  top_left: Dynamic,
+                  bottom_right: Dynamic|
+                  -> Result<(), Box<EvalAltResult>> {
+                ScriptExecutor::enqueue_rhai_step(
+                    &queue,
+                    "detect",
+                    ScriptExecutor::build_vision_step(VisionNode::Detect {
+                        input_var,
+                        out_var,
+                        region_top_left: ScriptExecutor::deserialize_rhai_helper::<RegionPoint>(
+                            "detect", &top_left,
+                        )?,
+                        region_bottom_right: ScriptExecutor::deserialize_rhai_helper::<RegionPoint>(
+                            "detect",
+                            &bottom_right,
+                        )?,
+                    }),
                 )
             },
         );
@@ -834,7 +979,38 @@ impl ScriptExecutor {
                 ScriptExecutor::enqueue_rhai_step(
                     &queue,
                     "ocr",
-                    ScriptExecutor::build_vision_step(VisionNode::Ocr { input_var, out_var }),
+                    ScriptExecutor::build_vision_step(VisionNode::Ocr {
+                        input_var,
+                        out_var,
+                        region_top_left: RegionPoint::default(),
+                        region_bottom_right: RegionPoint::default(),
+                    }),
+                )
+            },
+        );
+
+        let queue = self.rhai_step_queue.clone();
+        self.engine.register_fn(
+            "ocr",
+            move |input_var: String,
+                  out_var: String,
+                  top_left: Dynamic,
+                  bottom_right: Dynamic|
+                  -> Result<(), Box<EvalAltResult>> {
+                ScriptExecutor::enqueue_rhai_step(
+                    &queue,
+                    "ocr",
+                    ScriptExecutor::build_vision_step(VisionNode::Ocr {
+                        input_var,
+                        out_var,
+                        region_top_left: ScriptExecutor::deserialize_rhai_helper::<RegionPoint>(
+                            "ocr", &top_left,
+                        )?,
+                        region_bottom_right: ScriptExecutor::deserialize_rhai_helper::<RegionPoint>(
+                            "ocr",
+                            &bottom_right,
+                        )?,
+                    }),
                 )
             },
         );
@@ -1230,6 +1406,112 @@ impl ScriptExecutor {
                 )
             },
         );
+
+        let runtime_ctx = self.runtime_ctx.clone();
+        self.engine
+            .register_fn("store_get", move |key: String| -> Result<Dynamic, Box<EvalAltResult>> {
+                ScriptExecutor::store_get_now(&runtime_ctx, "store_get", &key, Dynamic::UNIT)
+            });
+
+        let runtime_ctx = self.runtime_ctx.clone();
+        self.engine.register_fn(
+            "store_get",
+            move |key: String, default: Dynamic| -> Result<Dynamic, Box<EvalAltResult>> {
+                ScriptExecutor::store_get_now(&runtime_ctx, "store_get", &key, default)
+            },
+        );
+
+        let queue = self.rhai_step_queue.clone();
+        self.engine.register_fn(
+            "store_set",
+            move |key: String, value: Dynamic| -> Result<(), Box<EvalAltResult>> {
+                ScriptExecutor::enqueue_store_set(&queue, "store_set", key, &value, StoreExpiry::Never)
+            },
+        );
+
+        let queue = self.rhai_step_queue.clone();
+        self.engine.register_fn(
+            "store_set",
+            move |key: String, value: Dynamic, ttl_seconds: INT| -> Result<(), Box<EvalAltResult>> {
+                let expiry = StoreExpiry::Ttl {
+                    seconds: ScriptExecutor::int_to_u64("store_set", "ttl_seconds", ttl_seconds)?,
+                };
+                ScriptExecutor::enqueue_store_set(&queue, "store_set", key, &value, expiry)
+            },
+        );
+
+        let queue = self.rhai_step_queue.clone();
+        self.engine.register_fn(
+            "store_set_daily",
+            move |key: String, value: Dynamic| -> Result<(), Box<EvalAltResult>> {
+                let expiry = StoreExpiry::Daily { reset_hour: 0 };
+                ScriptExecutor::enqueue_store_set(&queue, "store_set_daily", key, &value, expiry)
+            },
+        );
+
+        let queue = self.rhai_step_queue.clone();
+        self.engine.register_fn(
+            "store_set_daily",
+            move |key: String, value: Dynamic, reset_hour: INT| -> Result<(), Box<EvalAltResult>> {
+                let reset_hour = ScriptExecutor::int_to_u8("store_set_daily", "reset_hour", reset_hour)?;
+                if reset_hour > 23 {
+                    return Err(ScriptExecutor::rhai_helper_error(format!(
+                        "store_set_daily() 的 reset_hour 必须在 0-23 之间: {}",
+                        reset_hour
+                    )));
+                }
+                let expiry = StoreExpiry::Daily { reset_hour };
+                ScriptExecutor::enqueue_store_set(&queue, "store_set_daily", key, &value, expiry)
+            },
+        );
+
+        let queue = self.rhai_step_queue.clone();
+        self.engine
+            .register_fn("store_remove", move |key: String| -> Result<(), Box<EvalAltResult>> {
+                ScriptExecutor::enqueue_rhai_step(
+                    &queue,
+                    "store_remove",
+                    ScriptExecutor::build_data_step(DataHanding::StoreRemove { keys: vec![key] }),
+                )
+            });
+    }
+
+    /// 读取持久化存储缓存；未命中或已过期时返回默认值
+    fn store_get_now(
+        runtime_ctx: &SharedRuntimeContext,
+        helper_name: &'static str,
+        key: &str,
+        default: Dynamic,
+    ) -> Result<Dynamic, Box<EvalAltResult>> {
+        let stored = {
+            let ctx = Self::runtime_ctx_read_now(runtime_ctx, helper_name)?;
+            Self::runtime_store_value(&ctx.execution.runtime_store, key.trim())
+        };
+        match stored {
+            Some(value) => Self::to_rhai_dynamic(helper_name, &value),
+            None => Ok(default),
+        }
+    }
+
+    /// 写入与其它 helper 一样在 Rhai 代码块结束后执行
+    fn enqueue_store_set(
+        queue: &Arc<StdMutex<Vec<Vec<QueuedRhaiStep>>>>,
+        helper_name: &'static str,
+        key: String,
+        value: &Dynamic,
+        expiry: StoreExpiry,
+    ) -> Result<(), Box<EvalAltResult>> {
+        let json_val = Self::deserialize_rhai_helper::<Value>(helper_name, value)?;
+        Self::enqueue_rhai_step(
+            queue,
+            helper_name,
+            Self::build_data_step(DataHanding::StoreSet {
+                key,
+                json_val: Some(json_val),
+                expr: None,
+                expiry,
+            }),
+        )
     }
 
     fn rhai_helper_error(message: String) -> Box<EvalAltResult> {
@@ -1773,6 +2055,38 @@ impl ScriptExecutor {
         }
     }
 
+    /// `durations` 为相邻两点之间每段的耗时，长度必须比点数少一
+    fn gesture_waypoints_from_rhai(
+        helper_name: &'static str,
+        points: &Array,
+        durations: &[u64],
+    ) -> Result<Vec<GestureWaypoint>, Box<EvalAltResult>> {
+        if points.len() < 2 {
+            return Err(Self::rhai_helper_error(format!(
+                "{}() 至少需要两个点",
+                helper_name
+            )));
+        }
+        if durations.len() + 1 != points.len() {
+            return Err(Self::rhai_helper_error(format!(
+                "{}() 的耗时数量应为 {}，实际为 {}",
+                helper_name,
+                points.len() - 1,
+                durations.len()
+            )));
+        }
+        points
+            .iter()
+            .enumerate()
+            .map(|(index, point)| {
+                Ok(GestureWaypoint {
+                    point: Self::deserialize_rhai_helper::<RegionPoint>(helper_name, point)?,
+                    duration: index.checked_sub(1).map_or(0, |segment| durations[segment]),
+                })
+            })
+            .collect()
+    }
+
     fn swipe_mode_from_targets(
         helper_name: &'static str,
         from: &Dynamic,
@@ -1826,6 +2140,17 @@ impl ScriptExecutor {
         }
     }
 
+    fn build_data_step(data: DataHanding) -> Step {
+        Step {
+            id: None,
+            source_id: None,
+            target_id: None,
+            label: None,
+            skip_flag: false,
+            kind: StepKind::DataHanding { a: data },
+        }
+    }
+
     fn build_task_control_step(task: TaskControl) -> Step {
         Step {
             id: None,
@@ -0,0 +1,117 @@
+/// 从脚本 bundle 中解析 `import "名称"`，模块在同一个执行器内只求值一次
+struct BundleModuleResolver {
+    sources: HashMap<String, String>,
+    cache: StdMutex<HashMap<String, rhai::Shared<rhai::Module>>>,
+    loading: StdMutex<Vec<String>>,
+}
+
+impl BundleModuleResolver {
+    fn new(modules: &[RhaiModule]) -> Self {
+        Self {
+            sources: modules
+                .iter()
+                .map(|module| {
+                    (
+                        Self::normalize_module_name(&module.name),
+                        module.code.clone(),
+                    )
+                })
+                .collect(),
+            cache: StdMutex::new(HashMap::new()),
+            loading: StdMutex::new(Vec::new()),
+        }
+    }
+
+    /// `./lib/combat.rhai`、`lib\combat` 与 `lib/combat` 视为同一模块
+    fn normalize_module_name(name: &str) -> String {
+        let name = name.trim().replace('\\', "/");
+        let name = name.trim_start_matches("./");
+        name.strip_suffix(".rhai").unwrap_or(name).to_string()
+    }
+
+    fn module_error(
+        path: &str,
+        error: impl Into<Box<EvalAltResult>>,
+        pos: rhai::Position,
+    ) -> Box<EvalAltResult> {
+        Box::new(EvalAltResult::ErrorInModule(
+            path.to_string(),
+            error.into(),
+            pos,
+        ))
+    }
+
+    fn load_module(
+        &self,
+        engine: &Engine,
+        name: &str,
+        code: &str,
+        path: &str,
+        pos: rhai::Position,
+    ) -> Result<rhai::Shared<rhai::Module>, Box<EvalAltResult>> {
+        let mut ast = engine
+            .compile(code)
+            .map_err(|error| Self::module_error(path, error, pos))?;
+        ast.set_source(name);
+        let module = rhai::Module::eval_ast_as_new(Scope::new(), &ast, engine)
+            .map_err(|error| Self::module_error(path, error, pos))?;
+        Ok(module.into())
+    }
+}
+
+impl rhai::ModuleResolver for BundleModuleResolver {
+    fn resolve(
+        &self,
+        engine: &Engine,
+        _source: Option<&str>,
+        path: &str,
+        pos: rhai::Position,
+    ) -> Result<rhai::Shared<rhai::Module>, Box<EvalAltResult>> {
+        let name = Self::normalize_module_name(path);
+        if let Some(module) = self.cache.lock().unwrap().get(&name) {
+            return Ok(module.clone());
+        }
+        let code = self
+            .sources
+            .get(&name)
+            .ok_or_else(|| Box::new(EvalAltResult::ErrorModuleNotFound(path.to_string(), pos)))?;
+        {
+            let mut loading = self.loading.lock().unwrap();
+            if loading.contains(&name) {
+                let chain = loading.join(" -> ");
+                return Err(Self::module_error(
+                    path,
+                    format!("Rhai 模块循环引用: {} -> {}", chain, name),
+                    pos,
+                ));
+            }
+            loading.push(name.clone());
+        }
+        let result = self.load_module(engine, &name, code, path, pos);
+        self.loading.lock().unwrap().retain(|item| item != &name);
+        let module = result?;
+        self.cache.lock().unwrap().insert(name, module.clone());
+        Ok(module)
+    }
+}
+
+impl ScriptExecutor {
+    /// 按当前脚本携带的 Rhai 模块重建模块解析器
+    pub(crate) async fn install_script_rhai_modules(&mut self) {
+        let modules = {
+            let ctx = self.runtime_ctx.read().await;
+            ctx.execution
+                .script_info
+                .as_ref()
+                .map(|info| info.rhai_modules.clone())
+                .unwrap_or_default()
+        };
+        self.install_rhai_modules(&modules);
+    }
+
+    fn install_rhai_modules(&mut self, modules: &[RhaiModule]) {
+        self.engine
+            .set_module_resolver(BundleModuleResolver::new(modules));
+        self.compiled_rhai_blocks.clear();
+    }
+}
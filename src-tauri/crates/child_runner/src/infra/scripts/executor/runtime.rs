@@ -113,6 +113,31 @@ impl ScriptExecutor {
         }
     }
 
+    /// 多账号轮换时把当前账号装入 `system.account`，供切换账号任务与后续任务读取
+    pub(crate) async fn set_account_scope(
+        &mut self,
+        account_id: &str,
+        account_name: Option<&str>,
+        account_data_json: Option<&str>,
+    ) -> ExecuteResult<()> {
+        let data = match account_data_json {
+            Some(content) if !content.trim().is_empty() => serde_json::from_str::<Value>(content)
+                .map_err(|error| {
+                    Self::execute_error("runtime.account", format!("解析账号资料失败: {}", error))
+                })?,
+            _ => Value::Null,
+        };
+        let account = json!({
+            "id": account_id,
+            "name": account_name.unwrap_or(account_id),
+            "data": data,
+        });
+        let dynamic = to_dynamic(&account).map_err(|error| {
+            Self::execute_error("runtime.account", format!("账号信息装入运行时失败: {}", error))
+        })?;
+        self.set_runtime_var("system.account", dynamic).await
+    }
+
     fn parse_runtime_template_values(
         json: Option<&str>,
     ) -> ExecuteResult<Option<RuntimeTemplateValuesSnapshot>> {
@@ -131,6 +131,28 @@ impl<'a> SequenceOperationCompiler<'a> {
                 offset_y,
             } => self.compile_click(mode, *offset_x, *offset_y).await,
             Action::Swipe { duration, mode } => self.compile_swipe(mode, *duration).await,
+            Action::Path { points } => {
+                let plan = self.executor.plan_path_action(points).await?;
+                Ok(Self::compile_planned_gesture(
+                    plan,
+                    format!("轨迹滑动({} 个点)", points.len()),
+                ))
+            }
+            Action::Pinch {
+                center,
+                start_distance,
+                end_distance,
+                duration,
+            } => {
+                let plan = self
+                    .executor
+                    .plan_pinch_action(center, *start_distance, *end_distance, *duration)
+                    .await?;
+                Ok(Self::compile_planned_gesture(
+                    plan,
+                    format!("双指缩放({} -> {})", start_distance, end_distance),
+                ))
+            }
             Action::LongClick {
                 mode,
                 offset_x,
@@ -163,6 +185,21 @@ impl<'a> SequenceOperationCompiler<'a> {
                 )),
                 debug_label: format!("输入文本({})", text),
             })),
+            Action::KeyEvent {
+                code,
+                long_press,
+                repeat,
+            } => Ok(Ok(CompiledSequenceOperation {
+                operation: DeviceOperation::KeyEvent {
+                    code: code.clone(),
+                    long_press: *long_press,
+                    repeat: *repeat,
+                },
+                trace: Some(ActionTraceBuilder::build_simple_action_trace(
+                    PolicyActionKind::Press,
+                )),
+                debug_label: format!("按键({})", code),
+            })),
             Action::PosAdd { .. } | Action::PosMinus { .. } => Ok(Err(
                 "策略点击索引调整依赖运行时策略状态，不能合并进 Sequence".to_string(),
             )),
@@ -267,9 +304,9 @@ impl<'a> SequenceOperationCompiler<'a> {
             .executor
             .apply_click_fixed_offset(point, offset_x, offset_y)
             .await?;
-        let point = self.executor.apply_click_random_offset(point).await?;
+        // 序列只接受固定点位，没有识别框可供抖动，落点按拟人化配置的半径散布
         Ok(Ok(CompiledSequenceOperation {
-            operation: DeviceOperation::Click(point),
+            operation: self.executor.click_operation(point, None).await?,
             trace: Some(ActionTraceBuilder::build_action_trace(
                 PolicyActionKind::Click,
                 PolicyActionSource::Fixed,
@@ -322,9 +359,9 @@ impl<'a> SequenceOperationCompiler<'a> {
             .executor
             .apply_click_fixed_offset(point, offset_x, offset_y)
             .await?;
-        let point = self.executor.apply_click_random_offset(point).await?;
+        // 序列只接受固定点位，没有识别框可供抖动，落点按拟人化配置的半径散布
         Ok(Ok(CompiledSequenceOperation {
-            operation: DeviceOperation::LongClick(point),
+            operation: self.executor.long_click_operation(point, None).await?,
             trace: Some(ActionTraceBuilder::build_action_trace(
                 PolicyActionKind::Press,
                 PolicyActionSource::Fixed,
@@ -391,7 +428,7 @@ impl<'a> SequenceOperationCompiler<'a> {
             }
         };
         Ok(Ok(CompiledSequenceOperation {
-            operation: DeviceOperation::Swipe { from, to, duration },
+            operation: self.executor.swipe_operation(from, to, duration).await?,
             trace: Some(ActionTraceBuilder::build_action_trace(
                 PolicyActionKind::Swipe,
                 PolicyActionSource::Fixed,
@@ -407,6 +444,21 @@ impl<'a> SequenceOperationCompiler<'a> {
         }))
     }
 
+    fn compile_planned_gesture(
+        plan: PlannedDeviceAction,
+        debug_label: String,
+    ) -> Result<CompiledSequenceOperation, String> {
+        let mut operations = plan.operations.into_iter();
+        match (operations.next(), operations.next()) {
+            (Some(operation), None) => Ok(CompiledSequenceOperation {
+                operation,
+                trace: plan.trace,
+                debug_label,
+            }),
+            _ => Err("手势规划结果不是单条设备动作，不能合并进 Sequence".to_string()),
+        }
+    }
+
     fn describe_step(&self, step: &Step) -> String {
         step.label
             .as_deref()
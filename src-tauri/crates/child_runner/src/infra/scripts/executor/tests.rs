@@ -1,20 +1,23 @@
-use super::{ControlFlow, ScriptExecutor};
+use super::{ControlFlow, RegionRect, ScriptExecutor};
 use crate::infra::context::runtime_context::RuntimeContext;
 use crate::infra::session::runtime_session::{clear_runtime_session, replace_runtime_session};
 use ad_kernel::LogLevel;
 use ad_kernel::ids::{PolicyId, TaskId, UuidV7};
 use domain_device::{DeviceOperation, TimeoutAction};
+use domain_script::RhaiModule;
 use domain_script::ScriptInfo;
 use domain_script::ScriptTask;
 use domain_script::TaskCycle;
 use domain_script::{
     Action, ClickMode, ColorCompareMethod, ColorRgb, CompareOp, ConditionNode,
-    CurrentTaskCondition, DataHanding, FlowControl, OcrTextMatchMode, PointU16,
-    PolicySetResultCompareOp, PrintSource, StateStatus, StateTarget, Step, StepKind, SwipeMode,
-    TaskControl, VisionNode,
+    CurrentTaskCondition, DataHanding, FlowControl, OcrTextMatchMode, PointF32, PointU16,
+    PolicySetResultCompareOp, PrintSource, RegionPoint, StateStatus, StateTarget, Step, StepKind,
+    SwipeMode, TaskControl, VisionNode,
 };
 use domain_script::{DropSetDirection, PolicyInfo};
+use domain_script::{NumberField, NumberKind, OcrNumberSource};
 use domain_script::{PolicyProfile, ScriptTaskProfile, TaskRowType, TaskTone, TaskTriggerMode};
+use domain_script::{RuntimeStoreEntry, StoreExpiry, VarValue};
 use domain_script::{
     ScriptVariableCatalog, ScriptVariableDef, ScriptVariableNamespace, ScriptVariableSourceType,
     ScriptVariableValueType,
@@ -123,6 +126,39 @@ fn select_ocr_result_uses_position_for_policy_click() {
     assert_eq!(clamped.bounding_box.x1, 100);
 }
 
+#[test]
+fn vision_region_crops_input_and_keeps_screen_offset() {
+    let mut image = RgbaImage::new(200, 100);
+    image.put_pixel(150, 60, Rgba([255, 0, 0, 255]));
+
+    let region = ScriptExecutor::resolve_image_region(
+        "vision.ocr",
+        &RegionPoint::Percent {
+            p: PointF32 { x: 0.5, y: 0.5 },
+        },
+        &RegionPoint::Point {
+            p: PointU16 { x: 400, y: 400 },
+        },
+        (image.width(), image.height()),
+    )
+    .unwrap();
+    let (cropped, offset) = ScriptExecutor::crop_image_to_region(Arc::new(image), region);
+
+    assert_eq!((cropped.width(), cropped.height()), (100, 50));
+    assert_eq!(offset, Some((100, 50)));
+    assert_eq!(cropped.get_pixel(50, 10), &Rgba([255, 0, 0, 255]));
+    assert!(
+        ScriptExecutor::resolve_image_region(
+            "vision.ocr",
+            &RegionPoint::default(),
+            &RegionPoint::default(),
+            (200, 100),
+        )
+        .unwrap()
+        .is_none()
+    );
+}
+
 #[test]
 fn select_det_result_matches_label_index() {
     let items = vec![
@@ -220,6 +256,25 @@ fn drop_set_next_cycles_options_and_updates_template_root() {
     assert!(root.get("taskSettings").is_some());
 }
 
+#[test]
+fn gesture_waypoints_from_rhai_assign_segment_durations() {
+    let points = vec![
+        to_dynamic(json!({ "mode": "point", "p": { "x": 10, "y": 10 } })).unwrap(),
+        to_dynamic(json!({ "mode": "point", "p": { "x": 20, "y": 10 } })).unwrap(),
+        to_dynamic(json!({ "mode": "percent", "p": { "x": 0.5, "y": 0.5 } })).unwrap(),
+    ];
+    let waypoints =
+        ScriptExecutor::gesture_waypoints_from_rhai("path_timed", &points, &[100, 250]).unwrap();
+    assert_eq!(
+        waypoints
+            .iter()
+            .map(|waypoint| waypoint.duration)
+            .collect::<Vec<_>>(),
+        vec![0, 100, 250]
+    );
+    assert!(ScriptExecutor::gesture_waypoints_from_rhai("path_timed", &points, &[100]).is_err());
+}
+
 #[test]
 fn bounding_box_center_converts_to_device_point() {
     let point = ScriptExecutor::bounding_box_center_to_point(
@@ -288,6 +343,54 @@ fn ocr_text_cache_key_changes_with_pixels() {
     assert_ne!(key_a, key_b);
 }
 
+#[test]
+fn ocr_text_cache_hit_keeps_current_detection_boxes() {
+    let mut image = RgbaImage::from_pixel(200, 120, Rgba([255, 255, 255, 255]));
+    fill_rect(
+        &mut image,
+        &BoundingBox::new(110, 60, 150, 80),
+        [20, 20, 20, 255],
+    );
+    let image = Arc::new(image);
+    let region = RegionRect {
+        x1: 100,
+        y1: 50,
+        x2: 180,
+        y2: 100,
+    };
+    let full_det = build_det_result(0, "text", 110, 60, 150, 80);
+    let roi_det = build_det_result(0, "text", 10, 10, 50, 30);
+
+    let (full_image, _) = ScriptExecutor::crop_image_to_region(Arc::clone(&image), None);
+    let (roi_image, offset) = ScriptExecutor::crop_image_to_region(image, Some(region));
+    let (dx, dy) = offset.unwrap();
+    let crop_key = |image: &RgbaImage, det: &DetResult| {
+        let (_, crop) = ScriptExecutor::collect_ocr_crop_entries(image, std::slice::from_ref(det))
+            .pop()
+            .unwrap();
+        ScriptExecutor::build_ocr_text_cache_key(&crop, "rec:a")
+    };
+    assert_eq!(
+        crop_key(full_image.as_ref(), &full_det),
+        crop_key(roi_image.as_ref(), &roi_det)
+    );
+
+    // 整屏识别写入缓存后，区域识别命中：坐标取区域内检测框，再平移回整屏
+    let full_cached = build_ocr_result("开始", 110, 60, 150, 80);
+    let roi_hit =
+        ScriptExecutor::reuse_cached_ocr_result(&full_cached, &roi_det).translated(dx, dy, 8);
+    assert_eq!(roi_hit.txt, "开始");
+    assert_eq!(roi_hit.bounding_box, BoundingBox::new(110, 60, 150, 80));
+    assert_eq!(roi_hit.stable_box, full_cached.stable_box);
+
+    // 区域识别写入缓存后，整屏识别命中：不能沿用区域内的局部坐标
+    let roi_cached = build_ocr_result("开始", 10, 10, 50, 30);
+    let full_hit = ScriptExecutor::reuse_cached_ocr_result(&roi_cached, &full_det);
+    assert_eq!(full_hit.txt, "开始");
+    assert_eq!(full_hit.bounding_box, BoundingBox::new(110, 60, 150, 80));
+    assert_eq!(full_hit.stable_center, full_det.stable_center);
+}
+
 #[test]
 fn ocr_text_cache_key_changes_with_recognizer_signature() {
     let image = RgbaImage::from_pixel(4, 4, Rgba([12, 34, 56, 255]));
@@ -349,6 +452,7 @@ fn build_task_with_variables(task_id: TaskId, variables: Value) -> ScriptTaskPro
             ui_data: Value::Null,
             variables,
             steps: Vec::new(),
+            retry_policy: None,
         },
         created_at: chrono::Utc::now(),
         updated_at: chrono::Utc::now(),
@@ -473,6 +577,8 @@ async fn install_runtime_policy_for_test(timeout_action: TimeoutAction) {
             progress_timeout_ms: 1_000,
             timeout_action,
             timeout_notify_channels: Vec::new(),
+            retry_policy: domain_schedule::TaskRetryPolicy::default(),
+            humanize: domain_device::HumanizeProfile::default(),
         },
         queue: vec![RuntimeQueueItem {
             dispatch_id: UuidV7(504),
@@ -482,11 +588,13 @@ async fn install_runtime_policy_for_test(timeout_action: TimeoutAction) {
             script_id: UuidV7(1),
             time_template_id: None,
             account_id: None,
+            account_name: None,
             account_data_json: None,
             order_index: 0,
             window_start_at: None,
             template_values_json: None,
             dedup_scope_base_hash: String::new(),
+            account_runs: Vec::new(),
         }],
         script_bundles: Vec::new(),
         issued_at: chrono::Utc::now().to_rfc3339(),
@@ -536,6 +644,61 @@ fn build_rhai_step(code: &str, out_var: Option<&str>) -> Step {
     }
 }
 
+#[tokio::test]
+async fn rhai_step_imports_modules_from_script_bundle() {
+    let mut executor = build_executor();
+    {
+        let mut ctx = executor.runtime_ctx.write().await;
+        ctx.execution.script_info = Some(ScriptInfo {
+            rhai_modules: vec![
+                RhaiModule {
+                    name: "lib/math".to_string(),
+                    code: "fn twice(x) { x * 2 }".to_string(),
+                },
+                RhaiModule {
+                    name: "combat.rhai".to_string(),
+                    code: "import \"./lib/math\" as m; fn double(x) { m::twice(x) }".to_string(),
+                },
+                RhaiModule {
+                    name: "loop_a".to_string(),
+                    code: "import \"loop_b\" as b;".to_string(),
+                },
+                RhaiModule {
+                    name: "loop_b".to_string(),
+                    code: "import \"loop_a\" as a;".to_string(),
+                },
+            ],
+            ..ScriptInfo::default()
+        });
+    }
+    executor.install_script_rhai_modules().await;
+
+    executor
+        .execute(&[build_rhai_step(
+            "import \"combat\" as c; c::double(21)",
+            Some("runtime.result"),
+        )])
+        .await
+        .unwrap();
+    let result = executor.read_runtime_var("runtime.result").await.unwrap();
+    assert_eq!(
+        ScriptExecutor::deserialize_dynamic_value::<i64>(&result).unwrap(),
+        42
+    );
+
+    assert!(
+        executor
+            .execute(&[build_rhai_step("import \"missing\" as x;", None)])
+            .await
+            .is_err()
+    );
+    let cyclic = executor
+        .execute(&[build_rhai_step("import \"loop_a\" as a;", None)])
+        .await
+        .unwrap_err();
+    assert!(format!("{cyclic:?}").contains("循环引用"));
+}
+
 #[tokio::test]
 async fn print_step_accepts_text_and_variable_sources() {
     let mut executor = build_executor();
@@ -608,6 +771,158 @@ async fn rhai_step_executes_compiled_block_and_syncs_runtime_roots() {
     assert_eq!(executor.compiled_rhai_blocks.len(), 1);
 }
 
+#[tokio::test]
+async fn extract_number_step_and_condition_read_anchored_ocr_text() {
+    let mut executor = build_executor();
+    executor
+        .set_runtime_var(
+            "runtime.ocrResults",
+            to_dynamic(vec![
+                build_ocr_result("等级 12", 0, 0, 40, 12),
+                build_ocr_result("体力 40/120", 0, 20, 40, 32),
+            ])
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+    let source = OcrNumberSource {
+        input_var: "runtime.ocrResults".to_string(),
+        region_top_left: RegionPoint::default(),
+        region_bottom_right: RegionPoint::default(),
+        anchor_text: "体力".to_string(),
+        anchor_direction: None,
+        kind: NumberKind::Auto,
+    };
+
+    executor
+        .execute_data_handling_step(&DataHanding::ExtractNumber {
+            source: source.clone(),
+            field: NumberField::Denominator,
+            out_var: "runtime.staminaMax".to_string(),
+        })
+        .await
+        .unwrap();
+    let value = executor
+        .read_runtime_var("runtime.staminaMax")
+        .await
+        .unwrap();
+    assert_eq!(value.as_int().unwrap(), 120);
+
+    let condition = |op: CompareOp, value: f64| ConditionNode::OcrNumberCompare {
+        source: source.clone(),
+        field: NumberField::Value,
+        op,
+        value,
+    };
+    assert!(
+        executor
+            .evaluate_condition(&condition(CompareOp::Ge, 40.0))
+            .await
+            .unwrap()
+    );
+    assert!(
+        !executor
+            .evaluate_condition(&condition(CompareOp::Gt, 40.0))
+            .await
+            .unwrap()
+    );
+
+    let missing = ConditionNode::OcrNumberCompare {
+        source: OcrNumberSource {
+            anchor_text: "金币".to_string(),
+            ..source.clone()
+        },
+        field: NumberField::Value,
+        op: CompareOp::Ge,
+        value: 0.0,
+    };
+    assert!(!executor.evaluate_condition(&missing).await.unwrap());
+}
+
+#[tokio::test]
+async fn store_helpers_read_cache_and_queue_writes() {
+    let mut executor = build_executor();
+    {
+        let now = chrono::Local::now();
+        let mut ctx = executor.runtime_ctx.write().await;
+        for entry in [
+            RuntimeStoreEntry::new("coins".to_string(), json!(42), StoreExpiry::Never, now),
+            RuntimeStoreEntry {
+                expires_at: Some((now - chrono::Duration::seconds(1)).to_rfc3339()),
+                ..RuntimeStoreEntry::new(
+                    "expired".to_string(),
+                    json!("old"),
+                    StoreExpiry::Never,
+                    now,
+                )
+            },
+        ] {
+            ctx.execution.runtime_store.insert(entry.key.clone(), entry);
+        }
+    }
+
+    let (value, _) = executor
+        .execute_rhai_block(
+            r#"store_get("coins") + store_get("expired", 7)"#,
+            "test.rhai",
+        )
+        .await
+        .unwrap();
+    assert_eq!(value.as_int().unwrap(), 49);
+
+    executor
+        .execute_data_handling_step(&DataHanding::StoreGet {
+            key: "expired".to_string(),
+            out_var: "runtime.lastReward".to_string(),
+            default_val: Some(VarValue::String {
+                value: "never".to_string(),
+            }),
+        })
+        .await
+        .unwrap();
+    let value = executor
+        .read_runtime_var("runtime.lastReward")
+        .await
+        .unwrap();
+    assert_eq!(value.into_string().unwrap(), "never");
+
+    executor.push_rhai_step_queue();
+    assert!(
+        executor
+            .eval_rhai_block(
+                r#"store_set_daily("lastReward", #{ at: 5 }, 4)"#,
+                "test.rhai"
+            )
+            .unwrap()
+            .is_unit()
+    );
+    let error = executor
+        .eval_rhai_block(r#"store_set_daily("k", 1, 24)"#, "test.rhai")
+        .unwrap_err();
+    assert!(error.to_string().contains("reset_hour"));
+    let queued = executor.pop_rhai_step_queue();
+    assert_eq!(queued.len(), 1);
+    let step = executor
+        .materialize_queued_rhai_op(queued[0].helper_name, queued[0].op.clone())
+        .await
+        .unwrap();
+    let StepKind::DataHanding {
+        a:
+            DataHanding::StoreSet {
+                key,
+                json_val,
+                expiry,
+                ..
+            },
+    } = step.kind
+    else {
+        panic!("store_set_daily 应生成 StoreSet 步骤");
+    };
+    assert_eq!(key, "lastReward");
+    assert_eq!(json_val, Some(json!({ "at": 5 })));
+    assert_eq!(expiry, StoreExpiry::Daily { reset_hour: 4 });
+}
+
 #[tokio::test]
 async fn set_var_accepts_json_payload_for_structured_variables() {
     let mut executor = build_executor();
@@ -783,6 +1098,7 @@ async fn rhai_task_helper_updates_runtime_state() {
                 ui_data: Value::Null,
                 variables: Value::Null,
                 steps: Vec::new(),
+                retry_policy: None,
             },
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
@@ -835,6 +1151,8 @@ fn build_policy_table(
             before_action,
             cond: SearchRule::Txt {
                 pattern: "开始".to_string(),
+                mode: Default::default(),
+                normalize: Default::default(),
             },
             after_action,
         },
@@ -970,6 +1288,25 @@ async fn hydrate_input_scope_prefers_template_values_for_policy_debug_target() {
     );
 }
 
+#[tokio::test]
+async fn set_account_scope_exposes_account_under_system_namespace() {
+    let mut executor = build_executor_with_target(RunTarget::DeviceQueue);
+
+    executor
+        .set_account_scope("alt", Some("小号"), Some(r#"{"server":"s2"}"#))
+        .await
+        .unwrap();
+
+    let account = executor.read_runtime_var("system.account").await.unwrap();
+    let account = ScriptExecutor::deserialize_dynamic_value::<serde_json::Value>(&account).unwrap();
+    assert_eq!(
+        account,
+        serde_json::json!({ "id": "alt", "name": "小号", "data": { "server": "s2" } })
+    );
+    let system = executor.scope.get_value::<Dynamic>("system").unwrap();
+    assert!(system.is_map());
+}
+
 #[tokio::test]
 async fn policy_debug_candidate_steps_can_read_task_owned_inputs_after_hydration() {
     let script_id = UuidV7(1);
@@ -1142,6 +1479,7 @@ async fn policy_set_search_hits_are_cleared_when_no_policy_matches() {
                     pattern: "未命中".to_string(),
                     ocr_index: 0,
                     ocr_item: build_ocr_result("未命中", 0, 0, 30, 12),
+                    similarity: 1.0,
                 }],
                 det_results: Vec::new(),
             },
@@ -1180,6 +1518,8 @@ async fn timeout_handling_resets_progress_probe_after_stop_execution() {
         progress_timeout_ms: 1_000,
         timeout_action: TimeoutAction::StopExecution,
         timeout_notify_channels: Vec::new(),
+        retry_policy: domain_schedule::TaskRetryPolicy::default(),
+        humanize: domain_device::HumanizeProfile::default(),
     };
 
     let error = executor
@@ -1196,6 +1536,171 @@ async fn timeout_handling_resets_progress_probe_after_stop_execution() {
     assert!(executor.last_progress_probe.is_none());
 }
 
+#[tokio::test]
+async fn resuming_from_breakpoint_restarts_progress_timeout() {
+    use crate::infra::context::debug_control;
+    use runner_protocol::message::DebugResumeMode;
+
+    let _guard = acquire_runtime_session_test_guard().await;
+    install_runtime_policy_for_test(TimeoutAction::SkipCurrentTask).await;
+    let mut executor = build_executor();
+    let step_id = UuidV7::new_v7();
+    let step = Step {
+        id: Some(step_id),
+        source_id: None,
+        target_id: None,
+        label: None,
+        skip_flag: false,
+        kind: StepKind::FlowControl {
+            a: FlowControl::If {
+                con: ConditionNode::RawExpr {
+                    expr: "false".to_string(),
+                },
+                then: Vec::new(),
+                else_steps: None,
+            },
+        },
+    };
+    // 暂停前该步骤已停滞 900ms，阈值为 1000ms，暂停本身不应计入
+    executor.last_progress_probe = Some(super::ProgressProbe {
+        page_fingerprint: None,
+        evidence_signature: "flow.if".to_string(),
+        task_id: None,
+        step_id: Some(step_id),
+        stagnant_since: Instant::now() - std::time::Duration::from_millis(900),
+        notified: false,
+    });
+    debug_control::set_breakpoints(vec![step_id]);
+    let resume = tokio::spawn(async {
+        tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+        while !debug_control::resume_debug(DebugResumeMode::Continue) {
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+    });
+
+    let flow = executor.execute(std::slice::from_ref(&step)).await;
+    resume.await.unwrap();
+    debug_control::reset_debug_session();
+
+    assert!(matches!(flow.unwrap(), super::ControlFlow::Next));
+    let probe = executor.last_progress_probe.as_ref().unwrap();
+    assert!(!probe.notified);
+    assert!(probe.stagnant_since.elapsed() < std::time::Duration::from_millis(300));
+    clear_runtime_session().await;
+}
+
+fn wait_step(id: UuidV7) -> Step {
+    Step {
+        id: Some(id),
+        source_id: None,
+        target_id: None,
+        label: None,
+        skip_flag: false,
+        kind: StepKind::FlowControl {
+            a: FlowControl::WaitMs {
+                ms: 0,
+                input_var: None,
+                runtime_var: None,
+            },
+        },
+    }
+}
+
+/// 等到执行器暂停在 `step_id` 后按 `mode` 继续
+async fn resume_when_paused_at(
+    runtime_ctx: &Arc<RwLock<RuntimeContext>>,
+    step_id: UuidV7,
+    mode: runner_protocol::message::DebugResumeMode,
+) {
+    use crate::infra::context::debug_control;
+
+    tokio::time::timeout(std::time::Duration::from_secs(5), async {
+        loop {
+            let current_step_id = runtime_ctx.read().await.execution.current_step_id;
+            if current_step_id == Some(step_id) && debug_control::resume_debug(mode) {
+                return;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+    })
+    .await
+    .unwrap_or_else(|_| panic!("执行器没有暂停在步骤 {:?}", step_id));
+}
+
+async fn execute_debug_session(executor: &mut ScriptExecutor, steps: &[Step]) -> ControlFlow {
+    tokio::time::timeout(std::time::Duration::from_secs(5), executor.execute(steps))
+        .await
+        .expect("调试会话未能执行完毕")
+        .unwrap()
+}
+
+#[tokio::test]
+async fn debugger_pauses_steps_and_resumes_around_action_sequences() {
+    use crate::infra::context::debug_control;
+    use runner_protocol::message::DebugResumeMode;
+
+    let _guard = acquire_runtime_session_test_guard().await;
+    install_runtime_policy_for_test(TimeoutAction::SkipCurrentTask).await;
+    let mut executor = build_executor();
+    let runtime_ctx = executor.runtime_ctx.clone();
+    let (sequence_id, first_id, second_id, after_id) = (
+        UuidV7::new_v7(),
+        UuidV7::new_v7(),
+        UuidV7::new_v7(),
+        UuidV7::new_v7(),
+    );
+    let steps = vec![
+        Step {
+            id: Some(sequence_id),
+            source_id: None,
+            target_id: None,
+            label: None,
+            skip_flag: false,
+            kind: StepKind::Sequence {
+                steps: vec![wait_step(first_id), wait_step(second_id)],
+            },
+        },
+        wait_step(after_id),
+    ];
+
+    // Sequence 内有断点时逐个进入子步骤，单步跳过按同层推进
+    debug_control::set_breakpoints(vec![first_id]);
+    let ctx = runtime_ctx.clone();
+    let control = tokio::spawn(async move {
+        resume_when_paused_at(&ctx, first_id, DebugResumeMode::StepOver).await;
+        resume_when_paused_at(&ctx, second_id, DebugResumeMode::StepOver).await;
+        resume_when_paused_at(&ctx, after_id, DebugResumeMode::Continue).await;
+    });
+    let flow = execute_debug_session(&mut executor, &steps).await;
+    control.await.unwrap();
+    assert!(matches!(flow, ControlFlow::Next));
+
+    // 单步跳过整个 Sequence 时子步骤不会暂停，整批执行后停在下一个同层步骤
+    debug_control::set_breakpoints(vec![sequence_id]);
+    let ctx = runtime_ctx.clone();
+    let control = tokio::spawn(async move {
+        resume_when_paused_at(&ctx, sequence_id, DebugResumeMode::StepOver).await;
+        assert!(!debug_control::any_debug_pause([Some(first_id)], 2));
+        resume_when_paused_at(&ctx, after_id, DebugResumeMode::Continue).await;
+    });
+    let flow = execute_debug_session(&mut executor, &steps).await;
+    control.await.unwrap();
+    assert!(matches!(flow, ControlFlow::Next));
+
+    // 单步进入 Sequence 时停在第一个子步骤，继续后不再暂停
+    let ctx = runtime_ctx.clone();
+    let control = tokio::spawn(async move {
+        resume_when_paused_at(&ctx, sequence_id, DebugResumeMode::StepInto).await;
+        resume_when_paused_at(&ctx, first_id, DebugResumeMode::Continue).await;
+    });
+    let flow = execute_debug_session(&mut executor, &steps).await;
+    control.await.unwrap();
+    debug_control::reset_debug_session();
+
+    assert!(matches!(flow, ControlFlow::Next));
+    clear_runtime_session().await;
+}
+
 #[tokio::test]
 async fn device_operation_helper_times_out() {
     let error = ScriptExecutor::await_device_result_with_timeout(
@@ -1348,6 +1853,8 @@ async fn vision_search_path_triggers_timeout_detector() {
             ocr_res_var: None,
             rule: SearchRule::Txt {
                 pattern: "开始".to_string(),
+                mode: Default::default(),
+                normalize: Default::default(),
             },
             out_var: "runtime.visionHits".to_string(),
             out_det_var: None,
@@ -1554,6 +2061,47 @@ async fn swipe_point_expr_reads_independent_json_point_variables() {
     ));
 }
 
+#[tokio::test]
+async fn humanized_swipe_becomes_curved_gesture() {
+    let executor = build_executor();
+    {
+        let mut ctx = executor.runtime_ctx.write().await;
+        let mut script_info = ScriptInfo::default();
+        script_info.runtime_settings.humanize = Some(domain_device::HumanizeProfile {
+            enabled: true,
+            seed: Some(7),
+            ..domain_device::HumanizeProfile::default()
+        });
+        ctx.execution.script_info = Some(script_info);
+        ctx.observation.screen_size = (1280, 720);
+    }
+
+    let operation = executor
+        .swipe_operation(
+            ad_kernel::Point::new(100, 600),
+            ad_kernel::Point::new(900, 200),
+            400,
+        )
+        .await
+        .unwrap();
+
+    let DeviceOperation::Gesture(strokes) = operation else {
+        panic!("拟人化滑动应转成 Gesture: {operation:?}");
+    };
+    let [stroke] = strokes.as_slice() else {
+        panic!("拟人化滑动应只有一条轨迹: {strokes:?}");
+    };
+    assert!(stroke.len() > 2, "{stroke:?}");
+    let (from, to) = (stroke[0].point, stroke[stroke.len() - 1].point);
+    assert!(
+        from.x.abs_diff(100) <= 6 && from.y.abs_diff(600) <= 6,
+        "{from:?}"
+    );
+    assert!(to.x.abs_diff(900) <= 6 && to.y.abs_diff(200) <= 6, "{to:?}");
+    let duration = stroke.iter().map(|waypoint| waypoint.duration).sum::<u64>();
+    assert!((340..=460).contains(&duration), "{duration}");
+}
+
 #[tokio::test]
 async fn compile_action_sequence_rejects_bound_swipe_points() {
     let mut executor = build_executor();
@@ -1834,3 +2382,128 @@ fn color_compare_matches_font_color_against_top_three_clusters() {
         &ColorCompareMethod::OklabDistance { threshold: 0.04 },
     ));
 }
+
+#[tokio::test]
+async fn template_match_step_writes_det_compatible_results() {
+    let mut executor = build_executor();
+    let template = RgbaImage::from_fn(24, 16, |x, y| {
+        let value = if x < 12 { 30 + y * 8 } else { 220 - x * 4 };
+        Rgba([value as u8, 90, 200 - value as u8, 255])
+    });
+    let mut canvas = RgbaImage::from_pixel(120, 80, Rgba([128, 128, 128, 255]));
+    image::imageops::replace(&mut canvas, &template, 60, 40);
+    let encoded =
+        infra_vision::dynamic_image_to_base64(&image::DynamicImage::ImageRgba8(template)).unwrap();
+    executor
+        .set_runtime_var("runtime.captureResult", Dynamic::from(Arc::new(canvas)))
+        .await
+        .unwrap();
+
+    let flow = executor
+        .execute_vision_step(&VisionNode::TemplateMatch {
+            input_var: "runtime.captureResult".to_string(),
+            template: format!("data:image/png;base64,{}", encoded),
+            label: "icon".to_string(),
+            idx: 7,
+            threshold: 0.9,
+            scales: vec![1.0],
+            max_results: 0,
+            region_top_left: Default::default(),
+            region_bottom_right: Default::default(),
+            out_var: "runtime.templateHits".to_string(),
+        })
+        .await
+        .unwrap();
+    assert!(matches!(flow, ControlFlow::Next));
+
+    let value = executor
+        .read_runtime_var("runtime.templateHits")
+        .await
+        .unwrap();
+    let results = ScriptExecutor::deserialize_dynamic_value::<Vec<DetResult>>(&value).unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].bounding_box, BoundingBox::new(60, 40, 84, 56));
+    assert_eq!(results[0].index, 7);
+    assert_eq!(results[0].label, "icon");
+}
+
+#[test]
+fn frame_diff_ratio_respects_region() {
+    let previous = RgbaImage::from_pixel(40, 20, Rgba([100, 100, 100, 255]));
+    let mut current = previous.clone();
+    fill_rect(
+        &mut current,
+        &BoundingBox::new(0, 0, 9, 19),
+        [255, 255, 255, 255],
+    );
+
+    let full = ScriptExecutor::frame_diff_ratio(&previous, &current, None).unwrap();
+    assert!(full > 0.1 && full < 0.2);
+
+    let changed_region = super::RegionRect {
+        x1: 0,
+        y1: 0,
+        x2: 10,
+        y2: 20,
+    };
+    let changed =
+        ScriptExecutor::frame_diff_ratio(&previous, &current, Some(&changed_region)).unwrap();
+    assert!((changed - 155.0 / 255.0).abs() < 0.01);
+
+    let untouched_region = super::RegionRect {
+        x1: 20,
+        y1: 0,
+        x2: 40,
+        y2: 20,
+    };
+    assert_eq!(
+        ScriptExecutor::frame_diff_ratio(&previous, &current, Some(&untouched_region)),
+        Ok(0.0)
+    );
+
+    let off_screen_region = super::RegionRect {
+        x1: 60,
+        y1: 0,
+        x2: 80,
+        y2: 20,
+    };
+    assert!(
+        ScriptExecutor::frame_diff_ratio(&previous, &current, Some(&off_screen_region)).is_err()
+    );
+}
+
+#[test]
+fn frame_diff_ratio_treats_resized_frames_as_changed() {
+    let previous = RgbaImage::from_pixel(40, 20, Rgba([0, 0, 0, 255]));
+    let current = RgbaImage::from_pixel(20, 40, Rgba([0, 0, 0, 255]));
+    assert_eq!(
+        ScriptExecutor::frame_diff_ratio(&previous, &current, None),
+        Ok(1.0)
+    );
+}
+
+#[test]
+fn wait_until_deserializes_with_defaults() {
+    let flow: FlowControl = serde_json::from_value(json!({
+        "type": "waitUntil",
+        "until": { "type": "stable", "stable_ms": 800 }
+    }))
+    .unwrap();
+    match flow {
+        FlowControl::WaitUntil {
+            until,
+            timeout_ms,
+            interval_ms,
+            else_steps,
+        } => {
+            assert_eq!(timeout_ms, 10_000);
+            assert_eq!(interval_ms, 300);
+            assert!(else_steps.is_empty());
+            assert!(matches!(
+                until,
+                domain_script::WaitUntilTarget::Stable { stable_ms: 800, .. }
+            ));
+        }
+        other => panic!("unexpected flow: {:?}", other),
+    }
+}
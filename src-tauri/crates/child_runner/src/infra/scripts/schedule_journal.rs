@@ -1,4 +1,6 @@
-use ad_kernel::ids::{AssignmentId, DeviceId, ExecutionId, ScheduleId, ScriptId, TaskId};
+use ad_kernel::ids::{
+    AccountId, AssignmentId, DeviceId, ExecutionId, ScheduleId, ScriptId, TaskId,
+};
 use domain_schedule::{ExecutionScheduleProfile, TaskRunStatus};
 use domain_script::{ScriptTaskProfile, TaskCycle};
 use infra_sqlite::{find_latest_success_schedule, insert_execution_schedule};
@@ -39,6 +41,7 @@ impl ScheduleJournal {
         execution_id: ExecutionId,
         assignment_id: AssignmentId,
         script_id: ScriptId,
+        account_id: Option<AccountId>,
         task: &ScriptTaskProfile,
         dedup_scope_hash: &str,
         task_cycle: &TaskCycle,
@@ -54,6 +57,7 @@ impl ScheduleJournal {
             assignment_id: Some(assignment_id),
             script_id,
             task_id: task.id,
+            account_id,
             dedup_scope_hash: dedup_scope_hash.to_string(),
             task_cycle: Self::task_cycle_value(task_cycle)?,
             status: format!("{:?}", status),
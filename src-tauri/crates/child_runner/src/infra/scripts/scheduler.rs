@@ -10,11 +10,12 @@ use crate::infra::scripts::execution_plan::ExecutionPlanAssembler;
 use crate::infra::scripts::executor::ScriptExecutor;
 use crate::infra::scripts::schedule_journal::ScheduleJournal;
 use crate::infra::session::runtime_session::{
-    get_script_bundle_snapshot, try_current_session_summary,
+    get_runtime_execution_policy, get_script_bundle_snapshot, try_current_session_summary,
 };
+use ad_kernel::ids::AccountId;
 use ad_kernel::ids::ExecutionId;
 use ad_kernel::ids::ScriptId;
-use domain_schedule::TaskRunStatus;
+use domain_schedule::{RetryGiveUpAction, TaskRetryPolicy, TaskRunStatus};
 use domain_script::ScriptInfo;
 use domain_script::{
     PolicyGroupPolicyLink, PolicyGroupProfile, PolicyProfile, PolicySetGroupLink, PolicySetProfile,
@@ -29,8 +30,9 @@ use runner_protocol::message::{
 };
 use serde::Serialize;
 use serde::de::DeserializeOwned;
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 use tokio_util::sync::CancellationToken;
 
@@ -55,6 +57,8 @@ pub(crate) struct ScriptScheduler {
     queue: Arc<RwLock<VecDeque<RuntimeQueueItem>>>,
     /// 当前正在执行的脚本
     current_script: Arc<RwLock<Option<ScriptId>>>,
+    /// 设备上当前已登录的账号，相同账号连续运行时不重复执行切换任务
+    active_account: Arc<RwLock<Option<AccountId>>>,
     /// 取消令牌
     cancel_token: CancellationToken,
     #[cfg(feature = "testkit")]
@@ -66,6 +70,7 @@ impl ScriptScheduler {
         Arc::new(Self {
             queue: Arc::new(RwLock::new(VecDeque::new())),
             current_script: Arc::new(RwLock::new(None)),
+            active_account: Arc::new(RwLock::new(None)),
             cancel_token,
             #[cfg(feature = "testkit")]
             test_hooks: None,
@@ -80,6 +85,7 @@ impl ScriptScheduler {
         Arc::new(Self {
             queue: Arc::new(RwLock::new(VecDeque::new())),
             current_script: Arc::new(RwLock::new(None)),
+            active_account: Arc::new(RwLock::new(None)),
             cancel_token,
             test_hooks: Some(test_hooks),
         })
@@ -235,12 +241,52 @@ impl ScriptScheduler {
         state.exec_cur = state.exec_cur.saturating_add(1);
     }
 
+    /// 等待重试间隔；期间收到停止或取消时返回 false
+    async fn wait_retry_backoff(&self, delay: Duration) -> bool {
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = self.cancel_token.cancelled() => return false,
+        }
+        !crate::infra::context::runtime_control::stop_requested()
+    }
+
+    /// 在两次尝试之间执行脚本的恢复任务，失败只记录日志
+    async fn run_recovery_between_attempts(
+        executor: &mut ScriptExecutor,
+        runtime_ctx: &Arc<RwLock<crate::infra::context::runtime_context::RuntimeContext>>,
+        recovery_task: &ScriptTaskProfile,
+        failed_task: &ScriptTaskProfile,
+    ) {
+        Log::info(&format!(
+            "[ scheduler ] 任务[{}]重试前执行恢复任务: {}",
+            failed_task.name, recovery_task.name
+        ));
+        {
+            let mut ctx = runtime_ctx.write().await;
+            ctx.execution.current_task = Some(recovery_task.clone());
+            ctx.execution.current_step_id = None;
+            ctx.execution.current_step_name = None;
+        }
+        executor.reset_node_indices();
+        if let Err(error) = executor.execute(&recovery_task.task.steps).await {
+            Log::warn(&format!(
+                "[ scheduler ] 恢复任务[{}]执行失败: {}",
+                recovery_task.name, error
+            ));
+        }
+        let mut ctx = runtime_ctx.write().await;
+        ctx.execution.current_task = Some(failed_task.clone());
+        ctx.execution.current_step_id = None;
+        ctx.execution.current_step_name = None;
+    }
+
     /// 用完整 session 替换当前队列
     pub(crate) async fn load_session(&self, session: RuntimeSessionSnapshot) {
         let mut queue = self.queue.write().await;
         queue.clear();
         queue.extend(session.queue);
         *self.current_script.write().await = None;
+        *self.active_account.write().await = None;
         Log::info(&format!(
             "[ scheduler ] 已加载 session[{}]，队列长度: {}",
             session.session_id,
@@ -305,7 +351,7 @@ impl ScriptScheduler {
         );
 
         // 执行脚本
-        let result = self.execute_script(queue_item, execution_id).await;
+        let result = self.execute_queue_item(queue_item, execution_id).await;
 
         // 清除当前脚本
         *self.current_script.write().await = None;
@@ -368,6 +414,129 @@ impl ScriptScheduler {
         self.queue.read().await.len() > 0
     }
 
+    /// 脚本登记了账号时按账号顺序逐个运行，单个账号失败不影响后续账号
+    async fn execute_queue_item(
+        &self,
+        queue_item: RuntimeQueueItem,
+        execution_id: ExecutionId,
+    ) -> Result<ScriptExecutionOutcome, String> {
+        if queue_item.account_runs.is_empty() {
+            return self.execute_script(queue_item, execution_id).await;
+        }
+
+        let mut script_name = String::new();
+        let mut errors = Vec::new();
+        for (index, run) in queue_item.account_runs.iter().enumerate() {
+            let progress = format!(
+                "账号[{}] ({}/{})",
+                run.account_name,
+                index + 1,
+                queue_item.account_runs.len()
+            );
+            // 每个账号单独一条执行记录；首个账号沿用调度时已登记为 Running 的执行 id
+            let account_execution_id = if index == 0 {
+                execution_id
+            } else {
+                let account_execution_id = ExecutionId::new_v7();
+                emit_schedule_event(
+                    RuntimeScheduleStatus::Running,
+                    Some(account_execution_id),
+                    Some(queue_item.assignment_id),
+                    Some(queue_item.script_id),
+                    None,
+                    None,
+                    Some(format!("{}已进入执行", progress)),
+                );
+                account_execution_id
+            };
+            Log::info(&format!("[ scheduler ] 开始运行{}", progress));
+            match self
+                .execute_script(queue_item.for_account(run), account_execution_id)
+                .await
+            {
+                Ok(ScriptExecutionOutcome::Completed(name)) => script_name = name,
+                Ok(ScriptExecutionOutcome::Stopped(name)) => {
+                    return Ok(ScriptExecutionOutcome::Stopped(name));
+                }
+                Err(error) => {
+                    Log::warn(&format!(
+                        "[ scheduler ] 账号[{}]运行失败，继续下一个账号: {}",
+                        run.account_name, error
+                    ));
+                    errors.push(format!("账号[{}] {}", run.account_name, error));
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(ScriptExecutionOutcome::Completed(script_name))
+        } else {
+            Err(errors.join("；"))
+        }
+    }
+
+    /// 装入当前账号信息，账号与设备上已登录的账号不同时执行脚本配置的切换账号任务
+    async fn switch_account(
+        &self,
+        executor: &mut ScriptExecutor,
+        runtime_ctx: &Arc<RwLock<crate::infra::context::runtime_context::RuntimeContext>>,
+        queue_item: &RuntimeQueueItem,
+        account_id: &AccountId,
+        switch_task: Option<&ScriptTaskProfile>,
+        variable_catalog: &domain_script::ScriptVariableCatalog,
+    ) -> Result<(), String> {
+        let account_name = queue_item.account_name.as_deref().unwrap_or(account_id);
+        executor
+            .set_account_scope(
+                account_id,
+                queue_item.account_name.as_deref(),
+                queue_item.account_data_json.as_deref(),
+            )
+            .await
+            .map_err(|error| error.to_string())?;
+        if self.active_account.read().await.as_ref() == Some(account_id) {
+            return Ok(());
+        }
+        let Some(switch_task) = switch_task else {
+            Log::warn(&format!(
+                "[ scheduler ] 脚本未配置切换账号任务，账号[{}]沿用设备当前登录状态",
+                account_name
+            ));
+            *self.active_account.write().await = Some(account_id.clone());
+            return Ok(());
+        };
+
+        Log::info(&format!(
+            "[ scheduler ] 执行切换账号任务[{}]: {}",
+            switch_task.name, account_name
+        ));
+        {
+            let mut ctx = runtime_ctx.write().await;
+            ctx.execution.current_task = Some(switch_task.clone());
+            ctx.execution.current_step_id = None;
+            ctx.execution.current_step_name = None;
+        }
+        executor.reset_node_indices();
+        executor
+            .hydrate_input_scope(
+                variable_catalog,
+                queue_item.template_values_json.as_deref(),
+                Some(switch_task),
+            )
+            .await
+            .map_err(|error| error.to_string())?;
+        let result = executor.execute(&switch_task.task.steps).await;
+        {
+            let mut ctx = runtime_ctx.write().await;
+            ctx.execution.current_task = None;
+            ctx.execution.current_step_id = None;
+            ctx.execution.current_step_name = None;
+        }
+        result.map_err(|error| format!("切换账号[{}]失败: {}", account_name, error))?;
+        *self.active_account.write().await = Some(account_id.clone());
+        Ok(())
+    }
+
     /// 执行单个脚本
     async fn execute_script(
         &self,
@@ -389,6 +558,12 @@ impl ScriptScheduler {
         let runtime_ctx = get_runtime_ctx();
         let script_info = bundle.script.info;
         let script_name = script_info.name.clone();
+        let recovery_task_id = script_info.runtime_settings.recovery_task_id;
+        let switch_account_task_id = script_info.runtime_settings.switch_account_task_id;
+        let device_retry_policy = get_runtime_execution_policy()
+            .await
+            .map(|policy| policy.retry_policy)
+            .unwrap_or_default();
         let variable_catalog = script_info.variable_catalog.clone();
         Log::info(&format!("[ scheduler ] 开始执行脚本: {}", script_name));
         let capture_asset_signature = ScriptExecutor::build_capture_asset_signature(&script_info);
@@ -408,6 +583,26 @@ impl ScriptScheduler {
         let plan_summary = execution_plan.summary();
         let is_policy_debug_target = execution_plan.is_policy_debug();
         let task_selection = execution_plan.task_selection();
+        // 持久化存储读取失败时以空缓存继续执行，后续写入仍会落库
+        let runtime_store = match infra_sqlite::load_runtime_store_entries(
+            device_id,
+            queue_item.account_id.as_ref(),
+            script_id,
+        )
+        .await
+        {
+            Ok(entries) => entries
+                .into_iter()
+                .map(|entry| (entry.key.clone(), entry))
+                .collect(),
+            Err(error) => {
+                Log::warn(&format!(
+                    "[ scheduler ] 脚本[{}]加载持久化存储失败: {}",
+                    script_name, error
+                ));
+                HashMap::new()
+            }
+        };
         // 更新运行时上下文的 script_id
         {
             let mut ctx = runtime_ctx.write().await;
@@ -424,6 +619,7 @@ impl ScriptScheduler {
             ctx.execution.current_step_name = None;
             ctx.execution.var_map.clear();
             ctx.execution.template_values_json = queue_item.template_values_json.clone();
+            ctx.execution.runtime_store = runtime_store;
             ctx.execution.policy_states.clear();
             ctx.execution.task_states.clear();
             ctx.execution
@@ -524,6 +720,7 @@ impl ScriptScheduler {
                     execution_id,
                     assignment_id,
                     script_id,
+                    queue_item.account_id.clone(),
                     &skipped.task,
                     &ScheduleJournal::compute_dedup_scope_hash(
                         &queue_item.dedup_scope_base_hash,
@@ -541,6 +738,31 @@ impl ScriptScheduler {
 
         let root_tasks = task_selection.root_tasks.clone();
         let mut executor = self.create_executor(runtime_ctx.clone());
+        executor.install_script_rhai_modules().await;
+        if let Some(account_id) = queue_item.account_id.as_ref() {
+            let switch_task = switch_account_task_id
+                .and_then(|task_id| bundle.tasks.iter().find(|task| task.id == task_id));
+            if let Err(error) = self
+                .switch_account(
+                    &mut executor,
+                    &runtime_ctx,
+                    &queue_item,
+                    account_id,
+                    switch_task,
+                    &variable_catalog,
+                )
+                .await
+            {
+                if crate::infra::context::runtime_control::stop_requested() {
+                    Self::reset_execution_state(&runtime_ctx).await;
+                    Self::flush_ocr_cache(&runtime_ctx, &script_name, "停止后").await?;
+                    return Ok(ScriptExecutionOutcome::Stopped(script_name));
+                }
+                Self::reset_execution_state(&runtime_ctx).await;
+                Self::flush_ocr_cache(&runtime_ctx, &script_name, "失败后").await?;
+                return Err(format!("脚本[{}] {}", script_name, error));
+            }
+        }
         let mut pending_tasks: VecDeque<_> = root_tasks.clone().into_iter().collect();
         let linkable_tasks = task_selection.linkable_tasks;
         while let Some(planned_task) = pending_tasks.pop_front() {
@@ -586,16 +808,101 @@ impl ScriptScheduler {
                 Some(format!("开始执行任务: {}", task.name)),
             );
 
-            executor.reset_node_indices();
-            executor
-                .hydrate_input_scope(
-                    &variable_catalog,
-                    queue_item.template_values_json.as_deref(),
-                    Some(&task),
-                )
-                .await
-                .map_err(|error| error.to_string())?;
-            let task_result = executor.execute(&task.task.steps).await;
+            let retry_policy: &TaskRetryPolicy = task
+                .task
+                .retry_policy
+                .as_ref()
+                .unwrap_or(&device_retry_policy);
+            let max_attempts = retry_policy.attempts();
+            let recovery_task = recovery_task_id
+                .filter(|recovery_task_id| {
+                    retry_policy.run_recovery_task && *recovery_task_id != task.id
+                })
+                .and_then(|recovery_task_id| {
+                    bundle.tasks.iter().find(|task| task.id == recovery_task_id)
+                });
+            let mut attempt = 1;
+            let task_result = loop {
+                let attempt_started_at = chrono::Utc::now().to_rfc3339();
+                executor.reset_node_indices();
+                executor
+                    .hydrate_input_scope(
+                        &variable_catalog,
+                        queue_item.template_values_json.as_deref(),
+                        Some(&task),
+                    )
+                    .await
+                    .map_err(|error| error.to_string())?;
+                let result = executor.execute(&task.task.steps).await;
+                let Err(error) = &result else {
+                    break result;
+                };
+                if attempt >= max_attempts
+                    || crate::infra::context::runtime_control::stop_requested()
+                {
+                    break result;
+                }
+                let message = error.to_string();
+                let delay = retry_policy.delay_after(attempt);
+                Log::warn(&format!(
+                    "[ scheduler ] 任务[{}]第 {}/{} 次执行失败，{}ms 后重试: {}",
+                    task.name,
+                    attempt,
+                    max_attempts,
+                    delay.as_millis(),
+                    message
+                ));
+                if record_schedule {
+                    ScheduleJournal::append_task_record(
+                        device_id,
+                        execution_id,
+                        assignment_id,
+                        script_id,
+                        queue_item.account_id.clone(),
+                        &task,
+                        &ScheduleJournal::compute_dedup_scope_hash(
+                            &queue_item.dedup_scope_base_hash,
+                            task.id,
+                        )?,
+                        &task_cycle,
+                        TaskRunStatus::Retrying,
+                        attempt_started_at,
+                        Some(chrono::Utc::now().to_rfc3339()),
+                        Some(format!(
+                            "第 {}/{} 次尝试失败: {}",
+                            attempt, max_attempts, message
+                        )),
+                    )
+                    .await?;
+                }
+                emit_progress_event(
+                    RuntimeProgressPhase::Executing,
+                    Some(assignment_id),
+                    Some(script_id),
+                    Some(task.id),
+                    None,
+                    Some(format!(
+                        "任务执行失败，{}ms 后进行第 {}/{} 次尝试: {}",
+                        delay.as_millis(),
+                        attempt + 1,
+                        max_attempts,
+                        task.name
+                    )),
+                );
+                if !self.wait_retry_backoff(delay).await {
+                    break result;
+                }
+                if let Some(recovery_task) = recovery_task {
+                    Self::run_recovery_between_attempts(
+                        &mut executor,
+                        &runtime_ctx,
+                        recovery_task,
+                        &task,
+                    )
+                    .await;
+                }
+                attempt += 1;
+            };
 
             let completion_at = chrono::Utc::now().to_rfc3339();
             let stop_requested = crate::infra::context::runtime_control::stop_requested();
@@ -691,6 +998,7 @@ impl ScriptScheduler {
                             execution_id,
                             assignment_id,
                             script_id,
+                            queue_item.account_id.clone(),
                             &task,
                             &ScheduleJournal::compute_dedup_scope_hash(
                                 &queue_item.dedup_scope_base_hash,
@@ -710,8 +1018,10 @@ impl ScriptScheduler {
                                 Some("收到停止命令，任务已中断".to_string())
                             } else if stop_script {
                                 Some("任务触发跳过脚本，后续任务不再执行".to_string())
+                            } else if task_skipped {
+                                Some("任务在执行过程中被标记为跳过".to_string())
                             } else {
-                                task_skipped.then(|| "任务在执行过程中被标记为跳过".to_string())
+                                (attempt > 1).then(|| format!("第 {} 次尝试成功", attempt))
                             },
                         )
                         .await?;
@@ -759,6 +1069,7 @@ impl ScriptScheduler {
                                 execution_id,
                                 assignment_id,
                                 script_id,
+                                queue_item.account_id.clone(),
                                 &task,
                                 &ScheduleJournal::compute_dedup_scope_hash(
                                     &queue_item.dedup_scope_base_hash,
@@ -826,6 +1137,7 @@ impl ScriptScheduler {
                             execution_id,
                             assignment_id,
                             script_id,
+                            queue_item.account_id.clone(),
                             &task,
                             &ScheduleJournal::compute_dedup_scope_hash(
                                 &queue_item.dedup_scope_base_hash,
@@ -840,6 +1152,27 @@ impl ScriptScheduler {
                         .await?;
                     }
 
+                    match retry_policy.give_up_action {
+                        RetryGiveUpAction::SkipTask => {
+                            Log::warn(&format!(
+                                "[ scheduler ] 任务[{}]重试 {} 次后仍失败，跳过并继续后续任务",
+                                task.name, attempt
+                            ));
+                            let mut ctx = runtime_ctx.write().await;
+                            ctx.execution.current_task = None;
+                            ctx.execution.current_step_id = None;
+                            ctx.execution.current_step_name = None;
+                            continue;
+                        }
+                        RetryGiveUpAction::StopQueue => {
+                            Log::warn(&format!(
+                                "[ scheduler ] 任务[{}]重试 {} 次后仍失败，清空设备队列",
+                                task.name, attempt
+                            ));
+                            self.clear_queue().await;
+                        }
+                        RetryGiveUpAction::FailScript => {}
+                    }
                     Self::reset_execution_state(&runtime_ctx).await;
                     Self::flush_ocr_cache(&runtime_ctx, &script_name, "失败后").await?;
                     return Err(format!("脚本[{}] {}", script_name, message));
@@ -878,10 +1211,13 @@ impl ScriptScheduler {
     }
 
     #[cfg(feature = "testkit")]
-    pub(crate) async fn execute_test_item(
-        &self,
-        queue_item: RuntimeQueueItem,
-    ) -> Result<bool, String> {
+    pub(crate) async fn execute_test_item(&self) -> Result<bool, String> {
+        let queue_item = self
+            .queue
+            .write()
+            .await
+            .pop_front()
+            .ok_or_else(|| "测试队列为空".to_string())?;
         match self
             .execute_script(queue_item, ExecutionId::new_v7())
             .await?
@@ -891,6 +1227,11 @@ impl ScriptScheduler {
         }
     }
 
+    #[cfg(feature = "testkit")]
+    pub(crate) async fn queue_len(&self) -> usize {
+        self.queue.read().await.len()
+    }
+
     async fn execute_debug_policy_target(
         run_target: &RunTarget,
         assignment_id: ad_kernel::ids::AssignmentId,
@@ -912,6 +1253,7 @@ impl ScriptScheduler {
         );
 
         let mut executor = ScriptExecutor::new(runtime_ctx.clone());
+        executor.install_script_rhai_modules().await;
         executor
             .hydrate_input_scope(variable_catalog, template_values_json, None)
             .await
@@ -1042,6 +1384,7 @@ impl ScriptScheduler {
     pub(crate) async fn clear_session(&self) {
         self.clear_queue().await;
         *self.current_script.write().await = None;
+        *self.active_account.write().await = None;
         Log::info("[ scheduler ] 当前 session 已清空");
     }
 }
@@ -7,11 +7,14 @@ use ad_kernel::{
     LogLevel,
     ids::{AssignmentId, DeviceId, DispatchId, SessionId, TaskId},
 };
-use domain_device::{DeviceConfig, DeviceOperation, DevicePlatform, TimeoutAction};
+use domain_device::{
+    DeviceConfig, DeviceOperation, DevicePlatform, HumanizeProfile, TimeoutAction,
+};
+use domain_schedule::TaskRetryPolicy;
 use domain_script::Step;
 use domain_vision::VisionTextCacheRuntimeConfig;
 use image::RgbaImage;
-use infra_adb::ADBCtx;
+use infra_adb::{ADBCommand, ADBCtx};
 use infra_device_runtime::{
     DeviceCtx, ensure_device_connection_with_progress, init_device_ctx, try_get_device_ctx,
 };
@@ -21,6 +24,7 @@ use runner_protocol::message::{
     RuntimeSessionSnapshot, ScriptBundleSnapshot,
 };
 use serde_json::{Map, Value, json};
+use std::path::PathBuf;
 use std::sync::{Arc, OnceLock};
 use tokio::sync::{Mutex, RwLock};
 use tokio_util::sync::CancellationToken;
@@ -34,23 +38,87 @@ pub struct TestScriptRunRequest {
     pub device_id: DeviceId,
     pub device_config: Option<DeviceConfig>,
     pub template_values_json: Option<String>,
+    /// 非空时进入回放模式：截图按顺序取自这些 PNG，设备操作只记录不下发
+    pub replay_screenshots: Vec<PathBuf>,
+}
+
+struct ReplayFrames {
+    frames: Vec<(String, RgbaImage)>,
+    cursor: Mutex<usize>,
+}
+
+impl ReplayFrames {
+    fn load(paths: &[PathBuf]) -> Result<Option<Self>, String> {
+        if paths.is_empty() {
+            return Ok(None);
+        }
+        let frames = paths
+            .iter()
+            .map(|path| {
+                let image = image::open(path)
+                    .map_err(|error| format!("读取回放截图失败[{}]: {error}", path.display()))?;
+                let name = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| path.display().to_string());
+                Ok((name, image.to_rgba8()))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(Some(Self {
+            frames,
+            cursor: Mutex::new(0),
+        }))
+    }
 }
 
 pub(crate) struct TestRuntimeHooks {
     operations: Mutex<Vec<Value>>,
     prints: Mutex<Vec<Value>>,
     step_trace: Mutex<Vec<Value>>,
+    replay: Option<ReplayFrames>,
 }
 
 impl TestRuntimeHooks {
-    fn new() -> Self {
+    fn new(replay: Option<ReplayFrames>) -> Self {
         Self {
             operations: Mutex::new(Vec::new()),
             prints: Mutex::new(Vec::new()),
             step_trace: Mutex::new(Vec::new()),
+            replay,
         }
     }
 
+    pub(crate) fn is_replaying(&self) -> bool {
+        self.replay.is_some()
+    }
+
+    /// 回放模式下返回当前帧尺寸，不推进游标也不记录截图
+    pub(crate) async fn replay_screen_size(&self) -> Option<(u32, u32)> {
+        let replay = self.replay.as_ref()?;
+        let index = (*replay.cursor.lock().await).min(replay.frames.len() - 1);
+        let (_, image) = &replay.frames[index];
+        Some((image.width(), image.height()))
+    }
+
+    /// 回放模式下返回下一帧截图；截图用尽后持续返回最后一帧
+    pub(crate) async fn next_replay_frame(&self) -> Option<RgbaImage> {
+        let replay = self.replay.as_ref()?;
+        let index = {
+            let mut cursor = replay.cursor.lock().await;
+            let index = (*cursor).min(replay.frames.len() - 1);
+            *cursor += 1;
+            index
+        };
+        let (name, image) = &replay.frames[index];
+        self.operations.lock().await.push(json!({
+            "type": "capture",
+            "frame": index,
+            "file": name,
+            "status": "success",
+        }));
+        Some(image.clone())
+    }
+
     pub(crate) async fn record_operation(&self, operation: &DeviceOperation, error: Option<&str>) {
         let mut value = operation_to_value(operation.clone());
         if let Some(object) = value.as_object_mut() {
@@ -108,38 +176,70 @@ impl TestRuntimeHooks {
 }
 
 pub async fn run_script_test(request: TestScriptRunRequest) -> Result<Value, String> {
+    run_test(request, None).await
+}
+
+/// 按设备队列调度运行：开启 record_schedule 的任务会写入调度记录，调用方需先初始化数据库。
+/// `queued_behind` 为排在被测脚本之后的调度数量，只排队不执行，用于观察失败后是否清空队列
+pub async fn run_device_queue_test(
+    request: TestScriptRunRequest,
+    queued_behind: usize,
+) -> Result<Value, String> {
+    run_test(request, Some(queued_behind)).await
+}
+
+async fn run_test(
+    request: TestScriptRunRequest,
+    queued_behind: Option<usize>,
+) -> Result<Value, String> {
+    let device_queue = queued_behind.is_some();
     let _guard = TEST_RUN_MUTEX
         .get_or_init(|| Arc::new(Mutex::new(())))
         .clone()
         .lock_owned()
         .await;
     clear_stop_request();
-    if let Some(device_config) = request.device_config.as_ref() {
+    let replay = ReplayFrames::load(&request.replay_screenshots)?;
+    if let Some(device_config) = request.device_config.as_ref()
+        && replay.is_none()
+    {
         prepare_test_device(device_config).await?;
     }
     let runtime_ctx = ensure_test_runtime_context(request.device_id)?;
-    let hooks = Arc::new(TestRuntimeHooks::new());
+    // 测试共享运行时上下文，清掉上一轮设备或回放截图留下的屏幕尺寸
+    runtime_ctx.write().await.observation.screen_size = (0, 0);
+    let hooks = Arc::new(TestRuntimeHooks::new(replay));
     let script_id = request.bundle.script_id;
     let assignment_id = AssignmentId::new_v7();
     let queue_item = RuntimeQueueItem {
         dispatch_id: DispatchId::new_v7(),
-        dispatch_kind: DispatchKind::TemporaryTask,
-        dispatch_source: DispatchSource::Debug,
+        dispatch_kind: if device_queue {
+            DispatchKind::QueueAssignment
+        } else {
+            DispatchKind::TemporaryTask
+        },
+        dispatch_source: if device_queue {
+            DispatchSource::Planner
+        } else {
+            DispatchSource::Debug
+        },
         assignment_id,
         script_id,
         time_template_id: None,
         account_id: None,
+        account_name: None,
         account_data_json: None,
         order_index: 0,
         window_start_at: None,
         template_values_json: request.template_values_json,
         dedup_scope_base_hash: String::new(),
+        account_runs: Vec::new(),
+    };
+    let run_target = match request.task_id {
+        _ if device_queue => RunTarget::DeviceQueue,
+        Some(task_id) => RunTarget::Task { script_id, task_id },
+        None => RunTarget::FullScript { script_id },
     };
-    let run_target = request
-        .task_id
-        .map_or(RunTarget::FullScript { script_id }, |task_id| {
-            RunTarget::Task { script_id, task_id }
-        });
     let session = RuntimeSessionSnapshot {
         session_id: SessionId::new_v7(),
         device_id: request.device_id,
@@ -153,6 +253,8 @@ pub async fn run_script_test(request: TestScriptRunRequest) -> Result<Value, Str
                 progress_timeout_ms: config.execution_policy.progress_timeout_ms.into(),
                 timeout_action: config.execution_policy.timeout_action.clone(),
                 timeout_notify_channels: config.execution_policy.timeout_notify_channels.clone(),
+                retry_policy: config.execution_policy.retry_policy.clone(),
+                humanize: config.execution_policy.humanize.clone(),
             })
             .unwrap_or(RuntimeExecutionPolicy {
                 action_wait_ms: 0,
@@ -160,21 +262,37 @@ pub async fn run_script_test(request: TestScriptRunRequest) -> Result<Value, Str
                 progress_timeout_ms: 30_000,
                 timeout_action: TimeoutAction::StopExecution,
                 timeout_notify_channels: Vec::new(),
+                retry_policy: TaskRetryPolicy::default(),
+                humanize: HumanizeProfile::default(),
             }),
-        queue: vec![queue_item.clone()],
+        queue: std::iter::once(queue_item.clone())
+            .chain(
+                (1..=queued_behind.unwrap_or(0)).map(|index| RuntimeQueueItem {
+                    dispatch_id: DispatchId::new_v7(),
+                    order_index: index as u32,
+                    ..queue_item.clone()
+                }),
+            )
+            .collect(),
         script_bundles: vec![request.bundle],
         issued_at: chrono::Utc::now().to_rfc3339(),
     };
-    replace_runtime_session(session).await;
+    replace_runtime_session(session.clone()).await;
 
     let scheduler = ScriptScheduler::new_with_test_hooks(CancellationToken::new(), hooks.clone());
-    let execution = scheduler.execute_test_item(queue_item).await;
+    scheduler.load_session(session).await;
+    let execution = scheduler.execute_test_item().await;
     let (outcome, error) = match execution {
         Ok(false) => ("completed", None),
         Ok(true) => ("stopped", None),
         Err(error) => ("failed", Some(error)),
     };
-    let result = build_result(runtime_ctx, hooks, outcome, error).await;
+    // 只有设备队列运行才输出剩余队列，避免已录制的场景基线出现额外字段
+    let remaining_queue = match queued_behind {
+        Some(_) => Some(scheduler.queue_len().await),
+        None => None,
+    };
+    let result = build_result(runtime_ctx, hooks, outcome, error, remaining_queue).await;
     clear_runtime_session().await;
     clear_stop_request();
     Ok(result)
@@ -222,6 +340,7 @@ async fn build_result(
     hooks: Arc<TestRuntimeHooks>,
     outcome: &str,
     error: Option<String>,
+    remaining_queue: Option<usize>,
 ) -> Value {
     let ctx = runtime_ctx.read().await;
     let variables = ctx
@@ -330,11 +449,15 @@ async fn build_result(
     let operations = hooks.operations.lock().await.drain(..).collect::<Vec<_>>();
     let prints = hooks.prints.lock().await.drain(..).collect::<Vec<_>>();
     let step_trace = hooks.step_trace.lock().await.drain(..).collect::<Vec<_>>();
+    let mut execution = json!({
+        "outcome": outcome,
+        "error": error,
+    });
+    if let (Some(remaining_queue), Some(object)) = (remaining_queue, execution.as_object_mut()) {
+        object.insert("remainingQueue".to_string(), json!(remaining_queue));
+    }
     json!({
-        "execution": {
-            "outcome": outcome,
-            "error": error,
-        },
+        "execution": execution,
         "operations": operations,
         "prints": prints,
         "stepTrace": step_trace,
@@ -409,6 +532,28 @@ fn operation_to_value(operation: DeviceOperation) -> Value {
             "duration": duration,
             "adbShell": format!("adb shell input swipe {} {} {} {} {}", from.x, from.y, to.x, to.y, duration),
         }),
+        DeviceOperation::Gesture(strokes) => json!({
+            "type": "gesture",
+            "strokes": strokes.iter().map(|stroke| {
+                stroke.iter().map(|waypoint| json!({
+                    "x": waypoint.point.x, "y": waypoint.point.y, "duration": waypoint.duration,
+                })).collect::<Vec<_>>()
+            }).collect::<Vec<_>>(),
+            "adbShell": "# sendevent multi-touch replay",
+        }),
+        DeviceOperation::Pinch {
+            center,
+            start_distance,
+            end_distance,
+            duration,
+        } => json!({
+            "type": "pinch",
+            "center": { "x": center.x, "y": center.y },
+            "startDistance": start_distance,
+            "endDistance": end_distance,
+            "duration": duration,
+            "adbShell": "# sendevent multi-touch replay",
+        }),
         DeviceOperation::LaunchApp {
             pkg_name,
             activity_name,
@@ -428,6 +573,20 @@ fn operation_to_value(operation: DeviceOperation) -> Value {
             "type": "inputText", "text": text,
             "adbShell": format!("adb shell input text {}", text.replace(' ', "%s")),
         }),
+        DeviceOperation::KeyEvent {
+            code,
+            long_press,
+            repeat,
+        } => json!({
+            "type": "keyEvent", "code": code, "longPress": long_press, "repeat": repeat,
+            "adbShell": match infra_adb::android_keycode(&code) {
+                Some(keycode) => format!(
+                    "adb shell {}",
+                    ADBCommand::KeyEvent { code: keycode, long_press, repeat }
+                ),
+                None => format!("# unknown android key {code}"),
+            },
+        }),
         DeviceOperation::Back => {
             json!({ "type": "back", "adbShell": "adb shell input keyevent 4" })
         }
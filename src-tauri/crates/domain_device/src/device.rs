@@ -1,5 +1,8 @@
+use crate::HumanizeProfile;
 use ad_kernel::LogLevel;
 use ad_kernel::ids::DeviceId;
+use domain_notification::NotificationTarget;
+use domain_schedule::TaskRetryPolicy;
 use domain_vision::ImageCompression;
 use std::net::SocketAddrV4;
 
@@ -67,6 +70,15 @@ pub struct DeviceExecutionPolicy {
     pub progress_timeout_ms: u32,
     pub timeout_action: TimeoutAction,
     pub timeout_notify_channels: Vec<TimeoutNotifyChannel>,
+    /// 任务失败、超时和脚本完成时额外推送的通知目标
+    #[serde(default)]
+    pub notification_targets: Vec<NotificationTarget>,
+    /// 任务失败后的默认重试策略，任务自身配置优先
+    #[serde(default)]
+    pub retry_policy: TaskRetryPolicy,
+    /// 拟人化输入，脚本运行设置中配置时以脚本为准
+    #[serde(default)]
+    pub humanize: HumanizeProfile,
 }
 
 #[derive(
@@ -141,6 +153,12 @@ pub enum CapMethod {
         offset_bottom_px: u32,
     },
     Adb,
+    /// 常驻 ADB 截图流：设备端 screenrecord 推送 H.264 画面并在主机解码，截图时直接取内存中的最新帧，失败时回退为单次 ADB 截图
+    AdbStream {
+        /// 输入后等待新画面的时长（毫秒），超过后仍无新帧视为画面未变化
+        #[serde(default = "default_stream_max_frame_age_ms")]
+        max_frame_age_ms: u32,
+    },
 }
 
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, ts_rs::TS)]
@@ -238,6 +256,9 @@ impl Default for DeviceExecutionPolicy {
             progress_timeout_ms: 30_000,
             timeout_action: TimeoutAction::StopExecution,
             timeout_notify_channels: Vec::new(),
+            notification_targets: Vec::new(),
+            retry_policy: TaskRetryPolicy::default(),
+            humanize: HumanizeProfile::default(),
         }
     }
 }
@@ -264,6 +285,10 @@ fn default_window_offset_top_px() -> u32 {
     40
 }
 
+fn default_stream_max_frame_age_ms() -> u32 {
+    150
+}
+
 fn default_adb_server_connect() -> Option<SocketAddrV4> {
     "127.0.0.1:5037".parse().ok()
 }
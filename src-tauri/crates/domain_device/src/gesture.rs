@@ -0,0 +1,147 @@
+use ad_kernel::Point;
+
+/// 轨迹上的一个点，`duration` 为从上一个点移动到这里的耗时（毫秒），首个点忽略
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TouchWaypoint {
+    pub point: Point<u16>,
+    pub duration: u64,
+}
+
+impl TouchWaypoint {
+    pub fn new(point: Point<u16>, duration: u64) -> Self {
+        Self { point, duration }
+    }
+}
+
+/// 按固定间隔采样后的一帧，`delay` 为距上一帧的等待时间，`points` 按手指顺序排列
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GestureFrame {
+    pub delay: u64,
+    pub points: Vec<Point<u16>>,
+}
+
+fn stroke_duration(stroke: &[TouchWaypoint]) -> u64 {
+    stroke
+        .iter()
+        .skip(1)
+        .map(|waypoint| waypoint.duration)
+        .sum()
+}
+
+fn position_at(stroke: &[TouchWaypoint], at: u64) -> Point<u16> {
+    let mut elapsed = 0;
+    for pair in stroke.windows(2) {
+        let (from, to) = (pair[0].point, pair[1].point);
+        let span = pair[1].duration;
+        if at < elapsed + span {
+            let ratio = (at - elapsed) as f64 / span as f64;
+            let lerp = |a: u16, b: u16| {
+                (f64::from(a) + (f64::from(b) - f64::from(a)) * ratio).round() as u16
+            };
+            return Point::new(lerp(from.x, to.x), lerp(from.y, to.y));
+        }
+        elapsed += span;
+    }
+    stroke
+        .last()
+        .map_or(Point::new(0, 0), |waypoint| waypoint.point)
+}
+
+/// 把多根手指的轨迹按 `step_ms` 采样为同步的帧；先结束的手指停在终点直到全部结束
+pub fn sample_gesture(strokes: &[Vec<TouchWaypoint>], step_ms: u64) -> Vec<GestureFrame> {
+    let strokes: Vec<&[TouchWaypoint]> = strokes
+        .iter()
+        .map(Vec::as_slice)
+        .filter(|stroke| !stroke.is_empty())
+        .collect();
+    if strokes.is_empty() {
+        return Vec::new();
+    }
+    let total = strokes
+        .iter()
+        .map(|stroke| stroke_duration(stroke))
+        .max()
+        .unwrap_or(0);
+    let step_ms = step_ms.max(1);
+    let mut frames = Vec::new();
+    let mut previous = 0;
+    let mut at = 0;
+    loop {
+        frames.push(GestureFrame {
+            delay: at - previous,
+            points: strokes
+                .iter()
+                .map(|stroke| position_at(stroke, at))
+                .collect(),
+        });
+        if at >= total {
+            break;
+        }
+        previous = at;
+        at = (at + step_ms).min(total);
+    }
+    frames
+}
+
+/// 以 `center` 为中点、水平对称的双指缩放轨迹；`end_distance` 大于 `start_distance` 为放大
+pub fn pinch_strokes(
+    center: Point<u16>,
+    start_distance: u16,
+    end_distance: u16,
+    duration: u64,
+) -> Vec<Vec<TouchWaypoint>> {
+    let offset = |distance: u16, sign: i32| {
+        let x = i32::from(center.x) + sign * i32::from(distance / 2);
+        Point::new(x.clamp(0, i32::from(u16::MAX)) as u16, center.y)
+    };
+    [-1, 1]
+        .into_iter()
+        .map(|sign| {
+            vec![
+                TouchWaypoint::new(offset(start_distance, sign), 0),
+                TouchWaypoint::new(offset(end_distance, sign), duration),
+            ]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn samples_polyline_segments_with_their_own_timing() {
+        let stroke = vec![
+            TouchWaypoint::new(Point::new(0, 0), 0),
+            TouchWaypoint::new(Point::new(100, 0), 100),
+            TouchWaypoint::new(Point::new(100, 100), 50),
+        ];
+        let frames = sample_gesture(&[stroke], 50);
+        let points: Vec<_> = frames.iter().map(|frame| frame.points[0]).collect();
+        assert_eq!(
+            points,
+            vec![
+                Point::new(0, 0),
+                Point::new(50, 0),
+                Point::new(100, 0),
+                Point::new(100, 100),
+            ]
+        );
+        assert_eq!(frames[0].delay, 0);
+        assert!(frames[1..].iter().all(|frame| frame.delay == 50));
+    }
+
+    #[test]
+    fn pinch_moves_two_fingers_symmetrically() {
+        let strokes = pinch_strokes(Point::new(500, 300), 100, 300, 200);
+        let frames = sample_gesture(&strokes, 100);
+        assert_eq!(
+            frames.first().unwrap().points,
+            vec![Point::new(450, 300), Point::new(550, 300)]
+        );
+        assert_eq!(
+            frames.last().unwrap().points,
+            vec![Point::new(350, 300), Point::new(650, 300)]
+        );
+    }
+}
@@ -0,0 +1,302 @@
+use crate::gesture::TouchWaypoint;
+use ad_kernel::Point;
+
+fn default_click_sigma_permille() -> u16 {
+    350
+}
+
+fn default_click_radius_px() -> u16 {
+    6
+}
+
+fn default_swipe_curve_permille() -> u16 {
+    120
+}
+
+fn default_swipe_duration_jitter_permille() -> u16 {
+    150
+}
+
+fn default_press_min_ms() -> u32 {
+    40
+}
+
+fn default_press_max_ms() -> u32 {
+    120
+}
+
+fn default_delay_sigma_ms() -> u32 {
+    120
+}
+
+/// 拟人化输入配置：点击落点、滑动轨迹、按压时长与动作间隔的随机分布
+#[derive(
+    Clone,
+    Debug,
+    bincode::Encode,
+    bincode::Decode,
+    serde::Serialize,
+    serde::Deserialize,
+    PartialEq,
+    Eq,
+    Hash,
+    ts_rs::TS,
+)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct HumanizeProfile {
+    #[serde(default)]
+    pub enabled: bool,
+    /// 点击落点高斯分布的标准差，按目标框半宽/半高的千分比计算，落点不会超出目标框
+    #[serde(default = "default_click_sigma_permille")]
+    pub click_sigma_permille: u16,
+    /// 固定坐标没有目标框时的抖动半径（像素）
+    #[serde(default = "default_click_radius_px")]
+    pub click_radius_px: u16,
+    /// 滑动轨迹最大弯曲幅度，按滑动距离的千分比计算，0 表示保持直线
+    #[serde(default = "default_swipe_curve_permille")]
+    pub swipe_curve_permille: u16,
+    /// 滑动时长的随机浮动幅度（千分比）
+    #[serde(default = "default_swipe_duration_jitter_permille")]
+    pub swipe_duration_jitter_permille: u16,
+    /// 点击按压时长在该范围内均匀取值（毫秒）
+    #[serde(default = "default_press_min_ms")]
+    pub press_min_ms: u32,
+    #[serde(default = "default_press_max_ms")]
+    pub press_max_ms: u32,
+    /// 动作间等待在设备配置的基础上叠加的高斯扰动标准差（毫秒）
+    #[serde(default = "default_delay_sigma_ms")]
+    pub delay_sigma_ms: u32,
+    /// 固定随机种子，相同种子会得到相同的随机序列；为空时每次运行随机
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+impl Default for HumanizeProfile {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            click_sigma_permille: default_click_sigma_permille(),
+            click_radius_px: default_click_radius_px(),
+            swipe_curve_permille: default_swipe_curve_permille(),
+            swipe_duration_jitter_permille: default_swipe_duration_jitter_permille(),
+            press_min_ms: default_press_min_ms(),
+            press_max_ms: default_press_max_ms(),
+            delay_sigma_ms: default_delay_sigma_ms(),
+            seed: None,
+        }
+    }
+}
+
+/// 拟人化输入使用的伪随机数发生器（SplitMix64），同一种子产生的序列在各平台一致
+#[derive(Clone, Debug)]
+pub struct HumanizeRng {
+    state: u64,
+}
+
+impl HumanizeRng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// [0, 1) 区间的均匀分布
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// 标准正态分布（Box-Muller）
+    pub fn gaussian(&mut self) -> f64 {
+        let u1 = self.next_f64().max(f64::MIN_POSITIVE);
+        let u2 = self.next_f64();
+        (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos()
+    }
+
+    /// [min, max] 区间内的均匀整数
+    pub fn range_u64(&mut self, min: u64, max: u64) -> u64 {
+        if min >= max {
+            return min;
+        }
+        min + self.next_u64() % (max - min + 1)
+    }
+}
+
+fn clamp_to_screen(x: f64, y: f64, screen_size: (u32, u32)) -> Point<u16> {
+    let max_x = f64::from(screen_size.0.saturating_sub(1).min(u32::from(u16::MAX)));
+    let max_y = f64::from(screen_size.1.saturating_sub(1).min(u32::from(u16::MAX)));
+    Point::new(
+        x.round().clamp(0.0, max_x) as u16,
+        y.round().clamp(0.0, max_y) as u16,
+    )
+}
+
+/// 缓入缓出，使滑动在起止处较慢、中段较快
+fn ease_in_out(t: f64) -> f64 {
+    t * t * (3.0 - 2.0 * t)
+}
+
+impl HumanizeProfile {
+    /// 在目标点附近按高斯分布取落点；`half_extent` 为目标框的半宽/半高，没有目标框时按抖动半径处理
+    pub fn jitter_point(
+        &self,
+        rng: &mut HumanizeRng,
+        point: Point<u16>,
+        half_extent: Option<(f64, f64)>,
+        screen_size: (u32, u32),
+    ) -> Point<u16> {
+        let (limit_x, limit_y, sigma_x, sigma_y) = match half_extent {
+            Some((half_w, half_h)) => {
+                let ratio = f64::from(self.click_sigma_permille) / 1000.0;
+                (half_w, half_h, half_w * ratio, half_h * ratio)
+            }
+            None => {
+                let radius = f64::from(self.click_radius_px);
+                (radius, radius, radius / 2.0, radius / 2.0)
+            }
+        };
+        let dx = (rng.gaussian() * sigma_x).clamp(-limit_x, limit_x);
+        let dy = (rng.gaussian() * sigma_y).clamp(-limit_y, limit_y);
+        clamp_to_screen(
+            f64::from(point.x) + dx,
+            f64::from(point.y) + dy,
+            screen_size,
+        )
+    }
+
+    pub fn press_ms(&self, rng: &mut HumanizeRng) -> u64 {
+        let min = self.press_min_ms.min(self.press_max_ms);
+        let max = self.press_min_ms.max(self.press_max_ms);
+        rng.range_u64(u64::from(min), u64::from(max))
+    }
+
+    /// 按 `swipe_duration_jitter_permille` 随机伸缩滑动时长
+    pub fn swipe_duration_ms(&self, rng: &mut HumanizeRng, duration: u64) -> u64 {
+        let jitter = f64::from(self.swipe_duration_jitter_permille) / 1000.0;
+        let scale = 1.0 + (rng.next_f64() * 2.0 - 1.0) * jitter;
+        (duration as f64 * scale).round().max(1.0) as u64
+    }
+
+    /// 把直线滑动换成一条带随机弯曲的二次贝塞尔曲线，按缓入缓出的节奏采样为轨迹点
+    pub fn swipe_stroke(
+        &self,
+        rng: &mut HumanizeRng,
+        from: Point<u16>,
+        to: Point<u16>,
+        duration: u64,
+        screen_size: (u32, u32),
+    ) -> Vec<TouchWaypoint> {
+        let duration = self.swipe_duration_ms(rng, duration);
+
+        let (x0, y0) = (f64::from(from.x), f64::from(from.y));
+        let (x1, y1) = (f64::from(to.x), f64::from(to.y));
+        let bend = (rng.next_f64() * 2.0 - 1.0) * f64::from(self.swipe_curve_permille) / 1000.0;
+        // 控制点从中点沿垂直方向偏移，(-dy, dx) 的长度等于滑动距离
+        let (cx, cy) = (
+            (x0 + x1) / 2.0 - (y1 - y0) * bend,
+            (y0 + y1) / 2.0 + (x1 - x0) * bend,
+        );
+
+        let segments = (duration / 16).clamp(4, 24);
+        let mut stroke = vec![TouchWaypoint::new(from, 0)];
+        let mut elapsed = 0;
+        for index in 1..=segments {
+            let at = duration * index / segments;
+            let t = ease_in_out(index as f64 / segments as f64);
+            let u = 1.0 - t;
+            let x = u * u * x0 + 2.0 * u * t * cx + t * t * x1;
+            let y = u * u * y0 + 2.0 * u * t * cy + t * t * y1;
+            let point = if index == segments {
+                to
+            } else {
+                clamp_to_screen(x, y, screen_size)
+            };
+            stroke.push(TouchWaypoint::new(point, at - elapsed));
+            elapsed = at;
+        }
+        stroke
+    }
+
+    /// 动作间等待：在基础等待上叠加高斯扰动，最短不低于基础值的一半
+    pub fn action_delay_ms(&self, rng: &mut HumanizeRng, base_ms: u64) -> u64 {
+        let noise = rng.gaussian() * f64::from(self.delay_sigma_ms);
+        let delay = (base_ms as f64 + noise).round().max((base_ms / 2) as f64);
+        delay as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_reproduces_the_same_inputs() {
+        let profile = HumanizeProfile {
+            enabled: true,
+            ..HumanizeProfile::default()
+        };
+        let sample = |seed| {
+            let mut rng = HumanizeRng::new(seed);
+            (
+                profile.jitter_point(
+                    &mut rng,
+                    Point::new(500, 500),
+                    Some((40.0, 20.0)),
+                    (1280, 720),
+                ),
+                profile.press_ms(&mut rng),
+                profile.swipe_stroke(
+                    &mut rng,
+                    Point::new(100, 600),
+                    Point::new(900, 200),
+                    300,
+                    (1280, 720),
+                ),
+            )
+        };
+        assert_eq!(sample(7), sample(7));
+        assert_ne!(sample(7), sample(8));
+    }
+
+    #[test]
+    fn click_jitter_stays_inside_the_target_box() {
+        let profile = HumanizeProfile {
+            click_sigma_permille: 2000,
+            ..HumanizeProfile::default()
+        };
+        let mut rng = HumanizeRng::new(1);
+        for _ in 0..500 {
+            let point = profile.jitter_point(
+                &mut rng,
+                Point::new(200, 100),
+                Some((30.0, 10.0)),
+                (1280, 720),
+            );
+            assert!((170..=230).contains(&point.x), "{point:?}");
+            assert!((90..=110).contains(&point.y), "{point:?}");
+        }
+    }
+
+    #[test]
+    fn curved_swipe_keeps_endpoints_and_total_duration_range() {
+        let profile = HumanizeProfile::default();
+        let mut rng = HumanizeRng::new(42);
+        let stroke = profile.swipe_stroke(
+            &mut rng,
+            Point::new(100, 600),
+            Point::new(900, 200),
+            400,
+            (1280, 720),
+        );
+        assert_eq!(stroke.first().unwrap().point, Point::new(100, 600));
+        assert_eq!(stroke.last().unwrap().point, Point::new(900, 200));
+        let total: u64 = stroke.iter().map(|waypoint| waypoint.duration).sum();
+        assert!((340..=460).contains(&total), "{total}");
+    }
+}
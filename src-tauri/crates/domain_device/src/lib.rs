@@ -1,4 +1,6 @@
 mod device;
+mod gesture;
+mod humanize;
 mod operation;
 
 pub use ad_kernel::ids::DeviceId;
@@ -7,4 +9,6 @@ pub use device::{
     DeviceTransportKind, EmulatorConnectMode, TimeoutAction, TimeoutNotifyChannel,
     WindowCaptureInterface,
 };
+pub use gesture::{GestureFrame, TouchWaypoint, pinch_strokes, sample_gesture};
+pub use humanize::{HumanizeProfile, HumanizeRng};
 pub use operation::DeviceOperation;
@@ -1,3 +1,4 @@
+use crate::gesture::TouchWaypoint;
 use ad_kernel::Point;
 
 /// 与控制协议无关的设备操作。
@@ -10,6 +11,15 @@ pub enum DeviceOperation {
         to: Point<u16>,
         duration: u64,
     },
+    /// 多指手势，每根手指一条折线轨迹，同时按下并在全部轨迹结束后抬起
+    Gesture(Vec<Vec<TouchWaypoint>>),
+    /// 双指缩放，`end_distance` 大于 `start_distance` 为放大
+    Pinch {
+        center: Point<u16>,
+        start_distance: u16,
+        end_distance: u16,
+        duration: u64,
+    },
     LaunchApp {
         pkg_name: String,
         activity_name: String,
@@ -18,6 +28,12 @@ pub enum DeviceOperation {
         pkg_name: String,
     },
     InputText(String),
+    /// 按键事件；`code` 为按键名或 Android 键码，由各平台自行解析
+    KeyEvent {
+        code: String,
+        long_press: bool,
+        repeat: u32,
+    },
     Back,
     Home,
     Reboot,
@@ -0,0 +1,140 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+fn default_target_enabled() -> bool {
+    true
+}
+
+fn default_webhook_method() -> String {
+    "POST".to_string()
+}
+
+fn default_channel_timeout_seconds() -> u64 {
+    10
+}
+
+/// 触发通知的运行事件
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash, ts_rs::TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub enum NotificationEventKind {
+    TaskFailed,
+    ScriptFailed,
+    Timeout,
+    ScriptCompleted,
+}
+
+impl NotificationEventKind {
+    pub fn title(&self) -> &'static str {
+        match self {
+            Self::TaskFailed => "任务执行失败",
+            Self::ScriptFailed => "脚本执行失败",
+            Self::Timeout => "脚本执行超时",
+            Self::ScriptCompleted => "脚本执行完成",
+        }
+    }
+}
+
+/// 设备上配置的一个通知目标
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, ts_rs::TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationTarget {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default = "default_target_enabled")]
+    pub enabled: bool,
+    pub events: Vec<NotificationEventKind>,
+    pub channel: NotificationChannelConfig,
+}
+
+impl NotificationTarget {
+    pub fn accepts(&self, kind: NotificationEventKind) -> bool {
+        self.enabled && self.events.contains(&kind)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, ts_rs::TS)]
+#[ts(export)]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum NotificationChannelConfig {
+    /// HTTP 回调；body_template 为空时发送事件 JSON，否则替换其中的 `{{占位符}}`
+    Webhook {
+        url: String,
+        #[serde(default = "default_webhook_method")]
+        method: String,
+        #[serde(default)]
+        headers: BTreeMap<String, String>,
+        #[serde(default)]
+        body_template: String,
+        #[serde(default = "default_channel_timeout_seconds")]
+        timeout_seconds: u64,
+    },
+    /// 以 JSON Lines 追加写入本地文件
+    File { path: String },
+    /// 执行本地命令；参数支持占位符，事件 JSON 写入标准输入
+    Command {
+        program: String,
+        #[serde(default)]
+        args: Vec<String>,
+        #[serde(default = "default_channel_timeout_seconds")]
+        timeout_seconds: u64,
+    },
+    /// 使用设置中保存的 SMTP 配置发送邮件，需开启邮件通知
+    Email,
+}
+
+/// 发往通知渠道的事件内容
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationEvent {
+    pub kind: NotificationEventKind,
+    pub title: String,
+    pub device_id: String,
+    pub device_name: String,
+    pub script_id: Option<String>,
+    pub task_id: Option<String>,
+    pub message: String,
+    pub at: String,
+}
+
+impl NotificationEvent {
+    /// 模板占位符及其取值，缺失的可选字段替换为空字符串
+    pub fn placeholders(&self) -> [(&'static str, &str); 8] {
+        [
+            ("kind", self.kind_value()),
+            ("title", &self.title),
+            ("deviceId", &self.device_id),
+            ("deviceName", &self.device_name),
+            ("scriptId", self.script_id.as_deref().unwrap_or_default()),
+            ("taskId", self.task_id.as_deref().unwrap_or_default()),
+            ("message", &self.message),
+            ("at", &self.at),
+        ]
+    }
+
+    /// 按占位符渲染模板，`escape` 用于对取值做目标格式的转义
+    pub fn render_template(&self, template: &str, escape: impl Fn(&str) -> String) -> String {
+        let mut rendered = template.to_string();
+        for (key, value) in self.placeholders() {
+            let placeholder = format!("{{{{{key}}}}}");
+            if rendered.contains(&placeholder) {
+                rendered = rendered.replace(&placeholder, &escape(value));
+            }
+        }
+        rendered
+    }
+
+    fn kind_value(&self) -> &'static str {
+        match self.kind {
+            NotificationEventKind::TaskFailed => "taskFailed",
+            NotificationEventKind::ScriptFailed => "scriptFailed",
+            NotificationEventKind::Timeout => "timeout",
+            NotificationEventKind::ScriptCompleted => "scriptCompleted",
+        }
+    }
+}
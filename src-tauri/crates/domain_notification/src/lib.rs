@@ -1,3 +1,8 @@
+mod channel;
+
+pub use channel::{
+    NotificationChannelConfig, NotificationEvent, NotificationEventKind, NotificationTarget,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
@@ -45,6 +50,9 @@ pub struct EmailConfig {
     pub sender_email: String,
     pub recipient: String,
     pub timeout_seconds: u64,
+    /// 每天在 `daily_summary_time`（HH:MM）之后发送一次当日运行汇总
+    pub daily_summary_email: bool,
+    pub daily_summary_time: String,
 }
 
 impl Default for EmailConfig {
@@ -62,6 +70,8 @@ impl Default for EmailConfig {
             sender_email: String::new(),
             recipient: String::new(),
             timeout_seconds: 60,
+            daily_summary_email: false,
+            daily_summary_time: "23:30".to_string(),
         }
     }
 }
@@ -161,4 +171,23 @@ mod tests {
             vec!["a@example.com", "b@example.com", "c@example.com"]
         );
     }
+
+    #[test]
+    fn renders_notification_template_placeholders() {
+        let event = NotificationEvent {
+            kind: NotificationEventKind::TaskFailed,
+            title: NotificationEventKind::TaskFailed.title().to_string(),
+            device_id: "device-1".to_string(),
+            device_name: "模拟器".to_string(),
+            script_id: None,
+            task_id: Some("task-1".to_string()),
+            message: "找不到\"开始\"按钮".to_string(),
+            at: "2026-01-01T00:00:00Z".to_string(),
+        };
+        let rendered = event.render_template(
+            "{{kind}}|{{deviceName}}|{{scriptId}}|{{taskId}}|{{message}}",
+            |value| value.replace('"', "\\\""),
+        );
+        assert_eq!(rendered, "taskFailed|模拟器||task-1|找不到\\\"开始\\\"按钮");
+    }
 }
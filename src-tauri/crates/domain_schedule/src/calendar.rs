@@ -0,0 +1,451 @@
+use crate::{TimeOfDay, TimeOfDayError, TimeTemplateProfile, TimeWindow};
+use chrono::{DateTime, Datelike, Days, NaiveDate, TimeZone, Timelike};
+use std::collections::BTreeSet;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// 向前或向后查找触发时间的最大天数
+const SEARCH_HORIZON_DAYS: u64 = 366;
+/// cron 逐次迭代的上限，避免每分钟触发且长期被过滤时空转
+const MAX_CRON_ITERATIONS: usize = 100_000;
+
+/// 时间模板的日历规则；全部为空时每天生效
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, ts_rs::TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateCalendar {
+    /// 生效的星期，1 为周一、7 为周日；为空表示每天
+    #[serde(default)]
+    pub weekdays: Vec<u8>,
+    /// 生效起始日期（含），格式 YYYY-MM-DD
+    #[serde(default)]
+    pub start_date: Option<String>,
+    /// 生效结束日期（含），格式 YYYY-MM-DD
+    #[serde(default)]
+    pub end_date: Option<String>,
+    /// cron 表达式，支持 5 段（分 时 日 月 周）或带秒的 6/7 段；设置后取代开始时间作为触发点。
+    /// 5 段写法的星期按 Unix 约定（0/7 为周日、1 为周一），6/7 段沿用 cron crate 的 1 为周日
+    #[serde(default)]
+    pub cron: Option<String>,
+    /// 排除日期，格式 YYYY-MM-DD
+    #[serde(default)]
+    pub exclude_dates: Vec<String>,
+}
+
+impl TemplateCalendar {
+    pub fn cron_expr(&self) -> Option<&str> {
+        self.cron
+            .as_deref()
+            .map(str::trim)
+            .filter(|expr| !expr.is_empty())
+    }
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum TemplateScheduleError {
+    #[error(transparent)]
+    Time(#[from] TimeOfDayError),
+    #[error("日期必须是 YYYY-MM-DD 格式，收到 {0}")]
+    Date(String),
+    #[error("星期必须在 1-7 之间，收到 {0}")]
+    Weekday(u8),
+    #[error("生效起始日期 {start} 晚于结束日期 {end}")]
+    DateRange { start: String, end: String },
+    #[error("cron 表达式[{expr}]无效: {reason}")]
+    Cron { expr: String, reason: String },
+}
+
+/// 解析后的时间模板规则，用于计算下一次触发时间与当前窗口起点
+#[derive(Debug, Clone)]
+pub struct TemplateSchedule {
+    window: TimeWindow,
+    weekdays: BTreeSet<u8>,
+    start_date: Option<NaiveDate>,
+    end_date: Option<NaiveDate>,
+    exclude_dates: BTreeSet<NaiveDate>,
+    cron: Option<cron::Schedule>,
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate, TemplateScheduleError> {
+    NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+        .map_err(|_| TemplateScheduleError::Date(value.to_string()))
+}
+
+/// 把 Unix 星期字段（0/7=周日、1=周一）换算成 cron crate 的编号（1=周日、2=周一）。
+/// 数字项展开为逐日列表，避免 `5-7` 这类跨周日的区间换算后首尾颠倒；英文名称原样保留
+fn unix_weekday_field(field: &str) -> Result<String, String> {
+    if field == "*" || field == "?" {
+        return Ok(field.to_string());
+    }
+    let parse_day = |value: &str| {
+        value
+            .parse::<u8>()
+            .ok()
+            .filter(|day| *day <= 7)
+            .ok_or_else(|| format!("星期字段[{}]必须在 0-7 之间", field))
+    };
+    let mut items = Vec::new();
+    let mut days = BTreeSet::new();
+    for item in field.split(',') {
+        if item.chars().any(|ch| ch.is_ascii_alphabetic()) {
+            items.push(item.to_string());
+            continue;
+        }
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => (
+                range,
+                step.parse::<usize>()
+                    .ok()
+                    .filter(|step| *step > 0)
+                    .ok_or_else(|| format!("星期字段[{}]的步长无效", field))?,
+            ),
+            None => (item, 1),
+        };
+        // `*` 与 `N/步长` 都按 Unix 的 0-7 取值范围展开，7 与 0 同为周日
+        let (start, end) = match range.split_once('-') {
+            _ if range == "*" => (0, 7),
+            Some((start, end)) => (parse_day(start)?, parse_day(end)?),
+            None if step > 1 => (parse_day(range)?, 7),
+            None => (parse_day(range)?, parse_day(range)?),
+        };
+        if start > end {
+            return Err(format!("星期字段[{}]的区间起点晚于终点", field));
+        }
+        days.extend((start..=end).step_by(step).map(|day| day % 7 + 1));
+    }
+    items.extend(days.iter().map(u8::to_string));
+    Ok(items.join(","))
+}
+
+fn parse_cron(expr: &str) -> Result<cron::Schedule, TemplateScheduleError> {
+    let invalid = |reason: String| TemplateScheduleError::Cron {
+        expr: expr.to_string(),
+        reason,
+    };
+    let fields = expr.split_whitespace().collect::<Vec<_>>();
+    // cron crate 要求秒字段，常见的 5 段写法补 0 秒，并按 Unix 约定换算星期
+    let normalized = if let [minute, hour, day, month, weekday] = fields.as_slice() {
+        let weekday = unix_weekday_field(weekday).map_err(invalid)?;
+        format!("0 {} {} {} {} {}", minute, hour, day, month, weekday)
+    } else {
+        expr.to_string()
+    };
+    cron::Schedule::from_str(&normalized).map_err(|error| invalid(error.to_string()))
+}
+
+fn to_time_of_day<Tz: TimeZone>(value: &DateTime<Tz>) -> TimeOfDay {
+    TimeOfDay::from_hour_minute(value.hour() as u8, value.minute() as u8)
+        .expect("chrono 时间的时分必然合法")
+}
+
+fn local_datetime<Tz: TimeZone>(
+    tz: &Tz,
+    date: NaiveDate,
+    time: Option<TimeOfDay>,
+) -> Option<DateTime<Tz>> {
+    let (hour, minute) = time.map_or((0, 0), |time| (time.hour(), time.minute()));
+    let naive = date.and_hms_opt(hour as u32, minute as u32, 0)?;
+    // 夏令时跳过的时刻没有对应的本地时间，直接跳过这一天
+    tz.from_local_datetime(&naive).earliest()
+}
+
+impl TemplateSchedule {
+    pub fn parse(template: &TimeTemplateProfile) -> Result<Self, TemplateScheduleError> {
+        let window =
+            TimeWindow::parse(template.start_time.as_deref(), template.end_time.as_deref())?;
+        let calendar = &template.calendar;
+        let weekdays = calendar
+            .weekdays
+            .iter()
+            .map(|day| {
+                if (1..=7).contains(day) {
+                    Ok(*day)
+                } else {
+                    Err(TemplateScheduleError::Weekday(*day))
+                }
+            })
+            .collect::<Result<_, _>>()?;
+        let start_date = calendar.start_date.as_deref().map(parse_date).transpose()?;
+        let end_date = calendar.end_date.as_deref().map(parse_date).transpose()?;
+        if let (Some(start), Some(end)) = (start_date, end_date)
+            && start > end
+        {
+            return Err(TemplateScheduleError::DateRange {
+                start: start.to_string(),
+                end: end.to_string(),
+            });
+        }
+        let exclude_dates = calendar
+            .exclude_dates
+            .iter()
+            .map(|date| parse_date(date))
+            .collect::<Result<_, _>>()?;
+        let cron = calendar.cron_expr().map(parse_cron).transpose()?;
+        Ok(Self {
+            window,
+            weekdays,
+            start_date,
+            end_date,
+            exclude_dates,
+            cron,
+        })
+    }
+
+    /// 日期是否满足星期、日期范围与排除日期
+    pub fn allows_date(&self, date: NaiveDate) -> bool {
+        (self.weekdays.is_empty()
+            || self
+                .weekdays
+                .contains(&(date.weekday().number_from_monday() as u8)))
+            && self.start_date.is_none_or(|start| date >= start)
+            && self.end_date.is_none_or(|end| date <= end)
+            && !self.exclude_dates.contains(&date)
+    }
+
+    fn allows_fire<Tz: TimeZone>(&self, fire: &DateTime<Tz>) -> bool {
+        self.allows_date(fire.date_naive()) && self.window.contains(to_time_of_day(fire))
+    }
+
+    /// 严格晚于 `now` 的下一次触发时间；既无开始时间也无 cron 时返回 None
+    pub fn next_due_after<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let horizon = now.date_naive() + Days::new(SEARCH_HORIZON_DAYS);
+        let last_date = self.end_date.map_or(horizon, |end| end.min(horizon));
+
+        if let Some(cron) = &self.cron {
+            return cron
+                .after(now)
+                .take(MAX_CRON_ITERATIONS)
+                .take_while(|fire| fire.date_naive() <= last_date)
+                .find(|fire| self.allows_fire(fire));
+        }
+
+        let start = self.window.start()?;
+        let tz = now.timezone();
+        let mut date = now.date_naive();
+        while date <= last_date {
+            if self.allows_date(date)
+                && let Some(due) = local_datetime(&tz, date, Some(start))
+                && due > *now
+            {
+                return Some(due);
+            }
+            date = date.succ_opt()?;
+        }
+        None
+    }
+
+    /// `now` 所在窗口的起点；不在任何生效窗口内时返回 None
+    pub fn current_window_start<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let now_time = to_time_of_day(now);
+        if let Some(cron) = &self.cron {
+            // 以最近一次触发为窗口起点，直到下一次触发；最近一次被过滤则视为不在窗口内
+            let earliest = now.date_naive() - Days::new(SEARCH_HORIZON_DAYS);
+            let fire = cron
+                .after(now)
+                .next_back()
+                .filter(|fire| fire.date_naive() >= earliest)?;
+            return (self.allows_fire(&fire) && self.window.contains(now_time)).then_some(fire);
+        }
+
+        if self.window.is_unbounded() || !self.window.contains(now_time) {
+            return None;
+        }
+        let today = now.date_naive();
+        let start_date = if self.window.starts_previous_day(now_time) {
+            today - Days::new(1)
+        } else {
+            today
+        };
+        if !self.allows_date(start_date) {
+            return None;
+        }
+        local_datetime(&now.timezone(), start_date, self.window.start())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn template(
+        start: Option<&str>,
+        end: Option<&str>,
+        calendar: TemplateCalendar,
+    ) -> TemplateSchedule {
+        TemplateSchedule::parse(&TimeTemplateProfile {
+            start_time: start.map(str::to_string),
+            end_time: end.map(str::to_string),
+            calendar,
+            ..TimeTemplateProfile::default()
+        })
+        .unwrap()
+    }
+
+    fn at(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn weekday_mask_and_exclusions_skip_to_the_next_allowed_day() {
+        // 2026-10-16 是周五
+        let schedule = template(
+            Some("09:00"),
+            Some("18:00"),
+            TemplateCalendar {
+                weekdays: vec![1, 5],
+                exclude_dates: vec!["2026-10-16".to_string()],
+                ..TemplateCalendar::default()
+            },
+        );
+        let now = at("2026-10-15T10:00:00Z");
+        assert_eq!(
+            schedule.next_due_after(&now),
+            Some(at("2026-10-19T09:00:00Z"))
+        );
+        assert_eq!(
+            schedule.current_window_start(&at("2026-10-16T10:00:00Z")),
+            None
+        );
+        assert_eq!(
+            schedule.current_window_start(&at("2026-10-19T10:00:00Z")),
+            Some(at("2026-10-19T09:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn date_range_ends_the_schedule() {
+        let schedule = template(
+            Some("09:00"),
+            None,
+            TemplateCalendar {
+                start_date: Some("2026-10-20".to_string()),
+                end_date: Some("2026-10-21".to_string()),
+                ..TemplateCalendar::default()
+            },
+        );
+        assert_eq!(
+            schedule.next_due_after(&at("2026-10-01T00:00:00Z")),
+            Some(at("2026-10-20T09:00:00Z"))
+        );
+        assert_eq!(schedule.next_due_after(&at("2026-10-21T09:30:00Z")), None);
+    }
+
+    #[test]
+    fn cron_drives_triggers_inside_the_window() {
+        let schedule = template(
+            Some("08:00"),
+            Some("20:00"),
+            TemplateCalendar {
+                cron: Some("30 */6 * * *".to_string()),
+                ..TemplateCalendar::default()
+            },
+        );
+        assert_eq!(
+            schedule.next_due_after(&at("2026-10-17T12:40:00Z")),
+            Some(at("2026-10-17T18:30:00Z"))
+        );
+        assert_eq!(
+            schedule.next_due_after(&at("2026-10-17T18:40:00Z")),
+            Some(at("2026-10-18T12:30:00Z"))
+        );
+        assert_eq!(
+            schedule.current_window_start(&at("2026-10-17T13:00:00Z")),
+            Some(at("2026-10-17T12:30:00Z"))
+        );
+        assert_eq!(
+            schedule.current_window_start(&at("2026-10-17T21:00:00Z")),
+            None
+        );
+    }
+
+    #[test]
+    fn five_field_cron_uses_unix_weekday_numbers() {
+        let cron = |expr: &str| {
+            template(
+                None,
+                None,
+                TemplateCalendar {
+                    cron: Some(expr.to_string()),
+                    ..TemplateCalendar::default()
+                },
+            )
+        };
+        // 2026-10-17 是周六
+        let workdays = cron("0 10 * * 1-5");
+        let fires = std::iter::successors(
+            workdays.next_due_after(&at("2026-10-17T00:00:00Z")),
+            |fire| workdays.next_due_after(fire),
+        )
+        .take(6)
+        .map(|fire| fire.weekday())
+        .collect::<Vec<_>>();
+        use chrono::Weekday::*;
+        assert_eq!(fires, vec![Mon, Tue, Wed, Thu, Fri, Mon]);
+
+        for sunday in ["0 10 * * 0", "0 10 * * 7", "0 10 * * SUN"] {
+            assert_eq!(
+                cron(sunday).next_due_after(&at("2026-10-17T00:00:00Z")),
+                Some(at("2026-10-18T10:00:00Z")),
+                "{sunday}"
+            );
+        }
+        // 跨周日的区间：周五、周六、周日
+        let weekend = cron("0 10 * * 5-7");
+        assert_eq!(
+            weekend.next_due_after(&at("2026-10-18T11:00:00Z")),
+            Some(at("2026-10-23T10:00:00Z"))
+        );
+        assert_eq!(
+            cron("0 10 * * */2").next_due_after(&at("2026-10-18T11:00:00Z")),
+            Some(at("2026-10-20T10:00:00Z"))
+        );
+        // `1/2` 展开为 1,3,5,7：周一、周三、周五与周日
+        assert_eq!(unix_weekday_field("1/2").unwrap(), "1,2,4,6");
+        assert_eq!(
+            unix_weekday_field("1/2").unwrap(),
+            unix_weekday_field("1-7/2").unwrap()
+        );
+        assert_eq!(
+            cron("0 10 * * 5/2").next_due_after(&at("2026-10-17T00:00:00Z")),
+            Some(at("2026-10-18T10:00:00Z"))
+        );
+        assert_eq!(unix_weekday_field("MON-FRI,0").unwrap(), "MON-FRI,1");
+        assert!(unix_weekday_field("8").is_err());
+        assert!(unix_weekday_field("5-1").is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_rules() {
+        let parse = |calendar| {
+            TemplateSchedule::parse(&TimeTemplateProfile {
+                calendar,
+                ..TimeTemplateProfile::default()
+            })
+        };
+        assert!(matches!(
+            parse(TemplateCalendar {
+                weekdays: vec![0],
+                ..TemplateCalendar::default()
+            }),
+            Err(TemplateScheduleError::Weekday(0))
+        ));
+        assert!(matches!(
+            parse(TemplateCalendar {
+                cron: Some("every day".to_string()),
+                ..TemplateCalendar::default()
+            }),
+            Err(TemplateScheduleError::Cron { .. })
+        ));
+        assert!(matches!(
+            parse(TemplateCalendar {
+                start_date: Some("2026-10-02".to_string()),
+                end_date: Some("2026-10-01".to_string()),
+                ..TemplateCalendar::default()
+            }),
+            Err(TemplateScheduleError::DateRange { .. })
+        ));
+    }
+}
@@ -1,13 +1,23 @@
 mod assignment;
+mod calendar;
+mod retry_policy;
+mod run_summary;
 mod schedule_models;
+mod script_account;
 mod task_run_status;
 mod time_window;
 
 pub use ad_kernel::ids::{AssignmentId, DeviceId, ScriptId, TemplateId};
 pub use assignment::{AssignmentScheduleStatus, AssignmentTriggerSource};
+pub use calendar::{TemplateCalendar, TemplateSchedule, TemplateScheduleError};
+pub use retry_policy::{RetryGiveUpAction, TaskRetryPolicy};
+pub use run_summary::{
+    DeviceRunSummary, RunFailureEntry, RunSummaryCounts, RunSummaryReport, ScriptRunSummary,
+};
 pub use schedule_models::{
     AssignmentProfile, AssignmentScheduleProfile, ExecutionScheduleProfile, PlannerQueueItem,
     TemplateValueProfile, TimeTemplateProfile,
 };
+pub use script_account::ScriptAccountProfile;
 pub use task_run_status::TaskRunStatus;
 pub use time_window::{TimeOfDay, TimeOfDayError, TimeWindow};
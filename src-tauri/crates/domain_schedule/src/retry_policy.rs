@@ -0,0 +1,134 @@
+use std::time::Duration;
+
+/// 任务失败后的重试策略；`max_attempts` 为包含首次执行在内的总次数。
+#[derive(
+    Debug,
+    Clone,
+    bincode::Encode,
+    bincode::Decode,
+    serde::Serialize,
+    serde::Deserialize,
+    ts_rs::TS,
+    PartialEq,
+    Eq,
+)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskRetryPolicy {
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+    #[serde(default = "default_backoff_ms")]
+    pub backoff_ms: u32,
+    /// 每次重试后等待时间的倍数，0 和 1 都表示固定间隔
+    #[serde(default = "default_backoff_factor")]
+    pub backoff_factor: u32,
+    #[serde(default = "default_max_backoff_ms")]
+    pub max_backoff_ms: u32,
+    /// 两次尝试之间先执行脚本配置的恢复任务
+    #[serde(default = "default_run_recovery_task")]
+    pub run_recovery_task: bool,
+    #[serde(default)]
+    pub give_up_action: RetryGiveUpAction,
+}
+
+/// 重试次数用尽后的处理方式。
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    bincode::Encode,
+    bincode::Decode,
+    serde::Serialize,
+    serde::Deserialize,
+    ts_rs::TS,
+    PartialEq,
+    Eq,
+)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub enum RetryGiveUpAction {
+    /// 结束当前脚本，继续调度队列中的后续脚本
+    #[default]
+    FailScript,
+    /// 记录失败后继续执行当前脚本的后续任务
+    SkipTask,
+    /// 结束当前脚本并清空设备队列
+    StopQueue,
+}
+
+impl Default for TaskRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_max_attempts(),
+            backoff_ms: default_backoff_ms(),
+            backoff_factor: default_backoff_factor(),
+            max_backoff_ms: default_max_backoff_ms(),
+            run_recovery_task: default_run_recovery_task(),
+            give_up_action: RetryGiveUpAction::default(),
+        }
+    }
+}
+
+impl TaskRetryPolicy {
+    pub fn attempts(&self) -> u32 {
+        self.max_attempts.max(1)
+    }
+
+    /// 第 `failed_attempt` 次失败后、下一次尝试前的等待时间
+    pub fn delay_after(&self, failed_attempt: u32) -> Duration {
+        let factor = self.backoff_factor.max(1);
+        let cap = self.max_backoff_ms.max(self.backoff_ms);
+        let delay = (1..failed_attempt.max(1)).fold(self.backoff_ms, |delay, _| {
+            delay.saturating_mul(factor).min(cap)
+        });
+        Duration::from_millis(u64::from(delay))
+    }
+}
+
+fn default_max_attempts() -> u32 {
+    1
+}
+
+fn default_backoff_ms() -> u32 {
+    5_000
+}
+
+fn default_backoff_factor() -> u32 {
+    2
+}
+
+fn default_max_backoff_ms() -> u32 {
+    60_000
+}
+
+fn default_run_recovery_task() -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_grows_by_factor_and_is_capped() {
+        let policy = TaskRetryPolicy {
+            max_attempts: 5,
+            backoff_ms: 1_000,
+            backoff_factor: 3,
+            max_backoff_ms: 5_000,
+            ..TaskRetryPolicy::default()
+        };
+        assert_eq!(policy.delay_after(1), Duration::from_millis(1_000));
+        assert_eq!(policy.delay_after(2), Duration::from_millis(3_000));
+        assert_eq!(policy.delay_after(3), Duration::from_millis(5_000));
+        assert_eq!(policy.delay_after(10), Duration::from_millis(5_000));
+    }
+
+    #[test]
+    fn missing_fields_fall_back_to_single_attempt() {
+        let policy: TaskRetryPolicy = serde_json::from_str("{}").unwrap();
+        assert_eq!(policy, TaskRetryPolicy::default());
+        assert_eq!(policy.attempts(), 1);
+    }
+}
@@ -0,0 +1,142 @@
+use ad_kernel::ids::{DeviceId, ScriptId, TaskId};
+use std::fmt::Write;
+
+/// 一段时间内各设备、各脚本的运行汇总。
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize, ts_rs::TS, PartialEq)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct RunSummaryReport {
+    pub range_start: String,
+    pub range_end: String,
+    pub generated_at: String,
+    /// 各设备脚本调度结果合计
+    pub runs: RunSummaryCounts,
+    /// 各设备任务执行结果合计
+    pub tasks: RunSummaryCounts,
+    pub devices: Vec<DeviceRunSummary>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ts_rs::TS, PartialEq)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceRunSummary {
+    pub device_id: DeviceId,
+    pub device_name: String,
+    pub runs: RunSummaryCounts,
+    pub tasks: RunSummaryCounts,
+    pub scripts: Vec<ScriptRunSummary>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ts_rs::TS, PartialEq)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptRunSummary {
+    pub script_id: ScriptId,
+    pub script_name: String,
+    /// 来自 assignment_schedules 的脚本调度结果
+    pub runs: RunSummaryCounts,
+    /// 来自 device_script_schedules 的任务执行结果
+    pub tasks: RunSummaryCounts,
+    pub run_duration_ms: u64,
+    pub task_duration_ms: u64,
+    pub failures: Vec<RunFailureEntry>,
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize, ts_rs::TS, PartialEq)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct RunSummaryCounts {
+    pub success: u32,
+    pub failed: u32,
+    pub skipped: u32,
+    pub stopped: u32,
+    /// 计划中、执行中或已取消等未结束状态
+    pub other: u32,
+}
+
+impl RunSummaryCounts {
+    pub fn record(&mut self, status: &str) {
+        match status.to_ascii_lowercase().as_str() {
+            "success" => self.success += 1,
+            "failed" => self.failed += 1,
+            "skipped" => self.skipped += 1,
+            "stopped" => self.stopped += 1,
+            _ => self.other += 1,
+        }
+    }
+
+    pub fn merge(&mut self, other: &Self) {
+        self.success += other.success;
+        self.failed += other.failed;
+        self.skipped += other.skipped;
+        self.stopped += other.stopped;
+        self.other += other.other;
+    }
+
+    pub fn total(&self) -> u32 {
+        self.success + self.failed + self.skipped + self.stopped + self.other
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ts_rs::TS, PartialEq, Eq)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct RunFailureEntry {
+    pub task_id: Option<TaskId>,
+    pub task_name: Option<String>,
+    pub at: String,
+    pub message: String,
+}
+
+impl RunSummaryReport {
+    /// 纯文本形式，用于邮件正文
+    pub fn to_plain_text(&self) -> String {
+        let mut text = String::new();
+        let _ = writeln!(text, "统计区间: {} ~ {}", self.range_start, self.range_end);
+        let _ = writeln!(text, "{}", describe_counts("合计调度", &self.runs));
+        let _ = writeln!(text, "{}", describe_counts("合计任务", &self.tasks));
+        if self.devices.is_empty() {
+            text.push_str("\n该时间段内没有运行记录。\n");
+            return text;
+        }
+        for device in &self.devices {
+            let _ = writeln!(text, "\n设备: {}", device.device_name);
+            for script in &device.scripts {
+                let _ = writeln!(
+                    text,
+                    "  脚本: {}（运行耗时 {}）",
+                    script.script_name,
+                    format_duration(script.run_duration_ms.max(script.task_duration_ms))
+                );
+                let _ = writeln!(text, "    {}", describe_counts("调度", &script.runs));
+                let _ = writeln!(text, "    {}", describe_counts("任务", &script.tasks));
+                for failure in &script.failures {
+                    let _ = writeln!(
+                        text,
+                        "    失败 [{}] {}: {}",
+                        failure.at,
+                        failure.task_name.as_deref().unwrap_or("脚本"),
+                        failure.message
+                    );
+                }
+            }
+        }
+        text
+    }
+}
+
+fn describe_counts(label: &str, counts: &RunSummaryCounts) -> String {
+    format!(
+        "{label}: 成功 {} / 失败 {} / 跳过 {} / 停止 {} / 其他 {}",
+        counts.success, counts.failed, counts.skipped, counts.stopped, counts.other
+    )
+}
+
+fn format_duration(duration_ms: u64) -> String {
+    let seconds = duration_ms / 1000;
+    match (seconds / 3600, seconds / 60 % 60, seconds % 60) {
+        (0, 0, seconds) => format!("{seconds}秒"),
+        (0, minutes, seconds) => format!("{minutes}分{seconds}秒"),
+        (hours, minutes, _) => format!("{hours}小时{minutes}分"),
+    }
+}
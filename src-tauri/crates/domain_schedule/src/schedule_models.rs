@@ -1,3 +1,4 @@
+use crate::TemplateCalendar;
 use ad_kernel::ids::{
     AccountId, AssignmentId, AssignmentScheduleId, BatchId, DeviceId, DispatchId, ExecutionId,
     ScheduleId, ScriptId, ScriptTemplateValueId, TaskId, TemplateId,
@@ -39,6 +40,9 @@ pub struct ExecutionScheduleProfile {
     pub assignment_id: Option<AssignmentId>,
     pub script_id: ScriptId,
     pub task_id: TaskId,
+    /// 多账号轮换时记录本次运行所属账号
+    #[serde(default)]
+    pub account_id: Option<AccountId>,
     pub dedup_scope_hash: String,
     pub task_cycle: String,
     pub status: String,
@@ -89,6 +93,8 @@ pub struct TimeTemplateProfile {
     pub name: String,
     pub start_time: Option<String>,
     pub end_time: Option<String>,
+    #[serde(default)]
+    pub calendar: TemplateCalendar,
 }
 
 impl Default for TimeTemplateProfile {
@@ -98,6 +104,7 @@ impl Default for TimeTemplateProfile {
             name: String::new(),
             start_time: None,
             end_time: None,
+            calendar: TemplateCalendar::default(),
         }
     }
 }
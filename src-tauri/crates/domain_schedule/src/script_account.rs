@@ -0,0 +1,56 @@
+use ad_kernel::ids::{AccountId, DeviceId, ScriptId};
+
+/// 脚本账号登记，同一设备会话内按 `index` 顺序逐个账号运行脚本
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, ts_rs::TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptAccountProfile {
+    pub id: AccountId,
+    pub script_id: ScriptId,
+    /// 为空表示所有挂载该脚本的设备都会轮换此账号
+    pub device_id: Option<DeviceId>,
+    pub name: String,
+    pub enabled: bool,
+    pub index: u32,
+    /// 切换账号任务可读取的账号资料，运行时以 `system.account.data` 暴露
+    #[ts(type = "any")]
+    pub account_data: serde_json::Value,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl ScriptAccountProfile {
+    /// 账号是否参与指定设备上的轮换
+    pub fn runs_on(&self, device_id: DeviceId) -> bool {
+        self.enabled && self.device_id.is_none_or(|value| value == device_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ad_kernel::ids::UuidV7;
+
+    #[test]
+    fn runs_on_respects_enabled_flag_and_device_scope() {
+        let mut account = ScriptAccountProfile {
+            id: "main".to_string(),
+            script_id: UuidV7(1),
+            device_id: None,
+            name: "主号".to_string(),
+            enabled: true,
+            index: 0,
+            account_data: serde_json::Value::Null,
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+        assert!(account.runs_on(UuidV7(2)));
+
+        account.device_id = Some(UuidV7(3));
+        assert!(!account.runs_on(UuidV7(2)));
+        assert!(account.runs_on(UuidV7(3)));
+
+        account.enabled = false;
+        assert!(!account.runs_on(UuidV7(3)));
+    }
+}
@@ -7,4 +7,6 @@ pub enum TaskRunStatus {
     Failed,
     Stopped,
     Skipped,
+    /// 本次尝试失败，按重试策略继续执行
+    Retrying,
 }
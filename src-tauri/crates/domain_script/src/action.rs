@@ -1,4 +1,4 @@
-use crate::{PointF32, PointU16};
+use crate::{PointF32, PointU16, RegionPoint};
 use ad_kernel::ids::{PolicyId, TaskId};
 
 fn default_click_offset() -> i32 {
@@ -13,6 +13,10 @@ fn default_drop_set_cycle() -> bool {
     true
 }
 
+fn default_key_repeat() -> u32 {
+    1
+}
+
 #[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Copy, ts_rs::TS, Default)]
 #[ts(export)]
 #[serde(rename_all = "camelCase")]
@@ -78,6 +82,15 @@ pub enum SwipeMode {
     },
 }
 
+/// 手势轨迹上的一个点；duration 为从上一个点移动到这里的耗时（毫秒），首个点忽略
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, ts_rs::TS)]
+#[ts(export)]
+pub struct GestureWaypoint {
+    pub point: RegionPoint,
+    #[serde(default)]
+    pub duration: u64,
+}
+
 #[derive(Debug, serde::Serialize, serde::Deserialize, Clone, ts_rs::TS)]
 #[ts(export)]
 #[serde(rename_all = "camelCase", tag = "mode")]
@@ -127,6 +140,17 @@ pub enum Action {
         #[serde(flatten)]
         mode: SwipeMode,
     },
+    /// 单指沿折线轨迹滑动，每段可设置耗时；用于图案绘制、连线等操作
+    Path {
+        points: Vec<GestureWaypoint>,
+    },
+    /// 以 center 为中点的双指缩放；end_distance 大于 start_distance 为放大
+    Pinch {
+        center: RegionPoint,
+        start_distance: u16,
+        end_distance: u16,
+        duration: u64,
+    },
     LongClick {
         #[serde(default = "default_click_offset")]
         offset_x: i32,
@@ -144,6 +168,14 @@ pub enum Action {
     InputText {
         text: String,
     },
+    /// 按键；code 为按键名（ENTER、ESC、VOLUME_UP 等）或 `keycode:66` 形式的 Android 键码
+    KeyEvent {
+        code: String,
+        #[serde(default)]
+        long_press: bool,
+        #[serde(default = "default_key_repeat")]
+        repeat: u32,
+    },
     PosAdd {
         target: PolicyId,
     },
@@ -195,4 +227,40 @@ mod tests {
             }
         ));
     }
+
+    #[test]
+    fn path_waypoints_default_to_zero_duration() {
+        let action: Action = serde_json::from_value(serde_json::json!({
+            "ac": "path",
+            "points": [
+                { "point": { "mode": "point", "p": { "x": 10, "y": 20 } } },
+                { "point": { "mode": "percent", "p": { "x": 0.5, "y": 0.5 } }, "duration": 300 }
+            ]
+        }))
+        .unwrap();
+
+        let Action::Path { points } = action else {
+            panic!("expected path action");
+        };
+        assert_eq!(points[0].duration, 0);
+        assert_eq!(points[1].duration, 300);
+    }
+
+    #[test]
+    fn key_event_defaults_to_a_single_short_press() {
+        let action: Action = serde_json::from_value(serde_json::json!({
+            "ac": "keyEvent",
+            "code": "ENTER"
+        }))
+        .unwrap();
+
+        assert!(matches!(
+            action,
+            Action::KeyEvent {
+                long_press: false,
+                repeat: 1,
+                ..
+            }
+        ));
+    }
 }
@@ -1,4 +1,4 @@
-use crate::{PointF32, PointU16, Step};
+use crate::{NumberField, OcrNumberSource, PointF32, PointU16, Step, StoreExpiry};
 use ad_kernel::LogLevel;
 use domain_vision::{RelativeAnchorType, RelativeDirection, RelativeTargetKind};
 use serde_json::Value;
@@ -80,10 +80,37 @@ pub enum DataHanding {
         #[serde(default)]
         then_steps: Vec<Step>,
     },
+    /// 从 OCR 文本中提取数值写入变量，整数、分子、分母与时长毫秒为整型，其余为浮点；未提取到时写入空值
+    ExtractNumber {
+        #[serde(flatten)]
+        source: OcrNumberSource,
+        #[serde(default)]
+        field: NumberField,
+        out_var: String,
+    },
     Rhai {
         code: String,
         out_var: Option<String>,
     },
+    /// 从跨运行持久化存储读取值写入变量，未命中或已过期时写入默认值
+    StoreGet {
+        key: String,
+        out_var: String,
+        default_val: Option<VarValue>,
+    },
+    /// 将 JSON 值或表达式结果写入跨运行持久化存储
+    StoreSet {
+        key: String,
+        #[serde(default)]
+        json_val: Option<Value>,
+        #[serde(default)]
+        expr: Option<String>,
+        #[serde(default)]
+        expiry: StoreExpiry,
+    },
+    StoreRemove {
+        keys: Vec<String>,
+    },
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize, Clone, ts_rs::TS)]
@@ -1,4 +1,4 @@
-use crate::{StateTarget, Step, TaskControl, VarValue};
+use crate::{NumberField, OcrNumberSource, RegionPoint, StateTarget, Step, TaskControl, VarValue};
 use ad_kernel::ids::{PolicyGroupId, PolicyId, PolicySetId, TaskId};
 use domain_vision::LogicOp;
 
@@ -14,6 +14,15 @@ fn default_policy_set_search_hits_var() -> String {
 fn default_current_task_expected() -> bool {
     true
 }
+fn default_wait_until_timeout_ms() -> u64 {
+    10_000
+}
+fn default_wait_until_interval_ms() -> u64 {
+    300
+}
+fn default_wait_diff_threshold() -> f32 {
+    0.02
+}
 
 #[derive(Debug, serde::Serialize, serde::Deserialize, Clone, ts_rs::TS)]
 #[ts(export)]
@@ -47,6 +56,16 @@ pub enum FlowControl {
         input_var: Option<String>,
         runtime_var: Option<String>,
     },
+    /// 反复截图直到满足等待目标；超时后执行 else_steps
+    WaitUntil {
+        until: WaitUntilTarget,
+        #[serde(default = "default_wait_until_timeout_ms")]
+        timeout_ms: u64,
+        #[serde(default = "default_wait_until_interval_ms")]
+        interval_ms: u64,
+        #[serde(default)]
+        else_steps: Vec<Step>,
+    },
     Link {
         target: TaskId,
     },
@@ -123,6 +142,35 @@ pub enum FlowControl {
     },
 }
 
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, ts_rs::TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum WaitUntilTarget {
+    /// 画面（或区域）连续 stable_ms 毫秒无明显变化
+    Stable {
+        stable_ms: u64,
+        #[serde(default = "default_wait_diff_threshold")]
+        diff_threshold: f32,
+        #[serde(default)]
+        region_top_left: RegionPoint,
+        #[serde(default)]
+        region_bottom_right: RegionPoint,
+    },
+    /// 画面（或区域）与参考图不同；未指定参考图变量时以进入等待时的首帧为参考
+    Changed {
+        #[serde(default)]
+        reference_var: Option<String>,
+        #[serde(default = "default_wait_diff_threshold")]
+        diff_threshold: f32,
+        #[serde(default)]
+        region_top_left: RegionPoint,
+        #[serde(default)]
+        region_bottom_right: RegionPoint,
+    },
+    /// 每次截图并刷新视觉上下文后检查条件
+    Condition { con: ConditionNode },
+}
+
 #[derive(Debug, serde::Serialize, Clone, ts_rs::TS)]
 #[serde(rename_all = "camelCase")]
 pub struct CurrentTaskCondition {
@@ -214,6 +262,15 @@ pub enum ConditionNode {
         op: CompareOp,
         expected_count: i32,
     },
+    /// 从 OCR 文本中提取数值后与固定值比较，未提取到数值时视为不满足
+    OcrNumberCompare {
+        #[serde(flatten)]
+        source: OcrNumberSource,
+        #[serde(default)]
+        field: NumberField,
+        op: CompareOp,
+        value: f64,
+    },
     PolicySetResult {
         result_var: String,
         field: PolicySetResultField,
@@ -4,11 +4,16 @@ mod data_handing;
 mod error;
 mod flow_control;
 mod metadata;
+mod number_extract;
 mod point;
 mod policy;
+mod runtime_store;
 mod script_graph;
 mod script_info;
+mod script_lint;
+mod script_merge;
 mod script_profile;
+mod script_snapshot;
 mod script_transfer;
 mod step;
 mod task;
@@ -18,7 +23,7 @@ mod variable;
 mod vision_node;
 
 pub use access::{ScriptAccessError, clone_cloud_id, ensure_clone_allowed, ensure_editable};
-pub use action::{Action, ClickMode, DropSetDirection, SwipeMode, SwipeTarget};
+pub use action::{Action, ClickMode, DropSetDirection, GestureWaypoint, SwipeMode, SwipeTarget};
 pub use ad_kernel::ids::ScriptId;
 pub use data_handing::{
     ColorCompareMethod, ColorRgb, DataHanding, FilterMode, PrintSource, RegionPoint, VarValue,
@@ -26,19 +31,36 @@ pub use data_handing::{
 pub use error::{ExecuteResult, ScriptError};
 pub use flow_control::{
     CompareOp, ConditionNode, CurrentTaskCondition, ExecCountValue, FlowControl, OcrTextMatchMode,
-    PolicySetResultCompareOp, PolicySetResultField, VisionCountTarget,
+    PolicySetResultCompareOp, PolicySetResultField, VisionCountTarget, WaitUntilTarget,
 };
 pub use metadata::{
     RuntimeType, SCRIPT_RUNTIME_SCHEMA, ScriptPlatform, ScriptType, supported_script_features,
 };
+pub use number_extract::{
+    ExtractedNumber, NumberField, NumberKind, NumberValue, OcrNumberSource, extract_number,
+    parse_clock_duration_ms,
+};
 pub use point::{PointF32, PointU16};
 pub use policy::{PolicyGroupInfo, PolicyInfo, PolicySetInfo};
+pub use runtime_store::{RuntimeStoreEntry, StoreExpiry};
 pub use script_graph::{
     PolicyGroupPolicyLink, PolicyGroupProfile, PolicyProfile, PolicySetGroupLink, PolicySetProfile,
     ScriptTaskProfile, TaskRowType, TaskTone, TaskTriggerMode,
 };
-pub use script_info::{ScriptInfo, ScriptRuntimeSettings};
+pub use script_info::{RhaiModule, ScriptInfo, ScriptRuntimeSettings};
+pub use script_lint::{
+    RhaiSnippetKind, ScriptDiagnostic, ScriptDiagnosticCode, ScriptDiagnosticSeverity,
+    ScriptLintInput, lint_script_bundle,
+};
+pub use script_merge::{
+    MergeConflictKind, MergeEntityKind, MergeResolution, ScriptMergeConflict, ScriptMergeOutcome,
+    merge_script_graphs,
+};
 pub use script_profile::ScriptProfile;
+pub use script_snapshot::{
+    ScriptGraphSnapshot, ScriptSnapshotDiff, ScriptSnapshotSummary, SnapshotChangeKind,
+    SnapshotEntityChange, SnapshotStepChange, SnapshotStepOwner, diff_script_snapshots,
+};
 pub use script_transfer::ScriptTransferRecord;
 pub use step::{Step, StepKind};
 pub use task::ScriptTask;
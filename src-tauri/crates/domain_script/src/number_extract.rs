@@ -0,0 +1,387 @@
+use crate::RegionPoint;
+use domain_vision::{RelativeDirection, TextNormalize};
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// 从 OCR 文本中提取的数值类型
+#[derive(
+    Debug, Clone, Copy, Default, serde::Serialize, serde::Deserialize, PartialEq, Eq, ts_rs::TS,
+)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub enum NumberKind {
+    /// 按文本中最先出现的数值自动识别类型
+    #[default]
+    Auto,
+    Integer,
+    Decimal,
+    /// 形如 `3/5` 的分数
+    Fraction,
+    /// 形如 `45%` 的百分比
+    Percent,
+    /// 形如 `01:20:30`、`5分30秒`、`2h10m` 的时长，统一换算为毫秒
+    Duration,
+}
+
+/// 提取结果中参与比较或写入变量的字段
+#[derive(
+    Debug, Clone, Copy, Default, serde::Serialize, serde::Deserialize, PartialEq, Eq, ts_rs::TS,
+)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub enum NumberField {
+    /// 主值：整数/小数本身、分数的分子、百分比数值、时长毫秒数
+    #[default]
+    Value,
+    /// 分数的分母
+    Denominator,
+    /// 分数的分子/分母，或百分比除以 100
+    Ratio,
+}
+
+/// OCR 数值来源：结果集、可选区域与可选文字锚点
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, ts_rs::TS)]
+#[ts(export)]
+pub struct OcrNumberSource {
+    /// OCR 结果集变量，为空时使用当前帧快照
+    #[serde(default)]
+    pub input_var: String,
+    #[serde(default)]
+    pub region_top_left: RegionPoint,
+    #[serde(default)]
+    pub region_bottom_right: RegionPoint,
+    /// 文字锚点，为空时取第一条可解析的 OCR 文本
+    #[serde(default)]
+    pub anchor_text: String,
+    /// 数值相对锚点的方向，为空表示数值与锚点在同一条 OCR 文本中
+    #[serde(default)]
+    pub anchor_direction: Option<RelativeDirection>,
+    #[serde(default)]
+    pub kind: NumberKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExtractedNumber {
+    Integer(i64),
+    Decimal(f64),
+    Fraction { numerator: i64, denominator: i64 },
+    Percent(f64),
+    Duration { ms: i64 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NumberValue {
+    Int(i64),
+    Float(f64),
+}
+
+impl NumberValue {
+    pub fn as_f64(self) -> f64 {
+        match self {
+            Self::Int(value) => value as f64,
+            Self::Float(value) => value,
+        }
+    }
+}
+
+impl ExtractedNumber {
+    pub fn field_value(&self, field: NumberField) -> Option<NumberValue> {
+        match (field, *self) {
+            (NumberField::Value, Self::Integer(value)) => Some(NumberValue::Int(value)),
+            (NumberField::Value, Self::Decimal(value) | Self::Percent(value)) => {
+                Some(NumberValue::Float(value))
+            }
+            (NumberField::Value, Self::Fraction { numerator, .. }) => {
+                Some(NumberValue::Int(numerator))
+            }
+            (NumberField::Value, Self::Duration { ms }) => Some(NumberValue::Int(ms)),
+            (NumberField::Denominator, Self::Fraction { denominator, .. }) => {
+                Some(NumberValue::Int(denominator))
+            }
+            (
+                NumberField::Ratio,
+                Self::Fraction {
+                    numerator,
+                    denominator,
+                },
+            ) if denominator != 0 => {
+                Some(NumberValue::Float(numerator as f64 / denominator as f64))
+            }
+            (NumberField::Ratio, Self::Percent(value)) => Some(NumberValue::Float(value / 100.0)),
+            _ => None,
+        }
+    }
+}
+
+fn regex(cell: &'static OnceLock<Regex>, pattern: &str) -> &'static Regex {
+    cell.get_or_init(|| Regex::new(pattern).expect("number extract regex should be valid"))
+}
+
+fn clock_regex() -> &'static Regex {
+    static CELL: OnceLock<Regex> = OnceLock::new();
+    regex(&CELL, r"(?:(\d{1,2}):)?(\d{1,3}):(\d{2})")
+}
+
+fn unit_duration_regex() -> &'static Regex {
+    static CELL: OnceLock<Regex> = OnceLock::new();
+    regex(
+        &CELL,
+        r"(?:(\d+)\s*(?:天|d)\s*)?(?:(\d+)\s*(?:小时|时|h)\s*)?(?:(\d+)\s*(?:分钟|分|min|m)\s*)?(?:(\d+)\s*(?:秒|s))?",
+    )
+}
+
+fn fraction_regex() -> &'static Regex {
+    static CELL: OnceLock<Regex> = OnceLock::new();
+    regex(&CELL, r"(\d+)\s*/\s*(\d+)")
+}
+
+fn percent_regex() -> &'static Regex {
+    static CELL: OnceLock<Regex> = OnceLock::new();
+    regex(&CELL, r"(\d+(?:\.\d+)?)\s*%")
+}
+
+fn number_regex() -> &'static Regex {
+    static CELL: OnceLock<Regex> = OnceLock::new();
+    regex(&CELL, r"\d{1,3}(?:,\d{3})+(?:\.\d+)?|\d+(?:\.\d+)?")
+}
+
+/// 全角数字与符号（：／％）统一折叠为半角后再解析
+fn normalize_text(text: &str) -> String {
+    TextNormalize {
+        nfkc: true,
+        ..TextNormalize::default()
+    }
+    .apply(text)
+    .into_owned()
+}
+
+fn touches_digit(text: &str, start: usize, end: usize) -> bool {
+    text[..start]
+        .chars()
+        .next_back()
+        .is_some_and(|ch| ch.is_ascii_digit())
+        || text[end..]
+            .chars()
+            .next()
+            .is_some_and(|ch| ch.is_ascii_digit())
+}
+
+fn find_clock_duration(text: &str) -> Option<(usize, u64)> {
+    for captures in clock_regex().captures_iter(text) {
+        let matched = captures.get(0)?;
+        if touches_digit(text, matched.start(), matched.end()) {
+            continue;
+        }
+
+        let trailing_seconds = captures.get(3)?.as_str().parse::<u64>().ok()?;
+        let middle = captures.get(2)?.as_str().parse::<u64>().ok()?;
+        if let Some(hours) = captures.get(1) {
+            let hours = hours.as_str().parse::<u64>().ok()?;
+            if middle >= 60 || trailing_seconds >= 60 {
+                continue;
+            }
+            return Some((
+                matched.start(),
+                ((hours * 60 * 60) + (middle * 60) + trailing_seconds) * 1_000,
+            ));
+        }
+
+        return Some((matched.start(), ((middle * 60) + trailing_seconds) * 1_000));
+    }
+    None
+}
+
+fn find_unit_duration(text: &str) -> Option<(usize, u64)> {
+    const UNIT_MS: [u64; 4] = [24 * 60 * 60 * 1_000, 60 * 60 * 1_000, 60 * 1_000, 1_000];
+    for captures in unit_duration_regex().captures_iter(text) {
+        let matched = captures.get(0)?;
+        // 单位为英文字母时，后面紧跟字母说明是普通单词（如 5 months），不视为时长
+        let followed_by_letter = text[matched.end()..]
+            .chars()
+            .next()
+            .is_some_and(|ch| ch.is_ascii_alphabetic());
+        if matched.is_empty() || followed_by_letter {
+            continue;
+        }
+        let total = UNIT_MS
+            .iter()
+            .enumerate()
+            .try_fold(0u64, |total, (index, unit_ms)| {
+                match captures.get(index + 1) {
+                    Some(value) => value
+                        .as_str()
+                        .parse::<u64>()
+                        .ok()?
+                        .checked_mul(*unit_ms)?
+                        .checked_add(total),
+                    None => Some(total),
+                }
+            });
+        // OCR 误识别出的超长数字会溢出，不当作时长
+        let Some(total) = total else {
+            continue;
+        };
+        return Some((matched.start(), total));
+    }
+    None
+}
+
+fn find_fraction(text: &str) -> Option<(usize, ExtractedNumber)> {
+    let captures = fraction_regex().captures(text)?;
+    Some((
+        captures.get(0)?.start(),
+        ExtractedNumber::Fraction {
+            numerator: captures.get(1)?.as_str().parse().ok()?,
+            denominator: captures.get(2)?.as_str().parse().ok()?,
+        },
+    ))
+}
+
+fn find_percent(text: &str) -> Option<(usize, ExtractedNumber)> {
+    let captures = percent_regex().captures(text)?;
+    Some((
+        captures.get(0)?.start(),
+        ExtractedNumber::Percent(captures.get(1)?.as_str().parse().ok()?),
+    ))
+}
+
+fn find_plain_number(text: &str, integer_only: bool) -> Option<(usize, ExtractedNumber)> {
+    number_regex().find_iter(text).find_map(|matched| {
+        let digits = matched.as_str().replace(',', "");
+        let number = if digits.contains('.') {
+            if integer_only {
+                return None;
+            }
+            ExtractedNumber::Decimal(digits.parse().ok()?)
+        } else {
+            ExtractedNumber::Integer(digits.parse().ok()?)
+        };
+        Some((matched.start(), number))
+    })
+}
+
+fn find_duration(text: &str) -> Option<(usize, ExtractedNumber)> {
+    find_clock_duration(text)
+        .or_else(|| find_unit_duration(text))
+        .map(|(start, ms)| {
+            (
+                start,
+                ExtractedNumber::Duration {
+                    ms: i64::try_from(ms).unwrap_or(i64::MAX),
+                },
+            )
+        })
+}
+
+/// 解析 `MM:SS` 或 `HH:MM:SS` 格式的倒计时，返回毫秒数
+pub fn parse_clock_duration_ms(text: &str) -> Option<u64> {
+    find_clock_duration(&normalize_text(text)).map(|(_, ms)| ms)
+}
+
+/// 按指定类型从文本中提取数值；`Auto` 取最先出现的数值，同一位置上优先识别时长、分数与百分比
+pub fn extract_number(text: &str, kind: NumberKind) -> Option<ExtractedNumber> {
+    let text = normalize_text(text);
+    let found = match kind {
+        NumberKind::Integer => find_plain_number(&text, true),
+        NumberKind::Decimal => {
+            find_plain_number(&text, false).map(|(start, number)| match number {
+                ExtractedNumber::Integer(value) => (start, ExtractedNumber::Decimal(value as f64)),
+                number => (start, number),
+            })
+        }
+        NumberKind::Fraction => find_fraction(&text),
+        NumberKind::Percent => find_percent(&text),
+        NumberKind::Duration => find_duration(&text),
+        NumberKind::Auto => [
+            find_duration(&text),
+            find_fraction(&text),
+            find_percent(&text),
+            find_plain_number(&text, false),
+        ]
+        .into_iter()
+        .flatten()
+        .min_by_key(|(start, _)| *start),
+    };
+    found.map(|(_, number)| number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_each_number_kind() {
+        assert_eq!(
+            extract_number("体力 1,240", NumberKind::Integer),
+            Some(ExtractedNumber::Integer(1240))
+        );
+        assert_eq!(
+            extract_number("倍率 x1.5", NumberKind::Decimal),
+            Some(ExtractedNumber::Decimal(1.5))
+        );
+        assert_eq!(
+            extract_number("剩余次数 ３／５", NumberKind::Fraction),
+            Some(ExtractedNumber::Fraction {
+                numerator: 3,
+                denominator: 5
+            })
+        );
+        assert_eq!(
+            extract_number("进度 45.5％", NumberKind::Percent),
+            Some(ExtractedNumber::Percent(45.5))
+        );
+        assert_eq!(
+            extract_number("1小时20分", NumberKind::Duration),
+            Some(ExtractedNumber::Duration { ms: 4_800_000 })
+        );
+        assert_eq!(extract_number("5 months", NumberKind::Duration), None);
+        assert_eq!(parse_clock_duration_ms("剩余 01：02：03"), Some(3_723_000));
+    }
+
+    #[test]
+    fn overflowing_unit_duration_is_skipped() {
+        assert_eq!(
+            extract_number("99999999999999999h", NumberKind::Duration),
+            None
+        );
+        assert_eq!(
+            extract_number("99999999999999999999999d 后 5分", NumberKind::Duration),
+            Some(ExtractedNumber::Duration { ms: 300_000 })
+        );
+    }
+
+    #[test]
+    fn auto_prefers_the_earliest_and_most_specific_number() {
+        assert_eq!(
+            extract_number("今日 3/5 次，冷却 02:00", NumberKind::Auto),
+            Some(ExtractedNumber::Fraction {
+                numerator: 3,
+                denominator: 5
+            })
+        );
+        assert_eq!(
+            extract_number("冷却 02:00 后可领取 3 次", NumberKind::Auto),
+            Some(ExtractedNumber::Duration { ms: 120_000 })
+        );
+        assert_eq!(
+            extract_number("Lv.12", NumberKind::Auto),
+            Some(ExtractedNumber::Integer(12))
+        );
+
+        let fraction = extract_number("40/120", NumberKind::Auto).unwrap();
+        assert_eq!(
+            fraction.field_value(NumberField::Denominator),
+            Some(NumberValue::Int(120))
+        );
+        assert_eq!(
+            fraction
+                .field_value(NumberField::Ratio)
+                .map(NumberValue::as_f64),
+            Some(40.0 / 120.0)
+        );
+        assert_eq!(
+            ExtractedNumber::Integer(1).field_value(NumberField::Ratio),
+            None
+        );
+    }
+}
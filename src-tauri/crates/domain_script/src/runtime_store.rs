@@ -0,0 +1,141 @@
+use chrono::{DateTime, Duration, Local};
+use serde_json::Value;
+
+/// 持久化存储值的过期规则
+#[derive(
+    Debug, Clone, Copy, Default, serde::Serialize, serde::Deserialize, PartialEq, Eq, ts_rs::TS,
+)]
+#[ts(export)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum StoreExpiry {
+    /// 永不过期
+    #[default]
+    Never,
+    /// 写入后经过指定秒数过期
+    Ttl { seconds: u64 },
+    /// 到下一个每日重置时刻过期，`reset_hour` 为本地时间的整点（0-23）
+    Daily {
+        #[serde(default)]
+        reset_hour: u8,
+    },
+}
+
+impl StoreExpiry {
+    /// 计算在 `now` 写入时的过期时刻，`None` 表示永不过期
+    pub fn expires_at(&self, now: DateTime<Local>) -> Option<DateTime<Local>> {
+        match self {
+            Self::Never => None,
+            Self::Ttl { seconds } => {
+                let seconds = i64::try_from(*seconds).unwrap_or(i64::MAX);
+                Some(
+                    Duration::try_seconds(seconds)
+                        .and_then(|ttl| now.checked_add_signed(ttl))
+                        .unwrap_or(DateTime::<Local>::MAX_UTC.into()),
+                )
+            }
+            Self::Daily { reset_hour } => {
+                let hour = u32::from((*reset_hour).min(23));
+                let today_reset = now.date_naive().and_hms_opt(hour, 0, 0)?;
+                let next_reset = if now.naive_local() < today_reset {
+                    today_reset
+                } else {
+                    today_reset + Duration::days(1)
+                };
+                // 夏令时跳变导致重置时刻不存在时，退化为一天后过期
+                Some(
+                    next_reset
+                        .and_local_timezone(Local)
+                        .earliest()
+                        .unwrap_or_else(|| now + Duration::days(1)),
+                )
+            }
+        }
+    }
+}
+
+/// 跨运行持久化的键值，按设备、账号与脚本隔离
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, ts_rs::TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeStoreEntry {
+    pub key: String,
+    pub value: Value,
+    /// RFC3339 格式的过期时刻，为空表示永不过期
+    pub expires_at: Option<String>,
+    pub updated_at: String,
+}
+
+impl RuntimeStoreEntry {
+    pub fn new(key: String, value: Value, expiry: StoreExpiry, now: DateTime<Local>) -> Self {
+        Self {
+            key,
+            value,
+            expires_at: expiry.expires_at(now).map(|value| value.to_rfc3339()),
+            updated_at: now.to_rfc3339(),
+        }
+    }
+
+    /// 过期时刻无法解析时按已过期处理，避免读到语义不明的旧值
+    pub fn is_expired(&self, now: DateTime<Local>) -> bool {
+        self.expires_at.as_deref().is_some_and(|expires_at| {
+            DateTime::parse_from_rfc3339(expires_at).map_or(true, |value| value <= now)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn local(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Local> {
+        Local
+            .with_ymd_and_hms(year, month, day, hour, minute, 0)
+            .single()
+            .unwrap()
+    }
+
+    #[test]
+    fn computes_expiry_for_each_rule() {
+        let now = local(2026, 3, 10, 3, 30);
+
+        assert_eq!(StoreExpiry::Never.expires_at(now), None);
+        assert_eq!(
+            StoreExpiry::Ttl { seconds: 90 }.expires_at(now),
+            Some(now + Duration::seconds(90))
+        );
+        assert_eq!(
+            StoreExpiry::Daily { reset_hour: 5 }.expires_at(now),
+            Some(local(2026, 3, 10, 5, 0))
+        );
+        assert_eq!(
+            StoreExpiry::Daily { reset_hour: 0 }.expires_at(now),
+            Some(local(2026, 3, 11, 0, 0))
+        );
+        assert!(
+            StoreExpiry::Ttl { seconds: u64::MAX }
+                .expires_at(now)
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn entry_expires_at_reset_boundary() {
+        let written = local(2026, 3, 10, 23, 0);
+        let entry = RuntimeStoreEntry::new(
+            "lastReward".to_string(),
+            Value::from(1),
+            StoreExpiry::Daily { reset_hour: 4 },
+            written,
+        );
+
+        assert!(!entry.is_expired(local(2026, 3, 11, 3, 59)));
+        assert!(entry.is_expired(local(2026, 3, 11, 4, 0)));
+
+        let corrupted = RuntimeStoreEntry {
+            expires_at: Some("not a time".to_string()),
+            ..entry
+        };
+        assert!(corrupted.is_expired(written));
+    }
+}
@@ -3,6 +3,7 @@ use crate::{
     supported_script_features,
 };
 use ad_kernel::ids::{ScriptId, TaskId, UserId};
+use domain_device::HumanizeProfile;
 use domain_vision::{DetectorType, RecognizerType};
 use serde::{Deserialize, Serialize};
 
@@ -50,6 +51,17 @@ pub struct ScriptInfo {
     pub cloud_id: Option<ScriptId>,
     #[serde(default)]
     pub runtime_settings: ScriptRuntimeSettings,
+    /// 随脚本分发的 Rhai 模块，可通过 `import "名称" as 别名;` 在任务和策略中复用
+    #[serde(default)]
+    pub rhai_modules: Vec<RhaiModule>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, Hash, ts_rs::TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct RhaiModule {
+    pub name: String,
+    pub code: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, Hash, ts_rs::TS)]
@@ -57,8 +69,14 @@ pub struct ScriptInfo {
 #[serde(rename_all = "camelCase")]
 pub struct ScriptRuntimeSettings {
     pub recovery_task_id: Option<TaskId>,
+    /// 多账号轮换时，在运行每个账号之前执行的切换账号任务
+    #[serde(default)]
+    pub switch_account_task_id: Option<TaskId>,
     #[serde(default)]
     pub click_random_offset: u16,
+    /// 脚本自带的拟人化输入配置，为空时沿用设备配置
+    #[serde(default)]
+    pub humanize: Option<HumanizeProfile>,
 }
 
 impl Default for ScriptInfo {
@@ -92,6 +110,7 @@ impl Default for ScriptInfo {
             variable_catalog: ScriptVariableCatalog::default(),
             cloud_id: None,
             runtime_settings: ScriptRuntimeSettings::default(),
+            rhai_modules: Vec::new(),
         }
     }
 }
@@ -100,7 +119,9 @@ impl Default for ScriptRuntimeSettings {
     fn default() -> Self {
         Self {
             recovery_task_id: None,
+            switch_account_task_id: None,
             click_random_offset: 0,
+            humanize: None,
         }
     }
 }
@@ -0,0 +1,993 @@
+use crate::{
+    Action, ClickMode, ConditionNode, DataHanding, ExecCountValue, FlowControl,
+    PolicyGroupPolicyLink, PolicyGroupProfile, PolicyProfile, PolicySetGroupLink, PolicySetProfile,
+    PrintSource, ScriptInfo, ScriptTaskProfile, StateTarget, Step, StepKind, SwipeMode,
+    SwipeTarget, TaskControl, TaskRowType, VisionNode, WaitUntilTarget,
+};
+use ad_kernel::ids::{PolicyGroupId, PolicyId, PolicySetId, StepId, TaskId};
+use domain_vision::SearchRule;
+use std::collections::HashSet;
+
+/// 执行器为循环体预置的变量名
+const BUILTIN_LOOP_VARS: [&str; 4] = ["item", "item_index", "filter_item", "filter_index"];
+
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq, ts_rs::TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub enum ScriptDiagnosticSeverity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq, ts_rs::TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub enum ScriptDiagnosticCode {
+    UndefinedVariable,
+    MissingTask,
+    MissingPolicy,
+    MissingPolicyGroup,
+    MissingPolicySet,
+    LoopControlOutsideLoop,
+    RhaiCompileError,
+    EmptySearchPattern,
+    InvalidSearchPattern,
+}
+
+/// 静态检查结果；`step_id` 用于编辑器定位步骤
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq, ts_rs::TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptDiagnostic {
+    pub severity: ScriptDiagnosticSeverity,
+    pub code: ScriptDiagnosticCode,
+    pub message: String,
+    pub task_id: Option<TaskId>,
+    pub policy_id: Option<PolicyId>,
+    pub policy_set_id: Option<PolicySetId>,
+    pub step_id: Option<StepId>,
+}
+
+impl ScriptDiagnostic {
+    pub fn is_error(&self) -> bool {
+        self.severity == ScriptDiagnosticSeverity::Error
+    }
+}
+
+/// Rhai 片段的编译方式：整段脚本或单个表达式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RhaiSnippetKind {
+    Script,
+    Expression,
+}
+
+pub struct ScriptLintInput<'a> {
+    pub info: &'a ScriptInfo,
+    pub tasks: &'a [ScriptTaskProfile],
+    pub policies: &'a [PolicyProfile],
+    pub policy_groups: &'a [PolicyGroupProfile],
+    pub policy_sets: &'a [PolicySetProfile],
+    pub group_policies: &'a [PolicyGroupPolicyLink],
+    pub set_groups: &'a [PolicySetGroupLink],
+}
+
+#[derive(Clone, Copy)]
+enum LintOwner {
+    Task(TaskId),
+    Policy(PolicyId),
+}
+
+struct StepRef<'a> {
+    owner: LintOwner,
+    step: &'a Step,
+    in_loop: bool,
+}
+
+struct ScriptLinter<'a, F> {
+    input: &'a ScriptLintInput<'a>,
+    compile_rhai: F,
+    task_ids: HashSet<TaskId>,
+    policy_ids: HashSet<PolicyId>,
+    policy_group_ids: HashSet<PolicyGroupId>,
+    policy_set_ids: HashSet<PolicySetId>,
+    variable_ids: HashSet<&'a str>,
+    defined_vars: HashSet<&'a str>,
+    diagnostics: Vec<ScriptDiagnostic>,
+}
+
+/// 对脚本的任务与策略做静态检查，Rhai 编译由调用方提供以免领域层依赖脚本引擎
+pub fn lint_script_bundle<F>(input: &ScriptLintInput<'_>, compile_rhai: F) -> Vec<ScriptDiagnostic>
+where
+    F: Fn(RhaiSnippetKind, &str) -> Result<(), String>,
+{
+    let live_tasks = input
+        .tasks
+        .iter()
+        .filter(|task| !task.is_deleted)
+        .collect::<Vec<_>>();
+    let mut steps = Vec::new();
+    for task in live_tasks
+        .iter()
+        .filter(|task| task.row_type == TaskRowType::Task)
+    {
+        collect_steps(
+            LintOwner::Task(task.id),
+            &task.task.steps,
+            false,
+            &mut steps,
+        );
+    }
+    for policy in input.policies {
+        let owner = LintOwner::Policy(policy.id);
+        collect_steps(owner, &policy.info.before_action, false, &mut steps);
+        collect_steps(owner, &policy.info.after_action, false, &mut steps);
+    }
+
+    let mut linter = ScriptLinter {
+        input,
+        compile_rhai,
+        task_ids: live_tasks.iter().map(|task| task.id).collect(),
+        policy_ids: input.policies.iter().map(|policy| policy.id).collect(),
+        policy_group_ids: input.policy_groups.iter().map(|group| group.id).collect(),
+        policy_set_ids: input.policy_sets.iter().map(|set| set.id).collect(),
+        variable_ids: input
+            .info
+            .variable_catalog
+            .variables
+            .iter()
+            .map(|variable| variable.id.as_str())
+            .collect(),
+        defined_vars: input
+            .info
+            .variable_catalog
+            .variables
+            .iter()
+            .map(|variable| variable.key.as_str())
+            .chain(BUILTIN_LOOP_VARS)
+            .chain(
+                steps
+                    .iter()
+                    .flat_map(|step_ref| written_vars(step_ref.step)),
+            )
+            .collect(),
+        diagnostics: Vec::new(),
+    };
+    for step_ref in &steps {
+        linter.lint_step(step_ref);
+    }
+    for policy in input.policies {
+        linter.lint_search_rule(LintOwner::Policy(policy.id), None, &policy.info.cond);
+    }
+    linter.lint_policy_links();
+    linter.diagnostics
+}
+
+fn collect_steps<'a>(
+    owner: LintOwner,
+    steps: &'a [Step],
+    in_loop: bool,
+    out: &mut Vec<StepRef<'a>>,
+) {
+    for step in steps {
+        out.push(StepRef {
+            owner,
+            step,
+            in_loop,
+        });
+        for (children, loop_body) in child_step_lists(step) {
+            collect_steps(owner, children, in_loop || loop_body, out);
+        }
+    }
+}
+
+/// 子步骤列表以及它们是否位于循环体内
+fn child_step_lists(step: &Step) -> Vec<(&[Step], bool)> {
+    match &step.kind {
+        StepKind::Sequence { steps } => vec![(steps, false)],
+        StepKind::FlowControl { a } => match a {
+            FlowControl::If {
+                then, else_steps, ..
+            } => {
+                let mut lists = vec![(then.as_slice(), false)];
+                if let Some(else_steps) = else_steps {
+                    lists.push((else_steps, false));
+                }
+                lists
+            }
+            FlowControl::While { flow, .. }
+            | FlowControl::ForEach { flow, .. }
+            | FlowControl::Repeat { flow, .. } => vec![(flow, true)],
+            FlowControl::WaitUntil { else_steps, .. } => vec![(else_steps, false)],
+            _ => Vec::new(),
+        },
+        StepKind::DataHanding { a } => match a {
+            DataHanding::Filter { then_steps, .. } => vec![(then_steps, true)],
+            DataHanding::ColorCompare { then_steps, .. }
+            | DataHanding::RelativeFilter { then_steps, .. } => vec![(then_steps, false)],
+            _ => Vec::new(),
+        },
+        StepKind::Vision {
+            a: VisionNode::VisionSearch { then_steps, .. },
+        } => vec![(then_steps, false)],
+        _ => Vec::new(),
+    }
+}
+
+fn written_vars(step: &Step) -> Vec<&str> {
+    let vars: Vec<&str> = match &step.kind {
+        StepKind::Action {
+            a: Action::Capture { output_var },
+            ..
+        } => vec![output_var],
+        StepKind::DataHanding { a } => match a {
+            DataHanding::SetVar { name, .. } => vec![name],
+            DataHanding::Filter { out_name, .. } => vec![out_name],
+            DataHanding::ColorCompare { out_var, .. }
+            | DataHanding::RelativeFilter { out_var, .. }
+            | DataHanding::ExtractNumber { out_var, .. }
+            | DataHanding::StoreGet { out_var, .. } => vec![out_var],
+            DataHanding::Rhai {
+                out_var: Some(out_var),
+                ..
+            } => vec![out_var],
+            _ => Vec::new(),
+        },
+        StepKind::FlowControl { a } => match a {
+            FlowControl::ForEach {
+                item_var,
+                index_var,
+                ..
+            } => vec![item_var, index_var],
+            FlowControl::Repeat { index_var, .. } => vec![index_var],
+            FlowControl::SearchPolicySetText { out_var, .. }
+            | FlowControl::HandlePolicySet { out_var, .. }
+            | FlowControl::HandlePolicy { out_var, .. } => vec![out_var],
+            _ => Vec::new(),
+        },
+        StepKind::Vision { a } => match a {
+            VisionNode::Detect { out_var, .. }
+            | VisionNode::Ocr { out_var, .. }
+            | VisionNode::TemplateMatch { out_var, .. } => vec![out_var],
+            VisionNode::VisionSearch {
+                out_var,
+                out_det_var,
+                out_ocr_var,
+                ..
+            } => [Some(out_var), out_det_var.as_ref(), out_ocr_var.as_ref()]
+                .into_iter()
+                .flatten()
+                .map(String::as_str)
+                .collect(),
+        },
+        _ => Vec::new(),
+    };
+    vars.into_iter()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+fn read_vars(step: &Step) -> Vec<&str> {
+    let mut vars: Vec<&str> = Vec::new();
+    match &step.kind {
+        StepKind::Action { a, .. } => match a {
+            Action::Click { mode, .. } | Action::LongClick { mode, .. } => match mode {
+                ClickMode::Txt { input_var, .. } | ClickMode::LabelIdx { input_var, .. } => {
+                    vars.push(input_var)
+                }
+                _ => {}
+            },
+            Action::Swipe { mode, .. } => match mode {
+                SwipeMode::LabelIdx { input_var, .. } | SwipeMode::Txt { input_var, .. } => {
+                    vars.push(input_var)
+                }
+                SwipeMode::Mixed { from, to } => {
+                    for target in [from, to] {
+                        match target {
+                            SwipeTarget::Txt { input_var, .. }
+                            | SwipeTarget::LabelIdx { input_var, .. } => vars.push(input_var),
+                        }
+                    }
+                }
+                _ => {}
+            },
+            _ => {}
+        },
+        StepKind::DataHanding { a } => match a {
+            DataHanding::GetVar { name, .. } => vars.push(name),
+            DataHanding::Print {
+                source: PrintSource::Variable,
+                value,
+                ..
+            } => vars.push(value),
+            DataHanding::Filter { input_var, .. }
+            | DataHanding::ColorCompare { input_var, .. }
+            | DataHanding::RelativeFilter { input_var, .. } => vars.push(input_var),
+            DataHanding::ExtractNumber { source, .. } => vars.push(&source.input_var),
+            _ => {}
+        },
+        StepKind::FlowControl { a } => match a {
+            FlowControl::If { con, .. } | FlowControl::While { con, .. } => {
+                condition_read_vars(con, &mut vars)
+            }
+            FlowControl::ForEach { input_var, .. } => vars.push(input_var),
+            FlowControl::WaitMs {
+                input_var,
+                runtime_var,
+                ..
+            } => vars.extend(
+                [input_var, runtime_var]
+                    .into_iter()
+                    .flatten()
+                    .map(String::as_str),
+            ),
+            FlowControl::WaitUntil { until, .. } => match until {
+                WaitUntilTarget::Changed {
+                    reference_var: Some(reference_var),
+                    ..
+                } => vars.push(reference_var),
+                WaitUntilTarget::Condition { con } => condition_read_vars(con, &mut vars),
+                _ => {}
+            },
+            FlowControl::SearchPolicySetText { ocr_input_var, .. } => vars.push(ocr_input_var),
+            FlowControl::HandlePolicySet {
+                det_input_var,
+                search_hits_var,
+                ..
+            } => vars.extend([det_input_var.as_str(), search_hits_var.as_str()]),
+            FlowControl::HandlePolicy { input_var, .. } => vars.push(input_var),
+            _ => {}
+        },
+        StepKind::Vision { a } => match a {
+            VisionNode::Detect { input_var, .. }
+            | VisionNode::Ocr { input_var, .. }
+            | VisionNode::TemplateMatch { input_var, .. } => vars.push(input_var),
+            VisionNode::VisionSearch {
+                det_res_var,
+                ocr_res_var,
+                ..
+            } => vars.extend(
+                [det_res_var, ocr_res_var]
+                    .into_iter()
+                    .flatten()
+                    .map(String::as_str),
+            ),
+        },
+        _ => {}
+    }
+    vars.into_iter()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+fn condition_read_vars<'a>(con: &'a ConditionNode, vars: &mut Vec<&'a str>) {
+    match con {
+        ConditionNode::ExecNumCompare {
+            value: ExecCountValue::Variable { var_name },
+            ..
+        }
+        | ConditionNode::VarCompare { var_name, .. } => vars.push(var_name),
+        ConditionNode::VisionCountCompare { input_var, .. } => vars.push(input_var),
+        ConditionNode::OcrNumberCompare { source, .. } => vars.push(&source.input_var),
+        ConditionNode::PolicySetResult { result_var, .. } => vars.push(result_var),
+        ConditionNode::Group { items, .. } => {
+            for item in items {
+                condition_read_vars(item, vars);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn rhai_snippets(step: &Step) -> Vec<(RhaiSnippetKind, &str)> {
+    let mut snippets = Vec::new();
+    let mut exprs: Vec<Option<&String>> = Vec::new();
+    match &step.kind {
+        StepKind::Action { a, .. } => match a {
+            Action::Click { mode, .. } | Action::LongClick { mode, .. } => match mode {
+                ClickMode::Point { p_expr, .. } | ClickMode::Percent { p_expr, .. } => {
+                    exprs.push(p_expr.as_ref())
+                }
+                ClickMode::Txt { txt_expr, .. } => exprs.push(txt_expr.as_ref()),
+                ClickMode::LabelIdx { idx_expr, .. } => exprs.push(idx_expr.as_ref()),
+            },
+            Action::Swipe { mode, .. } => match mode {
+                SwipeMode::Percent {
+                    from_expr, to_expr, ..
+                }
+                | SwipeMode::Point {
+                    from_expr, to_expr, ..
+                }
+                | SwipeMode::Txt {
+                    from_expr, to_expr, ..
+                } => {
+                    exprs.push(from_expr.as_ref());
+                    exprs.push(to_expr.as_ref());
+                }
+                SwipeMode::Mixed { from, to } => {
+                    for target in [from, to] {
+                        if let SwipeTarget::Txt { value_expr, .. } = target {
+                            exprs.push(value_expr.as_ref());
+                        }
+                    }
+                }
+                SwipeMode::LabelIdx { .. } => {}
+            },
+            Action::LaunchApp {
+                pkg_name_expr,
+                activity_name_expr,
+                ..
+            } => {
+                exprs.push(pkg_name_expr.as_ref());
+                exprs.push(activity_name_expr.as_ref());
+            }
+            Action::StopApp { pkg_name_expr, .. } => exprs.push(pkg_name_expr.as_ref()),
+            _ => {}
+        },
+        StepKind::DataHanding { a } => match a {
+            DataHanding::SetVar { expr: value, .. } | DataHanding::StoreSet { expr: value, .. } => {
+                exprs.push(value.as_ref())
+            }
+            DataHanding::Filter { logic_expr, .. } => exprs.push(Some(logic_expr)),
+            DataHanding::Rhai { code, .. } => {
+                snippets.push((RhaiSnippetKind::Script, code.as_str()));
+            }
+            _ => {}
+        },
+        StepKind::FlowControl { a } => match a {
+            FlowControl::If { con, .. } | FlowControl::While { con, .. } => {
+                condition_exprs(con, &mut snippets)
+            }
+            FlowControl::WaitUntil {
+                until: WaitUntilTarget::Condition { con },
+                ..
+            } => condition_exprs(con, &mut snippets),
+            FlowControl::Repeat { count_expr, .. } => exprs.push(Some(count_expr)),
+            _ => {}
+        },
+        _ => {}
+    }
+    snippets.extend(
+        exprs
+            .into_iter()
+            .flatten()
+            .map(|value| (RhaiSnippetKind::Expression, value.as_str())),
+    );
+    snippets.retain(|(_, code)| !code.trim().is_empty());
+    snippets
+}
+
+fn condition_exprs<'a>(con: &'a ConditionNode, snippets: &mut Vec<(RhaiSnippetKind, &'a str)>) {
+    match con {
+        ConditionNode::RawExpr { expr } => snippets.push((RhaiSnippetKind::Expression, expr)),
+        ConditionNode::Group { items, .. } => {
+            for item in items {
+                condition_exprs(item, snippets);
+            }
+        }
+        _ => {}
+    }
+}
+
+impl<F> ScriptLinter<'_, F>
+where
+    F: Fn(RhaiSnippetKind, &str) -> Result<(), String>,
+{
+    fn push(
+        &mut self,
+        owner: Option<LintOwner>,
+        step_id: Option<StepId>,
+        severity: ScriptDiagnosticSeverity,
+        code: ScriptDiagnosticCode,
+        message: String,
+    ) {
+        self.diagnostics.push(ScriptDiagnostic {
+            severity,
+            code,
+            message,
+            task_id: match owner {
+                Some(LintOwner::Task(task_id)) => Some(task_id),
+                _ => None,
+            },
+            policy_id: match owner {
+                Some(LintOwner::Policy(policy_id)) => Some(policy_id),
+                _ => None,
+            },
+            policy_set_id: None,
+            step_id,
+        });
+    }
+
+    fn error(
+        &mut self,
+        owner: LintOwner,
+        step_id: Option<StepId>,
+        code: ScriptDiagnosticCode,
+        message: String,
+    ) {
+        self.push(
+            Some(owner),
+            step_id,
+            ScriptDiagnosticSeverity::Error,
+            code,
+            message,
+        );
+    }
+
+    fn lint_step(&mut self, step_ref: &StepRef<'_>) {
+        let StepRef {
+            owner,
+            step,
+            in_loop,
+        } = *step_ref;
+        let step_id = step.id;
+
+        for name in read_vars(step) {
+            self.lint_variable(owner, step_id, name);
+        }
+        for (kind, code) in rhai_snippets(step) {
+            if let Err(error) = (self.compile_rhai)(kind, code) {
+                self.error(
+                    owner,
+                    step_id,
+                    ScriptDiagnosticCode::RhaiCompileError,
+                    format!("Rhai 代码编译失败: {}", error),
+                );
+            }
+        }
+
+        match &step.kind {
+            StepKind::Action { a, .. } => match a {
+                Action::DropSetNext {
+                    task, variable_id, ..
+                } => {
+                    self.lint_task_ref(owner, step_id, *task);
+                    if !self.variable_ids.contains(variable_id.as_str()) {
+                        self.push(
+                            Some(owner),
+                            step_id,
+                            ScriptDiagnosticSeverity::Warning,
+                            ScriptDiagnosticCode::UndefinedVariable,
+                            format!("下拉切换引用的变量[{}]不在变量目录中", variable_id),
+                        );
+                    }
+                }
+                Action::PosAdd { target } | Action::PosMinus { target } => {
+                    self.lint_policy_ref(owner, step_id, *target)
+                }
+                _ => {}
+            },
+            StepKind::FlowControl { a } => self.lint_flow_control(owner, step_id, in_loop, a),
+            StepKind::TaskControl {
+                a: TaskControl::SetState {
+                    target, targets, ..
+                },
+            } => {
+                for target in std::iter::once(target).chain(targets) {
+                    self.lint_state_target(owner, step_id, target);
+                }
+            }
+            StepKind::Vision {
+                a: VisionNode::VisionSearch { rule, .. },
+            } => self.lint_search_rule(owner, step_id, rule),
+            _ => {}
+        }
+        if let StepKind::FlowControl {
+            a: FlowControl::If { con, .. } | FlowControl::While { con, .. },
+        } = &step.kind
+        {
+            self.lint_condition_refs(owner, step_id, con);
+        }
+    }
+
+    fn lint_flow_control(
+        &mut self,
+        owner: LintOwner,
+        step_id: Option<StepId>,
+        in_loop: bool,
+        flow: &FlowControl,
+    ) {
+        match flow {
+            FlowControl::Break | FlowControl::Continue if !in_loop => self.error(
+                owner,
+                step_id,
+                ScriptDiagnosticCode::LoopControlOutsideLoop,
+                "Break/Continue 不在 While、ForEach、Repeat 或 Filter 循环内，不会生效".to_string(),
+            ),
+            FlowControl::Link { target } => self.lint_task_ref(owner, step_id, *target),
+            FlowControl::AddPolicies { source, target, .. }
+            | FlowControl::RemovePolicies { source, target } => {
+                self.lint_policy_set_ref(owner, step_id, *source);
+                self.lint_policy_set_ref(owner, step_id, *target);
+            }
+            FlowControl::BindPolicyGroup { source, target, .. }
+            | FlowControl::RemovePolicyGroup { source, target } => {
+                self.lint_policy_group_ref(owner, step_id, *source);
+                self.lint_policy_set_ref(owner, step_id, *target);
+            }
+            FlowControl::AddPolicyGroups { source, target, .. }
+            | FlowControl::UnloadPolicyGroup { source, target } => {
+                self.lint_policy_group_ref(owner, step_id, *source);
+                self.lint_policy_group_ref(owner, step_id, *target);
+            }
+            FlowControl::BindPolicy { source, target, .. }
+            | FlowControl::UnloadPolicy { source, target } => {
+                self.lint_policy_ref(owner, step_id, *source);
+                self.lint_policy_group_ref(owner, step_id, *target);
+            }
+            FlowControl::SearchPolicySetText { target, .. }
+            | FlowControl::HandlePolicySet { target, .. } => {
+                for set_id in target {
+                    self.lint_policy_set_ref(owner, step_id, *set_id);
+                }
+            }
+            FlowControl::HandlePolicy { target, .. } => {
+                for policy_id in target {
+                    self.lint_policy_ref(owner, step_id, *policy_id);
+                }
+            }
+            FlowControl::WaitUntil {
+                until: WaitUntilTarget::Condition { con },
+                ..
+            } => self.lint_condition_refs(owner, step_id, con),
+            _ => {}
+        }
+    }
+
+    fn lint_condition_refs(
+        &mut self,
+        owner: LintOwner,
+        step_id: Option<StepId>,
+        con: &ConditionNode,
+    ) {
+        match con {
+            ConditionNode::ExecNumCompare { target, .. } => {
+                self.lint_state_target(owner, step_id, target)
+            }
+            ConditionNode::TaskStatus {
+                a: TaskControl::SetState {
+                    target, targets, ..
+                },
+            } => {
+                for target in std::iter::once(target).chain(targets) {
+                    self.lint_state_target(owner, step_id, target);
+                }
+            }
+            ConditionNode::CurrentTaskIn { current } => {
+                if let Some(task_id) = current.target {
+                    self.lint_task_ref(owner, step_id, task_id);
+                }
+            }
+            ConditionNode::Group { items, .. } => {
+                for item in items {
+                    self.lint_condition_refs(owner, step_id, item);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn lint_variable(&mut self, owner: LintOwner, step_id: Option<StepId>, name: &str) {
+        // 不带命名空间的名字可能是 Rhai 作用域变量，无法静态判断
+        let Some((namespace, _)) = name.split_once('.') else {
+            return;
+        };
+        if namespace == "system" || self.defined_vars.contains(name) {
+            return;
+        }
+        // input 变量只来自变量目录；runtime 变量还可能由 Rhai 代码写入
+        let severity = if namespace == "input" {
+            ScriptDiagnosticSeverity::Error
+        } else {
+            ScriptDiagnosticSeverity::Warning
+        };
+        self.push(
+            Some(owner),
+            step_id,
+            severity,
+            ScriptDiagnosticCode::UndefinedVariable,
+            format!("变量[{}]未在变量目录中定义，也没有步骤写入", name),
+        );
+    }
+
+    fn lint_state_target(
+        &mut self,
+        owner: LintOwner,
+        step_id: Option<StepId>,
+        target: &StateTarget,
+    ) {
+        match target {
+            StateTarget::Task { id } => self.lint_task_ref(owner, step_id, *id),
+            StateTarget::Policy { id } => self.lint_policy_ref(owner, step_id, *id),
+        }
+    }
+
+    fn lint_task_ref(&mut self, owner: LintOwner, step_id: Option<StepId>, task_id: TaskId) {
+        if !self.task_ids.contains(&task_id) {
+            self.error(
+                owner,
+                step_id,
+                ScriptDiagnosticCode::MissingTask,
+                format!("引用的任务[{}]不存在或已删除", task_id),
+            );
+        }
+    }
+
+    fn lint_policy_ref(&mut self, owner: LintOwner, step_id: Option<StepId>, policy_id: PolicyId) {
+        if !self.policy_ids.contains(&policy_id) {
+            self.error(
+                owner,
+                step_id,
+                ScriptDiagnosticCode::MissingPolicy,
+                format!("引用的策略[{}]不存在", policy_id),
+            );
+        }
+    }
+
+    fn lint_policy_group_ref(
+        &mut self,
+        owner: LintOwner,
+        step_id: Option<StepId>,
+        group_id: PolicyGroupId,
+    ) {
+        if !self.policy_group_ids.contains(&group_id) {
+            self.error(
+                owner,
+                step_id,
+                ScriptDiagnosticCode::MissingPolicyGroup,
+                format!("引用的策略组[{}]不存在", group_id),
+            );
+        }
+    }
+
+    fn lint_policy_set_ref(
+        &mut self,
+        owner: LintOwner,
+        step_id: Option<StepId>,
+        set_id: PolicySetId,
+    ) {
+        if !self.policy_set_ids.contains(&set_id) {
+            self.error(
+                owner,
+                step_id,
+                ScriptDiagnosticCode::MissingPolicySet,
+                format!("引用的策略集[{}]不存在", set_id),
+            );
+        }
+    }
+
+    fn lint_search_rule(&mut self, owner: LintOwner, step_id: Option<StepId>, rule: &SearchRule) {
+        match rule {
+            SearchRule::Txt { pattern, .. } if pattern.trim().is_empty() => self.error(
+                owner,
+                step_id,
+                ScriptDiagnosticCode::EmptySearchPattern,
+                "文字匹配规则的内容为空，永远不会命中".to_string(),
+            ),
+            SearchRule::Txt { pattern, mode, .. } => {
+                if let Err(e) = mode.validate(pattern.trim()) {
+                    self.error(
+                        owner,
+                        step_id,
+                        ScriptDiagnosticCode::InvalidSearchPattern,
+                        format!("文字匹配规则[{}]无效: {}", pattern.trim(), e),
+                    );
+                }
+            }
+            SearchRule::Group { items, .. } => {
+                for item in items {
+                    self.lint_search_rule(owner, step_id, item);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn lint_policy_links(&mut self) {
+        let input = self.input;
+        for link in input.set_groups {
+            if !self.policy_group_ids.contains(&link.group_id) {
+                self.diagnostics.push(ScriptDiagnostic {
+                    severity: ScriptDiagnosticSeverity::Error,
+                    code: ScriptDiagnosticCode::MissingPolicyGroup,
+                    message: format!("策略集引用的策略组[{}]已删除", link.group_id),
+                    task_id: None,
+                    policy_id: None,
+                    policy_set_id: Some(link.set_id),
+                    step_id: None,
+                });
+            }
+        }
+        for link in input.group_policies {
+            if !self.policy_ids.contains(&link.policy_id) {
+                self.diagnostics.push(ScriptDiagnostic {
+                    severity: ScriptDiagnosticSeverity::Error,
+                    code: ScriptDiagnosticCode::MissingPolicy,
+                    message: format!(
+                        "策略组[{}]引用的策略[{}]已删除",
+                        link.group_id, link.policy_id
+                    ),
+                    task_id: None,
+                    policy_id: Some(link.policy_id),
+                    policy_set_id: None,
+                    step_id: None,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ScriptTask, TaskCycle, TaskTone, TaskTriggerMode};
+    use ad_kernel::ids::ScriptId;
+
+    fn step(kind: StepKind) -> Step {
+        Step {
+            id: Some(StepId::new_v7()),
+            source_id: None,
+            target_id: None,
+            label: None,
+            skip_flag: false,
+            kind,
+        }
+    }
+
+    fn flow(a: FlowControl) -> Step {
+        step(StepKind::FlowControl { a })
+    }
+
+    fn set_var(name: &str, expr: Option<&str>) -> Step {
+        step(StepKind::DataHanding {
+            a: DataHanding::SetVar {
+                name: name.to_string(),
+                val: None,
+                json_val: None,
+                expr: expr.map(str::to_string),
+            },
+        })
+    }
+
+    fn get_var(name: &str) -> Step {
+        step(StepKind::DataHanding {
+            a: DataHanding::GetVar {
+                name: name.to_string(),
+                default_val: None,
+            },
+        })
+    }
+
+    fn task(steps: Vec<Step>) -> ScriptTaskProfile {
+        let now = chrono::Utc::now();
+        ScriptTaskProfile {
+            id: TaskId::new_v7(),
+            script_id: ScriptId::new_v7(),
+            name: "任务".to_string(),
+            description: String::new(),
+            row_type: TaskRowType::Task,
+            trigger_mode: TaskTriggerMode::RootOnly,
+            record_schedule: true,
+            section_id: None,
+            indent_level: 0,
+            default_task_cycle: TaskCycle::EveryRun,
+            exec_max: 1,
+            show_enabled_toggle: true,
+            default_enabled: true,
+            task_tone: TaskTone::Normal,
+            is_hidden: false,
+            task: ScriptTask {
+                ui_data: serde_json::Value::Null,
+                variables: serde_json::Value::Null,
+                steps,
+                retry_policy: None,
+            },
+            created_at: now,
+            updated_at: now,
+            deleted_at: None,
+            is_deleted: false,
+            index: 0,
+        }
+    }
+
+    fn lint(tasks: &[ScriptTaskProfile]) -> Vec<ScriptDiagnostic> {
+        let info = ScriptInfo::default();
+        let input = ScriptLintInput {
+            info: &info,
+            tasks,
+            policies: &[],
+            policy_groups: &[],
+            policy_sets: &[],
+            group_policies: &[],
+            set_groups: &[],
+        };
+        lint_script_bundle(&input, |_, code| {
+            if code.contains("@@") {
+                Err("语法错误".to_string())
+            } else {
+                Ok(())
+            }
+        })
+    }
+
+    fn codes(diagnostics: &[ScriptDiagnostic]) -> Vec<ScriptDiagnosticCode> {
+        diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.code)
+            .collect()
+    }
+
+    #[test]
+    fn reports_undefined_reads_and_accepts_written_vars() {
+        let diagnostics = lint(&[task(vec![
+            set_var("runtime.count", Some("1")),
+            get_var("runtime.count"),
+            get_var("input.missing"),
+            get_var("runtime.unknown"),
+            get_var("system.now"),
+        ])]);
+        assert_eq!(diagnostics.len(), 2);
+        assert!(diagnostics[0].is_error());
+        assert!(diagnostics[0].message.contains("input.missing"));
+        assert_eq!(diagnostics[1].severity, ScriptDiagnosticSeverity::Warning);
+    }
+
+    #[test]
+    fn reports_missing_tasks_loop_control_and_rhai_errors() {
+        let missing = TaskId::new_v7();
+        let mut deleted = task(Vec::new());
+        deleted.is_deleted = true;
+        let deleted_id = deleted.id;
+        let main = task(vec![
+            flow(FlowControl::Link { target: missing }),
+            flow(FlowControl::Link { target: deleted_id }),
+            flow(FlowControl::Break),
+            flow(FlowControl::Repeat {
+                count_expr: "3".to_string(),
+                index_var: "runtime.i".to_string(),
+                flow: vec![flow(FlowControl::Continue)],
+            }),
+            set_var("runtime.x", Some("1 @@ 2")),
+        ]);
+        let main_id = main.id;
+        let diagnostics = lint(&[main, deleted]);
+        assert_eq!(
+            codes(&diagnostics),
+            vec![
+                ScriptDiagnosticCode::MissingTask,
+                ScriptDiagnosticCode::MissingTask,
+                ScriptDiagnosticCode::LoopControlOutsideLoop,
+                ScriptDiagnosticCode::RhaiCompileError,
+            ]
+        );
+        assert!(diagnostics.iter().all(|item| item.task_id == Some(main_id)));
+    }
+
+    #[test]
+    fn reports_invalid_search_regex() {
+        let search = |pattern: &str| {
+            step(StepKind::Vision {
+                a: VisionNode::VisionSearch {
+                    det_res_var: None,
+                    ocr_res_var: None,
+                    rule: SearchRule::Txt {
+                        pattern: pattern.to_string(),
+                        mode: domain_vision::TextMatchMode::Regex,
+                        normalize: Default::default(),
+                    },
+                    out_var: "runtime.found".to_string(),
+                    out_det_var: None,
+                    out_ocr_var: None,
+                    then_steps: Vec::new(),
+                },
+            })
+        };
+        let diagnostics = lint(&[task(vec![search(r"剩余(\d+"), search(r"剩余(\d+)次")])]);
+        assert_eq!(
+            codes(&diagnostics),
+            vec![ScriptDiagnosticCode::InvalidSearchPattern]
+        );
+        assert!(diagnostics[0].message.contains("正则表达式无效"));
+    }
+}
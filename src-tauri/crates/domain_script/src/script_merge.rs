@@ -0,0 +1,1311 @@
+use crate::script_snapshot::{ScriptGraphSnapshot, SnapshotStepOwner};
+use ad_kernel::ids::{PolicyGroupId, PolicyId, PolicySetId, TaskId};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+/// 三方合并中参与合并的脚本信息字段，其余字段（名称、作者、版本、模型等）始终保留本地值
+const MERGED_SCRIPT_INFO_KEYS: &[&str] = &[
+    "description",
+    "contentMd",
+    "runtimeType",
+    "platform",
+    "minAppVersion",
+    "minRuntimeSchema",
+    "requiredFeatures",
+    "variableCatalog",
+    "runtimeSettings",
+    "rhaiModules",
+];
+
+/// 冲突的处理方式，未指定时保留本地
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, ts_rs::TS,
+)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub enum MergeResolution {
+    #[default]
+    Ours,
+    Theirs,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, ts_rs::TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub enum MergeEntityKind {
+    Script,
+    Task,
+    Step,
+    Policy,
+    PolicyGroup,
+    PolicySet,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, ts_rs::TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub enum MergeConflictKind {
+    /// 本地与上游都修改了同一对象
+    BothModified,
+    /// 本地删除，上游修改
+    OursDeleted,
+    /// 本地修改，上游删除
+    TheirsDeleted,
+}
+
+/// 合并冲突标记，ID 均为合并结果中的 ID，可直接在编辑器中定位
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ts_rs::TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptMergeConflict {
+    pub key: String,
+    pub entity: MergeEntityKind,
+    pub kind: MergeConflictKind,
+    pub entity_id: String,
+    pub name: String,
+    /// 步骤冲突所在的任务或策略
+    pub owner: Option<SnapshotStepOwner>,
+    pub owner_id: Option<String>,
+    /// 双方都修改时冲突的字段
+    pub fields: Vec<String>,
+    pub resolution: MergeResolution,
+}
+
+#[derive(Debug)]
+pub struct ScriptMergeOutcome {
+    /// 合并结果，ID 沿用本地脚本
+    pub merged: ScriptGraphSnapshot,
+    /// 映射到本地 ID 空间的上游脚本，作为下一次合并的基线
+    pub upstream_base: ScriptGraphSnapshot,
+    pub conflicts: Vec<ScriptMergeConflict>,
+}
+
+struct FieldSpec {
+    nested: &'static str,
+    nested_skip: &'static [&'static str],
+}
+
+const TASK_FIELDS: FieldSpec = FieldSpec {
+    nested: "task",
+    nested_skip: &["steps"],
+};
+const POLICY_FIELDS: FieldSpec = FieldSpec {
+    nested: "info",
+    nested_skip: &["beforeAction", "afterAction"],
+};
+const INFO_FIELDS: FieldSpec = FieldSpec {
+    nested: "info",
+    nested_skip: &[],
+};
+const TOP_LEVEL_SKIP: &[&str] = &["id", "scriptId", "createdAt", "updatedAt"];
+
+type StepLists = fn(&Value) -> Vec<(SnapshotStepOwner, &[Value])>;
+
+struct CollectionSpec {
+    entity: MergeEntityKind,
+    key: &'static str,
+    order_key: &'static str,
+    fields: FieldSpec,
+    lists: StepLists,
+}
+
+const TASK_COLLECTION: CollectionSpec = CollectionSpec {
+    entity: MergeEntityKind::Task,
+    key: "tasks",
+    order_key: "index",
+    fields: TASK_FIELDS,
+    lists: task_step_lists,
+};
+const POLICY_COLLECTION: CollectionSpec = CollectionSpec {
+    entity: MergeEntityKind::Policy,
+    key: "policies",
+    order_key: "orderIndex",
+    fields: POLICY_FIELDS,
+    lists: policy_step_lists,
+};
+const POLICY_GROUP_COLLECTION: CollectionSpec = CollectionSpec {
+    entity: MergeEntityKind::PolicyGroup,
+    key: "policyGroups",
+    order_key: "orderIndex",
+    fields: INFO_FIELDS,
+    lists: no_step_lists,
+};
+const POLICY_SET_COLLECTION: CollectionSpec = CollectionSpec {
+    entity: MergeEntityKind::PolicySet,
+    key: "policySets",
+    order_key: "orderIndex",
+    fields: INFO_FIELDS,
+    lists: no_step_lists,
+};
+
+fn str_field<'a>(value: &'a Value, key: &str) -> &'a str {
+    value.get(key).and_then(Value::as_str).unwrap_or_default()
+}
+
+fn item_id(value: &Value) -> String {
+    str_field(value, "id").to_string()
+}
+
+fn item_name(value: &Value) -> String {
+    value
+        .get("name")
+        .or_else(|| value.get("info").and_then(|info| info.get("name")))
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn array<'a>(value: &'a Value, path: &[&str]) -> &'a [Value] {
+    path.iter()
+        .try_fold(value, |current, key| current.get(key))
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+fn task_step_lists(task: &Value) -> Vec<(SnapshotStepOwner, &[Value])> {
+    vec![(SnapshotStepOwner::Task, array(task, &["task", "steps"]))]
+}
+
+fn policy_step_lists(policy: &Value) -> Vec<(SnapshotStepOwner, &[Value])> {
+    vec![
+        (
+            SnapshotStepOwner::PolicyBefore,
+            array(policy, &["info", "beforeAction"]),
+        ),
+        (
+            SnapshotStepOwner::PolicyAfter,
+            array(policy, &["info", "afterAction"]),
+        ),
+    ]
+}
+
+fn no_step_lists(_: &Value) -> Vec<(SnapshotStepOwner, &[Value])> {
+    Vec::new()
+}
+
+fn step_list_path(owner: SnapshotStepOwner) -> [&'static str; 2] {
+    match owner {
+        SnapshotStepOwner::Task => ["task", "steps"],
+        SnapshotStepOwner::PolicyBefore => ["info", "beforeAction"],
+        SnapshotStepOwner::PolicyAfter => ["info", "afterAction"],
+    }
+}
+
+/// 步骤内嵌套的子步骤列表：序列的 steps，以及流程控制、数据处理与视觉节点的分支和循环体
+const CHILD_STEP_LISTS: &[&[&str]] = &[
+    &["steps"],
+    &["a", "then"],
+    &["a", "else_steps"],
+    &["a", "flow"],
+    &["a", "then_steps"],
+];
+
+fn child_step_lists(step: &Value) -> impl Iterator<Item = &[Value]> {
+    CHILD_STEP_LISTS.iter().map(|path| array(step, path))
+}
+
+/// 去掉子步骤列表后的步骤本身
+fn step_shell(step: &Value) -> Value {
+    let mut shell = step.clone();
+    for path in CHILD_STEP_LISTS {
+        let (last, parents) = path.split_last().expect("路径非空");
+        let parent = parents
+            .iter()
+            .try_fold(&mut shell, |current, key| current.get_mut(*key));
+        if let Some(object) = parent.and_then(Value::as_object_mut) {
+            object.remove(*last);
+        }
+    }
+    shell
+}
+
+/// 步骤类型相同才逐层合并子步骤，类型变化时整个步骤作为一处冲突
+fn same_step_kind(left: &Value, right: &Value) -> bool {
+    str_field(left, "op") == str_field(right, "op")
+        && str_field(&left["a"], "type") == str_field(&right["a"], "type")
+}
+
+/// 无 ID 的步骤按位置匹配，嵌套列表中的位置带上父步骤前缀以免冲突 key 重复
+fn step_key(step: &Value, index: usize, scope: &str) -> String {
+    step.get("id")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("{scope}#{index}"))
+}
+
+fn step_label(step: &Value) -> String {
+    let label = str_field(step, "label").trim();
+    if label.is_empty() {
+        str_field(step, "op").to_string()
+    } else {
+        label.to_string()
+    }
+}
+
+fn collect_step_ids(steps: &[Value], ids: &mut HashSet<String>) {
+    for step in steps {
+        if let Some(id) = step.get("id").and_then(Value::as_str) {
+            ids.insert(id.to_string());
+        }
+        for children in child_step_lists(step) {
+            collect_step_ids(children, ids);
+        }
+    }
+}
+
+fn step_ids(lists: Vec<(SnapshotStepOwner, &[Value])>) -> HashSet<String> {
+    let mut ids = HashSet::new();
+    for (_, steps) in lists {
+        collect_step_ids(steps, &mut ids);
+    }
+    ids
+}
+
+/// 将上游对象对齐到基线：先按 ID，再按共享 StepId 数量，最后按名称
+fn align_collection(
+    base: &[Value],
+    theirs: &[Value],
+    lists: StepLists,
+    fresh_id: fn() -> String,
+    id_map: &mut HashMap<String, String>,
+) {
+    let mut unmatched_base = base.iter().map(item_id).collect::<BTreeSet<_>>();
+    let mut pending = Vec::new();
+    for item in theirs {
+        let id = item_id(item);
+        if unmatched_base.remove(&id) {
+            id_map.insert(id.clone(), id);
+        } else {
+            pending.push(item);
+        }
+    }
+
+    let mut still_pending = Vec::new();
+    for item in pending {
+        let ids = step_ids(lists(item));
+        let best = base
+            .iter()
+            .filter(|candidate| unmatched_base.contains(&item_id(candidate)))
+            .map(|candidate| {
+                let overlap = step_ids(lists(candidate)).intersection(&ids).count();
+                (overlap, item_id(candidate))
+            })
+            .filter(|(overlap, _)| *overlap > 0)
+            .max_by_key(|(overlap, _)| *overlap);
+        match best {
+            Some((_, base_id)) => {
+                unmatched_base.remove(&base_id);
+                id_map.insert(item_id(item), base_id);
+            }
+            None => still_pending.push(item),
+        }
+    }
+
+    for item in still_pending {
+        let name = item_name(item);
+        let matched = base
+            .iter()
+            .filter(|candidate| unmatched_base.contains(&item_id(candidate)))
+            .find(|candidate| !name.is_empty() && item_name(candidate) == name)
+            .map(item_id);
+        let target = match matched {
+            Some(base_id) => {
+                unmatched_base.remove(&base_id);
+                base_id
+            }
+            None => fresh_id(),
+        };
+        id_map.insert(item_id(item), target);
+    }
+}
+
+fn replace_ids(value: &mut Value, id_map: &HashMap<String, String>) {
+    match value {
+        Value::String(text) => {
+            if let Some(mapped) = id_map.get(text.as_str()) {
+                *text = mapped.clone();
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| replace_ids(item, id_map)),
+        Value::Object(object) => {
+            let entries = std::mem::take(object);
+            for (key, mut item) in entries {
+                replace_ids(&mut item, id_map);
+                let key = id_map.get(key.as_str()).cloned().unwrap_or(key);
+                object.insert(key, item);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 把上游脚本的全部 ID（含步骤中对任务/策略的引用）映射到基线 ID 空间
+fn rekey_theirs(base: &Value, theirs: &Value) -> Value {
+    let mut id_map = HashMap::new();
+    id_map.insert(
+        str_field(&theirs["script"], "id").to_string(),
+        str_field(&base["script"], "id").to_string(),
+    );
+    align_collection(
+        array(base, &["tasks"]),
+        array(theirs, &["tasks"]),
+        task_step_lists,
+        || TaskId::new_v7().to_string(),
+        &mut id_map,
+    );
+    align_collection(
+        array(base, &["policies"]),
+        array(theirs, &["policies"]),
+        policy_step_lists,
+        || PolicyId::new_v7().to_string(),
+        &mut id_map,
+    );
+    align_collection(
+        array(base, &["policyGroups"]),
+        array(theirs, &["policyGroups"]),
+        no_step_lists,
+        || PolicyGroupId::new_v7().to_string(),
+        &mut id_map,
+    );
+    align_collection(
+        array(base, &["policySets"]),
+        array(theirs, &["policySets"]),
+        no_step_lists,
+        || PolicySetId::new_v7().to_string(),
+        &mut id_map,
+    );
+    // 脚本自身的 cloudId 等信息不参与映射
+    let script_info = theirs["script"]["info"].clone();
+    let mut rekeyed = theirs.clone();
+    replace_ids(&mut rekeyed, &id_map);
+    rekeyed["script"]["info"] = script_info;
+    rekeyed
+}
+
+fn fields(item: Option<&Value>, spec: &FieldSpec) -> BTreeMap<String, Value> {
+    let mut result = BTreeMap::new();
+    let Some(object) = item.and_then(Value::as_object) else {
+        return result;
+    };
+    for (key, value) in object {
+        if TOP_LEVEL_SKIP.contains(&key.as_str()) {
+            continue;
+        }
+        if key == spec.nested {
+            if let Some(nested) = value.as_object() {
+                for (nested_key, nested_value) in nested {
+                    if !spec.nested_skip.contains(&nested_key.as_str()) {
+                        result.insert(format!("{key}.{nested_key}"), nested_value.clone());
+                    }
+                }
+            }
+            continue;
+        }
+        result.insert(key.clone(), value.clone());
+    }
+    result
+}
+
+fn write_fields(target: &mut Value, merged: &BTreeMap<String, Value>) {
+    for (key, value) in merged {
+        match key.split_once('.') {
+            Some((outer, inner)) => {
+                if !target[outer].is_object() {
+                    target[outer] = Value::Object(Map::new());
+                }
+                target[outer][inner] = value.clone();
+            }
+            None => target[key.as_str()] = value.clone(),
+        }
+    }
+}
+
+/// 逐字段三方合并，返回冲突字段
+fn merge_fields(
+    base: &BTreeMap<String, Value>,
+    ours: &BTreeMap<String, Value>,
+    theirs: &BTreeMap<String, Value>,
+    resolution: MergeResolution,
+) -> (BTreeMap<String, Value>, Vec<String>) {
+    let keys = base
+        .keys()
+        .chain(ours.keys())
+        .chain(theirs.keys())
+        .cloned()
+        .collect::<BTreeSet<_>>();
+    let mut merged = BTreeMap::new();
+    let mut conflicts = Vec::new();
+    for key in keys {
+        let (b, o, t) = (base.get(&key), ours.get(&key), theirs.get(&key));
+        let value = if o == t || b == t {
+            o
+        } else if b == o {
+            t
+        } else {
+            conflicts.push(key.clone());
+            match resolution {
+                MergeResolution::Ours => o,
+                MergeResolution::Theirs => t,
+            }
+        };
+        if let Some(value) = value {
+            merged.insert(key, value.clone());
+        }
+    }
+    (merged, conflicts)
+}
+
+struct MergeContext<'a> {
+    resolutions: &'a HashMap<String, MergeResolution>,
+    conflicts: Vec<ScriptMergeConflict>,
+}
+
+impl MergeContext<'_> {
+    fn resolution(&self, key: &str) -> MergeResolution {
+        self.resolutions.get(key).copied().unwrap_or_default()
+    }
+
+    fn push(
+        &mut self,
+        key: String,
+        entity: MergeEntityKind,
+        kind: MergeConflictKind,
+        entity_id: String,
+        name: String,
+    ) -> &mut ScriptMergeConflict {
+        let resolution = self.resolution(&key);
+        self.conflicts.push(ScriptMergeConflict {
+            key,
+            entity,
+            kind,
+            entity_id,
+            name,
+            owner: None,
+            owner_id: None,
+            fields: Vec::new(),
+            resolution,
+        });
+        self.conflicts.last_mut().expect("刚写入的冲突")
+    }
+}
+
+/// 在本地顺序基础上，把上游新增的项插到其上游前驱之后
+fn merge_order(
+    ours_keys: &[String],
+    theirs_keys: &[String],
+    kept: &HashSet<String>,
+) -> Vec<String> {
+    let mut order = ours_keys
+        .iter()
+        .filter(|key| kept.contains(*key))
+        .cloned()
+        .collect::<Vec<_>>();
+    for (index, key) in theirs_keys.iter().enumerate() {
+        if !kept.contains(key) || order.contains(key) {
+            continue;
+        }
+        let position = theirs_keys[..index]
+            .iter()
+            .rev()
+            .find_map(|previous| order.iter().position(|placed| placed == previous))
+            .map_or(0, |position| position + 1);
+        order.insert(position, key.clone());
+    }
+    order
+}
+
+fn push_step_conflict(
+    ctx: &mut MergeContext<'_>,
+    owner: SnapshotStepOwner,
+    owner_id: &str,
+    key: &str,
+    kind: MergeConflictKind,
+    label: String,
+) -> MergeResolution {
+    let conflict_key = format!("step:{owner_id}:{key}");
+    let resolution = ctx.resolution(&conflict_key);
+    let conflict = ctx.push(
+        conflict_key,
+        MergeEntityKind::Step,
+        kind,
+        key.to_string(),
+        label,
+    );
+    conflict.owner = Some(owner);
+    conflict.owner_id = Some(owner_id.to_string());
+    resolution
+}
+
+/// 双方都改动了同一步骤时，先合并步骤本身，再按 StepId 逐层合并子步骤列表
+fn merge_step_tree(
+    ctx: &mut MergeContext<'_>,
+    owner: SnapshotStepOwner,
+    owner_id: &str,
+    key: &str,
+    base: Option<&Value>,
+    ours: &Value,
+    theirs: &Value,
+) -> Value {
+    let (b, o, t) = (base.map(step_shell), step_shell(ours), step_shell(theirs));
+    let mut merged = if o == t || b.as_ref() == Some(&t) {
+        o
+    } else if b.as_ref() == Some(&o) {
+        t
+    } else {
+        let resolution = push_step_conflict(
+            ctx,
+            owner,
+            owner_id,
+            key,
+            MergeConflictKind::BothModified,
+            step_label(ours),
+        );
+        match resolution {
+            MergeResolution::Ours => o,
+            MergeResolution::Theirs => t,
+        }
+    };
+    for path in CHILD_STEP_LISTS {
+        let has_list = |step: &Value| {
+            path.iter()
+                .try_fold(step, |current, key| current.get(*key))
+                .is_some_and(Value::is_array)
+        };
+        if !has_list(ours) && !has_list(theirs) {
+            continue;
+        }
+        let scope = format!("{key}/{}/", path.last().expect("路径非空"));
+        let children = merge_steps(
+            ctx,
+            owner,
+            owner_id,
+            &scope,
+            base.map(|step| array(step, path)).unwrap_or_default(),
+            array(ours, path),
+            array(theirs, path),
+        );
+        match path {
+            [outer] => merged[*outer] = Value::Array(children),
+            [outer, inner] => merged[*outer][*inner] = Value::Array(children),
+            _ => {}
+        }
+    }
+    merged
+}
+
+fn merge_steps(
+    ctx: &mut MergeContext<'_>,
+    owner: SnapshotStepOwner,
+    owner_id: &str,
+    scope: &str,
+    base: &[Value],
+    ours: &[Value],
+    theirs: &[Value],
+) -> Vec<Value> {
+    let keyed = |steps: &[Value]| {
+        steps
+            .iter()
+            .enumerate()
+            .map(|(index, step)| (step_key(step, index, scope), step.clone()))
+            .collect::<Vec<_>>()
+    };
+    let (base, ours, theirs) = (keyed(base), keyed(ours), keyed(theirs));
+    let lookup = |steps: &[(String, Value)], key: &str| {
+        steps
+            .iter()
+            .find(|(item_key, _)| item_key == key)
+            .map(|(_, step)| step.clone())
+    };
+    let keys = ours
+        .iter()
+        .chain(theirs.iter())
+        .chain(base.iter())
+        .map(|(key, _)| key.clone())
+        .collect::<BTreeSet<_>>();
+
+    let mut merged = HashMap::new();
+    for key in keys {
+        let (b, o, t) = (
+            lookup(&base, &key),
+            lookup(&ours, &key),
+            lookup(&theirs, &key),
+        );
+        let value = if o == t || b == t {
+            o
+        } else if b == o {
+            t
+        } else if let (Some(our_step), Some(their_step)) = (&o, &t)
+            && same_step_kind(our_step, their_step)
+        {
+            Some(merge_step_tree(
+                ctx,
+                owner,
+                owner_id,
+                &key,
+                b.as_ref(),
+                our_step,
+                their_step,
+            ))
+        } else {
+            let kind = match (&o, &t) {
+                (None, _) => MergeConflictKind::OursDeleted,
+                (_, None) => MergeConflictKind::TheirsDeleted,
+                _ => MergeConflictKind::BothModified,
+            };
+            let label = o
+                .as_ref()
+                .or(t.as_ref())
+                .map(step_label)
+                .unwrap_or_default();
+            match push_step_conflict(ctx, owner, owner_id, &key, kind, label) {
+                MergeResolution::Ours => o,
+                MergeResolution::Theirs => t,
+            }
+        };
+        if let Some(value) = value {
+            merged.insert(key, value);
+        }
+    }
+
+    let kept = merged.keys().cloned().collect::<HashSet<_>>();
+    let ours_keys = ours.iter().map(|(key, _)| key.clone()).collect::<Vec<_>>();
+    let theirs_keys = theirs
+        .iter()
+        .map(|(key, _)| key.clone())
+        .collect::<Vec<_>>();
+    merge_order(&ours_keys, &theirs_keys, &kept)
+        .into_iter()
+        .filter_map(|key| merged.remove(&key))
+        .collect()
+}
+
+fn item_changed(base: &Value, side: &Value, spec: &FieldSpec, lists: StepLists) -> bool {
+    fields(Some(base), spec) != fields(Some(side), spec) || lists(base) != lists(side)
+}
+
+fn merge_collection(
+    ctx: &mut MergeContext<'_>,
+    spec: &CollectionSpec,
+    base: &[Value],
+    ours: &[Value],
+    theirs: &[Value],
+) -> Vec<Value> {
+    let (fields_spec, lists, order_key) = (&spec.fields, spec.lists, spec.order_key);
+    let find = |items: &[Value], id: &str| items.iter().find(|item| item_id(item) == id).cloned();
+    let ids = ours
+        .iter()
+        .chain(theirs.iter())
+        .chain(base.iter())
+        .map(item_id)
+        .collect::<BTreeSet<_>>();
+    let prefix = serde_json::to_value(spec.entity)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default();
+
+    let mut merged = Vec::new();
+    for id in ids {
+        let (b, o, t) = (find(base, &id), find(ours, &id), find(theirs, &id));
+        let conflict_key = format!("{prefix}:{id}");
+        let resolution = ctx.resolution(&conflict_key);
+        let item = match (&b, &o, &t) {
+            (_, None, None) => None,
+            (None, Some(item), None) | (None, None, Some(item)) => Some(item.clone()),
+            (Some(base_item), None, Some(their_item)) => {
+                if !item_changed(base_item, their_item, fields_spec, lists) {
+                    None
+                } else {
+                    ctx.push(
+                        conflict_key,
+                        spec.entity,
+                        MergeConflictKind::OursDeleted,
+                        id.clone(),
+                        item_name(their_item),
+                    );
+                    (resolution == MergeResolution::Theirs).then(|| their_item.clone())
+                }
+            }
+            (Some(base_item), Some(our_item), None) => {
+                if !item_changed(base_item, our_item, fields_spec, lists) {
+                    None
+                } else {
+                    ctx.push(
+                        conflict_key,
+                        spec.entity,
+                        MergeConflictKind::TheirsDeleted,
+                        id.clone(),
+                        item_name(our_item),
+                    );
+                    (resolution == MergeResolution::Ours).then(|| our_item.clone())
+                }
+            }
+            (_, Some(our_item), Some(their_item)) => {
+                let (merged_fields, conflicting) = merge_fields(
+                    &fields(b.as_ref(), fields_spec),
+                    &fields(Some(our_item), fields_spec),
+                    &fields(Some(their_item), fields_spec),
+                    resolution,
+                );
+                let mut item = our_item.clone();
+                write_fields(&mut item, &merged_fields);
+                if !conflicting.is_empty() {
+                    ctx.push(
+                        conflict_key,
+                        spec.entity,
+                        MergeConflictKind::BothModified,
+                        id.clone(),
+                        item_name(&item),
+                    )
+                    .fields = conflicting;
+                }
+                let base_lists = b.as_ref().map(lists).unwrap_or_default();
+                for (owner, our_steps) in lists(our_item) {
+                    let base_steps = base_lists
+                        .iter()
+                        .find(|(list_owner, _)| *list_owner == owner)
+                        .map(|(_, steps)| *steps)
+                        .unwrap_or_default();
+                    let their_steps = lists(their_item)
+                        .into_iter()
+                        .find(|(list_owner, _)| *list_owner == owner)
+                        .map(|(_, steps)| steps)
+                        .unwrap_or_default();
+                    let steps =
+                        merge_steps(ctx, owner, &id, "", base_steps, our_steps, their_steps);
+                    let [outer, inner] = step_list_path(owner);
+                    item[outer][inner] = Value::Array(steps);
+                }
+                Some(item)
+            }
+        };
+        if let Some(item) = item {
+            let from_ours = o.is_some();
+            merged.push((from_ours, item));
+        }
+    }
+
+    // 按合并后的顺序字段排序，同序号时本地优先，然后重新编号
+    merged.sort_by(|(left_ours, left), (right_ours, right)| {
+        let order = |item: &Value| item.get(order_key).and_then(Value::as_i64).unwrap_or(0);
+        order(left)
+            .cmp(&order(right))
+            .then_with(|| right_ours.cmp(left_ours))
+    });
+    merged
+        .into_iter()
+        .enumerate()
+        .map(|(index, (_, mut item))| {
+            item[order_key] = Value::from(index);
+            item
+        })
+        .collect()
+}
+
+/// 关联表按集合三方合并：任一方新增则保留，任一方删除且另一方未变则删除
+fn merge_links(
+    base: &[Value],
+    ours: &[Value],
+    theirs: &[Value],
+    owner_key: &str,
+    member_key: &str,
+    valid_owners: &HashSet<String>,
+    valid_members: &HashSet<String>,
+) -> Vec<Value> {
+    let pair = |link: &Value| {
+        (
+            str_field(link, owner_key).to_string(),
+            str_field(link, member_key).to_string(),
+        )
+    };
+    let base_pairs = base.iter().map(pair).collect::<HashSet<_>>();
+    let their_pairs = theirs.iter().map(pair).collect::<HashSet<_>>();
+    let our_pairs = ours.iter().map(pair).collect::<HashSet<_>>();
+    let mut links = ours
+        .iter()
+        .filter(|link| {
+            let key = pair(link);
+            their_pairs.contains(&key) || !base_pairs.contains(&key)
+        })
+        .chain(theirs.iter().filter(|link| {
+            let key = pair(link);
+            !our_pairs.contains(&key) && !base_pairs.contains(&key)
+        }))
+        .filter(|link| {
+            let (owner, member) = pair(link);
+            valid_owners.contains(&owner) && valid_members.contains(&member)
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+    links.sort_by(|left, right| {
+        let order = |link: &Value| link.get("orderIndex").and_then(Value::as_i64).unwrap_or(0);
+        str_field(left, owner_key)
+            .cmp(str_field(right, owner_key))
+            .then_with(|| order(left).cmp(&order(right)))
+    });
+    let mut counters = HashMap::<String, i64>::new();
+    for link in links.iter_mut() {
+        let counter = counters
+            .entry(str_field(link, owner_key).to_string())
+            .or_default();
+        link["orderIndex"] = Value::from(*counter);
+        *counter += 1;
+    }
+    links
+}
+
+fn live_tasks(graph: &Value) -> Vec<Value> {
+    array(graph, &["tasks"])
+        .iter()
+        .filter(|task| {
+            !task
+                .get("isDeleted")
+                .and_then(Value::as_bool)
+                .unwrap_or(false)
+        })
+        .cloned()
+        .collect()
+}
+
+fn to_graph_value(graph: &ScriptGraphSnapshot) -> Result<Value, String> {
+    serde_json::to_value(graph).map_err(|error| format!("序列化脚本图失败: {error}"))
+}
+
+fn from_graph_value(value: Value) -> Result<ScriptGraphSnapshot, String> {
+    serde_json::from_value(value).map_err(|error| format!("合并结果无法还原为脚本图: {error}"))
+}
+
+fn collect_ids(items: &[Value]) -> HashSet<String> {
+    items.iter().map(item_id).collect()
+}
+
+/// 三方合并脚本图：`base` 为克隆时的上游版本（本地 ID 空间），`ours` 为本地开发脚本，
+/// `theirs` 为新的上游版本。冲突默认保留本地，可通过 `resolutions` 按冲突 key 指定。
+pub fn merge_script_graphs(
+    base: &ScriptGraphSnapshot,
+    ours: &ScriptGraphSnapshot,
+    theirs: &ScriptGraphSnapshot,
+    resolutions: &HashMap<String, MergeResolution>,
+) -> Result<ScriptMergeOutcome, String> {
+    let mut base = to_graph_value(base)?;
+    let ours = to_graph_value(ours)?;
+    base["script"]["id"] = ours["script"]["id"].clone();
+    let theirs = rekey_theirs(&base, &to_graph_value(theirs)?);
+    let mut ctx = MergeContext {
+        resolutions,
+        conflicts: Vec::new(),
+    };
+
+    let mut script = ours["script"].clone();
+    let info_keys = |graph: &Value| {
+        MERGED_SCRIPT_INFO_KEYS
+            .iter()
+            .filter_map(|key| {
+                graph["script"]["info"]
+                    .get(*key)
+                    .map(|value| (format!("info.{key}"), value.clone()))
+            })
+            .collect::<BTreeMap<_, _>>()
+    };
+    let (script_fields, script_conflicts) = merge_fields(
+        &info_keys(&base),
+        &info_keys(&ours),
+        &info_keys(&theirs),
+        ctx.resolution("script"),
+    );
+    write_fields(&mut script, &script_fields);
+    if !script_conflicts.is_empty() {
+        ctx.push(
+            "script".to_string(),
+            MergeEntityKind::Script,
+            MergeConflictKind::BothModified,
+            str_field(&script, "id").to_string(),
+            item_name(&script["info"]),
+        )
+        .fields = script_conflicts;
+    }
+
+    let mut tasks = merge_collection(
+        &mut ctx,
+        &TASK_COLLECTION,
+        &live_tasks(&base),
+        &live_tasks(&ours),
+        &live_tasks(&theirs),
+    );
+    let [policies, policy_groups, policy_sets] = [
+        &POLICY_COLLECTION,
+        &POLICY_GROUP_COLLECTION,
+        &POLICY_SET_COLLECTION,
+    ]
+    .map(|spec| {
+        merge_collection(
+            &mut ctx,
+            spec,
+            array(&base, &[spec.key]),
+            array(&ours, &[spec.key]),
+            array(&theirs, &[spec.key]),
+        )
+    });
+
+    // 本地已软删除的任务原样保留
+    let merged_task_ids = collect_ids(&tasks);
+    tasks.extend(
+        array(&ours, &["tasks"])
+            .iter()
+            .filter(|task| {
+                task.get("isDeleted")
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false)
+            })
+            .filter(|task| !merged_task_ids.contains(&item_id(task)))
+            .cloned(),
+    );
+
+    let policy_ids = collect_ids(&policies);
+    let group_ids = collect_ids(&policy_groups);
+    let set_ids = collect_ids(&policy_sets);
+    let group_policies = merge_links(
+        array(&base, &["groupPolicies"]),
+        array(&ours, &["groupPolicies"]),
+        array(&theirs, &["groupPolicies"]),
+        "groupId",
+        "policyId",
+        &group_ids,
+        &policy_ids,
+    );
+    let set_groups = merge_links(
+        array(&base, &["setGroups"]),
+        array(&ours, &["setGroups"]),
+        array(&theirs, &["setGroups"]),
+        "setId",
+        "groupId",
+        &set_ids,
+        &group_ids,
+    );
+
+    let script_id = ours["script"]["id"].clone();
+    let mut merged = serde_json::json!({
+        "script": script,
+        "tasks": tasks,
+        "policies": policies,
+        "policyGroups": policy_groups,
+        "policySets": policy_sets,
+        "groupPolicies": group_policies,
+        "setGroups": set_groups,
+    });
+    for collection in ["tasks", "policies", "policyGroups", "policySets"] {
+        if let Some(items) = merged[collection].as_array_mut() {
+            for item in items {
+                item["scriptId"] = script_id.clone();
+            }
+        }
+    }
+
+    Ok(ScriptMergeOutcome {
+        merged: from_graph_value(merged)?,
+        upstream_base: from_graph_value(theirs)?,
+        conflicts: ctx.conflicts,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ConditionNode, FlowControl, ScriptProfile, ScriptTask, ScriptTaskProfile, Step, StepKind,
+        TaskCycle, TaskRowType, TaskTone, TaskTriggerMode,
+    };
+    use ad_kernel::ids::{ScriptId, StepId};
+
+    fn wait(ms: u64) -> Step {
+        Step {
+            id: Some(StepId::new_v7()),
+            source_id: None,
+            target_id: None,
+            label: None,
+            skip_flag: false,
+            kind: StepKind::FlowControl {
+                a: FlowControl::WaitMs {
+                    ms,
+                    input_var: None,
+                    runtime_var: None,
+                },
+            },
+        }
+    }
+
+    fn with_ms(step: &Step, ms: u64) -> Step {
+        let mut step = step.clone();
+        step.kind = StepKind::FlowControl {
+            a: FlowControl::WaitMs {
+                ms,
+                input_var: None,
+                runtime_var: None,
+            },
+        };
+        step
+    }
+
+    fn if_step(id: StepId, then: Vec<Step>, else_steps: Vec<Step>) -> Step {
+        Step {
+            id: Some(id),
+            source_id: None,
+            target_id: None,
+            label: None,
+            skip_flag: false,
+            kind: StepKind::FlowControl {
+                a: FlowControl::If {
+                    con: ConditionNode::RawExpr {
+                        expr: "true".to_string(),
+                    },
+                    then,
+                    else_steps: Some(else_steps),
+                },
+            },
+        }
+    }
+
+    fn task(script_id: ScriptId, name: &str, index: u32, steps: Vec<Step>) -> ScriptTaskProfile {
+        let now = chrono::Utc::now();
+        ScriptTaskProfile {
+            id: TaskId::new_v7(),
+            script_id,
+            name: name.to_string(),
+            description: String::new(),
+            row_type: TaskRowType::Task,
+            trigger_mode: TaskTriggerMode::RootOnly,
+            record_schedule: true,
+            section_id: None,
+            indent_level: 0,
+            default_task_cycle: TaskCycle::EveryRun,
+            exec_max: 1,
+            show_enabled_toggle: true,
+            default_enabled: true,
+            task_tone: TaskTone::Normal,
+            is_hidden: false,
+            task: ScriptTask {
+                ui_data: Value::Null,
+                variables: Value::Null,
+                steps,
+                retry_policy: None,
+            },
+            created_at: now,
+            updated_at: now,
+            deleted_at: None,
+            is_deleted: false,
+            index,
+        }
+    }
+
+    fn graph(script: ScriptProfile, tasks: Vec<ScriptTaskProfile>) -> ScriptGraphSnapshot {
+        ScriptGraphSnapshot {
+            script,
+            tasks,
+            policies: Vec::new(),
+            policy_groups: Vec::new(),
+            policy_sets: Vec::new(),
+            group_policies: Vec::new(),
+            set_groups: Vec::new(),
+        }
+    }
+
+    /// 模拟下载：上游副本的脚本与任务 ID 都会重新生成，步骤 ID 保持不变
+    fn as_download(tasks: Vec<ScriptTaskProfile>) -> ScriptGraphSnapshot {
+        let script = ScriptProfile::default();
+        let tasks = tasks
+            .into_iter()
+            .map(|mut task| {
+                task.id = TaskId::new_v7();
+                task.script_id = script.id;
+                task
+            })
+            .collect();
+        graph(script, tasks)
+    }
+
+    fn steps_ms(graph: &ScriptGraphSnapshot, task_id: TaskId) -> Vec<u64> {
+        let task = graph.tasks.iter().find(|task| task.id == task_id).unwrap();
+        task.task
+            .steps
+            .iter()
+            .map(|step| match &step.kind {
+                StepKind::FlowControl {
+                    a: FlowControl::WaitMs { ms, .. },
+                } => *ms,
+                _ => 0,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn merges_non_conflicting_local_and_upstream_changes() {
+        let local = ScriptProfile::default();
+        let (a, b) = (wait(100), wait(200));
+        let base_task = task(local.id, "日常", 0, vec![a.clone(), b.clone()]);
+
+        let mut our_task = base_task.clone();
+        our_task.task.steps = vec![with_ms(&a, 150), b.clone()];
+        let extra_local = task(local.id, "本地任务", 1, Vec::new());
+
+        let c = wait(300);
+        let mut their_task = base_task.clone();
+        their_task.name = "日常任务".to_string();
+        their_task.task.steps = vec![a.clone(), c.clone(), with_ms(&b, 250)];
+        let upstream_new = task(local.id, "上游新任务", 1, vec![wait(400)]);
+
+        let base = graph(ScriptProfile::default(), vec![base_task.clone()]);
+        let ours = graph(local, vec![our_task, extra_local.clone()]);
+        let theirs = as_download(vec![their_task, upstream_new]);
+
+        let outcome = merge_script_graphs(&base, &ours, &theirs, &HashMap::new()).unwrap();
+
+        assert!(outcome.conflicts.is_empty());
+        assert_eq!(outcome.merged.script.id, ours.script.id);
+        assert_eq!(outcome.merged.tasks.len(), 3);
+        let merged_task = outcome
+            .merged
+            .tasks
+            .iter()
+            .find(|task| task.id == base_task.id)
+            .unwrap();
+        assert_eq!(merged_task.name, "日常任务");
+        assert_eq!(steps_ms(&outcome.merged, base_task.id), vec![150, 300, 250]);
+        assert!(
+            outcome
+                .merged
+                .tasks
+                .iter()
+                .any(|task| task.id == extra_local.id)
+        );
+        assert!(
+            outcome
+                .merged
+                .tasks
+                .iter()
+                .all(|task| task.script_id == ours.script.id)
+        );
+        assert!(
+            outcome
+                .upstream_base
+                .tasks
+                .iter()
+                .any(|task| task.id == base_task.id)
+        );
+    }
+
+    #[test]
+    fn reports_step_conflicts_and_applies_resolutions() {
+        let local = ScriptProfile::default();
+        let a = wait(100);
+        let base_task = task(local.id, "日常", 0, vec![a.clone()]);
+        let mut our_task = base_task.clone();
+        our_task.task.steps = vec![with_ms(&a, 150)];
+        let mut their_task = base_task.clone();
+        their_task.task.steps = vec![with_ms(&a, 300)];
+
+        let base = graph(ScriptProfile::default(), vec![base_task.clone()]);
+        let ours = graph(local, vec![our_task]);
+        let theirs = as_download(vec![their_task]);
+
+        let outcome = merge_script_graphs(&base, &ours, &theirs, &HashMap::new()).unwrap();
+        assert_eq!(outcome.conflicts.len(), 1);
+        let conflict = &outcome.conflicts[0];
+        assert_eq!(conflict.entity, MergeEntityKind::Step);
+        assert_eq!(conflict.kind, MergeConflictKind::BothModified);
+        assert_eq!(conflict.owner_id, Some(base_task.id.to_string()));
+        assert_eq!(conflict.resolution, MergeResolution::Ours);
+        assert_eq!(steps_ms(&outcome.merged, base_task.id), vec![150]);
+
+        let resolutions = HashMap::from([(conflict.key.clone(), MergeResolution::Theirs)]);
+        let resolved = merge_script_graphs(&base, &ours, &theirs, &resolutions).unwrap();
+        assert_eq!(steps_ms(&resolved.merged, base_task.id), vec![300]);
+    }
+
+    #[test]
+    fn merges_edits_in_different_branches_of_the_same_if() {
+        let local = ScriptProfile::default();
+        let if_id = StepId::new_v7();
+        let (then_step, else_step) = (wait(100), wait(200));
+        let base_task = task(
+            local.id,
+            "日常",
+            0,
+            vec![if_step(
+                if_id,
+                vec![then_step.clone()],
+                vec![else_step.clone()],
+            )],
+        );
+        // 同一分支内本地改已有步骤、上游追加新步骤，也应逐步骤合并
+        let mut our_task = base_task.clone();
+        our_task.task.steps = vec![if_step(
+            if_id,
+            vec![with_ms(&then_step, 150)],
+            vec![with_ms(&else_step, 250)],
+        )];
+        let mut their_task = base_task.clone();
+        let added = wait(300);
+        their_task.task.steps = vec![if_step(
+            if_id,
+            vec![then_step.clone()],
+            vec![else_step.clone(), added.clone()],
+        )];
+
+        let base = graph(ScriptProfile::default(), vec![base_task.clone()]);
+        let ours = graph(local, vec![our_task]);
+        let theirs = as_download(vec![their_task]);
+
+        let outcome = merge_script_graphs(&base, &ours, &theirs, &HashMap::new()).unwrap();
+
+        assert!(outcome.conflicts.is_empty());
+        let merged_task = outcome
+            .merged
+            .tasks
+            .iter()
+            .find(|task| task.id == base_task.id)
+            .unwrap();
+        let StepKind::FlowControl {
+            a: FlowControl::If {
+                then, else_steps, ..
+            },
+        } = &merged_task.task.steps[0].kind
+        else {
+            panic!("合并后应仍为 If 步骤");
+        };
+        let branch_ms = |steps: &[Step]| {
+            steps
+                .iter()
+                .map(|step| match &step.kind {
+                    StepKind::FlowControl {
+                        a: FlowControl::WaitMs { ms, .. },
+                    } => *ms,
+                    _ => 0,
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(branch_ms(then), vec![150]);
+        assert_eq!(branch_ms(else_steps.as_deref().unwrap()), vec![250, 300]);
+    }
+
+    #[test]
+    fn upstream_deletion_conflicts_only_with_local_edits() {
+        let local = ScriptProfile::default();
+        let kept = task(local.id, "未改动", 0, vec![wait(100)]);
+        let edited = task(local.id, "已改动", 1, vec![wait(200)]);
+        let base = graph(ScriptProfile::default(), vec![kept.clone(), edited.clone()]);
+
+        let mut our_edited = edited.clone();
+        our_edited.exec_max = 5;
+        let ours = graph(local, vec![kept.clone(), our_edited]);
+        let theirs = as_download(Vec::new());
+
+        let outcome = merge_script_graphs(&base, &ours, &theirs, &HashMap::new()).unwrap();
+
+        assert_eq!(outcome.conflicts.len(), 1);
+        assert_eq!(outcome.conflicts[0].kind, MergeConflictKind::TheirsDeleted);
+        assert_eq!(outcome.conflicts[0].entity_id, edited.id.to_string());
+        assert_eq!(outcome.merged.tasks.len(), 1);
+        assert_eq!(outcome.merged.tasks[0].id, edited.id);
+        assert_eq!(outcome.merged.tasks[0].exec_max, 5);
+    }
+}
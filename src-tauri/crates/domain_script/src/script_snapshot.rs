@@ -0,0 +1,543 @@
+use crate::{
+    PolicyGroupPolicyLink, PolicyGroupProfile, PolicyProfile, PolicySetGroupLink, PolicySetProfile,
+    ScriptProfile, ScriptTaskProfile, Step,
+};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// 一次编辑器保存时的完整脚本图快照
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptGraphSnapshot {
+    pub script: ScriptProfile,
+    pub tasks: Vec<ScriptTaskProfile>,
+    pub policies: Vec<PolicyProfile>,
+    pub policy_groups: Vec<PolicyGroupProfile>,
+    pub policy_sets: Vec<PolicySetProfile>,
+    pub group_policies: Vec<PolicyGroupPolicyLink>,
+    pub set_groups: Vec<PolicySetGroupLink>,
+}
+
+/// 快照列表项（不含快照内容）
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ts_rs::TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptSnapshotSummary {
+    pub id: String,
+    pub script_id: String,
+    pub script_name: String,
+    pub ver_name: String,
+    pub author: Option<String>,
+    pub message: String,
+    pub size_bytes: i64,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, ts_rs::TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub enum SnapshotChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+/// 步骤所在位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, ts_rs::TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub enum SnapshotStepOwner {
+    Task,
+    PolicyBefore,
+    PolicyAfter,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ts_rs::TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotEntityChange {
+    pub id: String,
+    pub name: String,
+    pub change: SnapshotChangeKind,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ts_rs::TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotStepChange {
+    pub owner: SnapshotStepOwner,
+    pub owner_id: String,
+    pub owner_name: String,
+    /// 无 ID 的步骤按位置匹配，此时为空
+    pub step_id: Option<String>,
+    pub index: usize,
+    pub label: String,
+    pub change: SnapshotChangeKind,
+}
+
+/// 两个快照之间按任务/步骤/策略粒度的差异
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize, ts_rs::TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptSnapshotDiff {
+    pub script_changed: bool,
+    pub tasks: Vec<SnapshotEntityChange>,
+    pub steps: Vec<SnapshotStepChange>,
+    pub policies: Vec<SnapshotEntityChange>,
+    pub policy_groups: Vec<SnapshotEntityChange>,
+    pub policy_sets: Vec<SnapshotEntityChange>,
+    pub links_changed: bool,
+}
+
+impl ScriptSnapshotDiff {
+    pub fn is_empty(&self) -> bool {
+        !self.script_changed
+            && !self.links_changed
+            && self.tasks.is_empty()
+            && self.steps.is_empty()
+            && self.policies.is_empty()
+            && self.policy_groups.is_empty()
+            && self.policy_sets.is_empty()
+    }
+}
+
+fn to_value<T: Serialize>(value: &T) -> Value {
+    serde_json::to_value(value).unwrap_or(Value::Null)
+}
+
+/// 去掉时间戳与步骤后的任务内容，步骤差异单独统计
+fn task_value(task: &ScriptTaskProfile) -> Value {
+    let mut value = to_value(task);
+    if let Some(object) = value.as_object_mut() {
+        object.remove("createdAt");
+        object.remove("updatedAt");
+    }
+    if let Some(task) = value.get_mut("task").and_then(Value::as_object_mut) {
+        task.remove("steps");
+    }
+    value
+}
+
+fn policy_value(policy: &PolicyProfile) -> Value {
+    let mut value = to_value(policy);
+    if let Some(info) = value.get_mut("info").and_then(Value::as_object_mut) {
+        info.remove("beforeAction");
+        info.remove("afterAction");
+    }
+    value
+}
+
+fn script_value(script: &ScriptProfile) -> Value {
+    let mut value = to_value(script);
+    if let Some(info) = value.get_mut("info").and_then(Value::as_object_mut) {
+        info.remove("updateTime");
+    }
+    value
+}
+
+fn step_label(step: &Step) -> String {
+    step.label
+        .as_deref()
+        .map(str::trim)
+        .filter(|label| !label.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| {
+            to_value(&step.kind)
+                .get("op")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string()
+        })
+}
+
+fn step_key(step: &Step, index: usize) -> String {
+    step.id
+        .map(|id| id.to_string())
+        .unwrap_or_else(|| format!("#{index}"))
+}
+
+fn diff_entities<T>(
+    base: &[T],
+    target: &[T],
+    id: impl Fn(&T) -> String,
+    name: impl Fn(&T) -> String,
+    value: impl Fn(&T) -> Value,
+) -> Vec<SnapshotEntityChange> {
+    let base_by_id = base
+        .iter()
+        .map(|item| (id(item), item))
+        .collect::<HashMap<_, _>>();
+    let target_ids = target.iter().map(&id).collect::<Vec<_>>();
+    let mut changes = Vec::new();
+    for item in target {
+        let item_id = id(item);
+        let change = match base_by_id.get(&item_id) {
+            None => SnapshotChangeKind::Added,
+            Some(previous) if value(previous) != value(item) => SnapshotChangeKind::Modified,
+            Some(_) => continue,
+        };
+        changes.push(SnapshotEntityChange {
+            id: item_id,
+            name: name(item),
+            change,
+        });
+    }
+    for item in base {
+        let item_id = id(item);
+        if !target_ids.contains(&item_id) {
+            changes.push(SnapshotEntityChange {
+                id: item_id,
+                name: name(item),
+                change: SnapshotChangeKind::Removed,
+            });
+        }
+    }
+    changes
+}
+
+fn diff_steps(
+    owner: SnapshotStepOwner,
+    owner_id: &str,
+    owner_name: &str,
+    base: &[Step],
+    target: &[Step],
+    changes: &mut Vec<SnapshotStepChange>,
+) {
+    let base_by_key = base
+        .iter()
+        .enumerate()
+        .map(|(index, step)| (step_key(step, index), step))
+        .collect::<HashMap<_, _>>();
+    let target_keys = target
+        .iter()
+        .enumerate()
+        .map(|(index, step)| step_key(step, index))
+        .collect::<Vec<_>>();
+    let mut push = |step: &Step, index: usize, change| {
+        changes.push(SnapshotStepChange {
+            owner,
+            owner_id: owner_id.to_string(),
+            owner_name: owner_name.to_string(),
+            step_id: step.id.map(|id| id.to_string()),
+            index,
+            label: step_label(step),
+            change,
+        });
+    };
+    // 只比较两边共有步骤的相对顺序，增删步骤不会让其后的步骤都算作移动
+    let base_order = base
+        .iter()
+        .enumerate()
+        .map(|(index, step)| step_key(step, index))
+        .filter(|key| target_keys.contains(key))
+        .enumerate()
+        .map(|(position, key)| (key, position))
+        .collect::<HashMap<_, _>>();
+    let mut position = 0;
+    for (index, step) in target.iter().enumerate() {
+        let key = &target_keys[index];
+        match base_by_key.get(key) {
+            None => push(step, index, SnapshotChangeKind::Added),
+            Some(previous) => {
+                let moved = base_order.get(key) != Some(&position);
+                position += 1;
+                if moved || to_value(*previous) != to_value(step) {
+                    push(step, index, SnapshotChangeKind::Modified);
+                }
+            }
+        }
+    }
+    for (index, step) in base.iter().enumerate() {
+        if !target_keys.contains(&step_key(step, index)) {
+            push(step, index, SnapshotChangeKind::Removed);
+        }
+    }
+}
+
+/// 比较两个快照，`base` 为旧版本，`target` 为新版本
+pub fn diff_script_snapshots(
+    base: &ScriptGraphSnapshot,
+    target: &ScriptGraphSnapshot,
+) -> ScriptSnapshotDiff {
+    let base_tasks = base
+        .tasks
+        .iter()
+        .filter(|task| !task.is_deleted)
+        .cloned()
+        .collect::<Vec<_>>();
+    let target_tasks = target
+        .tasks
+        .iter()
+        .filter(|task| !task.is_deleted)
+        .cloned()
+        .collect::<Vec<_>>();
+
+    let mut steps = Vec::new();
+    let empty: &[Step] = &[];
+    for task in &target_tasks {
+        let previous = base_tasks.iter().find(|item| item.id == task.id);
+        diff_steps(
+            SnapshotStepOwner::Task,
+            &task.id.to_string(),
+            &task.name,
+            previous.map_or(empty, |item| item.task.steps.as_slice()),
+            &task.task.steps,
+            &mut steps,
+        );
+    }
+    for task in &base_tasks {
+        if !target_tasks.iter().any(|item| item.id == task.id) {
+            diff_steps(
+                SnapshotStepOwner::Task,
+                &task.id.to_string(),
+                &task.name,
+                &task.task.steps,
+                empty,
+                &mut steps,
+            );
+        }
+    }
+    for policy in &target.policies {
+        let previous = base.policies.iter().find(|item| item.id == policy.id);
+        let policy_id = policy.id.to_string();
+        diff_steps(
+            SnapshotStepOwner::PolicyBefore,
+            &policy_id,
+            &policy.info.name,
+            previous.map_or(empty, |item| item.info.before_action.as_slice()),
+            &policy.info.before_action,
+            &mut steps,
+        );
+        diff_steps(
+            SnapshotStepOwner::PolicyAfter,
+            &policy_id,
+            &policy.info.name,
+            previous.map_or(empty, |item| item.info.after_action.as_slice()),
+            &policy.info.after_action,
+            &mut steps,
+        );
+    }
+    for policy in &base.policies {
+        if !target.policies.iter().any(|item| item.id == policy.id) {
+            let policy_id = policy.id.to_string();
+            diff_steps(
+                SnapshotStepOwner::PolicyBefore,
+                &policy_id,
+                &policy.info.name,
+                &policy.info.before_action,
+                empty,
+                &mut steps,
+            );
+            diff_steps(
+                SnapshotStepOwner::PolicyAfter,
+                &policy_id,
+                &policy.info.name,
+                &policy.info.after_action,
+                empty,
+                &mut steps,
+            );
+        }
+    }
+
+    ScriptSnapshotDiff {
+        script_changed: script_value(&base.script) != script_value(&target.script),
+        tasks: diff_entities(
+            &base_tasks,
+            &target_tasks,
+            |task| task.id.to_string(),
+            |task| task.name.clone(),
+            task_value,
+        ),
+        steps,
+        policies: diff_entities(
+            &base.policies,
+            &target.policies,
+            |policy| policy.id.to_string(),
+            |policy| policy.info.name.clone(),
+            policy_value,
+        ),
+        policy_groups: diff_entities(
+            &base.policy_groups,
+            &target.policy_groups,
+            |group| group.id.to_string(),
+            |group| group.info.name.clone(),
+            to_value,
+        ),
+        policy_sets: diff_entities(
+            &base.policy_sets,
+            &target.policy_sets,
+            |set| set.id.to_string(),
+            |set| set.info.name.clone(),
+            to_value,
+        ),
+        links_changed: to_value(&base.group_policies) != to_value(&target.group_policies)
+            || to_value(&base.set_groups) != to_value(&target.set_groups),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        FlowControl, ScriptTask, StepKind, TaskCycle, TaskRowType, TaskTone, TaskTriggerMode,
+    };
+    use ad_kernel::ids::{ScriptId, StepId, TaskId};
+
+    fn wait(ms: u64) -> Step {
+        Step {
+            id: Some(StepId::new_v7()),
+            source_id: None,
+            target_id: None,
+            label: None,
+            skip_flag: false,
+            kind: StepKind::FlowControl {
+                a: FlowControl::WaitMs {
+                    ms,
+                    input_var: None,
+                    runtime_var: None,
+                },
+            },
+        }
+    }
+
+    fn task(name: &str, steps: Vec<Step>) -> ScriptTaskProfile {
+        let now = chrono::Utc::now();
+        ScriptTaskProfile {
+            id: TaskId::new_v7(),
+            script_id: ScriptId::new_v7(),
+            name: name.to_string(),
+            description: String::new(),
+            row_type: TaskRowType::Task,
+            trigger_mode: TaskTriggerMode::RootOnly,
+            record_schedule: true,
+            section_id: None,
+            indent_level: 0,
+            default_task_cycle: TaskCycle::EveryRun,
+            exec_max: 1,
+            show_enabled_toggle: true,
+            default_enabled: true,
+            task_tone: TaskTone::Normal,
+            is_hidden: false,
+            task: ScriptTask {
+                ui_data: Value::Null,
+                variables: Value::Null,
+                steps,
+                retry_policy: None,
+            },
+            created_at: now,
+            updated_at: now,
+            deleted_at: None,
+            is_deleted: false,
+            index: 0,
+        }
+    }
+
+    fn snapshot(tasks: Vec<ScriptTaskProfile>) -> ScriptGraphSnapshot {
+        ScriptGraphSnapshot {
+            script: ScriptProfile::default(),
+            tasks,
+            policies: Vec::new(),
+            policy_groups: Vec::new(),
+            policy_sets: Vec::new(),
+            group_policies: Vec::new(),
+            set_groups: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn diff_reports_task_and_step_changes() {
+        let kept = wait(100);
+        let removed = wait(200);
+        let base_task = task("日常", vec![kept.clone(), removed.clone()]);
+        let dropped_task = task("旧任务", Vec::new());
+        let base = snapshot(vec![base_task.clone(), dropped_task.clone()]);
+
+        let mut edited_task = base_task.clone();
+        edited_task.name = "日常任务".to_string();
+        let mut modified = kept.clone();
+        modified.skip_flag = true;
+        let added = wait(300);
+        edited_task.task.steps = vec![modified, added.clone()];
+        let new_task = task("新任务", Vec::new());
+        let mut target: ScriptGraphSnapshot =
+            serde_json::from_value(to_value(&base)).expect("快照应可往返序列化");
+        target.tasks = vec![edited_task, new_task.clone()];
+
+        let diff = diff_script_snapshots(&base, &target);
+
+        assert!(!diff.script_changed);
+        let task_change = |id: TaskId| {
+            diff.tasks
+                .iter()
+                .find(|change| change.id == id.to_string())
+                .map(|change| change.change)
+        };
+        assert_eq!(
+            task_change(base_task.id),
+            Some(SnapshotChangeKind::Modified)
+        );
+        assert_eq!(
+            task_change(dropped_task.id),
+            Some(SnapshotChangeKind::Removed)
+        );
+        assert_eq!(task_change(new_task.id), Some(SnapshotChangeKind::Added));
+
+        let step_change = |id: Option<StepId>| {
+            diff.steps
+                .iter()
+                .find(|change| change.step_id == id.map(|id| id.to_string()))
+                .map(|change| change.change)
+        };
+        assert_eq!(step_change(kept.id), Some(SnapshotChangeKind::Modified));
+        assert_eq!(step_change(removed.id), Some(SnapshotChangeKind::Removed));
+        assert_eq!(step_change(added.id), Some(SnapshotChangeKind::Added));
+        assert_eq!(diff.steps.len(), 3);
+    }
+
+    #[test]
+    fn reordered_steps_are_reported_as_modified() {
+        let (first, second, third) = (wait(100), wait(200), wait(300));
+        let base_task = task("日常", vec![first.clone(), second.clone(), third.clone()]);
+        let base = snapshot(vec![base_task.clone()]);
+        let mut target: ScriptGraphSnapshot = serde_json::from_value(to_value(&base)).unwrap();
+        target.tasks[0].task.steps = vec![second.clone(), first.clone(), third.clone()];
+
+        let diff = diff_script_snapshots(&base, &target);
+
+        assert!(!diff.is_empty());
+        let changed = diff
+            .steps
+            .iter()
+            .map(|change| (change.step_id.clone(), change.change))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            changed,
+            vec![
+                (
+                    second.id.map(|id| id.to_string()),
+                    SnapshotChangeKind::Modified
+                ),
+                (
+                    first.id.map(|id| id.to_string()),
+                    SnapshotChangeKind::Modified
+                ),
+            ]
+        );
+
+        // 前面插入新步骤不影响原有步骤的相对顺序
+        target.tasks[0].task.steps = vec![wait(50), first, second, third];
+        let diff = diff_script_snapshots(&base, &target);
+        assert_eq!(diff.steps.len(), 1);
+        assert_eq!(diff.steps[0].change, SnapshotChangeKind::Added);
+    }
+
+    #[test]
+    fn identical_snapshots_produce_empty_diff() {
+        let base = snapshot(vec![task("日常", vec![wait(100)])]);
+        let json = serde_json::to_string(&base).unwrap();
+        let target: ScriptGraphSnapshot = serde_json::from_str(&json).unwrap();
+
+        assert!(diff_script_snapshots(&base, &target).is_empty());
+    }
+}
@@ -1,4 +1,5 @@
 use crate::Step;
+use domain_schedule::TaskRetryPolicy;
 use serde_json::Value;
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ts_rs::TS)]
@@ -8,4 +9,7 @@ pub struct ScriptTask {
     pub ui_data: Value,
     pub variables: Value,
     pub steps: Vec<Step>,
+    /// 覆盖设备默认的失败重试策略
+    #[serde(default)]
+    pub retry_policy: Option<TaskRetryPolicy>,
 }
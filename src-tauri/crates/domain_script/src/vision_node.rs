@@ -1,17 +1,34 @@
-use crate::Step;
+use crate::{RegionPoint, Step};
 use domain_vision::SearchRule;
 
+fn default_template_threshold() -> f32 {
+    0.8
+}
+
+fn default_template_scales() -> Vec<f32> {
+    vec![1.0]
+}
+
 #[derive(Debug, serde::Serialize, serde::Deserialize, Clone, ts_rs::TS)]
 #[ts(export)]
 #[serde(rename_all = "camelCase", tag = "type")]
 pub enum VisionNode {
+    /// 区域两个角都为默认值时处理整张图，否则先裁剪到区域再推理，结果坐标换算回原图
     Detect {
         input_var: String,
         out_var: String,
+        #[serde(default)]
+        region_top_left: RegionPoint,
+        #[serde(default)]
+        region_bottom_right: RegionPoint,
     },
     Ocr {
         input_var: String,
         out_var: String,
+        #[serde(default)]
+        region_top_left: RegionPoint,
+        #[serde(default)]
+        region_bottom_right: RegionPoint,
     },
     VisionSearch {
         #[serde(default)]
@@ -26,4 +43,25 @@ pub enum VisionNode {
         out_ocr_var: Option<String>,
         then_steps: Vec<Step>,
     },
+    /// 在输入图像中按参考图做归一化互相关匹配，结果与目标检测结果兼容
+    TemplateMatch {
+        input_var: String,
+        /// 参考图，base64 编码的 PNG/JPEG，随脚本一起保存
+        template: String,
+        #[serde(default)]
+        label: String,
+        #[serde(default)]
+        idx: i32,
+        #[serde(default = "default_template_threshold")]
+        threshold: f32,
+        #[serde(default = "default_template_scales")]
+        scales: Vec<f32>,
+        #[serde(default)]
+        max_results: u32,
+        #[serde(default)]
+        region_top_left: RegionPoint,
+        #[serde(default)]
+        region_bottom_right: RegionPoint,
+        out_var: String,
+    },
 }
@@ -3,11 +3,12 @@ mod model_config;
 mod result;
 mod search;
 mod text_cache_config;
+mod text_match;
 mod vision_snapshot;
 
 pub use compression::ImageCompression;
 pub use model_config::{
-    BaseModel, DetectorType, InferenceBackend, ModelSource, ModelType, PaddleDetDbNet,
+    AttnRec, BaseModel, DetectorType, InferenceBackend, ModelSource, ModelType, PaddleDetDbNet,
     PaddleRecCrnn, RecProcessingMode, RecResizeFilter, RecognizerType, YoloDet,
     YoloPostprocessKind,
 };
@@ -17,4 +18,5 @@ pub use search::{
     RelativeValueType, SearchHit, SearchRule, SearchScope, VisionLayoutItem, VisionLayoutSource,
 };
 pub use text_cache_config::{VisionTextCacheConfig, VisionTextCacheRuntimeConfig};
+pub use text_match::{TextMatchMode, TextNormalize, substring_similarity};
 pub use vision_snapshot::{OcrSearcher, VisionSnapshot};
@@ -21,6 +21,8 @@ pub enum ModelType {
     PaddleCrnn5,
     PaddleDet6,
     PaddleCrnn6,
+    PaddleSvtr,
+    Parseq,
 }
 
 /// Requested inference provider. Provider fallback and ORT construction remain infrastructure work.
@@ -162,6 +164,19 @@ impl PaddleRecCrnn {
     }
 }
 
+/// 注意力解码识别模型（PARSeq 一类）：固定尺寸输入，逐位输出字符直到结束符
+#[derive(Debug, Clone, Serialize, Deserialize, ts_rs::TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct AttnRec {
+    pub base_model: BaseModel,
+    /// 字符表，每行一个字符；输出类别 0 固定为结束符，不写在字符表里
+    #[ts(as = "Option<String>")]
+    pub dict_path: Option<PathBuf>,
+    #[serde(default)]
+    pub resize_filter: RecResizeFilter,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ts_rs::TS)]
 #[ts(export)]
 pub enum DetectorType {
@@ -174,4 +189,45 @@ pub enum DetectorType {
 #[ts(export)]
 pub enum RecognizerType {
     PaddleCrnn(PaddleRecCrnn),
+    /// PP-OCR SVTR 系列，与 CRNN 共用预处理，按标准 CTC 解码并兼容 `[C, T]` 输出布局
+    PaddleSvtr(PaddleRecCrnn),
+    Parseq(AttnRec),
+}
+
+impl RecognizerType {
+    pub const fn kind_name(&self) -> &'static str {
+        match self {
+            Self::PaddleCrnn(_) => "PaddleCrnn",
+            Self::PaddleSvtr(_) => "PaddleSvtr",
+            Self::Parseq(_) => "Parseq",
+        }
+    }
+
+    pub fn base_model(&self) -> &BaseModel {
+        match self {
+            Self::PaddleCrnn(cfg) | Self::PaddleSvtr(cfg) => &cfg.base_model,
+            Self::Parseq(cfg) => &cfg.base_model,
+        }
+    }
+
+    pub fn base_model_mut(&mut self) -> &mut BaseModel {
+        match self {
+            Self::PaddleCrnn(cfg) | Self::PaddleSvtr(cfg) => &mut cfg.base_model,
+            Self::Parseq(cfg) => &mut cfg.base_model,
+        }
+    }
+
+    pub fn dict_path(&self) -> Option<&PathBuf> {
+        match self {
+            Self::PaddleCrnn(cfg) | Self::PaddleSvtr(cfg) => cfg.dict_path.as_ref(),
+            Self::Parseq(cfg) => cfg.dict_path.as_ref(),
+        }
+    }
+
+    pub fn dict_path_mut(&mut self) -> &mut Option<PathBuf> {
+        match self {
+            Self::PaddleCrnn(cfg) | Self::PaddleSvtr(cfg) => &mut cfg.dict_path,
+            Self::Parseq(cfg) => &mut cfg.dict_path,
+        }
+    }
 }
@@ -45,6 +45,17 @@ impl DetResult {
             score,
         }
     }
+
+    /// 平移到另一坐标系，稳定框按新坐标重新量化
+    pub fn translated(&self, dx: i32, dy: i32, grid_size: u16) -> Self {
+        Self::new(
+            self.bounding_box.translated(dx, dy),
+            self.index,
+            self.label.clone(),
+            self.score,
+            grid_size,
+        )
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode, PartialEq, ts_rs::TS)]
@@ -90,6 +101,16 @@ impl OcrResult {
             index,
         }
     }
+
+    pub fn translated(&self, dx: i32, dy: i32, grid_size: u16) -> Self {
+        Self::new(
+            self.bounding_box.translated(dx, dy),
+            self.txt.clone(),
+            self.score.clone(),
+            self.index.clone(),
+            grid_size,
+        )
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Encode, Decode, PartialEq, Eq, ts_rs::TS)]
@@ -106,6 +127,10 @@ impl BoundingBox {
         BoundingBox { x1, y1, x2, y2 }
     }
 
+    pub fn translated(&self, dx: i32, dy: i32) -> Self {
+        BoundingBox::new(self.x1 + dx, self.y1 + dy, self.x2 + dx, self.y2 + dy)
+    }
+
     pub fn center(&self) -> StablePoint {
         StablePoint {
             x: (self.x1 + self.x2) / 2,
@@ -169,4 +194,14 @@ mod tests {
         assert_eq!(box_area.to_stable_box(8), BoundingBox::new(8, 16, 24, 32));
         assert_eq!(box_area.to_stable_center(8), StablePoint { x: 16, y: 24 });
     }
+
+    #[test]
+    fn translated_results_requantize_stable_box() {
+        let det = DetResult::new(BoundingBox::new(1, 1, 9, 9), 0, "icon".into(), 0.9, 8);
+        let moved = det.translated(100, 50, 8);
+
+        assert_eq!(moved.bounding_box, BoundingBox::new(101, 51, 109, 59));
+        assert_eq!(moved.stable_box, BoundingBox::new(104, 48, 112, 56));
+        assert_eq!(moved.stable_center, StablePoint { x: 104, y: 56 });
+    }
 }
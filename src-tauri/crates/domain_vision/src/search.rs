@@ -1,4 +1,4 @@
-use crate::{BoundingBox, DetResult, OcrResult, StablePoint};
+use crate::{BoundingBox, DetResult, OcrResult, StablePoint, TextMatchMode, TextNormalize};
 use std::collections::HashMap;
 
 #[derive(
@@ -113,6 +113,10 @@ pub enum SearchScope {
 pub enum SearchRule {
     Txt {
         pattern: String,
+        #[serde(default)]
+        mode: TextMatchMode,
+        #[serde(default)]
+        normalize: TextNormalize,
     },
     DetLabel {
         idx: i32,
@@ -124,19 +128,63 @@ pub enum SearchRule {
     },
 }
 
+fn default_hit_similarity() -> f32 {
+    1.0
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ts_rs::TS)]
 #[ts(export)]
 pub struct SearchHit {
+    /// 命中规则的标识，默认匹配方式下即为去除首尾空白后的匹配内容
     pub pattern: String,
     pub ocr_index: usize,
     pub ocr_item: OcrResult,
+    /// 相似度（0~1），精确与正则命中恒为 1
+    #[serde(default = "default_hit_similarity")]
+    pub similarity: f32,
+}
+
+/// 从文字规则中提取的单个查询条件
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TextQuery {
+    pub key: String,
+    pub pattern: String,
+    pub mode: TextMatchMode,
+    pub normalize: TextNormalize,
+}
+
+impl TextQuery {
+    fn new(pattern: &str, mode: &TextMatchMode, normalize: TextNormalize) -> Option<Self> {
+        let pattern = normalized_pattern(pattern)?;
+        Some(Self {
+            key: query_key(pattern, mode, normalize),
+            pattern: pattern.to_string(),
+            mode: mode.clone(),
+            normalize,
+        })
+    }
+}
+
+/// 默认匹配方式沿用原始内容作为标识，其余方式附带模式与归一化描述，避免同名规则互相串用命中
+fn query_key(pattern: &str, mode: &TextMatchMode, normalize: TextNormalize) -> String {
+    let mode = match mode {
+        TextMatchMode::Contains if normalize.is_identity() => return pattern.to_string(),
+        TextMatchMode::Contains => "contains".to_string(),
+        TextMatchMode::Regex => "regex".to_string(),
+        TextMatchMode::Fuzzy { similarity } => format!("fuzzy>={:.2}", similarity),
+    };
+    format!("{}[{}]:{}", mode, normalize.describe(), pattern)
 }
 
 impl SearchRule {
     pub fn evaluate(&self, hits: &[SearchHit], det_results: &[DetResult]) -> bool {
         match self {
-            Self::Txt { pattern } => normalized_pattern(pattern)
-                .is_some_and(|pattern| hits.iter().any(|hit| hit.pattern == pattern)),
+            Self::Txt {
+                pattern,
+                mode,
+                normalize,
+            } => TextQuery::new(pattern, mode, *normalize)
+                .is_some_and(|query| hits.iter().any(|hit| hit.pattern == query.key)),
             Self::DetLabel { idx } => det_results.iter().any(|result| result.index == *idx),
             Self::Group { op, scope, items } => match scope {
                 SearchScope::Global => match op {
@@ -177,24 +225,33 @@ impl SearchRule {
     }
 
     pub(crate) fn get_all_keywords(&self) -> Vec<String> {
-        let mut keywords = Vec::new();
-        self.collect_keywords(&mut keywords);
+        let mut keywords: Vec<String> = self
+            .text_queries()
+            .into_iter()
+            .map(|query| query.key)
+            .collect();
         keywords.sort();
         keywords.dedup();
         keywords
     }
 
-    fn collect_keywords(&self, keywords: &mut Vec<String>) {
+    pub(crate) fn text_queries(&self) -> Vec<TextQuery> {
+        let mut queries = Vec::new();
+        self.collect_text_queries(&mut queries);
+        queries
+    }
+
+    fn collect_text_queries(&self, queries: &mut Vec<TextQuery>) {
         match self {
-            Self::Txt { pattern } => {
-                if let Some(pattern) = normalized_pattern(pattern) {
-                    keywords.push(pattern.to_string());
-                }
-            }
+            Self::Txt {
+                pattern,
+                mode,
+                normalize,
+            } => queries.extend(TextQuery::new(pattern, mode, *normalize)),
             Self::DetLabel { .. } => {}
             Self::Group { items, .. } => {
                 for item in items {
-                    item.collect_keywords(keywords);
+                    item.collect_text_queries(queries);
                 }
             }
         }
@@ -218,9 +275,13 @@ mod tests {
             items: vec![
                 SearchRule::Txt {
                     pattern: " a ".to_string(),
+                    mode: TextMatchMode::Contains,
+                    normalize: TextNormalize::default(),
                 },
                 SearchRule::Txt {
                     pattern: "a".to_string(),
+                    mode: TextMatchMode::Contains,
+                    normalize: TextNormalize::default(),
                 },
                 SearchRule::DetLabel { idx: 1 },
             ],
@@ -228,4 +289,37 @@ mod tests {
 
         assert_eq!(rule.get_all_keywords(), vec!["a"]);
     }
+
+    #[test]
+    fn keeps_rules_with_different_match_modes_apart() {
+        let rule = SearchRule::Group {
+            op: LogicOp::Or,
+            scope: SearchScope::Global,
+            items: vec![
+                SearchRule::Txt {
+                    pattern: "a".to_string(),
+                    mode: TextMatchMode::Contains,
+                    normalize: TextNormalize::default(),
+                },
+                SearchRule::Txt {
+                    pattern: "a".to_string(),
+                    mode: TextMatchMode::Fuzzy { similarity: 0.8 },
+                    normalize: TextNormalize {
+                        fold_case: true,
+                        ..TextNormalize::default()
+                    },
+                },
+            ],
+        };
+
+        assert_eq!(rule.get_all_keywords(), vec!["a", "fuzzy>=0.80[case]:a"]);
+        let legacy: SearchRule = serde_json::from_str(r#"{"type":"txt","pattern":"a"}"#).unwrap();
+        assert!(matches!(
+            legacy,
+            SearchRule::Txt {
+                mode: TextMatchMode::Contains,
+                ..
+            }
+        ));
+    }
 }
@@ -0,0 +1,315 @@
+use std::borrow::Cow;
+use unicode_normalization::UnicodeNormalization;
+
+fn default_fuzzy_similarity() -> f32 {
+    0.8
+}
+
+/// 文字匹配前的归一化选项，OCR 文本与匹配内容会按同一套规则处理
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+    PartialEq,
+    Eq,
+    Hash,
+    ts_rs::TS,
+)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct TextNormalize {
+    /// Unicode NFKC 兼容归一化，会同时处理全角字母数字、上标、合字等
+    #[serde(default)]
+    pub nfkc: bool,
+    /// 只把全角 ASCII 与全角空格折叠为半角
+    #[serde(default)]
+    pub fold_width: bool,
+    #[serde(default)]
+    pub fold_case: bool,
+    #[serde(default)]
+    pub remove_whitespace: bool,
+    /// 折叠 OCR 易混字符（0/O、1/l/I）以及常见繁体字
+    #[serde(default)]
+    pub fold_confusables: bool,
+}
+
+/// 文字规则的匹配方式
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize, PartialEq, ts_rs::TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum TextMatchMode {
+    /// OCR 文本包含匹配内容
+    #[default]
+    Contains,
+    /// 匹配内容作为正则表达式，在归一化后的 OCR 文本上查找。
+    /// 正则本身不做归一化：忽略大小写时按不区分大小写匹配，其余归一化需按处理后的文本书写正则
+    Regex,
+    /// 允许编辑距离误差的包含匹配，`similarity` 为 0~1 的最低相似度
+    Fuzzy {
+        #[serde(default = "default_fuzzy_similarity")]
+        similarity: f32,
+    },
+}
+
+impl TextMatchMode {
+    /// 校验匹配内容在当前模式下是否可用，返回面向用户的错误说明
+    pub fn validate(&self, pattern: &str) -> Result<(), String> {
+        match self {
+            Self::Contains => Ok(()),
+            Self::Regex => regex::Regex::new(pattern)
+                .map(|_| ())
+                .map_err(|e| format!("正则表达式无效: {}", e)),
+            Self::Fuzzy { similarity } => {
+                if *similarity > 0.0 && *similarity <= 1.0 {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "模糊匹配相似度需在 (0, 1] 之间，当前为 {}",
+                        similarity
+                    ))
+                }
+            }
+        }
+    }
+}
+
+/// OCR 常见的形近误识别，两侧都折叠到同一个字符
+const CONFUSABLE_CHARS: &[(char, char)] = &[
+    ('O', '0'),
+    ('o', '0'),
+    ('〇', '0'),
+    ('I', '1'),
+    ('l', '1'),
+    ('|', '1'),
+];
+
+/// 高频繁体字到简体字的折叠表，覆盖常见界面用字
+const TRADITIONAL_CHARS: &[(char, char)] = &[
+    ('開', '开'),
+    ('關', '关'),
+    ('閉', '闭'),
+    ('確', '确'),
+    ('認', '认'),
+    ('領', '领'),
+    ('獎', '奖'),
+    ('勵', '励'),
+    ('務', '务'),
+    ('設', '设'),
+    ('續', '续'),
+    ('戰', '战'),
+    ('鬥', '斗'),
+    ('體', '体'),
+    ('連', '连'),
+    ('級', '级'),
+    ('選', '选'),
+    ('擇', '择'),
+    ('點', '点'),
+    ('擊', '击'),
+    ('頁', '页'),
+    ('時', '时'),
+    ('間', '间'),
+    ('無', '无'),
+    ('處', '处'),
+    ('會', '会'),
+    ('動', '动'),
+    ('執', '执'),
+    ('進', '进'),
+    ('發', '发'),
+    ('買', '买'),
+    ('賣', '卖'),
+    ('購', '购'),
+    ('寶', '宝'),
+    ('禮', '礼'),
+    ('簽', '签'),
+    ('數', '数'),
+    ('經', '经'),
+    ('驗', '验'),
+    ('換', '换'),
+    ('兌', '兑'),
+    ('幣', '币'),
+    ('滿', '满'),
+    ('復', '复'),
+    ('來', '来'),
+    ('還', '还'),
+    ('歸', '归'),
+    ('總', '总'),
+    ('覽', '览'),
+    ('請', '请'),
+    ('記', '记'),
+    ('錄', '录'),
+    ('號', '号'),
+    ('帳', '帐'),
+    ('線', '线'),
+    ('網', '网'),
+    ('絡', '络'),
+    ('誤', '误'),
+    ('錯', '错'),
+    ('說', '说'),
+    ('幫', '帮'),
+    ('隊', '队'),
+    ('員', '员'),
+    ('與', '与'),
+    ('後', '后'),
+    ('戲', '戏'),
+    ('遊', '游'),
+    ('場', '场'),
+    ('對', '对'),
+    ('話', '话'),
+    ('訊', '讯'),
+    ('郵', '邮'),
+    ('單', '单'),
+    ('雙', '双'),
+    ('邊', '边'),
+    ('張', '张'),
+    ('強', '强'),
+    ('裝', '装'),
+    ('備', '备'),
+    ('鍵', '键'),
+    ('啟', '启'),
+    ('諾', '诺'),
+    ('議', '议'),
+    ('階', '阶'),
+    ('個', '个'),
+    ('長', '长'),
+    ('門', '门'),
+    ('為', '为'),
+    ('這', '这'),
+    ('樣', '样'),
+    ('嗎', '吗'),
+];
+
+fn fold_confusable(ch: char) -> char {
+    CONFUSABLE_CHARS
+        .iter()
+        .chain(TRADITIONAL_CHARS)
+        .find(|(from, _)| *from == ch)
+        .map_or(ch, |(_, to)| *to)
+}
+
+fn fold_width(ch: char) -> char {
+    match ch {
+        '\u{3000}' => ' ',
+        '\u{FF01}'..='\u{FF5E}' => char::from_u32(ch as u32 - 0xFEE0).unwrap_or(ch),
+        _ => ch,
+    }
+}
+
+impl TextNormalize {
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn apply<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if self.is_identity() {
+            return Cow::Borrowed(text);
+        }
+        let text: Cow<str> = if self.nfkc {
+            Cow::Owned(text.nfkc().collect())
+        } else {
+            Cow::Borrowed(text)
+        };
+        let mut output = String::with_capacity(text.len());
+        for ch in text.chars() {
+            let ch = if self.fold_width { fold_width(ch) } else { ch };
+            if self.remove_whitespace && ch.is_whitespace() {
+                continue;
+            }
+            let mut push = |ch: char| {
+                output.push(if self.fold_confusables {
+                    fold_confusable(ch)
+                } else {
+                    ch
+                })
+            };
+            // 先折叠大小写再折叠易混字符，保证 L/l、O/o 落到同一个结果
+            if self.fold_case {
+                ch.to_lowercase().for_each(&mut push);
+            } else {
+                push(ch);
+            }
+        }
+        Cow::Owned(output)
+    }
+
+    /// 用于规则标识的简短描述，默认选项为空串
+    pub(crate) fn describe(&self) -> String {
+        [
+            (self.nfkc, "nfkc"),
+            (self.fold_width, "width"),
+            (self.fold_case, "case"),
+            (self.remove_whitespace, "space"),
+            (self.fold_confusables, "confusable"),
+        ]
+        .iter()
+        .filter(|(enabled, _)| *enabled)
+        .map(|(_, name)| *name)
+        .collect::<Vec<_>>()
+        .join(",")
+    }
+}
+
+/// `pattern` 与 `text` 任意子串之间的最小编辑距离换算成的相似度（0~1）
+pub fn substring_similarity(pattern: &str, text: &str) -> f32 {
+    let pattern: Vec<char> = pattern.chars().collect();
+    if pattern.is_empty() {
+        return 1.0;
+    }
+    // 首行全为 0，允许匹配从文本任意位置开始；取末行最小值，允许在任意位置结束
+    let mut previous: Vec<usize> = (0..=pattern.len()).collect();
+    let mut best = pattern.len();
+    for text_char in text.chars() {
+        let mut current = Vec::with_capacity(pattern.len() + 1);
+        current.push(0);
+        for (index, pattern_char) in pattern.iter().enumerate() {
+            let substitution = previous[index] + usize::from(*pattern_char != text_char);
+            current.push(
+                substitution
+                    .min(previous[index + 1] + 1)
+                    .min(current[index] + 1),
+            );
+        }
+        best = best.min(current[pattern.len()]);
+        previous = current;
+    }
+    1.0 - best as f32 / pattern.len() as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalization_folds_width_case_space_and_confusables() {
+        let normalize = TextNormalize {
+            fold_width: true,
+            fold_case: true,
+            remove_whitespace: true,
+            fold_confusables: true,
+            ..TextNormalize::default()
+        };
+        assert_eq!(normalize.apply("ＧＯ　Ｌｖ 10"), "g01v10");
+        assert_eq!(normalize.apply("確認"), "确认");
+        assert_eq!(
+            TextNormalize {
+                nfkc: true,
+                ..TextNormalize::default()
+            }
+            .apply("①ＡＢ"),
+            "1AB"
+        );
+        assert!(matches!(
+            TextNormalize::default().apply("x"),
+            Cow::Borrowed("x")
+        ));
+    }
+
+    #[test]
+    fn substring_similarity_allows_edits_inside_longer_text() {
+        assert_eq!(substring_similarity("确认", "点击确认按钮"), 1.0);
+        assert_eq!(substring_similarity("领取奖励", "一键领取奖厉"), 0.75);
+        assert_eq!(substring_similarity("abc", "xyz"), 0.0);
+    }
+}
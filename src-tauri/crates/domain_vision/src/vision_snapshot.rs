@@ -1,7 +1,11 @@
+use crate::search::TextQuery;
 use crate::{
-    DetResult, OcrResult, SearchHit, SearchRule, StablePoint, VisionLayoutItem, VisionLayoutSource,
+    DetResult, OcrResult, SearchHit, SearchRule, StablePoint, TextMatchMode, TextNormalize,
+    VisionLayoutItem, VisionLayoutSource, substring_similarity,
 };
 use aho_corasick::AhoCorasick;
+use regex::{Regex, RegexBuilder};
+use std::borrow::Cow;
 use std::cmp::Ordering;
 use std::collections::HashSet;
 
@@ -93,12 +97,16 @@ fn normalize_det_items(items: &mut [DetResult], grid_size: u16) {
 }
 
 fn build_ocr_buffer(ocr_results: &[OcrResult]) -> (String, Vec<(usize, usize)>) {
+    build_text_buffer(ocr_results.iter().map(|ocr| ocr.txt.as_str()))
+}
+
+fn build_text_buffer<'a>(texts: impl Iterator<Item = &'a str>) -> (String, Vec<(usize, usize)>) {
     let mut buffer = String::new();
     let mut offset_map = Vec::new();
 
-    for (idx, ocr) in ocr_results.iter().enumerate() {
+    for (idx, text) in texts.enumerate() {
         let start_offset = buffer.len();
-        buffer.push_str(&ocr.txt);
+        buffer.push_str(text);
         buffer.push('\n');
         offset_map.push((start_offset, idx));
     }
@@ -106,33 +114,99 @@ fn build_ocr_buffer(ocr_results: &[OcrResult]) -> (String, Vec<(usize, usize)>)
     (buffer, offset_map)
 }
 
-/// OCR 文本搜索器：仅处理第一阶段 OCR 文本召回条件，
-/// 通过 Aho-Corasick 自动机实现多模式一次遍历。
+/// 同一归一化方式下的包含匹配，共用一个自动机
+struct ContainsGroup {
+    normalize: TextNormalize,
+    automaton: AhoCorasick,
+    keys: Vec<String>,
+}
+
+struct RegexQuery {
+    key: String,
+    regex: Regex,
+    normalize: TextNormalize,
+}
+
+struct FuzzyQuery {
+    key: String,
+    pattern: String,
+    similarity: f32,
+    normalize: TextNormalize,
+}
+
+/// OCR 文本搜索器：仅处理第一阶段 OCR 文本召回条件。
+/// 包含匹配按归一化方式分组，通过 Aho-Corasick 自动机实现多模式一次遍历；
+/// 正则与模糊匹配逐条 OCR 文本计算。
 pub struct OcrSearcher {
-    automaton: Option<AhoCorasick>,
-    patterns: Vec<String>,
+    contains: Vec<ContainsGroup>,
+    regexes: Vec<RegexQuery>,
+    fuzzy: Vec<FuzzyQuery>,
 }
 
 impl OcrSearcher {
     /// 从规则集中提取文本条件，构建搜索自动机。
-    /// DetLabel 变体会被跳过，不参与文本搜索。
+    /// DetLabel 变体会被跳过，不参与文本搜索；无效的正则不会产生命中，由脚本检查提前报告。
     pub fn new(rules: &[SearchRule]) -> Self {
-        let mut keywords: Vec<String> = rules
-            .iter()
-            .flat_map(SearchRule::get_all_keywords)
-            .collect();
-
-        keywords.sort();
-        keywords.dedup();
-
-        let automaton = if keywords.is_empty() {
-            None
-        } else {
-            Some(AhoCorasick::new(&keywords).unwrap())
-        };
+        let mut queries: Vec<TextQuery> = rules.iter().flat_map(SearchRule::text_queries).collect();
+        queries.sort_by(|left, right| left.key.cmp(&right.key));
+        queries.dedup_by(|left, right| left.key == right.key);
+
+        let mut contains: Vec<(TextNormalize, Vec<String>, Vec<String>)> = Vec::new();
+        let mut regexes = Vec::new();
+        let mut fuzzy = Vec::new();
+        for query in queries {
+            match query.mode {
+                TextMatchMode::Contains => {
+                    let pattern = query.normalize.apply(&query.pattern).into_owned();
+                    if pattern.is_empty() {
+                        continue;
+                    }
+                    let index = match contains
+                        .iter()
+                        .position(|(normalize, _, _)| *normalize == query.normalize)
+                    {
+                        Some(index) => index,
+                        None => {
+                            contains.push((query.normalize, Vec::new(), Vec::new()));
+                            contains.len() - 1
+                        }
+                    };
+                    contains[index].1.push(pattern);
+                    contains[index].2.push(query.key);
+                }
+                TextMatchMode::Regex => {
+                    // 正则本身不做归一化，忽略大小写改用不区分大小写匹配
+                    if let Ok(regex) = RegexBuilder::new(&query.pattern)
+                        .case_insensitive(query.normalize.fold_case)
+                        .build()
+                    {
+                        regexes.push(RegexQuery {
+                            key: query.key,
+                            regex,
+                            normalize: query.normalize,
+                        });
+                    }
+                }
+                TextMatchMode::Fuzzy { similarity } => fuzzy.push(FuzzyQuery {
+                    pattern: query.normalize.apply(&query.pattern).into_owned(),
+                    key: query.key,
+                    similarity,
+                    normalize: query.normalize,
+                }),
+            }
+        }
+
         Self {
-            automaton,
-            patterns: keywords,
+            contains: contains
+                .into_iter()
+                .map(|(normalize, patterns, keys)| ContainsGroup {
+                    normalize,
+                    automaton: AhoCorasick::new(&patterns).unwrap(),
+                    keys,
+                })
+                .collect(),
+            regexes,
+            fuzzy,
         }
     }
 
@@ -155,19 +229,52 @@ impl OcrSearcher {
     ) -> Vec<SearchHit> {
         let mut hits = Vec::new();
         let mut seen = HashSet::new();
+        let mut push_hit = |key: &str, ocr_index: usize, ocr: &OcrResult, similarity: f32| {
+            if seen.insert((key.to_string(), ocr_index)) {
+                hits.push(SearchHit {
+                    pattern: key.to_string(),
+                    ocr_index,
+                    ocr_item: ocr.clone(),
+                    similarity,
+                });
+            }
+        };
 
-        // 1. Aho-Corasick 关键字匹配
-        if let Some(automaton) = &self.automaton {
-            for mat in automaton.find_iter(buffer) {
+        // 1. Aho-Corasick 关键字匹配，非默认归一化时在归一化后的缓冲区上查找
+        for group in &self.contains {
+            let normalized;
+            let (buffer, offset_map) = if group.normalize.is_identity() {
+                (buffer, offset_map)
+            } else {
+                let texts: Vec<Cow<str>> = ocr_items
+                    .iter()
+                    .map(|ocr| group.normalize.apply(&ocr.txt))
+                    .collect();
+                normalized = build_text_buffer(texts.iter().map(AsRef::as_ref));
+                (normalized.0.as_str(), normalized.1.as_slice())
+            };
+            for mat in group.automaton.find_iter(buffer) {
                 if let Some((ocr_index, ocr)) = find_ocr_at(offset_map, ocr_items, mat.start()) {
-                    let pattern = self.patterns[mat.pattern().as_usize()].clone();
-                    if seen.insert((pattern.clone(), ocr_index)) {
-                        hits.push(SearchHit {
-                            pattern,
-                            ocr_index,
-                            ocr_item: ocr.clone(),
-                        });
-                    }
+                    push_hit(&group.keys[mat.pattern().as_usize()], ocr_index, ocr, 1.0);
+                }
+            }
+        }
+
+        // 2. 正则与模糊匹配逐条计算
+        if self.regexes.is_empty() && self.fuzzy.is_empty() {
+            return hits;
+        }
+        for (ocr_index, ocr) in ocr_items.iter().enumerate() {
+            for query in &self.regexes {
+                if query.regex.is_match(&query.normalize.apply(&ocr.txt)) {
+                    push_hit(&query.key, ocr_index, ocr, 1.0);
+                }
+            }
+            for query in &self.fuzzy {
+                let similarity =
+                    substring_similarity(&query.pattern, &query.normalize.apply(&ocr.txt));
+                if similarity >= query.similarity {
+                    push_hit(&query.key, ocr_index, ocr, similarity);
                 }
             }
         }
@@ -298,6 +405,8 @@ mod tests {
             items: vec![
                 SearchRule::Txt {
                     pattern: "Confirm".into(),
+                    mode: TextMatchMode::Contains,
+                    normalize: TextNormalize::default(),
                 },
                 SearchRule::DetLabel { idx: 5 },
             ],
@@ -400,6 +509,8 @@ mod tests {
 
         let rule = SearchRule::Txt {
             pattern: "Hello".into(),
+            mode: TextMatchMode::Contains,
+            normalize: TextNormalize::default(),
         };
         let searcher = OcrSearcher::new(&[rule]);
         let hits = searcher.search(&snapshot);
@@ -422,10 +533,96 @@ mod tests {
 
         let hits = OcrSearcher::new(&[SearchRule::Txt {
             pattern: "Confirm".into(),
+            mode: TextMatchMode::Contains,
+            normalize: TextNormalize::default(),
         }])
         .search_ocr_items(&items);
 
         assert_eq!(hits.len(), 1);
         assert_eq!(hits[0].ocr_index, 0);
     }
+
+    #[test]
+    fn test_normalized_regex_and_fuzzy_search() {
+        let items = vec![
+            OcrResult::new(
+                BoundingBox::new(10, 10, 100, 30),
+                "ＬＶ　1O".to_string(),
+                vec![0.9],
+                vec![0],
+                8,
+            ),
+            OcrResult::new(
+                BoundingBox::new(10, 40, 100, 60),
+                "領取獎勵".to_string(),
+                vec![0.9],
+                vec![1],
+                8,
+            ),
+            OcrResult::new(
+                BoundingBox::new(10, 70, 100, 90),
+                "剩余 12 次".to_string(),
+                vec![0.9],
+                vec![2],
+                8,
+            ),
+        ];
+        let normalize = TextNormalize {
+            fold_width: true,
+            fold_case: true,
+            remove_whitespace: true,
+            fold_confusables: true,
+            ..TextNormalize::default()
+        };
+        let normalized = SearchRule::Txt {
+            pattern: "lv10".into(),
+            mode: TextMatchMode::Contains,
+            normalize,
+        };
+        let regex = SearchRule::Txt {
+            pattern: r"剩余\s*\d+\s*次".into(),
+            mode: TextMatchMode::Regex,
+            normalize: TextNormalize::default(),
+        };
+        let folded_regex = SearchRule::Txt {
+            pattern: r"^LV\d".into(),
+            mode: TextMatchMode::Regex,
+            normalize: TextNormalize {
+                fold_width: true,
+                fold_case: true,
+                remove_whitespace: true,
+                ..TextNormalize::default()
+            },
+        };
+        let fuzzy = SearchRule::Txt {
+            pattern: "领取奖品".into(),
+            mode: TextMatchMode::Fuzzy { similarity: 0.7 },
+            normalize,
+        };
+        let exact = SearchRule::Txt {
+            pattern: "lv10".into(),
+            mode: TextMatchMode::Contains,
+            normalize: TextNormalize::default(),
+        };
+        let rules = [
+            normalized.clone(),
+            regex.clone(),
+            folded_regex.clone(),
+            fuzzy.clone(),
+            exact.clone(),
+        ];
+
+        let hits = OcrSearcher::new(&rules).search_ocr_items(&items);
+
+        assert_eq!(hits.len(), 4);
+        assert!(normalized.evaluate(&hits, &[]));
+        assert!(regex.evaluate(&hits, &[]));
+        assert!(folded_regex.evaluate(&hits, &[]));
+        assert!(fuzzy.evaluate(&hits, &[]));
+        assert!(!exact.evaluate(&hits, &[]));
+        let fuzzy_hit = fuzzy.filter_hits(&hits);
+        assert_eq!(fuzzy_hit.len(), 1);
+        assert_eq!(fuzzy_hit[0].ocr_index, 1);
+        assert_eq!(fuzzy_hit[0].similarity, 0.75);
+    }
 }
@@ -1,4 +1,5 @@
 use crate::adb_config::ADBConnectConfig;
+use crate::adb_touch::TouchFrame;
 use ad_kernel::Point;
 use image::RgbaImage;
 use std::ops::Add;
@@ -7,6 +8,7 @@ use std::ops::Add;
 pub(crate) const BACK: &str = "input keyevent 4";
 pub(crate) const HOME: &str = "input keyevent 3";
 pub(crate) const POWER: &str = "input keyevent 26";
+pub(crate) const KEY_EVENT: &str = "input keyevent";
 pub(crate) const CLICK: &str = "input tap";
 pub(crate) const SWIPE: &str = "input swipe";
 pub(crate) const TOUCH_SCREEN: &str = "input touchscreen swipe";
@@ -50,6 +52,18 @@ pub(crate) fn input_text_cmd(text: &str) -> String {
     format!("input text {}", text)
 }
 
+/// `input keyevent` 支持一次传入多个键码，重复按键合并为一条命令
+pub(crate) fn key_event_cmd(code: u32, long_press: bool, repeat: u32) -> String {
+    let mut cmd = String::from(KEY_EVENT);
+    if long_press {
+        cmd.push_str(" --longpress");
+    }
+    for _ in 0..repeat.max(1) {
+        cmd.push_str(&format!(" {}", code));
+    }
+    cmd
+}
+
 pub(crate) fn stop_app_cmd(package_name: &str) -> String {
     format!("{} {}", STOP_APP, package_name)
 }
@@ -65,11 +79,18 @@ pub enum ADBCommand {
     LongClickAndSwipe(Point<u16>, Point<u16>, u64),
     Swipe(Point<u16>, Point<u16>),
     SwipeWithDuration(Point<u16>, Point<u16>, u64),
+    // 多点触控手势，通过 sendevent 回放
+    Touch(Vec<TouchFrame>),
     Reboot,
     StartActivity(String, String),
     Capture(crossbeam_channel::Sender<Result<RgbaImage, String>>),
     StopApp(String),
     InputText(String),
+    KeyEvent {
+        code: u32,
+        long_press: bool,
+        repeat: u32,
+    },
     Back,
     Home,
 
@@ -105,6 +126,12 @@ impl std::fmt::Display for ADBCommand {
             ADBCommand::SwipeWithDuration(p1, p2, duration) => {
                 write!(f, "{}", swipe_duration_cmd(p1, p2, duration))
             }
+            ADBCommand::Touch(frames) => write!(
+                f,
+                "touch:{}x{}",
+                frames.first().map_or(0, |frame| frame.points.len()),
+                frames.len()
+            ),
             ADBCommand::Reboot => write!(f, "reboot:{}", POWER),
             ADBCommand::StartActivity(package_name, activity_name) => {
                 write!(f, "{}", start_activity_cmd(package_name, activity_name))
@@ -112,6 +139,11 @@ impl std::fmt::Display for ADBCommand {
             ADBCommand::Capture(_) => write!(f, "capture"),
             ADBCommand::StopApp(package_name) => write!(f, "{} {}", STOP_APP, package_name),
             ADBCommand::InputText(text) => write!(f, "input:{}", text),
+            ADBCommand::KeyEvent {
+                code,
+                long_press,
+                repeat,
+            } => write!(f, "{}", key_event_cmd(*code, *long_press, *repeat)),
             ADBCommand::Back => write!(f, "back:{}", BACK),
             ADBCommand::Home => write!(f, "home:{}", HOME),
             ADBCommand::Sequence(commands) => {
@@ -174,7 +206,7 @@ pub(crate) enum ADBCommandResult {
 
 #[cfg(test)]
 mod tests {
-    use super::{sleep_cmd, start_activity_cmd};
+    use super::{key_event_cmd, sleep_cmd, start_activity_cmd};
 
     #[test]
     fn sleep_cmd_formats_milliseconds() {
@@ -184,6 +216,13 @@ mod tests {
         assert_eq!(sleep_cmd(1250), "sleep 1.250");
     }
 
+    #[test]
+    fn key_event_cmd_batches_repeats_into_one_invocation() {
+        assert_eq!(key_event_cmd(66, false, 0), "input keyevent 66");
+        assert_eq!(key_event_cmd(66, false, 3), "input keyevent 66 66 66");
+        assert_eq!(key_event_cmd(24, true, 1), "input keyevent --longpress 24");
+    }
+
     #[test]
     fn start_activity_cmd_waits_for_launch_completion() {
         assert_eq!(
@@ -2,6 +2,7 @@ use crate::Log;
 use crate::adb_command::{ADBCmdConv, ADBCommand};
 use crate::adb_config::ADBConnectConfig;
 use crate::adb_executor::ADBExecutor;
+use crate::adb_stream::InputClock;
 //use core_affinity::CoreId;
 use std::collections::VecDeque;
 use std::sync::Arc;
@@ -51,6 +52,8 @@ pub struct ADBCtx {
     pub cmd_loop_sender: crossbeam_channel::Sender<ADBCommand>,
     //截图命令发送通道（高优先级，避免被Loop热路径拖慢）
     pub capture_sender: crossbeam_channel::Sender<ADBCommand>,
+    //最近一次输入执行完成的时间，截图流据此判断帧是否新鲜
+    pub input_clock: Arc<InputClock>,
 }
 
 impl ADBCtx {
@@ -69,6 +72,7 @@ impl ADBCtx {
             //ADBExecutor::new(Arc::new(Mutex::new(runtime_connect_conf)),core_id, err_tx);
             ADBExecutor::new(adb_config.clone(), err_tx);
         let cmds_after_conversion = executor.cmds_after_conversion.clone();
+        let input_clock = executor.input_clock.clone();
 
         let adb_ctx = ADBCtx {
             adb_config,
@@ -76,6 +80,7 @@ impl ADBCtx {
             cmd_sender,
             cmd_loop_sender,
             capture_sender,
+            input_clock,
         };
 
         if ADB_CONTEXT.set(adb_ctx).is_err() {
@@ -1,11 +1,13 @@
 use crate::Log;
 use crate::adb_command::{
-    ADBCmdConv, ADBCommand, ADBCommandResult, BACK, HOME, click_cmd, input_text_cmd,
+    ADBCmdConv, ADBCommand, ADBCommandResult, BACK, HOME, click_cmd, input_text_cmd, key_event_cmd,
     long_click_and_swipe, long_click_cmd, sleep_cmd, start_activity_cmd, stop_app_cmd, swipe_cmd,
     swipe_duration_cmd,
 };
 use crate::adb_config::ADBConnectConfig;
 use crate::adb_error::{AdbError, AdbResult};
+use crate::adb_stream::InputClock;
+use crate::adb_touch::{TOUCH_DEVICE_PROBE, TouchDevice, touch_cmd};
 use adb_client::server::ADBServer;
 use adb_client::tcp::ADBTcpDevice;
 use adb_client::{ADBDeviceExt, RebootType};
@@ -31,6 +33,9 @@ pub(crate) struct ADBExecutor {
     pub(crate) cmds_after_conversion: Arc<Mutex<VecDeque<ADBCmdConv>>>,
     duration: Duration,
     need_duration: Arc<AtomicBool>,
+    /// 当前连接上探测到的触控设备，重连后失效
+    touch_device: Option<TouchDevice>,
+    pub(crate) input_clock: Arc<InputClock>,
 }
 
 impl std::fmt::Debug for ADBExecutor {
@@ -71,6 +76,8 @@ impl ADBExecutor {
                 cmds_after_conversion,
                 duration: Duration::from_millis(300),
                 need_duration: Arc::new(AtomicBool::new(false)),
+                touch_device: None,
+                input_clock: Arc::new(InputClock::default()),
             },
             cmd_tx,
             cmd_loop_tx,
@@ -180,11 +187,19 @@ impl ADBExecutor {
         let mut new_cmds = VecDeque::new();
 
         if let ADBCommand::Loop(commands) = cmd_high {
+            let touch_device = self.touch_device_for(commands).await;
             for cmd in commands.iter() {
-                if Self::translate_cmd(cmd, &mut new_cmds) {
-                    sleep_count += 1;
-                } else {
-                    not_sleep_count += 1;
+                match Self::translate_cmd(cmd, touch_device.as_ref(), &mut new_cmds) {
+                    Ok(true) => sleep_count += 1,
+                    Ok(false) => not_sleep_count += 1,
+                    Err(error) => {
+                        let _ = self.error_tx.send(ADBCommandResult::Failed(format!(
+                            "[ ADBExecutor ] 转换循环命令[{}]失败: {}",
+                            cmd, error
+                        )));
+                        self.cmds_after_conversion.lock().await.clear();
+                        return false;
+                    }
                 }
             }
         } else {
@@ -269,7 +284,11 @@ impl ADBExecutor {
         )));
     }
 
-    fn translate_cmd(cmd: &ADBCommand, cmds_str: &mut VecDeque<ADBCmdConv>) -> bool {
+    fn translate_cmd(
+        cmd: &ADBCommand,
+        touch_device: Option<&TouchDevice>,
+        cmds_str: &mut VecDeque<ADBCmdConv>,
+    ) -> Result<bool, String> {
         match cmd {
             ADBCommand::Reboot => cmds_str.push_back(ADBCmdConv::ADBClientCommand(cmd.clone())),
             ADBCommand::StartActivity(_, _) => {
@@ -283,7 +302,7 @@ impl ADBExecutor {
             }
             ADBCommand::Duration(_) => {
                 cmds_str.push_back(ADBCmdConv::ADBSleepCommand(cmd.clone()));
-                return true;
+                return Ok(true);
             }
             ADBCommand::Click(point) => {
                 cmds_str.push_back(ADBCmdConv::ADBShellCommand(click_cmd(point)))
@@ -300,16 +319,28 @@ impl ADBExecutor {
             ADBCommand::SwipeWithDuration(point1, point2, duration) => cmds_str.push_back(
                 ADBCmdConv::ADBShellCommand(swipe_duration_cmd(point1, point2, duration)),
             ),
+            ADBCommand::Touch(frames) => cmds_str.push_back(ADBCmdConv::ADBShellCommand(
+                touch_cmd(touch_device, frames)?,
+            )),
             ADBCommand::StopApp(pkg_name) => {
                 cmds_str.push_back(ADBCmdConv::ADBShellCommand(stop_app_cmd(pkg_name)))
             }
             ADBCommand::InputText(text) => {
                 cmds_str.push_back(ADBCmdConv::ADBShellCommand(input_text_cmd(text)))
             }
+            ADBCommand::KeyEvent {
+                code,
+                long_press,
+                repeat,
+            } => cmds_str.push_back(ADBCmdConv::ADBShellCommand(key_event_cmd(
+                *code,
+                *long_press,
+                *repeat,
+            ))),
             ADBCommand::Back => cmds_str.push_back(ADBCmdConv::ADBShellCommand(BACK.to_string())),
             ADBCommand::Home => cmds_str.push_back(ADBCmdConv::ADBShellCommand(HOME.to_string())),
             ADBCommand::Sequence(cmds) | ADBCommand::ReliableSequence(cmds) => {
-                let cmd_string = Self::translate_sequence_cmd(cmds);
+                let cmd_string = Self::translate_sequence_cmd(cmds, touch_device)?;
                 cmds_str.push_back(ADBCmdConv::ADBShellCommand(cmd_string));
             }
             _ => {
@@ -319,10 +350,13 @@ impl ADBExecutor {
                 ));
             }
         }
-        false
+        Ok(false)
     }
 
-    fn translate_sequence_cmd(cmds: &[ADBCommand]) -> String {
+    fn translate_sequence_cmd(
+        cmds: &[ADBCommand],
+        touch_device: Option<&TouchDevice>,
+    ) -> Result<String, String> {
         let mut cmd_string = String::new();
         for sub_cmd in cmds.iter() {
             match sub_cmd {
@@ -346,6 +380,10 @@ impl ADBExecutor {
                     cmd_string.push_str(&swipe_duration_cmd(point1, point2, duration));
                     cmd_string.push_str(" &&");
                 }
+                ADBCommand::Touch(frames) => {
+                    cmd_string.push_str(&touch_cmd(touch_device, frames)?);
+                    cmd_string.push_str(" &&");
+                }
                 ADBCommand::StopApp(pkg_name) => {
                     cmd_string.push_str(&stop_app_cmd(pkg_name));
                     cmd_string.push_str(" &&");
@@ -358,6 +396,14 @@ impl ADBExecutor {
                     cmd_string.push_str(&input_text_cmd(text));
                     cmd_string.push_str(" &&");
                 }
+                ADBCommand::KeyEvent {
+                    code,
+                    long_press,
+                    repeat,
+                } => {
+                    cmd_string.push_str(&key_event_cmd(*code, *long_press, *repeat));
+                    cmd_string.push_str(" &&");
+                }
                 ADBCommand::Back => {
                     cmd_string.push_str(BACK);
                     cmd_string.push_str(" &&");
@@ -379,7 +425,7 @@ impl ADBExecutor {
             cmd_string.truncate(cmd_string.len() - 2);
         }
         Log::debug(&format!("[ ADBExecutor ] 合并命令队列: {}", cmd_string));
-        cmd_string
+        Ok(cmd_string)
     }
 
     async fn execute_single(&mut self, cmd: &ADBCmdConv) -> AdbResult<ADBCommandResult> {
@@ -586,6 +632,22 @@ impl ADBExecutor {
                 self.execute_shell(&swipe_duration_cmd(point1, point2, duration))
                     .await
             }
+            ADBCommand::Touch(frames) => {
+                let device = match self.touch_device().await {
+                    Ok(device) => Some(device),
+                    Err(error) => {
+                        Log::warn(&format!("[ ADBExecutor ] 探测触控设备失败: {}", error));
+                        None
+                    }
+                };
+                let cmd_string = touch_cmd(device.as_ref(), frames).map_err(|e| {
+                    AdbError::ExecuteShellFailed {
+                        cmd: "sendevent".to_string(),
+                        e,
+                    }
+                })?;
+                self.execute_shell(&cmd_string).await
+            }
             ADBCommand::StartActivity(package_name, activity_name) => {
                 self.execute_shell(&start_activity_cmd(package_name, activity_name))
                     .await
@@ -595,10 +657,23 @@ impl ADBExecutor {
             }),
             ADBCommand::StopApp(pkg_name) => self.execute_shell(&stop_app_cmd(pkg_name)).await,
             ADBCommand::InputText(text) => self.execute_shell(&input_text_cmd(text)).await,
+            ADBCommand::KeyEvent {
+                code,
+                long_press,
+                repeat,
+            } => {
+                self.execute_shell(&key_event_cmd(*code, *long_press, *repeat))
+                    .await
+            }
             ADBCommand::Back => self.execute_shell(BACK).await,
             ADBCommand::Home => self.execute_shell(HOME).await,
             ADBCommand::Sequence(cmds) | ADBCommand::ReliableSequence(cmds) => {
-                let cmd_string = Self::translate_sequence_cmd(cmds);
+                let touch_device = self.touch_device_for(cmds).await;
+                let cmd_string = Self::translate_sequence_cmd(cmds, touch_device.as_ref())
+                    .map_err(|e| AdbError::ExecuteShellFailed {
+                        cmd: "sendevent".to_string(),
+                        e,
+                    })?;
                 self.execute_shell(&cmd_string).await
             }
             ADBCommand::Loop(_) | ADBCommand::StopLoop(_) => {
@@ -649,6 +724,7 @@ impl ADBExecutor {
                 .shell_command(&cmd, None, None)
                 .map(|_| ADBCommandResult::Success)
                 .unwrap_or_else(|error| ADBCommandResult::Failed(error.to_string()));
+            self.input_clock.mark();
             Ok(res)
         } else {
             Err(AdbError::ConnectFailed {
@@ -658,13 +734,82 @@ impl ADBExecutor {
         }
     }
 
+    /// 取当前连接的触控设备，首次使用时探测并缓存
+    async fn touch_device(&mut self) -> AdbResult<TouchDevice> {
+        if let Some(device) = &self.touch_device {
+            return Ok(device.clone());
+        }
+        let Some(device) = self.device.as_mut() else {
+            return Err(AdbError::ConnectFailed {
+                ipv4: "unknown".to_string(),
+                e: "ADBDeviceExt为空".to_string(),
+            });
+        };
+        let mut stdout = Vec::new();
+        device
+            .shell_command(&TOUCH_DEVICE_PROBE, Some(&mut stdout), None)
+            .map_err(|error| AdbError::ExecuteShellFailed {
+                cmd: "getevent -p".to_string(),
+                e: error.to_string(),
+            })?;
+        let touch_device =
+            TouchDevice::parse(&String::from_utf8_lossy(&stdout)).map_err(|detail| {
+                AdbError::ExecuteShellFailed {
+                    cmd: "getevent -p".to_string(),
+                    e: detail,
+                }
+            })?;
+        Log::info(&format!(
+            "[ ADBExecutor ] 触控设备: {} ({}x{})",
+            touch_device.path, touch_device.max_x, touch_device.max_y
+        ));
+        self.touch_device = Some(touch_device.clone());
+        Ok(touch_device)
+    }
+
+    /// 命令（含嵌套序列）中有手势时才探测触控设备，探测失败记录日志后返回 None
+    async fn touch_device_for(&mut self, cmds: &[ADBCommand]) -> Option<TouchDevice> {
+        fn has_touch(cmds: &[ADBCommand]) -> bool {
+            cmds.iter().any(|cmd| match cmd {
+                ADBCommand::Touch(_) => true,
+                ADBCommand::Sequence(cmds) | ADBCommand::ReliableSequence(cmds) => has_touch(cmds),
+                _ => false,
+            })
+        }
+        if !has_touch(cmds) {
+            return None;
+        }
+        match self.touch_device().await {
+            Ok(device) => Some(device),
+            Err(error) => {
+                Log::warn(&format!("[ ADBExecutor ] 探测触控设备失败: {}", error));
+                None
+            }
+        }
+    }
+
     async fn reconnect(&mut self) -> bool {
         self.try_to_connect().await
     }
 
     async fn try_to_connect(&mut self) -> bool {
-        let cfg = self.adb_config.lock().await;
-        let device: Option<Box<dyn ADBDeviceExt + Send + Sync>> = match &*cfg {
+        let cfg = self.adb_config.lock().await.clone();
+        self.device = Self::open_device(&cfg);
+        self.touch_device = None;
+        let connected = self.device.is_some();
+        if connected {
+            Log::info("[ ADBExecutor ] 设备连接成功");
+        } else {
+            Log::warn("[ ADBExecutor ] 设备连接失败");
+        }
+        connected
+    }
+
+    /// 按连接配置打开一条新的设备连接，截图流等需要独立连接的场景也复用这里
+    pub(crate) fn open_device(
+        cfg: &ADBConnectConfig,
+    ) -> Option<Box<dyn ADBDeviceExt + Send + Sync>> {
+        match cfg {
             ADBConnectConfig::ServeByIdentifier(dev) => {
                 if !dev.valid() {
                     Log::warn(
@@ -707,16 +852,6 @@ impl ADBExecutor {
                     }
                 }
             }
-        };
-        drop(cfg);
-
-        self.device = device;
-        let connected = self.device.is_some();
-        if connected {
-            Log::info("[ ADBExecutor ] 设备连接成功");
-        } else {
-            Log::warn("[ ADBExecutor ] 设备连接失败");
         }
-        connected
     }
 }
@@ -0,0 +1,113 @@
+/// 把按键名解析为 Android 键码；支持单个字符、`KEYCODE_` 前缀名与常用别名，忽略大小写。
+/// 原始键码需写成 `keycode:66`，单个数字按数字键处理
+pub fn android_keycode(name: &str) -> Option<u32> {
+    let name = name.trim();
+    if let Some(code) = name
+        .get(..8)
+        .filter(|prefix| prefix.eq_ignore_ascii_case("keycode:"))
+        .and_then(|_| name[8..].trim().parse::<u32>().ok())
+    {
+        return Some(code);
+    }
+    let upper = name.to_ascii_uppercase();
+    let upper = upper.strip_prefix("KEYCODE_").unwrap_or(&upper);
+
+    let mut chars = upper.chars();
+    if let (Some(ch), None) = (chars.next(), chars.next()) {
+        return match ch {
+            'A'..='Z' => Some(29 + (ch as u32 - 'A' as u32)),
+            '0'..='9' => Some(7 + (ch as u32 - '0' as u32)),
+            ',' => Some(55),
+            '.' => Some(56),
+            ' ' => Some(62),
+            '-' => Some(69),
+            '=' => Some(70),
+            '/' => Some(76),
+            '@' => Some(77),
+            '+' => Some(81),
+            _ => None,
+        };
+    }
+    if let Some(number) = upper.strip_prefix('F').and_then(|n| n.parse::<u32>().ok()) {
+        return (1..=12).contains(&number).then_some(130 + number);
+    }
+
+    let code = match upper {
+        "HOME" => 3,
+        "BACK" => 4,
+        "CALL" => 5,
+        "ENDCALL" => 6,
+        "STAR" => 17,
+        "POUND" => 18,
+        "DPAD_UP" | "UP" => 19,
+        "DPAD_DOWN" | "DOWN" => 20,
+        "DPAD_LEFT" | "LEFT" => 21,
+        "DPAD_RIGHT" | "RIGHT" => 22,
+        "DPAD_CENTER" => 23,
+        "VOLUME_UP" => 24,
+        "VOLUME_DOWN" => 25,
+        "POWER" => 26,
+        "CAMERA" => 27,
+        "CLEAR" => 28,
+        "COMMA" => 55,
+        "PERIOD" => 56,
+        "ALT_LEFT" | "ALT" => 57,
+        "ALT_RIGHT" => 58,
+        "SHIFT_LEFT" | "SHIFT" => 59,
+        "SHIFT_RIGHT" => 60,
+        "TAB" => 61,
+        "SPACE" => 62,
+        "ENTER" | "RETURN" => 66,
+        "DEL" | "BACKSPACE" => 67,
+        "MINUS" => 69,
+        "EQUALS" => 70,
+        "MENU" => 82,
+        "NOTIFICATION" => 83,
+        "SEARCH" => 84,
+        "MEDIA_PLAY_PAUSE" => 85,
+        "MEDIA_STOP" => 86,
+        "MEDIA_NEXT" => 87,
+        "MEDIA_PREVIOUS" => 88,
+        "MUTE" => 91,
+        "PAGE_UP" | "PAGEUP" => 92,
+        "PAGE_DOWN" | "PAGEDOWN" => 93,
+        "ESCAPE" | "ESC" => 111,
+        "FORWARD_DEL" | "DELETE" => 112,
+        "CTRL_LEFT" | "CTRL" | "CONTROL" => 113,
+        "CTRL_RIGHT" => 114,
+        "MOVE_HOME" => 122,
+        "MOVE_END" | "END" => 123,
+        "VOLUME_MUTE" => 164,
+        "APP_SWITCH" => 187,
+        "SLEEP" => 223,
+        "WAKEUP" => 224,
+        _ => return None,
+    };
+    Some(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::android_keycode;
+
+    #[test]
+    fn resolves_names_aliases_and_raw_codes() {
+        assert_eq!(android_keycode("ENTER"), Some(66));
+        assert_eq!(android_keycode("keycode_volume_up"), Some(24));
+        assert_eq!(android_keycode("esc"), Some(111));
+        assert_eq!(android_keycode("a"), Some(29));
+        assert_eq!(android_keycode("F5"), Some(135));
+        assert_eq!(android_keycode("keycode:187"), Some(187));
+        assert_eq!(android_keycode("KEYCODE: 3"), Some(3));
+        assert_eq!(android_keycode("187"), None);
+        assert_eq!(android_keycode(""), None);
+        assert_eq!(android_keycode("NOT_A_KEY"), None);
+    }
+
+    #[test]
+    fn single_digit_is_the_digit_key_not_a_raw_code() {
+        assert_eq!(android_keycode("3"), Some(10));
+        assert_eq!(android_keycode("0"), Some(7));
+        assert_eq!(android_keycode("keycode:3"), Some(3));
+    }
+}
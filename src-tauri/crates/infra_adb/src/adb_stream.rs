@@ -0,0 +1,584 @@
+use crate::Log;
+use crate::adb_config::ADBConnectConfig;
+use crate::adb_executor::ADBExecutor;
+use image::RgbaImage;
+use std::io::{Read, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+const MAX_FRAME_SIDE: u32 = 16384;
+const STREAM_RECONNECT_DELAY_MS: u64 = 1000;
+/// screenrecord 单次会话时长上限；到期后会话循环重新建立连接
+const SCREENRECORD_TIME_LIMIT_SECS: u32 = 60;
+/// 主机侧 H.264 解码器，需在 PATH 中可执行
+const H264_DECODER: &str = "ffmpeg";
+/// 设备编码、传输与主机解码的保守延迟估计，用于推算帧的实际采集时刻
+const PIPELINE_LATENCY: Duration = Duration::from_millis(100);
+const DECODER_READ_BUF_LEN: usize = 64 * 1024;
+
+/// 截图流参数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdbStreamOptions {
+    /// 最近一次输入后需要等待新画面的时长；超过后仍无新帧视为画面未变化
+    pub max_frame_age: Duration,
+    /// 等待新帧的最长时间，超时后由调用方回退到单次截图
+    pub frame_timeout: Duration,
+}
+
+/// 设备端 screenrecord 把 H.264 裸流写到标准输出，画面变化时才产生新帧。
+/// 先输出一行 shell 进程号再 exec，停止时据此结束静止画面下阻塞的会话
+fn screenrecord_cmd() -> String {
+    format!(
+        "echo $$; exec screenrecord --output-format=h264 --time-limit {} -",
+        SCREENRECORD_TIME_LIMIT_SECS
+    )
+}
+
+/// 低延迟解码参数：关闭探测与缓冲，每解出一帧立即以 PPM 写到标准输出
+fn decoder_args() -> [&'static str; 16] {
+    [
+        "-loglevel",
+        "error",
+        "-fflags",
+        "nobuffer",
+        "-flags",
+        "low_delay",
+        "-probesize",
+        "32",
+        "-f",
+        "h264",
+        "-i",
+        "pipe:0",
+        "-f",
+        "image2pipe",
+        "-vcodec",
+        "ppm",
+    ]
+}
+
+fn spawn_decoder() -> Result<Child, String> {
+    Command::new(H264_DECODER)
+        .args(decoder_args())
+        .arg("pipe:1")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|error| format!("启动H.264解码器{}失败: {}", H264_DECODER, error))
+}
+
+/// 按 PPM（P6）帧头切分解码器输出，输入可以在任意位置被截断
+pub(crate) struct PpmFrameParser {
+    buffer: Vec<u8>,
+}
+
+impl PpmFrameParser {
+    pub(crate) fn new() -> Self {
+        Self { buffer: Vec::new() }
+    }
+
+    /// 解析帧头，返回宽、高与像素数据起始位置；数据不足时返回 None
+    fn parse_header(&self) -> Result<Option<(u32, u32, usize)>, String> {
+        let mut fields = [0u32; 3];
+        let mut pos = 2;
+        if self.buffer.len() < pos {
+            return Ok(None);
+        }
+        if &self.buffer[..2] != b"P6" {
+            return Err("截图流帧头异常：不是PPM帧".to_string());
+        }
+        for field in fields.iter_mut() {
+            while pos < self.buffer.len() && self.buffer[pos].is_ascii_whitespace() {
+                pos += 1;
+            }
+            let digits_start = pos;
+            while pos < self.buffer.len() && self.buffer[pos].is_ascii_digit() {
+                pos += 1;
+            }
+            if pos >= self.buffer.len() {
+                return Ok(None);
+            }
+            *field = std::str::from_utf8(&self.buffer[digits_start..pos])
+                .ok()
+                .and_then(|digits| digits.parse().ok())
+                .ok_or_else(|| "截图流帧头异常：尺寸字段无效".to_string())?;
+        }
+        // 最大值字段后紧跟一个空白字符，随后是像素数据
+        let [width, height, max_value] = fields;
+        if width == 0 || height == 0 || width > MAX_FRAME_SIDE || height > MAX_FRAME_SIDE {
+            return Err(format!("截图流帧头异常：{}x{}", width, height));
+        }
+        if max_value != 255 {
+            return Err(format!("截图流不支持的像素位深：{}", max_value));
+        }
+        Ok(Some((width, height, pos + 1)))
+    }
+
+    /// 追加数据并返回其中已完整的帧
+    pub(crate) fn push(&mut self, bytes: &[u8]) -> Result<Vec<RgbaImage>, String> {
+        self.buffer.extend_from_slice(bytes);
+        let mut frames = Vec::new();
+        while let Some((width, height, data_start)) = self.parse_header()? {
+            let frame_len = data_start + width as usize * height as usize * 3;
+            if self.buffer.len() < frame_len {
+                break;
+            }
+            let pixels = self.buffer[data_start..frame_len]
+                .chunks_exact(3)
+                .flat_map(|pixel| [pixel[0], pixel[1], pixel[2], 255])
+                .collect();
+            self.buffer.drain(..frame_len);
+            let image = RgbaImage::from_raw(width, height, pixels)
+                .ok_or_else(|| "截图流帧数据长度与尺寸不一致".to_string())?;
+            frames.push(image);
+        }
+        Ok(frames)
+    }
+}
+
+/// 最近一次输入操作在设备上执行完成的时间，截图流据此丢弃输入前采集的帧
+#[derive(Debug, Default)]
+pub struct InputClock {
+    last_input_at: Mutex<Option<Instant>>,
+}
+
+impl InputClock {
+    pub(crate) fn mark(&self) {
+        *self
+            .last_input_at
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(Instant::now());
+    }
+
+    fn last(&self) -> Option<Instant> {
+        *self
+            .last_input_at
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+struct StreamFrame {
+    image: RgbaImage,
+    /// 画面在设备上出现的时间（主机侧估计）：接收时刻减去编解码链路延迟
+    captured_at: Instant,
+}
+
+#[derive(Default)]
+struct StreamState {
+    latest: Option<StreamFrame>,
+    // 最近一次连接失败的原因，收到新帧后清空
+    last_error: Option<String>,
+    // 当前 screenrecord 会话在设备上的进程号
+    session_pid: Option<u32>,
+}
+
+struct StreamShared {
+    state: Mutex<StreamState>,
+    frame_ready: Condvar,
+    stopped: AtomicBool,
+}
+
+impl StreamShared {
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, StreamState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn publish(&self, image: RgbaImage, captured_at: Instant) {
+        let mut state = self.lock_state();
+        state.latest = Some(StreamFrame { image, captured_at });
+        state.last_error = None;
+        self.frame_ready.notify_all();
+    }
+
+    fn fail(&self, error: String) {
+        self.lock_state().last_error = Some(error);
+        self.frame_ready.notify_all();
+    }
+
+    /// 等待 `timeout` 或截图流停止，返回是否已停止
+    fn wait_stopped(&self, timeout: Duration) -> bool {
+        let state = self.lock_state();
+        let _ = self
+            .frame_ready
+            .wait_timeout_while(state, timeout, |_| !self.stopped.load(Ordering::Acquire))
+            .unwrap_or_else(PoisonError::into_inner);
+        self.stopped.load(Ordering::Acquire)
+    }
+}
+
+/// 会话结束的原因；解码器无法启动时重连也无济于事
+enum StreamFailure {
+    Interrupted(String),
+    DecoderUnavailable(String),
+}
+
+/// 把 shell 输出的 H.264 裸流转写给解码器，停止后返回错误以中断阻塞中的 shell 会话
+struct H264Sink<'a> {
+    shared: &'a StreamShared,
+    decoder_stdin: ChildStdin,
+    // 首行进程号读完之前为 Some，暂存不完整的首行
+    pid_line: Option<Vec<u8>>,
+}
+
+impl Write for H264Sink<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.shared.stopped.load(Ordering::Acquire) {
+            return Err(std::io::Error::other("截图流已停止"));
+        }
+        let mut video = buf;
+        if let Some(pid_line) = self.pid_line.as_mut() {
+            let Some(line_end) = buf.iter().position(|byte| *byte == b'\n') else {
+                pid_line.extend_from_slice(buf);
+                return Ok(buf.len());
+            };
+            pid_line.extend_from_slice(&buf[..line_end]);
+            self.shared.lock_state().session_pid = std::str::from_utf8(pid_line)
+                .ok()
+                .and_then(|pid| pid.trim().parse().ok());
+            self.pid_line = None;
+            video = &buf[line_end + 1..];
+        }
+        if video.is_empty() {
+            return Ok(buf.len());
+        }
+        self.decoder_stdin.write_all(video)?;
+        self.decoder_stdin.flush()?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.decoder_stdin.flush()
+    }
+}
+
+/// 读取解码器输出的 PPM 帧并发布，解码器退出或截图流停止后结束
+fn publish_decoded_frames(shared: &StreamShared, mut decoder_stdout: impl Read) {
+    let mut parser = PpmFrameParser::new();
+    let mut buf = vec![0u8; DECODER_READ_BUF_LEN];
+    while !shared.stopped.load(Ordering::Acquire) {
+        let len = match decoder_stdout.read(&mut buf) {
+            Ok(0) => break,
+            Ok(len) => len,
+            Err(error) => {
+                Log::warn(&format!("[ AdbFrameStream ] 读取解码器输出失败: {}", error));
+                break;
+            }
+        };
+        match parser.push(&buf[..len]) {
+            Ok(frames) => {
+                if let Some(image) = frames.into_iter().last() {
+                    let now = Instant::now();
+                    shared.publish(image, now.checked_sub(PIPELINE_LATENCY).unwrap_or(now));
+                }
+            }
+            Err(error) => {
+                Log::warn(&format!("[ AdbFrameStream ] 解码帧解析失败: {}", error));
+                break;
+            }
+        }
+    }
+}
+
+/// 常驻截图流：独立连接上持续接收 screenrecord 的 H.264 画面并在主机解码，内存中只保留最新一帧
+pub struct AdbFrameStream {
+    shared: Arc<StreamShared>,
+    config: ADBConnectConfig,
+    options: AdbStreamOptions,
+    input_clock: Arc<InputClock>,
+}
+
+impl AdbFrameStream {
+    pub fn start(
+        config: ADBConnectConfig,
+        options: AdbStreamOptions,
+        input_clock: Arc<InputClock>,
+    ) -> Self {
+        let shared = Arc::new(StreamShared {
+            state: Mutex::new(StreamState::default()),
+            frame_ready: Condvar::new(),
+            stopped: AtomicBool::new(false),
+        });
+        let worker = shared.clone();
+        let worker_config = config.clone();
+        std::thread::spawn(move || Self::run(&worker, &worker_config));
+        Self {
+            shared,
+            config,
+            options,
+            input_clock,
+        }
+    }
+
+    /// 截图流仍在运行且连接配置与参数都未变化时才可复用
+    pub fn serves(&self, config: &ADBConnectConfig, options: &AdbStreamOptions) -> bool {
+        !self.shared.stopped.load(Ordering::Acquire)
+            && &self.config == config
+            && &self.options == options
+    }
+
+    fn run(shared: &StreamShared, config: &ADBConnectConfig) {
+        Log::info("[ AdbFrameStream ] 截图流启动");
+        while !shared.stopped.load(Ordering::Acquire) {
+            match Self::stream_once(shared, config) {
+                Ok(()) => Log::debug("[ AdbFrameStream ] screenrecord 会话到期，重新连接"),
+                Err(StreamFailure::DecoderUnavailable(error)) => {
+                    // 保留错误且不再重连，取帧直接失败并由调用方回退为单次截图
+                    Log::warn(&format!("[ AdbFrameStream ] 截图流不可用: {}", error));
+                    shared.fail(error);
+                    break;
+                }
+                Err(StreamFailure::Interrupted(error)) => {
+                    Log::warn(&format!("[ AdbFrameStream ] 截图流中断: {}", error));
+                    shared.fail(error);
+                    if shared.wait_stopped(Duration::from_millis(STREAM_RECONNECT_DELAY_MS)) {
+                        break;
+                    }
+                }
+            }
+        }
+        Log::info("[ AdbFrameStream ] 截图流已停止");
+    }
+
+    fn stream_once(shared: &StreamShared, config: &ADBConnectConfig) -> Result<(), StreamFailure> {
+        let mut decoder = spawn_decoder().map_err(StreamFailure::DecoderUnavailable)?;
+        let (Some(decoder_stdin), Some(decoder_stdout)) =
+            (decoder.stdin.take(), decoder.stdout.take())
+        else {
+            let _ = decoder.kill();
+            return Err(StreamFailure::DecoderUnavailable(
+                "H.264解码器管道不可用".to_string(),
+            ));
+        };
+        let Some(mut device) = ADBExecutor::open_device(config) else {
+            let _ = decoder.kill();
+            let _ = decoder.wait();
+            return Err(StreamFailure::Interrupted("截图流连接设备失败".to_string()));
+        };
+
+        std::thread::scope(|scope| {
+            scope.spawn(|| publish_decoded_frames(shared, decoder_stdout));
+            let mut sink = H264Sink {
+                shared,
+                decoder_stdin,
+                pid_line: Some(Vec::new()),
+            };
+            let result = device
+                .shell_command(
+                    &screenrecord_cmd(),
+                    Some(&mut sink),
+                    Some(&mut std::io::sink()),
+                )
+                .map(|_| ())
+                .map_err(|error| StreamFailure::Interrupted(format!("截图流读取失败: {}", error)));
+            // 关闭输入让解码器输出剩余帧后退出，解码线程随之结束
+            drop(sink);
+            shared.lock_state().session_pid = None;
+            if shared.stopped.load(Ordering::Acquire) {
+                let _ = decoder.kill();
+            }
+            let _ = decoder.wait();
+            result
+        })
+    }
+
+    /// 取一帧在最近一次输入之后才出现的画面；没有这样的帧时等待新画面。
+    /// screenrecord 只在画面变化时出帧，输入后超过 `max_frame_age` 仍无新帧即视为画面未变化，直接返回最新帧。
+    /// 连接已失败且没有新帧时立即返回错误，避免每次截图都等满超时。
+    pub fn latest_frame(&self) -> Result<RgbaImage, String> {
+        let deadline = Instant::now() + self.options.frame_timeout;
+        let last_input_at = self.input_clock.last();
+        // 输入后的静止判定需要计入编解码链路延迟
+        let settled_at =
+            last_input_at.map(|input_at| input_at + self.options.max_frame_age + PIPELINE_LATENCY);
+        let mut state = self.shared.lock_state();
+        loop {
+            let now = Instant::now();
+            if let Some(frame) = state.latest.as_ref()
+                && (last_input_at.is_none_or(|input_at| frame.captured_at > input_at)
+                    || settled_at.is_none_or(|settled_at| now >= settled_at))
+            {
+                return Ok(frame.image.clone());
+            }
+            if let Some(error) = state.last_error.as_ref() {
+                return Err(error.clone());
+            }
+            if self.shared.stopped.load(Ordering::Acquire) {
+                return Err("截图流已停止".to_string());
+            }
+            if now >= deadline {
+                return Err(format!(
+                    "等待截图流新帧超时{}ms",
+                    self.options.frame_timeout.as_millis()
+                ));
+            }
+            let wake_at = match settled_at {
+                Some(settled_at) if state.latest.is_some() => settled_at.min(deadline),
+                _ => deadline,
+            };
+            state = self
+                .shared
+                .frame_ready
+                .wait_timeout(state, wake_at.saturating_duration_since(now))
+                .unwrap_or_else(PoisonError::into_inner)
+                .0;
+        }
+    }
+
+    /// 画面静止时 screenrecord 没有输出，阻塞中的会话要靠结束设备端进程才能立即退出
+    pub fn stop(&self) {
+        if self.shared.stopped.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        self.shared.frame_ready.notify_all();
+        let Some(pid) = self.shared.lock_state().session_pid else {
+            return;
+        };
+        let config = self.config.clone();
+        // 可能在异步运行时中被 Drop 调用，另起线程发送结束命令
+        std::thread::spawn(move || {
+            let Some(mut device) = ADBExecutor::open_device(&config) else {
+                return;
+            };
+            if let Err(error) =
+                device.shell_command(&format!("kill {}", pid), None, Some(&mut std::io::sink()))
+            {
+                Log::warn(&format!(
+                    "[ AdbFrameStream ] 结束screenrecord失败: {}",
+                    error
+                ));
+            }
+        });
+    }
+}
+
+impl Drop for AdbFrameStream {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ppm_frame(width: u32, height: u32, pixels: &[[u8; 3]]) -> Vec<u8> {
+        let mut bytes = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+        pixels
+            .iter()
+            .for_each(|pixel| bytes.extend_from_slice(pixel));
+        bytes
+    }
+
+    #[test]
+    fn parser_reassembles_frames_split_across_writes() {
+        let mut stream = ppm_frame(2, 1, &[[1, 2, 3], [4, 5, 6]]);
+        stream.extend(ppm_frame(1, 2, &[[9, 9, 9], [8, 8, 8]]));
+        let mut parser = PpmFrameParser::new();
+
+        let mut frames = Vec::new();
+        for chunk in stream.chunks(3) {
+            frames.extend(parser.push(chunk).unwrap());
+        }
+
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].as_raw(), &vec![1, 2, 3, 255, 4, 5, 6, 255]);
+        assert_eq!(frames[1].dimensions(), (1, 2));
+        assert_eq!(frames[1].as_raw(), &vec![9, 9, 9, 255, 8, 8, 8, 255]);
+    }
+
+    #[test]
+    fn parser_rejects_garbage_headers() {
+        assert!(PpmFrameParser::new().push(b"P5\n1 1\n255\n\0").is_err());
+        assert!(PpmFrameParser::new().push(b"P6\n0 1\n255\n").is_err());
+        assert!(PpmFrameParser::new().push(b"P6\n1 1\n65535\n").is_err());
+    }
+
+    #[test]
+    fn screenrecord_streams_raw_h264_to_stdout() {
+        assert_eq!(
+            screenrecord_cmd(),
+            "echo $$; exec screenrecord --output-format=h264 --time-limit 60 -"
+        );
+    }
+
+    #[test]
+    fn wait_stopped_wakes_up_on_stop() {
+        let stream = idle_stream(Arc::new(InputClock::default()), Duration::ZERO);
+        let shared = stream.shared.clone();
+        let waiter = std::thread::spawn(move || {
+            let started_at = Instant::now();
+            (
+                shared.wait_stopped(Duration::from_secs(30)),
+                started_at.elapsed(),
+            )
+        });
+        std::thread::sleep(Duration::from_millis(20));
+        stream.stop();
+
+        let (stopped, waited) = waiter.join().unwrap();
+        assert!(stopped);
+        assert!(waited < Duration::from_secs(5));
+    }
+
+    fn idle_stream(input_clock: Arc<InputClock>, max_frame_age: Duration) -> AdbFrameStream {
+        AdbFrameStream {
+            shared: Arc::new(StreamShared {
+                state: Mutex::new(StreamState::default()),
+                frame_ready: Condvar::new(),
+                stopped: AtomicBool::new(false),
+            }),
+            config: ADBConnectConfig::DirectTcp(None),
+            options: AdbStreamOptions {
+                max_frame_age,
+                frame_timeout: Duration::from_millis(50),
+            },
+            input_clock,
+        }
+    }
+
+    #[test]
+    fn frames_captured_before_the_last_input_are_not_served() {
+        let input_clock = Arc::new(InputClock::default());
+        let stream = idle_stream(input_clock.clone(), Duration::from_secs(5));
+        let before_click = RgbaImage::from_pixel(1, 1, image::Rgba([1, 1, 1, 255]));
+        let after_click = RgbaImage::from_pixel(1, 1, image::Rgba([2, 2, 2, 255]));
+
+        // 点击前已出现在屏幕上、点击后才解码完成的帧
+        let captured_at = Instant::now();
+        std::thread::sleep(Duration::from_millis(2));
+        input_clock.mark();
+        stream.shared.publish(before_click.clone(), captured_at);
+        assert!(
+            stream
+                .latest_frame()
+                .unwrap_err()
+                .contains("等待截图流新帧超时")
+        );
+
+        stream.shared.publish(after_click.clone(), Instant::now());
+        assert_eq!(stream.latest_frame().unwrap(), after_click);
+
+        let no_input = idle_stream(Arc::new(InputClock::default()), Duration::from_secs(5));
+        no_input.shared.publish(before_click.clone(), captured_at);
+        assert_eq!(no_input.latest_frame().unwrap(), before_click);
+    }
+
+    #[test]
+    fn unchanged_screen_is_served_once_the_input_has_settled() {
+        let input_clock = Arc::new(InputClock::default());
+        let mut stream = idle_stream(input_clock.clone(), Duration::ZERO);
+        stream.options.frame_timeout = Duration::from_secs(2);
+        let frame = RgbaImage::from_pixel(1, 1, image::Rgba([3, 3, 3, 255]));
+        stream.shared.publish(frame.clone(), Instant::now());
+        std::thread::sleep(Duration::from_millis(2));
+        input_clock.mark();
+
+        // 输入后一直没有新帧：等过链路延迟后视为画面未变化
+        let requested_at = Instant::now();
+        assert_eq!(stream.latest_frame().unwrap(), frame);
+        assert!(requested_at.elapsed() >= PIPELINE_LATENCY - Duration::from_millis(5));
+    }
+}
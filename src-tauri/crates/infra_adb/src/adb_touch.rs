@@ -0,0 +1,206 @@
+use crate::adb_command::{sleep_cmd, swipe_duration_cmd};
+use ad_kernel::Point;
+use std::fmt::Write;
+
+/// 多点触控的一帧，`delay` 为距上一帧的等待毫秒数，`points` 按手指（slot）顺序排列
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TouchFrame {
+    pub delay: u64,
+    pub points: Vec<Point<u16>>,
+}
+
+/// 多点触控设备节点与坐标范围。`getevent -p` 遍历输入设备较慢，同一设备连接内只探测一次
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TouchDevice {
+    pub path: String,
+    pub max_x: u32,
+    pub max_y: u32,
+}
+
+/// 在设备端查找多点触控设备及其坐标范围，输出 `节点 最大X 最大Y`。
+/// 只依赖 toybox 自带的 grep/sed，兼容没有 awk 的旧系统。
+pub(crate) const TOUCH_DEVICE_PROBE: &str = "for f in /dev/input/event*; do getevent -pl $f 2>/dev/null | grep -q ABS_MT_POSITION_X && { d=$f; break; }; done; \
+[ -n \"$d\" ] || { echo 'no multi-touch input device' >&2; exit 1; }; \
+mx=$(getevent -pl $d | grep ABS_MT_POSITION_X | sed 's/.*max \\([0-9]*\\).*/\\1/'); \
+my=$(getevent -pl $d | grep ABS_MT_POSITION_Y | sed 's/.*max \\([0-9]*\\).*/\\1/'); \
+echo \"$d $mx $my\"";
+
+/// 屏幕物理尺寸与方向可能随旋转变化，每次手势都重新读取
+const TOUCH_SCREEN_PROBE: &str = "s=$(wm size | grep Physical | sed 's/.*: //'); w=${s%x*}; h=${s#*x}; \
+o=$(dumpsys input | grep -m1 SurfaceOrientation | sed 's/.*: //'); \
+t(){ case \"$o\" in 1) a=$((w-$3)); b=$2;; 2) a=$((w-$2)); b=$((h-$3));; 3) a=$3; b=$((h-$2));; *) a=$2; b=$3;; esac; \
+sendevent $d 3 47 $1; sendevent $d 3 53 $((a*mx/w)); sendevent $d 3 54 $((b*my/h)); }";
+
+impl TouchDevice {
+    /// 解析 [`TOUCH_DEVICE_PROBE`] 的输出
+    pub(crate) fn parse(output: &str) -> Result<Self, String> {
+        let mut parts = output.split_whitespace();
+        let (Some(path), Some(max_x), Some(max_y)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(format!("未找到多点触控设备: {}", output.trim()));
+        };
+        let parse_max = |value: &str| {
+            value
+                .parse::<u32>()
+                .ok()
+                .filter(|value| *value > 0)
+                .ok_or_else(|| format!("触控设备坐标范围无效: {}", output.trim()))
+        };
+        Ok(Self {
+            path: path.to_string(),
+            max_x: parse_max(max_x)?,
+            max_y: parse_max(max_y)?,
+        })
+    }
+}
+
+/// 把触控帧转换为一条 `sendevent` 脚本（Linux 多点触控协议 B）。
+/// 屏幕坐标按当前方向换算到触控面板的自然方向，脚本放在子 shell 中以便合并进命令序列。
+pub(crate) fn touch_gesture_cmd(device: &TouchDevice, frames: &[TouchFrame]) -> String {
+    let mut script = format!(
+        "(d={}; mx={}; my={}; {}; ",
+        device.path, device.max_x, device.max_y, TOUCH_SCREEN_PROBE
+    );
+    let fingers = frames.first().map_or(0, |frame| frame.points.len());
+    for (index, frame) in frames.iter().enumerate() {
+        if index > 0 && frame.delay > 0 {
+            let _ = write!(script, "{}; ", sleep_cmd(frame.delay));
+        }
+        for (slot, point) in frame.points.iter().enumerate() {
+            let _ = write!(script, "t {} {} {}; ", slot, point.x, point.y);
+            if index == 0 {
+                let _ = write!(script, "sendevent $d 3 57 {}; ", slot);
+            }
+        }
+        if index == 0 {
+            script.push_str("sendevent $d 1 330 1; ");
+        }
+        script.push_str("sendevent $d 0 0 0; ");
+    }
+    for slot in 0..fingers {
+        let _ = write!(script, "sendevent $d 3 47 {}; sendevent $d 3 57 -1; ", slot);
+    }
+    script.push_str("sendevent $d 1 330 0; sendevent $d 0 0 0)");
+    script
+}
+
+/// 生成触控帧对应的 shell 命令。有触控设备时走 `sendevent`；
+/// 没有时单指路径退化为一次首尾相连、时长为各帧间隔之和的 `input swipe`，
+/// 避免逐段滑动时手指在段间抬起；多指手势无法模拟则返回错误
+pub(crate) fn touch_cmd(
+    device: Option<&TouchDevice>,
+    frames: &[TouchFrame],
+) -> Result<String, String> {
+    if let Some(device) = device {
+        return Ok(touch_gesture_cmd(device, frames));
+    }
+    if frames.is_empty() || frames.iter().any(|frame| frame.points.len() != 1) {
+        return Err("未探测到触控设备，无法执行多点手势".to_string());
+    }
+    let from = frames[0].points[0];
+    let to = frames[frames.len() - 1].points[0];
+    let duration = frames
+        .iter()
+        .skip(1)
+        .map(|frame| frame.delay)
+        .sum::<u64>()
+        .max(1);
+    Ok(swipe_duration_cmd(&from, &to, &duration))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn emits_down_moves_and_up_for_every_finger() {
+        let device = TouchDevice {
+            path: "/dev/input/event2".to_string(),
+            max_x: 1079,
+            max_y: 2399,
+        };
+        let cmd = touch_gesture_cmd(
+            &device,
+            &[
+                TouchFrame {
+                    delay: 0,
+                    points: vec![Point::new(10, 20), Point::new(30, 40)],
+                },
+                TouchFrame {
+                    delay: 50,
+                    points: vec![Point::new(5, 20), Point::new(35, 40)],
+                },
+            ],
+        );
+        assert!(cmd.starts_with("(d=/dev/input/event2; mx=1079; my=2399; s=$(wm size"));
+        let body = cmd.split("esac; ").nth(1).unwrap();
+        assert!(body.contains(
+            "t 0 10 20; sendevent $d 3 57 0; t 1 30 40; sendevent $d 3 57 1; sendevent $d 1 330 1; sendevent $d 0 0 0; "
+        ));
+        assert!(body.contains("sleep 0.050; t 0 5 20; t 1 35 40; sendevent $d 0 0 0; "));
+        assert!(cmd.ends_with(
+            "sendevent $d 3 47 0; sendevent $d 3 57 -1; sendevent $d 3 47 1; sendevent $d 3 57 -1; sendevent $d 1 330 0; sendevent $d 0 0 0)"
+        ));
+    }
+
+    #[test]
+    fn falls_back_to_one_input_swipe_for_single_pointer_without_device() {
+        let path = [
+            TouchFrame {
+                delay: 0,
+                points: vec![Point::new(10, 20)],
+            },
+            TouchFrame {
+                delay: 100,
+                points: vec![Point::new(30, 40)],
+            },
+            TouchFrame {
+                delay: 50,
+                points: vec![Point::new(50, 60)],
+            },
+        ];
+        assert_eq!(
+            touch_cmd(None, &path).unwrap(),
+            "input swipe 10,20 50,60 150"
+        );
+        let tap = [TouchFrame {
+            delay: 0,
+            points: vec![Point::new(10, 20)],
+        }];
+        assert_eq!(touch_cmd(None, &tap).unwrap(), "input swipe 10,20 10,20 1");
+        let pinch = [TouchFrame {
+            delay: 0,
+            points: vec![Point::new(10, 20), Point::new(30, 40)],
+        }];
+        assert!(touch_cmd(None, &pinch).is_err());
+        assert!(touch_cmd(None, &[]).is_err());
+    }
+
+    #[test]
+    fn keeps_curved_stroke_as_one_input_swipe_without_device() {
+        // 拟人化滑动的曲线轨迹：首帧按下，之后每 16ms 一个途经点
+        let stroke = (0..=24u16)
+            .map(|index| TouchFrame {
+                delay: if index == 0 { 0 } else { 16 },
+                points: vec![Point::new(100 + index * 10, 800 - index * 20 + index % 3)],
+            })
+            .collect::<Vec<_>>();
+        let cmd = touch_cmd(None, &stroke).unwrap();
+        assert!(!cmd.contains("&&"));
+        assert_eq!(cmd, "input swipe 100,800 340,320 384");
+    }
+
+    #[test]
+    fn parses_probe_output_and_rejects_missing_device() {
+        assert_eq!(
+            TouchDevice::parse("/dev/input/event2 1079 2399\n").unwrap(),
+            TouchDevice {
+                path: "/dev/input/event2".to_string(),
+                max_x: 1079,
+                max_y: 2399,
+            }
+        );
+        assert!(TouchDevice::parse("").is_err());
+        assert!(TouchDevice::parse("/dev/input/event2  ").is_err());
+    }
+}
@@ -3,10 +3,16 @@ mod adb_config;
 mod adb_context;
 mod adb_error;
 mod adb_executor;
+mod adb_keycode;
+mod adb_stream;
+mod adb_touch;
 
 pub use adb_command::ADBCommand;
 pub use adb_config::{ADBConnectConfig, AdbServeByIdentifier, AdbServerConfig};
 pub use adb_context::{ADBCtx, try_get_adb_ctx};
+pub use adb_keycode::android_keycode;
+pub use adb_stream::{AdbFrameStream, AdbStreamOptions, InputClock};
+pub use adb_touch::TouchFrame;
 
 pub(crate) struct Log;
 
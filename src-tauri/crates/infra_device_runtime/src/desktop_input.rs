@@ -0,0 +1,559 @@
+#[cfg(target_os = "linux")]
+mod x11;
+
+use ad_kernel::Point;
+use domain_device::{DeviceOperation, sample_gesture};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// 与 ADB 长按保持一致的按压时长
+const LONG_PRESS_MS: u64 = 1500;
+const CLICK_PRESS_MS: u64 = 50;
+/// 拖动时相邻两次移动之间的间隔，约等于 60 帧
+const DRAG_STEP_MS: u64 = 16;
+/// 双指缩放在桌面上换算为 Ctrl+滚轮，两指距离每变化这么多像素滚动一格
+const PINCH_PIXELS_PER_WHEEL_STEP: i32 = 40;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+}
+
+/// 桌面按键；名字解析见 [`DesktopKey::parse`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DesktopKey {
+    Escape,
+    Enter,
+    Tab,
+    Backspace,
+    Delete,
+    Space,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Left,
+    Up,
+    Right,
+    Down,
+    Shift,
+    Control,
+    Alt,
+    F(u8),
+    Char(char),
+}
+
+impl DesktopKey {
+    /// 按名字解析按键，忽略大小写并兼容 Android 的 `KEYCODE_` 前缀；单个字符按字符键处理
+    pub fn parse(name: &str) -> Option<Self> {
+        let name = name.trim();
+        let name = match name.get(..8) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("KEYCODE_") => &name[8..],
+            _ => name,
+        };
+        let mut chars = name.chars();
+        if let (Some(ch), None) = (chars.next(), chars.next()) {
+            return Some(Self::Char(ch.to_ascii_lowercase()));
+        }
+        let upper = name.to_ascii_uppercase();
+        let key = match upper.as_str() {
+            "ESC" | "ESCAPE" | "BACK" => Self::Escape,
+            "ENTER" | "RETURN" => Self::Enter,
+            "TAB" => Self::Tab,
+            "BACKSPACE" | "DEL" => Self::Backspace,
+            "DELETE" | "FORWARD_DEL" => Self::Delete,
+            "SPACE" => Self::Space,
+            "HOME" | "MOVE_HOME" => Self::Home,
+            "END" | "MOVE_END" => Self::End,
+            "PAGEUP" | "PAGE_UP" => Self::PageUp,
+            "PAGEDOWN" | "PAGE_DOWN" => Self::PageDown,
+            "LEFT" | "DPAD_LEFT" => Self::Left,
+            "UP" | "DPAD_UP" => Self::Up,
+            "RIGHT" | "DPAD_RIGHT" => Self::Right,
+            "DOWN" | "DPAD_DOWN" => Self::Down,
+            "SHIFT" => Self::Shift,
+            "CTRL" | "CONTROL" => Self::Control,
+            "ALT" => Self::Alt,
+            _ => {
+                let number = upper.strip_prefix('F')?.parse::<u8>().ok()?;
+                return (1..=24).contains(&number).then_some(Self::F(number));
+            }
+        };
+        Some(key)
+    }
+}
+
+/// 发往桌面输入后端的底层事件，坐标为屏幕绝对坐标
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DesktopInputEvent {
+    MoveTo {
+        x: i32,
+        y: i32,
+    },
+    Button {
+        button: MouseButton,
+        pressed: bool,
+    },
+    Key {
+        key: DesktopKey,
+        pressed: bool,
+    },
+    /// 滚轮格数，正数向上
+    Wheel(i32),
+    Text(String),
+    Wait(u64),
+}
+
+/// 桌面输入后端；`Wait` 由调用方处理，不会发给后端
+pub trait DesktopInputBackend: Send + Sync {
+    fn send(&self, event: &DesktopInputEvent) -> Result<(), String>;
+}
+
+/// 只记录事件的后端，供测试断言输入序列
+#[derive(Debug, Default)]
+pub struct RecordingInputBackend {
+    events: Mutex<Vec<DesktopInputEvent>>,
+}
+
+impl RecordingInputBackend {
+    pub fn events(&self) -> Vec<DesktopInputEvent> {
+        self.events.lock().unwrap().clone()
+    }
+}
+
+impl DesktopInputBackend for RecordingInputBackend {
+    fn send(&self, event: &DesktopInputEvent) -> Result<(), String> {
+        self.events.lock().unwrap().push(event.clone());
+        Ok(())
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+struct UnsupportedInputBackend;
+
+#[cfg(not(target_os = "linux"))]
+impl DesktopInputBackend for UnsupportedInputBackend {
+    fn send(&self, _event: &DesktopInputEvent) -> Result<(), String> {
+        Err("当前系统尚未实现桌面输入后端".to_string())
+    }
+}
+
+/// 当前系统的默认输入后端；Linux 使用 X11 XTest，首次发送时才连接显示服务
+pub fn platform_input_backend() -> Arc<dyn DesktopInputBackend> {
+    #[cfg(target_os = "linux")]
+    {
+        Arc::new(x11::X11InputBackend::default())
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        Arc::new(UnsupportedInputBackend)
+    }
+}
+
+/// 窗口内容区在屏幕上的位置，与截图裁剪使用同一组偏移
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContentArea {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl ContentArea {
+    fn to_screen(self, point: Point<u16>) -> Result<(i32, i32), String> {
+        if u32::from(point.x) >= self.width || u32::from(point.y) >= self.height {
+            return Err(format!(
+                "坐标({}, {})超出窗口内容区 {}x{}",
+                point.x, point.y, self.width, self.height
+            ));
+        }
+        Ok((self.x + i32::from(point.x), self.y + i32::from(point.y)))
+    }
+}
+
+fn press_at(events: &mut Vec<DesktopInputEvent>, (x, y): (i32, i32), hold_ms: u64) {
+    events.extend([
+        DesktopInputEvent::MoveTo { x, y },
+        DesktopInputEvent::Button {
+            button: MouseButton::Left,
+            pressed: true,
+        },
+        DesktopInputEvent::Wait(hold_ms),
+        DesktopInputEvent::Button {
+            button: MouseButton::Left,
+            pressed: false,
+        },
+    ]);
+}
+
+fn tap_key(events: &mut Vec<DesktopInputEvent>, key: DesktopKey) {
+    events.extend([
+        DesktopInputEvent::Key { key, pressed: true },
+        DesktopInputEvent::Key {
+            key,
+            pressed: false,
+        },
+    ]);
+}
+
+fn hold_key(events: &mut Vec<DesktopInputEvent>, key: DesktopKey, hold_ms: u64) {
+    events.extend([
+        DesktopInputEvent::Key { key, pressed: true },
+        DesktopInputEvent::Wait(hold_ms),
+        DesktopInputEvent::Key {
+            key,
+            pressed: false,
+        },
+    ]);
+}
+
+/// 把设备操作翻译为屏幕坐标下的输入事件
+pub fn plan_desktop_operation(
+    operation: &DeviceOperation,
+    area: ContentArea,
+) -> Result<Vec<DesktopInputEvent>, String> {
+    let mut events = Vec::new();
+    match operation {
+        DeviceOperation::Click(point) => {
+            press_at(&mut events, area.to_screen(*point)?, CLICK_PRESS_MS)
+        }
+        DeviceOperation::LongClick(point) => {
+            press_at(&mut events, area.to_screen(*point)?, LONG_PRESS_MS)
+        }
+        DeviceOperation::Swipe { from, to, duration } => {
+            let (from_x, from_y) = area.to_screen(*from)?;
+            let (to_x, to_y) = area.to_screen(*to)?;
+            let steps = (*duration / DRAG_STEP_MS).max(1);
+            events.extend([
+                DesktopInputEvent::MoveTo {
+                    x: from_x,
+                    y: from_y,
+                },
+                DesktopInputEvent::Button {
+                    button: MouseButton::Left,
+                    pressed: true,
+                },
+            ]);
+            for step in 1..=steps {
+                let ratio = step as f64 / steps as f64;
+                events.push(DesktopInputEvent::Wait(*duration / steps));
+                events.push(DesktopInputEvent::MoveTo {
+                    x: from_x + ((to_x - from_x) as f64 * ratio).round() as i32,
+                    y: from_y + ((to_y - from_y) as f64 * ratio).round() as i32,
+                });
+            }
+            events.push(DesktopInputEvent::Button {
+                button: MouseButton::Left,
+                pressed: false,
+            });
+        }
+        DeviceOperation::Gesture(strokes) => {
+            if strokes.len() != 1 {
+                return Err(format!("桌面设备只支持单指手势，收到 {} 指", strokes.len()));
+            }
+            let frames = sample_gesture(strokes, DRAG_STEP_MS);
+            let Some((first, rest)) = frames.split_first() else {
+                return Err("手势轨迹为空".to_string());
+            };
+            let (x, y) = area.to_screen(first.points[0])?;
+            events.extend([
+                DesktopInputEvent::MoveTo { x, y },
+                DesktopInputEvent::Button {
+                    button: MouseButton::Left,
+                    pressed: true,
+                },
+            ]);
+            for frame in rest {
+                let (x, y) = area.to_screen(frame.points[0])?;
+                events.push(DesktopInputEvent::Wait(frame.delay));
+                events.push(DesktopInputEvent::MoveTo { x, y });
+            }
+            events.push(DesktopInputEvent::Button {
+                button: MouseButton::Left,
+                pressed: false,
+            });
+        }
+        DeviceOperation::Pinch {
+            center,
+            start_distance,
+            end_distance,
+            duration,
+        } => {
+            let (x, y) = area.to_screen(*center)?;
+            let delta = i32::from(*end_distance) - i32::from(*start_distance);
+            let steps = (delta.abs() / PINCH_PIXELS_PER_WHEEL_STEP).max(1);
+            events.extend([
+                DesktopInputEvent::MoveTo { x, y },
+                DesktopInputEvent::Key {
+                    key: DesktopKey::Control,
+                    pressed: true,
+                },
+            ]);
+            for _ in 0..steps {
+                events.push(DesktopInputEvent::Wheel(delta.signum()));
+                events.push(DesktopInputEvent::Wait(*duration / steps as u64));
+            }
+            events.push(DesktopInputEvent::Key {
+                key: DesktopKey::Control,
+                pressed: false,
+            });
+        }
+        DeviceOperation::InputText(text) => events.push(DesktopInputEvent::Text(text.clone())),
+        DeviceOperation::KeyEvent {
+            code,
+            long_press,
+            repeat,
+        } => {
+            let key =
+                DesktopKey::parse(code).ok_or_else(|| format!("桌面设备不支持按键: {}", code))?;
+            for _ in 0..(*repeat).max(1) {
+                if *long_press {
+                    hold_key(&mut events, key, LONG_PRESS_MS);
+                } else {
+                    tap_key(&mut events, key);
+                }
+            }
+        }
+        DeviceOperation::Back => tap_key(&mut events, DesktopKey::Escape),
+        DeviceOperation::Delay(ms) => events.push(DesktopInputEvent::Wait(*ms)),
+        DeviceOperation::Home
+        | DeviceOperation::LaunchApp { .. }
+        | DeviceOperation::StopApp { .. }
+        | DeviceOperation::Reboot => {
+            return Err(format!("桌面设备不支持操作: {:?}", operation));
+        }
+    }
+    Ok(events)
+}
+
+/// 回放中已按下、尚未松开的鼠标键与按键；提前退出时按相反顺序松开，避免宿主桌面上的按键被一直按住
+struct HeldInputs<'a> {
+    backend: &'a dyn DesktopInputBackend,
+    releases: Vec<DesktopInputEvent>,
+}
+
+impl HeldInputs<'_> {
+    fn track(&mut self, event: &DesktopInputEvent) {
+        let release = match event {
+            DesktopInputEvent::Button { button, .. } => DesktopInputEvent::Button {
+                button: *button,
+                pressed: false,
+            },
+            DesktopInputEvent::Key { key, .. } => DesktopInputEvent::Key {
+                key: *key,
+                pressed: false,
+            },
+            _ => return,
+        };
+        if event == &release {
+            self.releases.retain(|held| held != &release);
+        } else if !self.releases.contains(&release) {
+            self.releases.push(release);
+        }
+    }
+}
+
+impl Drop for HeldInputs<'_> {
+    fn drop(&mut self) {
+        for release in self.releases.drain(..).rev() {
+            let _ = self.backend.send(&release);
+        }
+    }
+}
+
+/// 依次发送事件，`Wait` 在当前线程休眠；应在阻塞线程中调用。
+/// 中途失败时先松开已按下的鼠标键与按键再返回错误
+pub fn replay_desktop_events(
+    backend: &dyn DesktopInputBackend,
+    events: &[DesktopInputEvent],
+) -> Result<(), String> {
+    let mut held = HeldInputs {
+        backend,
+        releases: Vec::new(),
+    };
+    for event in events {
+        match event {
+            DesktopInputEvent::Wait(ms) => std::thread::sleep(Duration::from_millis(*ms)),
+            event => {
+                backend.send(event)?;
+                held.track(event);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AREA: ContentArea = ContentArea {
+        x: 100,
+        y: 240,
+        width: 800,
+        height: 600,
+    };
+
+    #[test]
+    fn maps_operations_into_the_window_content_area() {
+        let events =
+            plan_desktop_operation(&DeviceOperation::Click(Point::new(10, 20)), AREA).unwrap();
+        assert_eq!(events[0], DesktopInputEvent::MoveTo { x: 110, y: 260 });
+        assert!(plan_desktop_operation(&DeviceOperation::Click(Point::new(800, 0)), AREA).is_err());
+        assert!(plan_desktop_operation(&DeviceOperation::Home, AREA).is_err());
+        let key_events = plan_desktop_operation(
+            &DeviceOperation::KeyEvent {
+                code: "ESC".to_string(),
+                long_press: false,
+                repeat: 2,
+            },
+            AREA,
+        )
+        .unwrap();
+        assert_eq!(key_events.len(), 4);
+        assert!(
+            plan_desktop_operation(
+                &DeviceOperation::KeyEvent {
+                    code: "VOLUME_UP".to_string(),
+                    long_press: false,
+                    repeat: 1,
+                },
+                AREA,
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn drag_ends_at_the_target_and_replays_without_waits() {
+        let events = plan_desktop_operation(
+            &DeviceOperation::Swipe {
+                from: Point::new(0, 0),
+                to: Point::new(40, 20),
+                duration: 32,
+            },
+            AREA,
+        )
+        .unwrap();
+        let backend = RecordingInputBackend::default();
+        replay_desktop_events(&backend, &events).unwrap();
+        assert_eq!(
+            backend.events(),
+            vec![
+                DesktopInputEvent::MoveTo { x: 100, y: 240 },
+                DesktopInputEvent::Button {
+                    button: MouseButton::Left,
+                    pressed: true
+                },
+                DesktopInputEvent::MoveTo { x: 120, y: 250 },
+                DesktopInputEvent::MoveTo { x: 140, y: 260 },
+                DesktopInputEvent::Button {
+                    button: MouseButton::Left,
+                    pressed: false
+                },
+            ]
+        );
+    }
+
+    /// 发送到指定事件时失败，其余事件照常记录
+    struct FailingInputBackend {
+        fail_on: DesktopInputEvent,
+        recorder: RecordingInputBackend,
+    }
+
+    impl DesktopInputBackend for FailingInputBackend {
+        fn send(&self, event: &DesktopInputEvent) -> Result<(), String> {
+            if event == &self.fail_on {
+                return Err("注入失败".to_string());
+            }
+            self.recorder.send(event)
+        }
+    }
+
+    #[test]
+    fn failed_replay_releases_held_buttons_and_modifiers() {
+        let pinch = plan_desktop_operation(
+            &DeviceOperation::Pinch {
+                center: Point::new(400, 300),
+                start_distance: 100,
+                end_distance: 180,
+                duration: 0,
+            },
+            AREA,
+        )
+        .unwrap();
+        let backend = FailingInputBackend {
+            fail_on: DesktopInputEvent::Wheel(1),
+            recorder: RecordingInputBackend::default(),
+        };
+        assert!(replay_desktop_events(&backend, &pinch).is_err());
+        assert_eq!(
+            backend.recorder.events().last(),
+            Some(&DesktopInputEvent::Key {
+                key: DesktopKey::Control,
+                pressed: false
+            })
+        );
+
+        let drag = plan_desktop_operation(
+            &DeviceOperation::Swipe {
+                from: Point::new(0, 0),
+                to: Point::new(40, 20),
+                duration: 0,
+            },
+            AREA,
+        )
+        .unwrap();
+        let backend = FailingInputBackend {
+            fail_on: DesktopInputEvent::MoveTo { x: 140, y: 260 },
+            recorder: RecordingInputBackend::default(),
+        };
+        assert!(replay_desktop_events(&backend, &drag).is_err());
+        assert_eq!(
+            backend.recorder.events().last(),
+            Some(&DesktopInputEvent::Button {
+                button: MouseButton::Left,
+                pressed: false
+            })
+        );
+    }
+
+    #[test]
+    fn pinch_maps_to_ctrl_wheel_and_multi_finger_paths_are_rejected() {
+        let events = plan_desktop_operation(
+            &DeviceOperation::Pinch {
+                center: Point::new(400, 300),
+                start_distance: 100,
+                end_distance: 180,
+                duration: 200,
+            },
+            AREA,
+        )
+        .unwrap();
+        let wheels: Vec<_> = events
+            .iter()
+            .filter(|event| matches!(event, DesktopInputEvent::Wheel(_)))
+            .collect();
+        assert_eq!(wheels, vec![&DesktopInputEvent::Wheel(1); 2]);
+
+        let stroke = vec![domain_device::TouchWaypoint::new(Point::new(0, 0), 0)];
+        assert!(
+            plan_desktop_operation(
+                &DeviceOperation::Gesture(vec![stroke.clone(), stroke]),
+                AREA
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn parses_key_names() {
+        assert_eq!(DesktopKey::parse("esc"), Some(DesktopKey::Escape));
+        assert_eq!(DesktopKey::parse("F12"), Some(DesktopKey::F(12)));
+        assert_eq!(DesktopKey::parse("A"), Some(DesktopKey::Char('a')));
+        assert_eq!(DesktopKey::parse("KEYCODE_ENTER"), Some(DesktopKey::Enter));
+        assert_eq!(DesktopKey::parse("F30"), None);
+    }
+}
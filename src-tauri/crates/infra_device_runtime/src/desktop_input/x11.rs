@@ -0,0 +1,246 @@
+use super::{DesktopInputBackend, DesktopInputEvent, DesktopKey, MouseButton};
+use std::sync::Mutex;
+use x11rb::connection::Connection;
+use x11rb::protocol::xproto::{
+    BUTTON_PRESS_EVENT, BUTTON_RELEASE_EVENT, ConnectionExt as _, KEY_PRESS_EVENT,
+    KEY_RELEASE_EVENT, Keycode, Keysym, MOTION_NOTIFY_EVENT, Window,
+};
+use x11rb::protocol::xtest::ConnectionExt as _;
+use x11rb::rust_connection::RustConnection;
+
+const NO_SYMBOL: Keysym = 0;
+const XK_SHIFT_L: Keysym = 0xffe1;
+
+struct X11Session {
+    conn: RustConnection,
+    root: Window,
+    min_keycode: Keycode,
+    keysyms_per_keycode: u8,
+    keysyms: Vec<Keysym>,
+    /// 没有映射任何符号的键码，用来临时输入键盘布局里不存在的字符
+    scratch_keycode: Option<Keycode>,
+}
+
+/// 通过 XTest 扩展注入输入；连接在第一次发送事件时建立，断开后下次重连
+#[derive(Default)]
+pub(super) struct X11InputBackend {
+    session: Mutex<Option<X11Session>>,
+}
+
+fn x11_error(error: impl std::fmt::Display) -> String {
+    format!("X11 输入失败: {}", error)
+}
+
+fn key_keysym(key: DesktopKey) -> Keysym {
+    match key {
+        DesktopKey::Escape => 0xff1b,
+        DesktopKey::Enter => 0xff0d,
+        DesktopKey::Tab => 0xff09,
+        DesktopKey::Backspace => 0xff08,
+        DesktopKey::Delete => 0xffff,
+        DesktopKey::Space => 0x0020,
+        DesktopKey::Home => 0xff50,
+        DesktopKey::Left => 0xff51,
+        DesktopKey::Up => 0xff52,
+        DesktopKey::Right => 0xff53,
+        DesktopKey::Down => 0xff54,
+        DesktopKey::PageUp => 0xff55,
+        DesktopKey::PageDown => 0xff56,
+        DesktopKey::End => 0xff57,
+        DesktopKey::Shift => XK_SHIFT_L,
+        DesktopKey::Control => 0xffe3,
+        DesktopKey::Alt => 0xffe9,
+        DesktopKey::F(number) => 0xffbe + Keysym::from(number) - 1,
+        DesktopKey::Char(ch) => char_keysym(ch),
+    }
+}
+
+fn char_keysym(ch: char) -> Keysym {
+    match ch {
+        '\n' => 0xff0d,
+        '\t' => 0xff09,
+        // Latin-1 字符的 keysym 与码位相同，其余字符使用 Unicode keysym
+        ' '..='~' | '\u{a0}'..='\u{ff}' => ch as Keysym,
+        _ => 0x0100_0000 | ch as Keysym,
+    }
+}
+
+fn button_detail(button: MouseButton) -> u8 {
+    match button {
+        MouseButton::Left => 1,
+        MouseButton::Middle => 2,
+        MouseButton::Right => 3,
+    }
+}
+
+impl X11Session {
+    fn connect() -> Result<Self, String> {
+        let (conn, screen_num) = x11rb::connect(None).map_err(x11_error)?;
+        conn.xtest_get_version(2, 2)
+            .map_err(x11_error)?
+            .reply()
+            .map_err(|error| x11_error(format!("X 服务未启用 XTest 扩展: {}", error)))?;
+        let setup = conn.setup();
+        let root = setup.roots[screen_num].root;
+        let min_keycode = setup.min_keycode;
+        let count = setup.max_keycode - min_keycode + 1;
+        let mapping = conn
+            .get_keyboard_mapping(min_keycode, count)
+            .map_err(x11_error)?
+            .reply()
+            .map_err(x11_error)?;
+        let per_keycode = usize::from(mapping.keysyms_per_keycode.max(1));
+        let scratch_keycode = mapping
+            .keysyms
+            .chunks(per_keycode)
+            .rposition(|syms| syms.iter().all(|sym| *sym == NO_SYMBOL))
+            .map(|index| min_keycode + index as u8);
+        Ok(Self {
+            conn,
+            root,
+            min_keycode,
+            keysyms_per_keycode: mapping.keysyms_per_keycode,
+            keysyms: mapping.keysyms,
+            scratch_keycode,
+        })
+    }
+
+    fn fake(&self, type_: u8, detail: u8, x: i16, y: i16) -> Result<(), String> {
+        self.conn
+            .xtest_fake_input(type_, detail, x11rb::CURRENT_TIME, self.root, x, y, 0)
+            .map_err(x11_error)?;
+        Ok(())
+    }
+
+    /// 等待服务端处理完之前的请求，确保键码映射的修改已经生效
+    fn round_trip(&self) -> Result<(), String> {
+        self.conn
+            .get_input_focus()
+            .map_err(x11_error)?
+            .reply()
+            .map_err(x11_error)?;
+        Ok(())
+    }
+
+    /// 查找 keysym 所在的键码，以及是否需要按住 Shift（位于第二列）
+    fn lookup_keycode(&self, keysym: Keysym) -> Option<(Keycode, bool)> {
+        let per_keycode = usize::from(self.keysyms_per_keycode.max(1));
+        self.keysyms
+            .chunks(per_keycode)
+            .enumerate()
+            .find_map(|(index, syms)| {
+                let column = syms.iter().take(2).position(|sym| *sym == keysym)?;
+                Some((self.min_keycode + index as u8, column == 1))
+            })
+    }
+
+    fn send_keysym(&self, keysym: Keysym, pressed: bool) -> Result<(), String> {
+        let (keycode, _) = self
+            .lookup_keycode(keysym)
+            .ok_or_else(|| x11_error(format!("键盘布局中没有按键 0x{:x}", keysym)))?;
+        let type_ = if pressed {
+            KEY_PRESS_EVENT
+        } else {
+            KEY_RELEASE_EVENT
+        };
+        self.fake(type_, keycode, 0, 0)
+    }
+
+    fn tap_keycode(&self, keycode: Keycode, shift: bool) -> Result<(), String> {
+        if shift {
+            self.send_keysym(XK_SHIFT_L, true)?;
+        }
+        let result = self
+            .fake(KEY_PRESS_EVENT, keycode, 0, 0)
+            .and_then(|()| self.fake(KEY_RELEASE_EVENT, keycode, 0, 0));
+        if shift {
+            // 按键失败也要松开 Shift
+            return result.and(self.send_keysym(XK_SHIFT_L, false));
+        }
+        result
+    }
+
+    fn type_char(&self, ch: char) -> Result<(), String> {
+        let keysym = char_keysym(ch);
+        if let Some((keycode, shift)) = self.lookup_keycode(keysym) {
+            return self.tap_keycode(keycode, shift);
+        }
+        // 布局里没有的字符：临时把空闲键码映射到该字符，输入后还原
+        let keycode = self
+            .scratch_keycode
+            .ok_or_else(|| x11_error(format!("没有空闲键码可用于输入字符 {:?}", ch)))?;
+        let mut syms = vec![NO_SYMBOL; usize::from(self.keysyms_per_keycode.max(1))];
+        syms[0] = keysym;
+        if let Some(second) = syms.get_mut(1) {
+            *second = keysym;
+        }
+        self.conn
+            .change_keyboard_mapping(1, keycode, self.keysyms_per_keycode, &syms)
+            .map_err(x11_error)?;
+        let result = self
+            .round_trip()
+            .and_then(|()| self.tap_keycode(keycode, false))
+            .and_then(|()| self.round_trip());
+        // 无论输入是否成功都还原映射，避免键盘布局被改掉
+        syms.fill(NO_SYMBOL);
+        let restored = self
+            .conn
+            .change_keyboard_mapping(1, keycode, self.keysyms_per_keycode, &syms)
+            .map_err(x11_error)
+            .and_then(|_| self.conn.flush().map_err(x11_error));
+        result.and(restored)
+    }
+
+    fn send(&self, event: &DesktopInputEvent) -> Result<(), String> {
+        match event {
+            DesktopInputEvent::MoveTo { x, y } => {
+                let x = i16::try_from(*x).map_err(x11_error)?;
+                let y = i16::try_from(*y).map_err(x11_error)?;
+                self.fake(MOTION_NOTIFY_EVENT, 0, x, y)?;
+            }
+            DesktopInputEvent::Button { button, pressed } => {
+                let type_ = if *pressed {
+                    BUTTON_PRESS_EVENT
+                } else {
+                    BUTTON_RELEASE_EVENT
+                };
+                self.fake(type_, button_detail(*button), 0, 0)?;
+            }
+            DesktopInputEvent::Wheel(steps) => {
+                // X11 以按钮 4/5 表示滚轮向上/向下
+                let detail = if *steps > 0 { 4 } else { 5 };
+                for _ in 0..steps.unsigned_abs() {
+                    self.fake(BUTTON_PRESS_EVENT, detail, 0, 0)?;
+                    self.fake(BUTTON_RELEASE_EVENT, detail, 0, 0)?;
+                }
+            }
+            DesktopInputEvent::Key { key, pressed } => {
+                self.send_keysym(key_keysym(*key), *pressed)?
+            }
+            DesktopInputEvent::Text(text) => {
+                for ch in text.chars() {
+                    self.type_char(ch)?;
+                }
+            }
+            DesktopInputEvent::Wait(_) => return Ok(()),
+        }
+        self.conn.flush().map_err(x11_error)
+    }
+}
+
+impl DesktopInputBackend for X11InputBackend {
+    fn send(&self, event: &DesktopInputEvent) -> Result<(), String> {
+        let mut session = self.session.lock().unwrap();
+        if session.is_none() {
+            *session = Some(X11Session::connect()?);
+        }
+        let result = session
+            .as_ref()
+            .map_or(Ok(()), |session| session.send(event));
+        if result.is_err() {
+            // 连接可能已失效，下次发送时重新连接
+            *session = None;
+        }
+        result
+    }
+}
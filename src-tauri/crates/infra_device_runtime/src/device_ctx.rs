@@ -6,6 +6,7 @@ use domain_device::{
     WindowCaptureInterface as DeviceWindowCaptureInterface,
 };
 use image::RgbaImage;
+use infra_adb::AdbStreamOptions;
 use infra_window_capture::{
     CaptureMethod, WindowCaptureConfig, WindowCaptureInterface, WindowCaptureOffsets,
 };
@@ -13,6 +14,8 @@ use std::sync::{Arc, OnceLock};
 use tokio::sync::RwLock;
 
 static DEVICE_CTX: OnceLock<Arc<DeviceCtx>> = OnceLock::new();
+/// 截图流首帧包含建立连接的耗时，等待超过该时长就回退为单次 ADB 截图
+const ADB_STREAM_FRAME_TIMEOUT_SECS: u64 = 2;
 
 pub fn try_get_device_ctx() -> Option<Arc<DeviceCtx>> {
     DEVICE_CTX.get().cloned()
@@ -68,6 +71,16 @@ impl DeviceCtx {
         }
     }
 
+    fn build_stream_options(config: &DeviceConfig) -> Option<AdbStreamOptions> {
+        match &config.cap_method {
+            CapMethod::AdbStream { max_frame_age_ms } => Some(AdbStreamOptions {
+                max_frame_age: std::time::Duration::from_millis(u64::from(*max_frame_age_ms)),
+                frame_timeout: std::time::Duration::from_secs(ADB_STREAM_FRAME_TIMEOUT_SECS),
+            }),
+            _ => None,
+        }
+    }
+
     fn build_runtime(config: &DeviceConfig) -> DeviceRuntime {
         let (capture_method, window_capture_config) = match &config.cap_method {
             CapMethod::Window { .. } => (
@@ -75,13 +88,17 @@ impl DeviceCtx {
                 Self::build_window_capture_config(config),
             ),
             CapMethod::Adb => (CaptureMethod::Adb, None),
+            CapMethod::AdbStream { .. } => (CaptureMethod::AdbStream, None),
         };
         match config.platform {
             DevicePlatform::Android => DeviceRuntime::Android(AndroidDeviceRuntime::new(
                 capture_method,
                 window_capture_config,
+                Self::build_stream_options(config),
             )),
-            DevicePlatform::Desktop => DeviceRuntime::Desktop(DesktopDeviceRuntime::new()),
+            DevicePlatform::Desktop => {
+                DeviceRuntime::Desktop(DesktopDeviceRuntime::new(window_capture_config))
+            }
         }
     }
 
@@ -108,7 +125,9 @@ impl DeviceCtx {
     pub async fn apply_device_config(&self, next_config: DeviceConfig) {
         let runtime = Self::build_runtime(&next_config);
         *self.device_config.write().await = next_config;
-        *self.runtime.write().await = runtime;
+        let previous = std::mem::replace(&mut *self.runtime.write().await, runtime);
+        // 截图方式或截图流参数可能已变化，旧运行时的截图流需要立即停止
+        previous.release();
     }
 
     pub async fn execute_operations(&self, operations: &[DeviceOperation]) -> Result<(), String> {
@@ -1,10 +1,16 @@
 use crate::Log;
-use domain_device::{DeviceOperation, DevicePlatform};
+use crate::desktop_input::{
+    ContentArea, DesktopInputBackend, plan_desktop_operation, platform_input_backend,
+    replay_desktop_events,
+};
+use domain_device::{DeviceOperation, TouchWaypoint, pinch_strokes, sample_gesture};
 use image::RgbaImage;
-use infra_adb::{ADBCommand, try_get_adb_ctx};
+use infra_adb::{
+    ADBCommand, AdbFrameStream, AdbStreamOptions, TouchFrame, android_keycode, try_get_adb_ctx,
+};
 use infra_window_capture::{CaptureMethod, WindowCaptureConfig, WindowCaptureOffsets, WindowInfo};
-use std::sync::Arc;
-use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 #[derive(Clone)]
@@ -31,10 +37,7 @@ impl DeviceRuntime {
     pub(crate) async fn execute_operation(&self, operation: DeviceOperation) -> Result<(), String> {
         match self {
             Self::Android(runtime) => runtime.execute_operation(operation).await,
-            Self::Desktop(_) => Err(Self::unsupported_platform_message(
-                "Android 操作执行",
-                DevicePlatform::Desktop,
-            )),
+            Self::Desktop(runtime) => runtime.execute_operation(operation).await,
         }
     }
 
@@ -44,10 +47,7 @@ impl DeviceRuntime {
     ) -> Result<(), String> {
         match self {
             Self::Android(runtime) => runtime.execute_operations(operations).await,
-            Self::Desktop(_) => Err(Self::unsupported_platform_message(
-                "Android 动作序列执行",
-                DevicePlatform::Desktop,
-            )),
+            Self::Desktop(runtime) => runtime.execute_operations(operations).await,
         }
     }
 
@@ -57,15 +57,32 @@ impl DeviceRuntime {
     ) -> Result<(), String> {
         match self {
             Self::Android(runtime) => runtime.execute_sequence(operations).await,
-            Self::Desktop(_) => Err(Self::unsupported_platform_message(
-                "Android Sequence 执行",
-                DevicePlatform::Desktop,
-            )),
+            Self::Desktop(runtime) => runtime.execute_operations(operations).await,
         }
     }
 
-    fn unsupported_platform_message(action: &str, platform: DevicePlatform) -> String {
-        format!("当前平台 {:?} 尚未实现 {}", platform, action)
+    /// 运行时被新配置替换前释放常驻资源
+    pub(crate) fn release(&self) {
+        if let Self::Android(runtime) = self {
+            runtime.stop_frame_stream();
+        }
+    }
+}
+
+/// sendevent 每次调用都会启动进程，手势帧间隔不宜过小
+const ADB_GESTURE_FRAME_MS: u64 = 40;
+
+fn fallback_window_capture_config() -> WindowCaptureConfig {
+    WindowCaptureConfig {
+        title: String::new(),
+        interface: infra_window_capture::WindowCaptureInterface::Gdi,
+        frame_timeout: Duration::from_secs(10),
+        offsets: WindowCaptureOffsets {
+            left: 1,
+            top: 40,
+            right: 1,
+            bottom: 1,
+        },
     }
 }
 
@@ -75,31 +92,29 @@ pub(crate) struct AndroidDeviceRuntime {
     cap_tx: crossbeam_channel::Sender<Result<RgbaImage, String>>,
     cap_rx: crossbeam_channel::Receiver<Result<RgbaImage, String>>,
     window_info: Arc<WindowInfo>,
+    stream_options: Option<AdbStreamOptions>,
+    // 截图流在首次截图时按当前 ADB 连接配置启动，连接配置变化时重建，运行时替换时停止
+    frame_stream: Arc<Mutex<Option<Arc<AdbFrameStream>>>>,
+    released: Arc<AtomicBool>,
 }
 
 impl AndroidDeviceRuntime {
     pub(crate) fn new(
         capture_method: CaptureMethod,
         window_capture_config: Option<WindowCaptureConfig>,
+        stream_options: Option<AdbStreamOptions>,
     ) -> Self {
         let (tx, rx) = crossbeam_channel::bounded(1);
         Self {
             capture_method: Arc::new(AtomicU8::new(capture_method as u8)),
             cap_tx: tx,
             cap_rx: rx,
-            window_info: Arc::new(WindowInfo::init(window_capture_config.unwrap_or(
-                WindowCaptureConfig {
-                    title: String::new(),
-                    interface: infra_window_capture::WindowCaptureInterface::Gdi,
-                    frame_timeout: Duration::from_secs(10),
-                    offsets: WindowCaptureOffsets {
-                        left: 1,
-                        top: 40,
-                        right: 1,
-                        bottom: 1,
-                    },
-                },
-            ))),
+            window_info: Arc::new(WindowInfo::init(
+                window_capture_config.unwrap_or_else(fallback_window_capture_config),
+            )),
+            stream_options,
+            frame_stream: Arc::new(Mutex::new(None)),
+            released: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -173,11 +188,71 @@ impl AndroidDeviceRuntime {
         }
     }
 
+    async fn ensure_frame_stream(&self) -> Result<Arc<AdbFrameStream>, String> {
+        let options = self
+            .stream_options
+            .ok_or_else(|| "未配置截图流参数".to_string())?;
+        if self.released.load(Ordering::Acquire) {
+            return Err("设备运行时已被替换，不再启动截图流".to_string());
+        }
+        let adb_ctx = try_get_adb_ctx()?;
+        let config = adb_ctx.adb_config.lock().await.clone();
+        let mut guard = self
+            .frame_stream
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some(stream) = guard.as_ref() {
+            if stream.serves(&config, &options) {
+                return Ok(stream.clone());
+            }
+            Log::info("ADB连接配置已变化，重建截图流");
+            stream.stop();
+        }
+        let stream = Arc::new(AdbFrameStream::start(
+            config,
+            options,
+            adb_ctx.input_clock.clone(),
+        ));
+        *guard = Some(stream.clone());
+        Ok(stream)
+    }
+
+    /// 停止截图流；运行时被替换后，仍持有旧运行时副本的截图调用直接回退为单次截图
+    pub(crate) fn stop_frame_stream(&self) {
+        self.released.store(true, Ordering::Release);
+        if let Some(stream) = self
+            .frame_stream
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .take()
+        {
+            stream.stop();
+        }
+    }
+
+    /// 优先从截图流取最新帧，截图流不可用时回退为单次 ADB 截图
+    async fn capture_screen_via_adb_stream_result(&self) -> Result<RgbaImage, String> {
+        let frame = match self.ensure_frame_stream().await {
+            Ok(stream) => tokio::task::spawn_blocking(move || stream.latest_frame())
+                .await
+                .unwrap_or_else(|error| Err(format!("等待截图流任务异常：{}", error))),
+            Err(error) => Err(error),
+        };
+        match frame {
+            Ok(image) => Ok(image),
+            Err(error) => {
+                Log::warn(&format!("ADB截图流不可用，回退为单次ADB截图：{}", error));
+                self.capture_screen_via_adb_result().await
+            }
+        }
+    }
+
     fn estimate_reliable_sequence_timeout_ms(commands: &[ADBCommand]) -> u64 {
         let duration_sum_ms: u64 = commands
             .iter()
             .filter_map(|command| match command {
                 ADBCommand::Duration(ms) => Some(*ms),
+                ADBCommand::Touch(frames) => Some(frames.iter().map(|frame| frame.delay).sum()),
                 _ => None,
             })
             .sum();
@@ -191,6 +266,32 @@ impl AndroidDeviceRuntime {
         5_000 + duration_sum_ms + command_overhead_ms
     }
 
+    fn key_event_command(code: &str, long_press: bool, repeat: u32) -> Result<ADBCommand, String> {
+        let code =
+            android_keycode(code).ok_or_else(|| format!("无法识别的 Android 按键: {}", code))?;
+        Ok(ADBCommand::KeyEvent {
+            code,
+            long_press,
+            repeat,
+        })
+    }
+
+    fn gesture_command(strokes: &[Vec<TouchWaypoint>]) -> Result<ADBCommand, String> {
+        let frames = sample_gesture(strokes, ADB_GESTURE_FRAME_MS);
+        if frames.is_empty() {
+            return Err("手势轨迹为空".to_string());
+        }
+        Ok(ADBCommand::Touch(
+            frames
+                .into_iter()
+                .map(|frame| TouchFrame {
+                    delay: frame.delay,
+                    points: frame.points,
+                })
+                .collect(),
+        ))
+    }
+
     fn to_sequence_command(operation: &DeviceOperation) -> Result<ADBCommand, String> {
         match operation {
             DeviceOperation::Click(point) => Ok(ADBCommand::Click(*point)),
@@ -198,6 +299,18 @@ impl AndroidDeviceRuntime {
             DeviceOperation::Swipe { from, to, duration } => {
                 Ok(ADBCommand::SwipeWithDuration(*from, *to, *duration))
             }
+            DeviceOperation::Gesture(strokes) => Self::gesture_command(strokes),
+            DeviceOperation::Pinch {
+                center,
+                start_distance,
+                end_distance,
+                duration,
+            } => Self::gesture_command(&pinch_strokes(
+                *center,
+                *start_distance,
+                *end_distance,
+                *duration,
+            )),
             DeviceOperation::LaunchApp {
                 pkg_name,
                 activity_name,
@@ -207,6 +320,11 @@ impl AndroidDeviceRuntime {
             )),
             DeviceOperation::StopApp { pkg_name } => Ok(ADBCommand::StopApp(pkg_name.clone())),
             DeviceOperation::InputText(text) => Ok(ADBCommand::InputText(text.clone())),
+            DeviceOperation::KeyEvent {
+                code,
+                long_press,
+                repeat,
+            } => Self::key_event_command(code, *long_press, *repeat),
             DeviceOperation::Back => Ok(ADBCommand::Back),
             DeviceOperation::Home => Ok(ADBCommand::Home),
             DeviceOperation::Delay(ms) => Ok(ADBCommand::Duration(*ms)),
@@ -237,7 +355,7 @@ impl AndroidDeviceRuntime {
                 }
                 true
             }
-            2 => {
+            2 | 3 => {
                 Log::debug("验证adb截图设置...");
                 match try_get_adb_ctx() {
                     Ok(adb_ctx) => adb_ctx.validate_config(),
@@ -261,6 +379,7 @@ impl AndroidDeviceRuntime {
                 self.capture_screen_via_window_result().await
             }
             2 => self.capture_screen_via_adb_result().await,
+            3 => self.capture_screen_via_adb_stream_result().await,
             _ => Err("截图失败：不支持的截图方式！".to_string()),
         }
     }
@@ -272,6 +391,20 @@ impl AndroidDeviceRuntime {
             DeviceOperation::Swipe { from, to, duration } => {
                 self.send_command(ADBCommand::SwipeWithDuration(from, to, duration))
             }
+            DeviceOperation::Gesture(strokes) => {
+                self.send_command(Self::gesture_command(&strokes)?)
+            }
+            DeviceOperation::Pinch {
+                center,
+                start_distance,
+                end_distance,
+                duration,
+            } => self.send_command(Self::gesture_command(&pinch_strokes(
+                center,
+                start_distance,
+                end_distance,
+                duration,
+            ))?),
             DeviceOperation::LaunchApp {
                 pkg_name,
                 activity_name,
@@ -284,6 +417,11 @@ impl AndroidDeviceRuntime {
                     .await
             }
             DeviceOperation::InputText(text) => self.send_command(ADBCommand::InputText(text)),
+            DeviceOperation::KeyEvent {
+                code,
+                long_press,
+                repeat,
+            } => self.send_command(Self::key_event_command(&code, long_press, repeat)?),
             DeviceOperation::Back => self.send_command(ADBCommand::Back),
             DeviceOperation::Home => self.send_command(ADBCommand::Home),
             DeviceOperation::Reboot => self.send_await_result_command(ADBCommand::Reboot).await,
@@ -338,28 +476,72 @@ impl AndroidDeviceRuntime {
 }
 
 #[derive(Clone)]
-pub(crate) struct DesktopDeviceRuntime;
+pub(crate) struct DesktopDeviceRuntime {
+    window_info: Arc<WindowInfo>,
+    input: Arc<dyn DesktopInputBackend>,
+}
 
 impl DesktopDeviceRuntime {
-    pub(crate) fn new() -> Self {
-        Self
+    pub(crate) fn new(window_capture_config: Option<WindowCaptureConfig>) -> Self {
+        Self::with_input_backend(window_capture_config, platform_input_backend())
     }
 
-    fn unsupported(action: &str) -> String {
-        format!("DesktopDeviceRuntime 尚未实现: {}", action)
+    pub(crate) fn with_input_backend(
+        window_capture_config: Option<WindowCaptureConfig>,
+        input: Arc<dyn DesktopInputBackend>,
+    ) -> Self {
+        Self {
+            window_info: Arc::new(WindowInfo::init(
+                window_capture_config.unwrap_or_else(fallback_window_capture_config),
+            )),
+            input,
+        }
     }
 
     pub(crate) async fn valid_capture(&self) -> bool {
-        Log::warn("DesktopDeviceRuntime 尚未实现截图校验");
-        false
+        if !self.window_info.valid_capture().await {
+            Log::error("验证截图设置失败：未找到桌面目标窗口！");
+            return false;
+        }
+        true
     }
 
     pub(crate) async fn capture_screen_result(&self) -> Result<RgbaImage, String> {
-        Err("DesktopDeviceRuntime 尚未实现截图".to_string())
+        self.window_info.capture_image_result().await
+    }
+
+    async fn content_area(&self) -> Result<ContentArea, String> {
+        let (x, y, width, height) = self.window_info.content_bounds().await?;
+        Ok(ContentArea {
+            x,
+            y,
+            width,
+            height,
+        })
     }
 
-    #[allow(dead_code)]
-    pub(crate) async fn unsupported_action(&self, action: &str) -> Result<(), String> {
-        Err(Self::unsupported(action))
+    /// 每批操作前重新读取窗口位置，窗口被移动后坐标仍然对齐
+    pub(crate) async fn execute_operations(
+        &self,
+        operations: &[DeviceOperation],
+    ) -> Result<(), String> {
+        if operations.is_empty() {
+            return Ok(());
+        }
+        let area = self.content_area().await?;
+        let events = operations
+            .iter()
+            .map(|operation| plan_desktop_operation(operation, area))
+            .collect::<Result<Vec<_>, _>>()?
+            .concat();
+        let input = self.input.clone();
+        tokio::task::spawn_blocking(move || replay_desktop_events(input.as_ref(), &events))
+            .await
+            .map_err(|error| format!("桌面输入任务异常：{}", error))?
+    }
+
+    pub(crate) async fn execute_operation(&self, operation: DeviceOperation) -> Result<(), String> {
+        self.execute_operations(std::slice::from_ref(&operation))
+            .await
     }
 }
@@ -1,7 +1,12 @@
+mod desktop_input;
 mod device_ctx;
 mod device_launcher;
 mod device_runtime;
 
+pub use desktop_input::{
+    ContentArea, DesktopInputBackend, DesktopInputEvent, DesktopKey, MouseButton,
+    RecordingInputBackend, plan_desktop_operation, platform_input_backend, replay_desktop_events,
+};
 pub use device_ctx::{DeviceCtx, get_device_ctx, init_device_ctx, try_get_device_ctx};
 pub use device_launcher::{
     ensure_device_connection_with_progress, probe_device_config_connection_with_timeout,
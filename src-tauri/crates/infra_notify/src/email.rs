@@ -0,0 +1,42 @@
+use crate::NotificationChannel;
+use async_trait::async_trait;
+use domain_notification::{EmailConfig, NotificationEvent};
+use infra_mail::{EmailMessagePayload, send_email};
+
+/// 复用全局邮件配置发送通知
+pub struct EmailChannel {
+    config: EmailConfig,
+}
+
+impl EmailChannel {
+    pub fn new(config: EmailConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl NotificationChannel for EmailChannel {
+    fn label(&self) -> String {
+        "email".to_string()
+    }
+
+    async fn send(&self, event: &NotificationEvent) -> Result<(), String> {
+        let mut body = format!("设备: {}\n时间: {}\n", event.device_name, event.at);
+        if let Some(script_id) = event.script_id.as_deref() {
+            body.push_str(&format!("脚本: {script_id}\n"));
+        }
+        if let Some(task_id) = event.task_id.as_deref() {
+            body.push_str(&format!("任务: {task_id}\n"));
+        }
+        body.push('\n');
+        body.push_str(&event.message);
+        send_email(
+            &self.config,
+            &EmailMessagePayload {
+                subject: format!("AutoDaily {} - 设备 {}", event.title, event.device_name),
+                body,
+            },
+        )
+        .await
+    }
+}
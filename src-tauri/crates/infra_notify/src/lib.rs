@@ -0,0 +1,150 @@
+mod email;
+mod sink;
+mod webhook;
+
+use async_trait::async_trait;
+use domain_notification::{
+    EmailConfig, NotificationChannelConfig, NotificationEvent, NotificationTarget,
+};
+
+pub use email::EmailChannel;
+pub use sink::{CommandSinkChannel, FileSinkChannel};
+pub use webhook::WebhookChannel;
+
+/// 一个可发送运行事件的通知出口
+#[async_trait]
+pub trait NotificationChannel: Send + Sync {
+    fn label(&self) -> String;
+
+    async fn send(&self, event: &NotificationEvent) -> Result<(), String>;
+}
+
+/// 按目标配置创建通知出口；邮件出口使用设置中保存的 SMTP 配置，未开启邮件通知时返回错误
+pub fn build_channel(
+    config: &NotificationChannelConfig,
+    email_config: Option<&EmailConfig>,
+) -> Result<Box<dyn NotificationChannel>, String> {
+    Ok(match config {
+        NotificationChannelConfig::Webhook {
+            url,
+            method,
+            headers,
+            body_template,
+            timeout_seconds,
+        } => Box::new(WebhookChannel::new(
+            url.clone(),
+            method.clone(),
+            headers.clone(),
+            body_template.clone(),
+            *timeout_seconds,
+        )),
+        NotificationChannelConfig::File { path } => Box::new(FileSinkChannel::new(path.into())),
+        NotificationChannelConfig::Command {
+            program,
+            args,
+            timeout_seconds,
+        } => Box::new(CommandSinkChannel::new(
+            program.clone(),
+            args.clone(),
+            *timeout_seconds,
+        )),
+        NotificationChannelConfig::Email => match email_config {
+            Some(email_config) if email_config.email_notification => {
+                Box::new(EmailChannel::new(email_config.clone()))
+            }
+            _ => return Err("未在设置中开启邮件通知".to_string()),
+        },
+    })
+}
+
+/// 向订阅了该事件的所有目标发送通知，返回各失败目标的错误信息
+pub async fn dispatch_notification(
+    targets: &[NotificationTarget],
+    email_config: Option<&EmailConfig>,
+    event: &NotificationEvent,
+) -> Vec<String> {
+    let mut errors = Vec::new();
+    for target in targets.iter().filter(|target| target.accepts(event.kind)) {
+        let result = match build_channel(&target.channel, email_config) {
+            Ok(channel) => channel
+                .send(event)
+                .await
+                .map_err(|error| (channel.label(), error)),
+            Err(error) => Err(("email".to_string(), error)),
+        };
+        if let Err((label, error)) = result {
+            let name = if target.name.trim().is_empty() {
+                label
+            } else {
+                target.name.trim().to_string()
+            };
+            errors.push(format!("通知目标[{name}]发送失败: {error}"));
+        }
+    }
+    errors
+}
+
+#[cfg(test)]
+pub(crate) fn sample_event() -> NotificationEvent {
+    use domain_notification::NotificationEventKind;
+    NotificationEvent {
+        kind: NotificationEventKind::TaskFailed,
+        title: NotificationEventKind::TaskFailed.title().to_string(),
+        device_id: "device-1".to_string(),
+        device_name: "MuMu模拟器12".to_string(),
+        script_id: Some("script-1".to_string()),
+        task_id: Some("task-1".to_string()),
+        message: "找不到\"开始\"按钮".to_string(),
+        at: "2026-01-01T00:00:00Z".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use domain_notification::NotificationEventKind;
+
+    #[tokio::test]
+    async fn dispatch_skips_targets_not_subscribed_to_the_event() {
+        let target = NotificationTarget {
+            name: "完成通知".to_string(),
+            enabled: true,
+            events: vec![NotificationEventKind::ScriptCompleted],
+            channel: NotificationChannelConfig::File {
+                path: String::new(),
+            },
+        };
+        assert!(
+            dispatch_notification(&[target], None, &sample_event())
+                .await
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn dispatch_reports_failed_targets_by_name() {
+        let target = NotificationTarget {
+            name: "失败记录".to_string(),
+            enabled: true,
+            events: vec![NotificationEventKind::TaskFailed],
+            channel: NotificationChannelConfig::File {
+                path: String::new(),
+            },
+        };
+        let errors = dispatch_notification(&[target], None, &sample_event()).await;
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("通知目标[失败记录]发送失败"));
+    }
+
+    #[test]
+    fn email_target_requires_email_notification_in_settings() {
+        let mut email_config = EmailConfig::default();
+        assert!(build_channel(&NotificationChannelConfig::Email, None).is_err());
+        assert!(build_channel(&NotificationChannelConfig::Email, Some(&email_config)).is_err());
+
+        email_config.email_notification = true;
+        let channel =
+            build_channel(&NotificationChannelConfig::Email, Some(&email_config)).unwrap();
+        assert_eq!(channel.label(), "email");
+    }
+}
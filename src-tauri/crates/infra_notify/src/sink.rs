@@ -0,0 +1,199 @@
+use crate::NotificationChannel;
+use async_trait::async_trait;
+use domain_notification::NotificationEvent;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+
+/// 以 JSON Lines 形式把事件追加到本地文件
+pub struct FileSinkChannel {
+    path: PathBuf,
+}
+
+impl FileSinkChannel {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+#[async_trait]
+impl NotificationChannel for FileSinkChannel {
+    fn label(&self) -> String {
+        format!("file {}", self.path.display())
+    }
+
+    async fn send(&self, event: &NotificationEvent) -> Result<(), String> {
+        if self.path.as_os_str().is_empty() {
+            return Err("通知文件路径不能为空".to_string());
+        }
+        if let Some(parent) = self
+            .path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|error| format!("创建通知目录失败[{}]: {error}", parent.display()))?;
+        }
+        let mut line =
+            serde_json::to_string(event).map_err(|error| format!("序列化通知事件失败: {error}"))?;
+        line.push('\n');
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .map_err(|error| format!("打开通知文件失败[{}]: {error}", self.path.display()))?;
+        file.write_all(line.as_bytes())
+            .await
+            .map_err(|error| format!("写入通知文件失败[{}]: {error}", self.path.display()))?;
+        file.flush()
+            .await
+            .map_err(|error| format!("写入通知文件失败[{}]: {error}", self.path.display()))
+    }
+}
+
+/// 执行本地命令；参数中的占位符按事件替换，事件 JSON 写入标准输入
+pub struct CommandSinkChannel {
+    program: String,
+    args: Vec<String>,
+    timeout_seconds: u64,
+}
+
+impl CommandSinkChannel {
+    pub fn new(program: String, args: Vec<String>, timeout_seconds: u64) -> Self {
+        Self {
+            program,
+            args,
+            timeout_seconds,
+        }
+    }
+}
+
+#[async_trait]
+impl NotificationChannel for CommandSinkChannel {
+    fn label(&self) -> String {
+        format!("command {}", self.program.trim())
+    }
+
+    async fn send(&self, event: &NotificationEvent) -> Result<(), String> {
+        let program = self.program.trim();
+        if program.is_empty() {
+            return Err("通知命令不能为空".to_string());
+        }
+        let input =
+            serde_json::to_vec(event).map_err(|error| format!("序列化通知事件失败: {error}"))?;
+        let mut child = tokio::process::Command::new(program)
+            .args(
+                self.args
+                    .iter()
+                    .map(|arg| event.render_template(arg, str::to_string)),
+            )
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|error| format!("启动通知命令失败[{program}]: {error}"))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            // 命令不读取标准输入时写入可能失败，不视为通知失败
+            let _ = stdin.write_all(&input).await;
+        }
+        let output = tokio::time::timeout(
+            Duration::from_secs(self.timeout_seconds.max(1)),
+            child.wait_with_output(),
+        )
+        .await
+        .map_err(|_| format!("通知命令超时[{program}]"))?
+        .map_err(|error| format!("等待通知命令失败[{program}]: {error}"))?;
+        if !output.status.success() {
+            return Err(format!(
+                "通知命令退出异常[{program}]: {}; {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sample_event;
+    use serde_json::Value;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn temp_dir(label: &str) -> PathBuf {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        std::env::temp_dir().join(format!(
+            "autodaily-notify-{label}-{}-{unique}",
+            std::process::id()
+        ))
+    }
+
+    #[tokio::test]
+    async fn file_sink_appends_json_lines() {
+        let directory = temp_dir("file");
+        let path = directory.join("events.jsonl");
+        let channel = FileSinkChannel::new(path.clone());
+
+        channel.send(&sample_event()).await.unwrap();
+        channel.send(&sample_event()).await.unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let lines = content.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        let event = serde_json::from_str::<Value>(lines[0]).unwrap();
+        assert_eq!(event["deviceName"], "MuMu模拟器12");
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn command_sink_passes_placeholders_and_stdin() {
+        let directory = temp_dir("command");
+        std::fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("out.txt");
+        let channel = CommandSinkChannel::new(
+            "sh".to_string(),
+            vec![
+                "-c".to_string(),
+                "printf '%s\\n' \"$1\" > \"$2\"; cat >> \"$2\"".to_string(),
+                "notify".to_string(),
+                "{{kind}}:{{taskId}}".to_string(),
+                path.display().to_string(),
+            ],
+            5,
+        );
+
+        channel.send(&sample_event()).await.unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let (first, rest) = content.split_once('\n').unwrap();
+        assert_eq!(first, "taskFailed:task-1");
+        assert_eq!(
+            serde_json::from_str::<Value>(rest).unwrap()["kind"],
+            "taskFailed"
+        );
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn command_sink_reports_non_zero_exit() {
+        let channel = CommandSinkChannel::new(
+            "sh".to_string(),
+            vec!["-c".to_string(), "echo boom >&2; exit 3".to_string()],
+            5,
+        );
+
+        let error = channel.send(&sample_event()).await.unwrap_err();
+
+        assert!(error.contains("boom"), "{error}");
+    }
+}
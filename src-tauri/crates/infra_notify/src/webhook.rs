@@ -0,0 +1,203 @@
+use crate::NotificationChannel;
+use async_trait::async_trait;
+use domain_notification::NotificationEvent;
+use reqwest::header::{CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Client, Method};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+pub struct WebhookChannel {
+    url: String,
+    method: String,
+    headers: BTreeMap<String, String>,
+    body_template: String,
+    timeout_seconds: u64,
+}
+
+impl WebhookChannel {
+    pub fn new(
+        url: String,
+        method: String,
+        headers: BTreeMap<String, String>,
+        body_template: String,
+        timeout_seconds: u64,
+    ) -> Self {
+        Self {
+            url,
+            method,
+            headers,
+            body_template,
+            timeout_seconds,
+        }
+    }
+
+    /// 模板为空时发送完整事件 JSON；否则按 JSON 字符串规则转义后替换占位符
+    pub fn render_body(&self, event: &NotificationEvent) -> Result<String, String> {
+        if self.body_template.trim().is_empty() {
+            return serde_json::to_string(event)
+                .map_err(|error| format!("序列化通知事件失败: {error}"));
+        }
+        Ok(event.render_template(&self.body_template, json_escape))
+    }
+
+    fn header_map(&self) -> Result<HeaderMap, String> {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        for (name, value) in &self.headers {
+            let name = HeaderName::from_bytes(name.trim().as_bytes())
+                .map_err(|error| format!("Webhook 请求头名称[{name}]无效: {error}"))?;
+            let value = HeaderValue::from_str(value.trim())
+                .map_err(|error| format!("Webhook 请求头[{name}]取值无效: {error}"))?;
+            headers.insert(name, value);
+        }
+        Ok(headers)
+    }
+}
+
+#[async_trait]
+impl NotificationChannel for WebhookChannel {
+    fn label(&self) -> String {
+        format!("webhook {}", self.url.trim())
+    }
+
+    async fn send(&self, event: &NotificationEvent) -> Result<(), String> {
+        let url = self.url.trim();
+        if !(url.starts_with("http://") || url.starts_with("https://")) {
+            return Err(format!(
+                "Webhook 地址必须以 http:// 或 https:// 开头: {url}"
+            ));
+        }
+        let method = Method::from_bytes(self.method.trim().to_ascii_uppercase().as_bytes())
+            .map_err(|error| format!("Webhook 请求方法[{}]无效: {error}", self.method))?;
+        let client = Client::builder()
+            .timeout(Duration::from_secs(self.timeout_seconds.max(1)))
+            .build()
+            .map_err(|error| format!("创建 Webhook 客户端失败: {error}"))?;
+        let response = client
+            .request(method, url)
+            .headers(self.header_map()?)
+            .body(self.render_body(event)?)
+            .send()
+            .await
+            .map_err(|error| format!("Webhook 请求失败: {error}"))?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("Webhook 返回状态 {status}: {}", body.trim()));
+        }
+        Ok(())
+    }
+}
+
+fn json_escape(value: &str) -> String {
+    let quoted = serde_json::Value::String(value.to_string()).to_string();
+    quoted[1..quoted.len() - 1].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sample_event;
+    use serde_json::{Value, json};
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+
+    /// 本地 HTTP 替身：接收一次请求，返回给定状态码，并交出请求行、请求头和请求体
+    async fn serve_once(
+        status: u16,
+    ) -> (
+        String,
+        tokio::task::JoinHandle<(String, Vec<String>, String)>,
+    ) {
+        let _ = rustls::crypto::ring::default_provider().install_default();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let handle = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).await.unwrap();
+            let mut headers = Vec::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).await.unwrap();
+                let line = line.trim_end().to_string();
+                if line.is_empty() {
+                    break;
+                }
+                if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    content_length = value.trim().parse().unwrap();
+                }
+                headers.push(line);
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).await.unwrap();
+            let response =
+                format!("HTTP/1.1 {status} X\r\ncontent-length: 2\r\nconnection: close\r\n\r\nok");
+            reader
+                .get_mut()
+                .write_all(response.as_bytes())
+                .await
+                .unwrap();
+            (
+                request_line.trim_end().to_string(),
+                headers,
+                String::from_utf8(body).unwrap(),
+            )
+        });
+        (url, handle)
+    }
+
+    #[tokio::test]
+    async fn webhook_posts_rendered_json_template() {
+        let (url, server) = serve_once(200).await;
+        let channel = WebhookChannel::new(
+            url,
+            "post".to_string(),
+            BTreeMap::from([("X-Token".to_string(), "secret".to_string())]),
+            r#"{"text":"{{title}}: {{message}}","device":"{{deviceName}}"}"#.to_string(),
+            5,
+        );
+
+        channel.send(&sample_event()).await.unwrap();
+
+        let (request_line, headers, body) = server.await.unwrap();
+        assert_eq!(request_line, "POST /hook HTTP/1.1");
+        assert!(
+            headers
+                .iter()
+                .any(|header| header.eq_ignore_ascii_case("x-token: secret"))
+        );
+        assert_eq!(
+            serde_json::from_str::<Value>(&body).unwrap(),
+            json!({ "text": "任务执行失败: 找不到\"开始\"按钮", "device": "MuMu模拟器12" })
+        );
+    }
+
+    #[tokio::test]
+    async fn webhook_without_template_sends_event_json() {
+        let (url, server) = serve_once(200).await;
+        let channel =
+            WebhookChannel::new(url, "POST".to_string(), BTreeMap::new(), String::new(), 5);
+
+        channel.send(&sample_event()).await.unwrap();
+
+        let (_, _, body) = server.await.unwrap();
+        let body = serde_json::from_str::<Value>(&body).unwrap();
+        assert_eq!(body["kind"], json!("taskFailed"));
+        assert_eq!(body["taskId"], json!("task-1"));
+    }
+
+    #[tokio::test]
+    async fn webhook_reports_error_status() {
+        let (url, server) = serve_once(500).await;
+        let channel =
+            WebhookChannel::new(url, "POST".to_string(), BTreeMap::new(), String::new(), 5);
+
+        let error = channel.send(&sample_event()).await.unwrap_err();
+
+        server.await.unwrap();
+        assert!(error.contains("500"), "{error}");
+    }
+}
@@ -13,13 +13,14 @@ pub(crate) static POOL: OnceCell<SqlitePool> = OnceCell::const_new();
 pub use bootstrap::{get_pool, init_db_and_migrate_with_path, init_db_with_path};
 pub use devices::{delete_device_with_assignments, get_all_devices, get_device, save_device};
 pub use schedules::{
-    cleanup_expired_schedule_records, fail_active_assignment_schedules_by_device,
-    has_complete_assignment_schedule_batch, insert_assignment_schedule,
-    insert_assignment_schedule_batch, load_assignment_schedules_by_device,
-    load_next_planned_assignment_schedule, reactivate_retryable_planner_schedules_for_device,
-    stop_active_assignment_schedules_by_device, stop_planned_planner_schedules_by_device,
-    sync_active_planner_schedule_order_indices, sync_active_planner_schedules_from_queue,
-    update_assignment_schedule_status, update_assignment_schedule_status_by_dispatch_id,
+    build_run_summary_report, cleanup_expired_schedule_records,
+    fail_active_assignment_schedules_by_device, has_complete_assignment_schedule_batch,
+    insert_assignment_schedule, insert_assignment_schedule_batch,
+    load_assignment_schedules_by_device, load_next_planned_assignment_schedule,
+    reactivate_retryable_planner_schedules_for_device, stop_active_assignment_schedules_by_device,
+    stop_planned_planner_schedules_by_device, sync_active_planner_schedule_order_indices,
+    sync_active_planner_schedules_from_queue, update_assignment_schedule_status,
+    update_assignment_schedule_status_by_dispatch_id,
 };
 pub use schedules::{
     clear_schedules_by_device, clear_schedules_by_script, compact_assignment_indices,
@@ -30,11 +31,26 @@ pub use schedules::{
     list_time_templates, reorder_assignment_indices, save_assignment, save_template_value,
     save_time_template, upsert_template_value,
 };
+pub use schedules::{
+    delete_runtime_store_entries, load_runtime_store_entries, upsert_runtime_store_entry,
+};
+pub use schedules::{
+    delete_script_account, list_account_execution_schedules, list_script_accounts,
+    reorder_script_accounts, save_script_account,
+};
+pub use scripts::{
+    CreateScriptSnapshotInput, SCRIPT_SNAPSHOT_RETENTION, delete_script_snapshot,
+    get_latest_script_snapshot, get_script_snapshot, insert_script_snapshot, list_script_snapshots,
+    load_script_graph_snapshot,
+};
 pub use scripts::{
     CreateScriptTransferRecordInput, FinishScriptTransferRecordInput,
     clear_script_transfer_records, delete_script_transfer_record, finish_script_transfer_record,
     insert_script_transfer_record, list_script_transfer_records,
 };
+pub use scripts::{
+    ScriptMergeBase, get_script_merge_base, save_script_merge_base, save_script_upstream_merge,
+};
 pub use scripts::{
     batch_insert_script_related, delete_script_graph_in_transaction, save_cloned_script_graph,
     save_script_editor_graph,
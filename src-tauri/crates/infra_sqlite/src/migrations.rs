@@ -3,6 +3,9 @@ use sqlx::{Pool, Sqlite};
 
 const DEVICE_LOG_CONFIG_DEFAULTS_MIGRATION_VERSION: &str = "2026-06-10_device_log_config_defaults";
 const SCRIPT_TASK_DESCRIPTION_MIGRATION_VERSION: &str = "2026-07-01_script_task_description";
+const TIME_TEMPLATE_CALENDAR_MIGRATION_VERSION: &str = "2026-10-17_time_template_calendar";
+const EXECUTION_SCHEDULE_ACCOUNT_MIGRATION_VERSION: &str =
+    "2026-10-17_device_script_schedules_account";
 
 async fn has_migration(pool: &Pool<Sqlite>, version: &str) -> Result<bool, String> {
     let count =
@@ -51,6 +54,26 @@ async fn script_tasks_has_description_column(pool: &Pool<Sqlite>) -> Result<bool
     Ok(count > 0)
 }
 
+async fn time_templates_has_calendar_column(pool: &Pool<Sqlite>) -> Result<bool, String> {
+    let count = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM pragma_table_info('time_templates') WHERE name = 'calendar_json'",
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(count > 0)
+}
+
+async fn execution_schedules_has_account_column(pool: &Pool<Sqlite>) -> Result<bool, String> {
+    let count = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM pragma_table_info('device_script_schedules') WHERE name = 'account_id'",
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(count > 0)
+}
+
 pub(crate) async fn run_schema_migrations(pool: &Pool<Sqlite>) -> Result<(), String> {
     sqlx::query(SCHEMA_MIGRATIONS_TABLE_SQL)
         .execute(pool)
@@ -86,5 +109,33 @@ pub(crate) async fn run_schema_migrations(pool: &Pool<Sqlite>) -> Result<(), Str
     )
     .await?;
 
+    let calendar_column_exists = time_templates_has_calendar_column(pool).await?;
+    let calendar_migration: &[&str] = if calendar_column_exists {
+        &[]
+    } else {
+        &["ALTER TABLE time_templates ADD COLUMN calendar_json TEXT"]
+    };
+    apply_migration(
+        pool,
+        TIME_TEMPLATE_CALENDAR_MIGRATION_VERSION,
+        "add calendar_json column to time_templates",
+        calendar_migration,
+    )
+    .await?;
+
+    let account_column_exists = execution_schedules_has_account_column(pool).await?;
+    let account_migration: &[&str] = if account_column_exists {
+        &[]
+    } else {
+        &["ALTER TABLE device_script_schedules ADD COLUMN account_id TEXT"]
+    };
+    apply_migration(
+        pool,
+        EXECUTION_SCHEDULE_ACCOUNT_MIGRATION_VERSION,
+        "add account_id column to device_script_schedules",
+        account_migration,
+    )
+    .await?;
+
     Ok(())
 }
@@ -2,6 +2,9 @@ mod assignment_queue_repository;
 mod assignment_write_repository;
 mod device_schedule_record;
 mod planner_ledger_repository;
+mod run_summary_report;
+mod runtime_store_record;
+mod script_account_record;
 mod script_time_template_values_record;
 mod time_template_record;
 
@@ -10,8 +13,8 @@ pub use assignment_write_repository::save_assignment;
 pub use device_schedule_record::{
     clear_schedules_by_device, clear_schedules_by_script, delete_assignment,
     find_latest_success_schedule, has_active_assignment_schedules, insert_execution_schedule,
-    list_assigned_device_ids_by_script, list_assigned_device_ids_by_time_template,
-    list_assignments, list_execution_schedules,
+    list_account_execution_schedules, list_assigned_device_ids_by_script,
+    list_assigned_device_ids_by_time_template, list_assignments, list_execution_schedules,
 };
 pub use planner_ledger_repository::{
     cleanup_expired_schedule_records, fail_active_assignment_schedules_by_device,
@@ -22,6 +25,13 @@ pub use planner_ledger_repository::{
     sync_active_planner_schedule_order_indices, sync_active_planner_schedules_from_queue,
     update_assignment_schedule_status, update_assignment_schedule_status_by_dispatch_id,
 };
+pub use run_summary_report::build_run_summary_report;
+pub use runtime_store_record::{
+    delete_runtime_store_entries, load_runtime_store_entries, upsert_runtime_store_entry,
+};
+pub use script_account_record::{
+    delete_script_account, list_script_accounts, reorder_script_accounts, save_script_account,
+};
 pub use script_time_template_values_record::{
     delete_template_value, find_template_value, find_template_value_exact, save_template_value,
     upsert_template_value,
@@ -26,6 +26,7 @@ struct ExecutionScheduleRow {
     assignment_id: Option<String>,
     script_id: String,
     task_id: String,
+    account_id: Option<String>,
     dedup_scope_hash: String,
     task_cycle: String,
     status: String,
@@ -86,6 +87,7 @@ impl TryFrom<ExecutionScheduleRow> for ExecutionScheduleProfile {
             assignment_id: row.assignment_id.map(id).transpose()?,
             script_id: id(row.script_id)?,
             task_id: id(row.task_id)?,
+            account_id: row.account_id,
             dedup_scope_hash: row.dedup_scope_hash,
             task_cycle: row.task_cycle,
             status: row.status,
@@ -129,12 +131,21 @@ pub async fn list_assignments(device_id: DeviceId) -> Result<Vec<AssignmentProfi
 pub async fn list_execution_schedules(
     device_id: DeviceId,
 ) -> Result<Vec<ExecutionScheduleProfile>, String> {
-    sqlx::query_as::<_, ExecutionScheduleRow>("SELECT id, device_id, execution_id, assignment_id, script_id, task_id, dedup_scope_hash, task_cycle, status, started_at, completed_at, message FROM device_script_schedules WHERE device_id = ? ORDER BY started_at DESC").bind(device_id.to_string()).fetch_all(crate::bootstrap::get_pool()).await.map_err(|error| error.to_string())?.into_iter().map(TryInto::try_into).collect()
+    sqlx::query_as::<_, ExecutionScheduleRow>("SELECT id, device_id, execution_id, assignment_id, script_id, task_id, account_id, dedup_scope_hash, task_cycle, status, started_at, completed_at, message FROM device_script_schedules WHERE device_id = ? ORDER BY started_at DESC").bind(device_id.to_string()).fetch_all(crate::bootstrap::get_pool()).await.map_err(|error| error.to_string())?.into_iter().map(TryInto::try_into).collect()
+}
+
+/// 账号运行历史，按开始时间倒序返回最近的任务记录
+pub async fn list_account_execution_schedules(
+    script_id: ScriptId,
+    account_id: &str,
+    limit: u32,
+) -> Result<Vec<ExecutionScheduleProfile>, String> {
+    sqlx::query_as::<_, ExecutionScheduleRow>("SELECT id, device_id, execution_id, assignment_id, script_id, task_id, account_id, dedup_scope_hash, task_cycle, status, started_at, completed_at, message FROM device_script_schedules WHERE script_id = ? AND account_id = ? ORDER BY started_at DESC LIMIT ?").bind(script_id.to_string()).bind(account_id).bind(limit).fetch_all(crate::bootstrap::get_pool()).await.map_err(|error| error.to_string())?.into_iter().map(TryInto::try_into).collect()
 }
 
 pub async fn insert_execution_schedule(profile: &ExecutionScheduleProfile) -> Result<(), String> {
-    sqlx::query("INSERT INTO device_script_schedules (id, device_id, execution_id, assignment_id, script_id, task_id, dedup_scope_hash, task_cycle, status, started_at, completed_at, message) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)")
-        .bind(profile.id.to_string()).bind(profile.device_id.to_string()).bind(profile.execution_id.map(|id| id.to_string())).bind(profile.assignment_id.map(|id| id.to_string())).bind(profile.script_id.to_string()).bind(profile.task_id.to_string()).bind(&profile.dedup_scope_hash).bind(&profile.task_cycle).bind(&profile.status).bind(&profile.started_at).bind(&profile.completed_at).bind(&profile.message)
+    sqlx::query("INSERT INTO device_script_schedules (id, device_id, execution_id, assignment_id, script_id, task_id, account_id, dedup_scope_hash, task_cycle, status, started_at, completed_at, message) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)")
+        .bind(profile.id.to_string()).bind(profile.device_id.to_string()).bind(profile.execution_id.map(|id| id.to_string())).bind(profile.assignment_id.map(|id| id.to_string())).bind(profile.script_id.to_string()).bind(profile.task_id.to_string()).bind(&profile.account_id).bind(&profile.dedup_scope_hash).bind(&profile.task_cycle).bind(&profile.status).bind(&profile.started_at).bind(&profile.completed_at).bind(&profile.message)
         .execute(crate::bootstrap::get_pool()).await.map_err(|error| error.to_string())?;
     Ok(())
 }
@@ -144,7 +155,7 @@ pub async fn find_latest_success_schedule(
     dedup_scope_hash: &str,
     task_id: TaskId,
 ) -> Result<Option<ExecutionScheduleProfile>, String> {
-    sqlx::query_as::<_, ExecutionScheduleRow>("SELECT id, device_id, execution_id, assignment_id, script_id, task_id, dedup_scope_hash, task_cycle, status, started_at, completed_at, message FROM device_script_schedules WHERE assignment_id = ? AND dedup_scope_hash = ? AND task_id = ? AND status = ? ORDER BY COALESCE(completed_at, started_at) DESC, started_at DESC LIMIT 1")
+    sqlx::query_as::<_, ExecutionScheduleRow>("SELECT id, device_id, execution_id, assignment_id, script_id, task_id, account_id, dedup_scope_hash, task_cycle, status, started_at, completed_at, message FROM device_script_schedules WHERE assignment_id = ? AND dedup_scope_hash = ? AND task_id = ? AND status = ? ORDER BY COALESCE(completed_at, started_at) DESC, started_at DESC LIMIT 1")
         .bind(assignment_id.to_string()).bind(dedup_scope_hash).bind(task_id.to_string()).bind("Success")
         .fetch_optional(crate::bootstrap::get_pool()).await.map_err(|error| error.to_string())?
         .map(TryInto::try_into).transpose()
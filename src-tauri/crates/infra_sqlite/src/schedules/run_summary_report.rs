@@ -0,0 +1,312 @@
+use ad_kernel::ids::{DeviceId, ScriptId, TaskId, UuidV7};
+use domain_schedule::{
+    DeviceRunSummary, RunFailureEntry, RunSummaryCounts, RunSummaryReport, ScriptRunSummary,
+};
+use sqlx::FromRow;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+fn id(value: String) -> Result<UuidV7, String> {
+    Uuid::parse_str(&value)
+        .map(Into::into)
+        .map_err(|error| error.to_string())
+}
+
+#[derive(FromRow)]
+struct RunSummaryRow {
+    device_id: String,
+    device_name: Option<String>,
+    script_id: String,
+    script_name: Option<String>,
+    task_id: Option<String>,
+    task_name: Option<String>,
+    status: String,
+    started_at: Option<String>,
+    completed_at: Option<String>,
+    message: Option<String>,
+}
+
+/// 汇总用的单条运行记录；`task_id` 为空表示脚本级调度记录
+struct RunRecord {
+    device_id: DeviceId,
+    device_name: Option<String>,
+    script_id: ScriptId,
+    script_name: Option<String>,
+    task_id: Option<TaskId>,
+    task_name: Option<String>,
+    status: String,
+    started_at: Option<String>,
+    completed_at: Option<String>,
+    message: Option<String>,
+}
+
+impl TryFrom<RunSummaryRow> for RunRecord {
+    type Error = String;
+    fn try_from(row: RunSummaryRow) -> Result<Self, String> {
+        Ok(Self {
+            device_id: id(row.device_id)?,
+            device_name: row.device_name,
+            script_id: id(row.script_id)?,
+            script_name: row.script_name,
+            task_id: row.task_id.map(id).transpose()?,
+            task_name: row.task_name,
+            status: row.status,
+            started_at: row.started_at,
+            completed_at: row.completed_at,
+            message: row.message,
+        })
+    }
+}
+
+/// 统计 `[range_start, range_end)` 内各设备、各脚本的调度与任务结果
+pub async fn build_run_summary_report(
+    range_start: &str,
+    range_end: &str,
+) -> Result<RunSummaryReport, String> {
+    let pool = crate::bootstrap::get_pool();
+    let script_rows = sqlx::query_as::<_, RunSummaryRow>(
+        "SELECT s.device_id, json_extract(d.`data`, '$.deviceName') AS device_name,
+                s.script_id, json_extract(sc.`data`, '$.name') AS script_name,
+                NULL AS task_id, NULL AS task_name, s.status,
+                COALESCE(s.started_at, s.created_at) AS started_at, s.completed_at, s.message
+         FROM assignment_schedules s
+         LEFT JOIN devices d ON d.id = s.device_id
+         LEFT JOIN scripts sc ON sc.id = s.script_id
+         WHERE s.script_id IS NOT NULL
+           AND julianday(COALESCE(s.started_at, s.created_at)) >= julianday(?)
+           AND julianday(COALESCE(s.started_at, s.created_at)) < julianday(?)",
+    )
+    .bind(range_start)
+    .bind(range_end)
+    .fetch_all(pool)
+    .await
+    .map_err(|error| error.to_string())?;
+    let task_rows = sqlx::query_as::<_, RunSummaryRow>(
+        "SELECT s.device_id, json_extract(d.`data`, '$.deviceName') AS device_name,
+                s.script_id, json_extract(sc.`data`, '$.name') AS script_name,
+                s.task_id, t.`name` AS task_name, s.status,
+                s.started_at, s.completed_at, s.message
+         FROM device_script_schedules s
+         LEFT JOIN devices d ON d.id = s.device_id
+         LEFT JOIN scripts sc ON sc.id = s.script_id
+         LEFT JOIN script_tasks t ON t.id = s.task_id
+         WHERE julianday(s.started_at) >= julianday(?)
+           AND julianday(s.started_at) < julianday(?)",
+    )
+    .bind(range_start)
+    .bind(range_end)
+    .fetch_all(pool)
+    .await
+    .map_err(|error| error.to_string())?;
+
+    let records = script_rows
+        .into_iter()
+        .chain(task_rows)
+        .map(TryInto::try_into)
+        .collect::<Result<Vec<RunRecord>, String>>()?;
+    Ok(aggregate_run_summary(
+        range_start,
+        range_end,
+        chrono::Local::now().to_rfc3339(),
+        records,
+    ))
+}
+
+fn aggregate_run_summary(
+    range_start: &str,
+    range_end: &str,
+    generated_at: String,
+    records: Vec<RunRecord>,
+) -> RunSummaryReport {
+    let mut devices = BTreeMap::<DeviceId, DeviceRunSummary>::new();
+    let mut scripts = BTreeMap::<(DeviceId, ScriptId), ScriptRunSummary>::new();
+    for record in records {
+        let device = devices
+            .entry(record.device_id)
+            .or_insert_with(|| DeviceRunSummary {
+                device_id: record.device_id,
+                device_name: record.device_id.to_string(),
+                runs: RunSummaryCounts::default(),
+                tasks: RunSummaryCounts::default(),
+                scripts: Vec::new(),
+            });
+        if let Some(name) = record.device_name.filter(|name| !name.trim().is_empty()) {
+            device.device_name = name;
+        }
+        let script = scripts
+            .entry((record.device_id, record.script_id))
+            .or_insert_with(|| ScriptRunSummary {
+                script_id: record.script_id,
+                script_name: record.script_id.to_string(),
+                runs: RunSummaryCounts::default(),
+                tasks: RunSummaryCounts::default(),
+                run_duration_ms: 0,
+                task_duration_ms: 0,
+                failures: Vec::new(),
+            });
+        if let Some(name) = record.script_name.filter(|name| !name.trim().is_empty()) {
+            script.script_name = name;
+        }
+
+        let duration_ms = duration_ms(record.started_at.as_deref(), record.completed_at.as_deref());
+        if record.task_id.is_some() {
+            script.tasks.record(&record.status);
+            script.task_duration_ms += duration_ms;
+        } else {
+            script.runs.record(&record.status);
+            script.run_duration_ms += duration_ms;
+        }
+        if record.status.eq_ignore_ascii_case("failed") {
+            script.failures.push(RunFailureEntry {
+                task_name: record
+                    .task_id
+                    .map(|task_id| record.task_name.unwrap_or_else(|| task_id.to_string())),
+                task_id: record.task_id,
+                at: record
+                    .completed_at
+                    .or(record.started_at)
+                    .unwrap_or_default(),
+                message: record.message.unwrap_or_default(),
+            });
+        }
+    }
+
+    let mut runs = RunSummaryCounts::default();
+    let mut tasks = RunSummaryCounts::default();
+    for ((device_id, _), mut script) in scripts {
+        script
+            .failures
+            .sort_by(|left, right| left.at.cmp(&right.at));
+        if let Some(device) = devices.get_mut(&device_id) {
+            device.runs.merge(&script.runs);
+            device.tasks.merge(&script.tasks);
+            device.scripts.push(script);
+        }
+    }
+    let mut devices = devices.into_values().collect::<Vec<_>>();
+    for device in &mut devices {
+        device
+            .scripts
+            .sort_by(|left, right| left.script_name.cmp(&right.script_name));
+        runs.merge(&device.runs);
+        tasks.merge(&device.tasks);
+    }
+    devices.sort_by(|left, right| left.device_name.cmp(&right.device_name));
+
+    RunSummaryReport {
+        range_start: range_start.to_string(),
+        range_end: range_end.to_string(),
+        generated_at,
+        runs,
+        tasks,
+        devices,
+    }
+}
+
+fn duration_ms(started_at: Option<&str>, completed_at: Option<&str>) -> u64 {
+    let (Some(started_at), Some(completed_at)) = (started_at, completed_at) else {
+        return 0;
+    };
+    let (Ok(started_at), Ok(completed_at)) = (
+        chrono::DateTime::parse_from_rfc3339(started_at),
+        chrono::DateTime::parse_from_rfc3339(completed_at),
+    ) else {
+        return 0;
+    };
+    (completed_at - started_at).num_milliseconds().max(0) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(
+        device_id: DeviceId,
+        script_id: ScriptId,
+        task_id: Option<TaskId>,
+        status: &str,
+        started_at: &str,
+        completed_at: &str,
+    ) -> RunRecord {
+        RunRecord {
+            device_id,
+            device_name: Some("模拟器".to_string()),
+            script_id,
+            script_name: Some("日常".to_string()),
+            task_id,
+            task_name: task_id.map(|_| "签到".to_string()),
+            status: status.to_string(),
+            started_at: Some(started_at.to_string()),
+            completed_at: Some(completed_at.to_string()),
+            message: (status == "Failed").then(|| "找不到按钮".to_string()),
+        }
+    }
+
+    #[test]
+    fn aggregates_counts_durations_and_failures_per_script() {
+        let device_id = DeviceId::new_v7();
+        let script_id = ScriptId::new_v7();
+        let task_id = TaskId::new_v7();
+        let report = aggregate_run_summary(
+            "2026-01-01T00:00:00+08:00",
+            "2026-01-02T00:00:00+08:00",
+            String::new(),
+            vec![
+                record(
+                    device_id,
+                    script_id,
+                    None,
+                    "success",
+                    "2026-01-01T08:00:00+08:00",
+                    "2026-01-01T08:10:00+08:00",
+                ),
+                record(
+                    device_id,
+                    script_id,
+                    Some(task_id),
+                    "Success",
+                    "2026-01-01T08:00:00Z",
+                    "2026-01-01T08:00:30Z",
+                ),
+                record(
+                    device_id,
+                    script_id,
+                    Some(task_id),
+                    "Failed",
+                    "2026-01-01T09:00:00Z",
+                    "2026-01-01T09:00:10Z",
+                ),
+                record(
+                    device_id,
+                    script_id,
+                    Some(task_id),
+                    "Skipped",
+                    "2026-01-01T10:00:00Z",
+                    "2026-01-01T10:00:00Z",
+                ),
+            ],
+        );
+
+        assert_eq!(report.devices.len(), 1);
+        let device = &report.devices[0];
+        assert_eq!(device.device_name, "模拟器");
+        let script = &device.scripts[0];
+        assert_eq!(script.runs.success, 1);
+        assert_eq!(script.run_duration_ms, 600_000);
+        assert_eq!(
+            (
+                script.tasks.success,
+                script.tasks.failed,
+                script.tasks.skipped
+            ),
+            (1, 1, 1)
+        );
+        assert_eq!(script.task_duration_ms, 40_000);
+        assert_eq!(script.failures.len(), 1);
+        assert_eq!(script.failures[0].task_name.as_deref(), Some("签到"));
+        assert_eq!(script.failures[0].message, "找不到按钮");
+        assert_eq!(device.runs.total(), 1);
+        assert_eq!(device.tasks.total(), 3);
+        assert_eq!((report.runs.success, report.tasks.total()), (1, 3));
+    }
+}
@@ -0,0 +1,99 @@
+use ad_kernel::ids::{AccountId, DeviceId, ScriptId};
+use domain_script::RuntimeStoreEntry;
+use sqlx::{FromRow, QueryBuilder, Sqlite, SqlitePool, types::Json};
+
+#[derive(FromRow)]
+struct RuntimeStoreRow {
+    store_key: String,
+    value_json: Json<serde_json::Value>,
+    expires_at: Option<String>,
+    updated_at: String,
+}
+
+impl From<RuntimeStoreRow> for RuntimeStoreEntry {
+    fn from(row: RuntimeStoreRow) -> Self {
+        Self {
+            key: row.store_key,
+            value: row.value_json.0,
+            expires_at: row.expires_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+/// 无账号的运行按空字符串归入同一作用域
+fn account_scope(account_id: Option<&AccountId>) -> String {
+    account_id
+        .map(|value| value.trim().to_string())
+        .unwrap_or_default()
+}
+
+/// 测试与回放场景下子进程可能未初始化数据库，此时返回错误而不是 panic
+fn store_pool() -> Result<&'static SqlitePool, String> {
+    crate::POOL
+        .get()
+        .ok_or_else(|| "数据库连接池未初始化".to_string())
+}
+
+/// 读取作用域内全部未过期的键值，并顺带清理已过期的记录
+pub async fn load_runtime_store_entries(
+    device_id: DeviceId,
+    account_id: Option<&AccountId>,
+    script_id: ScriptId,
+) -> Result<Vec<RuntimeStoreEntry>, String> {
+    let entries = sqlx::query_as::<_, RuntimeStoreRow>("SELECT store_key, value_json, expires_at, updated_at FROM runtime_store WHERE device_id = ? AND account_id = ? AND script_id = ? ORDER BY store_key")
+        .bind(device_id.to_string()).bind(account_scope(account_id)).bind(script_id.to_string())
+        .fetch_all(store_pool()?).await.map_err(|error| error.to_string())?
+        .into_iter().map(RuntimeStoreEntry::from).collect::<Vec<_>>();
+    let now = chrono::Local::now();
+    let (expired, live): (Vec<_>, Vec<_>) =
+        entries.into_iter().partition(|entry| entry.is_expired(now));
+    let expired_keys = expired
+        .into_iter()
+        .map(|entry| entry.key)
+        .collect::<Vec<_>>();
+    delete_runtime_store_entries(device_id, account_id, script_id, &expired_keys).await?;
+    Ok(live)
+}
+
+pub async fn upsert_runtime_store_entry(
+    device_id: DeviceId,
+    account_id: Option<&AccountId>,
+    script_id: ScriptId,
+    entry: &RuntimeStoreEntry,
+) -> Result<(), String> {
+    sqlx::query("INSERT INTO runtime_store (device_id, account_id, script_id, store_key, value_json, expires_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?) ON CONFLICT(device_id, account_id, script_id, store_key) DO UPDATE SET value_json = excluded.value_json, expires_at = excluded.expires_at, updated_at = excluded.updated_at")
+        .bind(device_id.to_string()).bind(account_scope(account_id)).bind(script_id.to_string()).bind(&entry.key).bind(Json(&entry.value)).bind(&entry.expires_at).bind(&entry.updated_at)
+        .execute(store_pool()?).await.map_err(|error| format!("写入持久化存储失败: {error}"))?;
+    Ok(())
+}
+
+pub async fn delete_runtime_store_entries(
+    device_id: DeviceId,
+    account_id: Option<&AccountId>,
+    script_id: ScriptId,
+    keys: &[String],
+) -> Result<(), String> {
+    if keys.is_empty() {
+        return Ok(());
+    }
+    let mut query = QueryBuilder::<Sqlite>::new("DELETE FROM runtime_store WHERE device_id = ");
+    query
+        .push_bind(device_id.to_string())
+        .push(" AND account_id = ")
+        .push_bind(account_scope(account_id))
+        .push(" AND script_id = ")
+        .push_bind(script_id.to_string())
+        .push(" AND store_key IN (");
+    let mut separated = query.separated(", ");
+    for key in keys {
+        separated.push_bind(key);
+    }
+    separated.push_unseparated(")");
+    query
+        .build()
+        .execute(store_pool()?)
+        .await
+        .map_err(|error| format!("删除持久化存储失败: {error}"))?;
+    Ok(())
+}
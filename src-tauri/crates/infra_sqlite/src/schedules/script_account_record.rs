@@ -0,0 +1,95 @@
+use crate::bootstrap::get_pool;
+use ad_kernel::ids::{AccountId, ScriptId};
+use domain_schedule::ScriptAccountProfile;
+use sqlx::{FromRow, types::Json};
+use uuid::Uuid;
+
+#[derive(FromRow)]
+struct ScriptAccountRow {
+    id: String,
+    script_id: String,
+    device_id: Option<String>,
+    name: String,
+    enabled: bool,
+    sort_index: i64,
+    account_data: Json<serde_json::Value>,
+    created_at: String,
+    updated_at: String,
+}
+
+fn id(value: String) -> Result<ad_kernel::ids::UuidV7, String> {
+    Uuid::parse_str(&value)
+        .map(Into::into)
+        .map_err(|error| error.to_string())
+}
+
+impl TryFrom<ScriptAccountRow> for ScriptAccountProfile {
+    type Error = String;
+    fn try_from(row: ScriptAccountRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: row.id,
+            script_id: id(row.script_id)?,
+            device_id: row.device_id.map(id).transpose()?,
+            name: row.name,
+            enabled: row.enabled,
+            index: u32::try_from(row.sort_index).unwrap_or_default(),
+            account_data: row.account_data.0,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        })
+    }
+}
+
+pub async fn list_script_accounts(
+    script_id: ScriptId,
+) -> Result<Vec<ScriptAccountProfile>, String> {
+    sqlx::query_as::<_, ScriptAccountRow>("SELECT id, script_id, device_id, name, enabled, sort_index, account_data, created_at, updated_at FROM script_accounts WHERE script_id = ? ORDER BY sort_index ASC, name ASC").bind(script_id.to_string()).fetch_all(get_pool()).await.map_err(|error| error.to_string())?.into_iter().map(TryInto::try_into).collect()
+}
+
+pub async fn save_script_account(profile: &ScriptAccountProfile) -> Result<(), String> {
+    sqlx::query("INSERT INTO script_accounts (id, script_id, device_id, name, enabled, sort_index, account_data, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?) ON CONFLICT(script_id, id) DO UPDATE SET device_id = excluded.device_id, name = excluded.name, enabled = excluded.enabled, sort_index = excluded.sort_index, account_data = excluded.account_data, updated_at = excluded.updated_at")
+        .bind(&profile.id).bind(profile.script_id.to_string()).bind(profile.device_id.map(|value| value.to_string())).bind(&profile.name).bind(profile.enabled).bind(profile.index).bind(Json(&profile.account_data)).bind(&profile.created_at).bind(&profile.updated_at)
+        .execute(get_pool()).await.map_err(|error| format!("保存脚本账号失败: {error}"))?;
+    Ok(())
+}
+
+/// 删除账号时一并清理该账号专属的模板取值与持久化存储
+pub async fn delete_script_account(
+    script_id: ScriptId,
+    account_id: &AccountId,
+) -> Result<(), String> {
+    let mut tx = get_pool()
+        .begin()
+        .await
+        .map_err(|error| error.to_string())?;
+    for sql in [
+        "DELETE FROM script_time_template_values WHERE script_id = ? AND account_id = ?",
+        "DELETE FROM runtime_store WHERE script_id = ? AND account_id = ?",
+        "DELETE FROM script_accounts WHERE script_id = ? AND id = ?",
+    ] {
+        sqlx::query(sql)
+            .bind(script_id.to_string())
+            .bind(account_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|error| format!("删除脚本账号失败: {error}"))?;
+    }
+    tx.commit().await.map_err(|error| error.to_string())
+}
+
+pub async fn reorder_script_accounts(
+    script_id: ScriptId,
+    account_ids: &[AccountId],
+) -> Result<(), String> {
+    for (index, account_id) in account_ids.iter().enumerate() {
+        let index = u32::try_from(index).map_err(|_| "账号数量超过排序范围".to_string())?;
+        sqlx::query("UPDATE script_accounts SET sort_index = ? WHERE script_id = ? AND id = ?")
+            .bind(index)
+            .bind(script_id.to_string())
+            .bind(account_id)
+            .execute(get_pool())
+            .await
+            .map_err(|error| error.to_string())?;
+    }
+    Ok(())
+}
@@ -9,6 +9,7 @@ struct TimeTemplateRow {
     name: String,
     start_time: Option<String>,
     end_time: Option<String>,
+    calendar_json: Option<String>,
 }
 
 impl TryFrom<TimeTemplateRow> for TimeTemplateProfile {
@@ -19,13 +20,20 @@ impl TryFrom<TimeTemplateRow> for TimeTemplateProfile {
             name: row.name,
             start_time: row.start_time,
             end_time: row.end_time,
+            calendar: row
+                .calendar_json
+                .as_deref()
+                .map(serde_json::from_str)
+                .transpose()
+                .map_err(|error| format!("解析时间模板日历规则失败: {}", error))?
+                .unwrap_or_default(),
         })
     }
 }
 
 pub async fn list_time_templates() -> Result<Vec<TimeTemplateProfile>, String> {
     sqlx::query_as::<_, TimeTemplateRow>(
-        "SELECT id, name, start_time, end_time FROM time_templates ORDER BY name",
+        "SELECT id, name, start_time, end_time, calendar_json FROM time_templates ORDER BY name",
     )
     .fetch_all(crate::bootstrap::get_pool())
     .await
@@ -37,7 +45,7 @@ pub async fn list_time_templates() -> Result<Vec<TimeTemplateProfile>, String> {
 
 pub async fn get_time_template(id: TemplateId) -> Result<Option<TimeTemplateProfile>, String> {
     sqlx::query_as::<_, TimeTemplateRow>(
-        "SELECT id, name, start_time, end_time FROM time_templates WHERE id = ?",
+        "SELECT id, name, start_time, end_time, calendar_json FROM time_templates WHERE id = ?",
     )
     .bind(id.to_string())
     .fetch_optional(crate::bootstrap::get_pool())
@@ -48,8 +56,10 @@ pub async fn get_time_template(id: TemplateId) -> Result<Option<TimeTemplateProf
 }
 
 pub async fn save_time_template(template: &TimeTemplateProfile) -> Result<(), String> {
-    sqlx::query("INSERT INTO time_templates (id, name, start_time, end_time) VALUES (?, ?, ?, ?) ON CONFLICT(id) DO UPDATE SET name = excluded.name, start_time = excluded.start_time, end_time = excluded.end_time")
-        .bind(template.id.to_string()).bind(&template.name).bind(&template.start_time).bind(&template.end_time)
+    let calendar_json =
+        serde_json::to_string(&template.calendar).map_err(|error| error.to_string())?;
+    sqlx::query("INSERT INTO time_templates (id, name, start_time, end_time, calendar_json) VALUES (?, ?, ?, ?, ?) ON CONFLICT(id) DO UPDATE SET name = excluded.name, start_time = excluded.start_time, end_time = excluded.end_time, calendar_json = excluded.calendar_json")
+        .bind(template.id.to_string()).bind(&template.name).bind(&template.start_time).bind(&template.end_time).bind(calendar_json)
         .execute(crate::bootstrap::get_pool()).await.map_err(|error| error.to_string())?;
     Ok(())
 }
@@ -53,6 +53,60 @@ pub(crate) const SCRIPT_TRANSFER_RECORDS_TABLE_SQL: &str =
             FOREIGN KEY (local_script_id) REFERENCES scripts(id) ON DELETE CASCADE
         )";
 
+pub(crate) const SCRIPT_SNAPSHOTS_TABLE_SQL: &str = "CREATE TABLE IF NOT EXISTS script_snapshots (
+            id TEXT PRIMARY KEY,
+            script_id TEXT NOT NULL,
+            script_name TEXT NOT NULL DEFAULT '',
+            ver_name TEXT NOT NULL DEFAULT '',
+            author TEXT,
+            message TEXT NOT NULL DEFAULT '',
+            payload BLOB NOT NULL,
+            size_bytes INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (script_id) REFERENCES scripts(id) ON DELETE CASCADE
+        )";
+
+pub(crate) const SCRIPT_SNAPSHOTS_SCRIPT_INDEX_SQL: &str =
+    "CREATE INDEX IF NOT EXISTS idx_script_snapshots_script
+        ON script_snapshots (script_id, created_at)";
+
+pub(crate) const SCRIPT_MERGE_BASES_TABLE_SQL: &str =
+    "CREATE TABLE IF NOT EXISTS script_merge_bases (
+            script_id TEXT PRIMARY KEY,
+            cloud_id TEXT NOT NULL,
+            base_ver_num INTEGER NOT NULL DEFAULT 0,
+            payload BLOB NOT NULL,
+            updated_at TEXT NOT NULL,
+            FOREIGN KEY (script_id) REFERENCES scripts(id) ON DELETE CASCADE
+        )";
+
+pub(crate) const RUNTIME_STORE_TABLE_SQL: &str = "CREATE TABLE IF NOT EXISTS runtime_store (
+            device_id TEXT NOT NULL,
+            account_id TEXT NOT NULL DEFAULT '',
+            script_id TEXT NOT NULL,
+            store_key TEXT NOT NULL,
+            value_json JSON NOT NULL,
+            expires_at TEXT,
+            updated_at TEXT NOT NULL,
+            PRIMARY KEY (device_id, account_id, script_id, store_key),
+            FOREIGN KEY (device_id) REFERENCES devices(id) ON DELETE CASCADE
+        )";
+
+pub(crate) const SCRIPT_ACCOUNTS_TABLE_SQL: &str = "CREATE TABLE IF NOT EXISTS script_accounts (
+            id TEXT NOT NULL,
+            script_id TEXT NOT NULL,
+            device_id TEXT,
+            name TEXT NOT NULL,
+            enabled INTEGER NOT NULL DEFAULT 1,
+            sort_index INTEGER NOT NULL DEFAULT 0,
+            account_data JSON NOT NULL DEFAULT '{}',
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            PRIMARY KEY (script_id, id),
+            FOREIGN KEY (script_id) REFERENCES scripts(id) ON DELETE CASCADE,
+            FOREIGN KEY (device_id) REFERENCES devices(id) ON DELETE CASCADE
+        )";
+
 pub(crate) const SCRIPT_TRANSFER_RECORDS_SCOPE_INDEX_SQL: &str =
     "CREATE INDEX IF NOT EXISTS idx_script_transfer_records_scope
         ON script_transfer_records (
@@ -249,6 +303,7 @@ pub(crate) async fn create_base_tables(pool: &Pool<Sqlite>) -> Result<(), String
             assignment_id TEXT,
             script_id TEXT NOT NULL,
             task_id TEXT NOT NULL,
+            account_id TEXT,
             dedup_scope_hash TEXT NOT NULL DEFAULT '',
             task_cycle TEXT NOT NULL DEFAULT 'everyRun',
             status TEXT NOT NULL DEFAULT 'success',
@@ -312,7 +367,8 @@ pub(crate) async fn create_base_tables(pool: &Pool<Sqlite>) -> Result<(), String
             id TEXT PRIMARY KEY,
             name TEXT NOT NULL,
             start_time TEXT,
-            end_time TEXT
+            end_time TEXT,
+            calendar_json TEXT
         )",
     )
     .execute(pool)
@@ -335,6 +391,26 @@ pub(crate) async fn create_base_tables(pool: &Pool<Sqlite>) -> Result<(), String
         .execute(pool)
         .await
         .map_err(|e| e.to_string())?;
+    sqlx::query(SCRIPT_SNAPSHOTS_TABLE_SQL)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    sqlx::query(SCRIPT_SNAPSHOTS_SCRIPT_INDEX_SQL)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    sqlx::query(SCRIPT_MERGE_BASES_TABLE_SQL)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    sqlx::query(RUNTIME_STORE_TABLE_SQL)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    sqlx::query(SCRIPT_ACCOUNTS_TABLE_SQL)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
 
     Ok(())
 }
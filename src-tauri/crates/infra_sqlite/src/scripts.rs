@@ -1,15 +1,20 @@
 mod graph_write_repository;
+mod merge_base_repository;
 pub(crate) mod policy_record;
 mod script_access_repository;
 mod script_graph_repository;
 mod script_repository;
 pub(crate) mod script_task_record;
+mod snapshot_repository;
 mod transfer_repository;
 
 pub use graph_write_repository::{
     batch_insert_script_related, delete_script_graph_in_transaction, save_cloned_script_graph,
     save_script_editor_graph,
 };
+pub use merge_base_repository::{
+    ScriptMergeBase, get_script_merge_base, save_script_merge_base, save_script_upstream_merge,
+};
 pub use script_access_repository::{
     ensure_existing_script_editable, ensure_stored_script_editable,
 };
@@ -22,6 +27,11 @@ pub use script_graph_repository::{
 pub use script_repository::{
     delete_script, find_dev_script_by_cloud_id, get_script, list_scripts, save_script,
 };
+pub use snapshot_repository::{
+    CreateScriptSnapshotInput, SCRIPT_SNAPSHOT_RETENTION, delete_script_snapshot,
+    get_latest_script_snapshot, get_script_snapshot, insert_script_snapshot, list_script_snapshots,
+    load_script_graph_snapshot,
+};
 pub use transfer_repository::{
     CreateScriptTransferRecordInput, FinishScriptTransferRecordInput,
     clear_script_transfer_records, delete_script_transfer_record, finish_script_transfer_record,
@@ -213,17 +213,37 @@ pub async fn save_script_editor_graph(
     set_groups: &[PolicySetGroupLink],
     tasks: &[ScriptTaskProfile],
 ) -> Result<(), String> {
-    let script_id = script.id.to_string();
     let mut tx = get_pool()
         .begin()
         .await
         .map_err(|error| error.to_string())?;
+    clear_script_related_in_transaction(&mut tx, script.id).await?;
+    batch_insert_script_related(
+        &mut tx,
+        script,
+        policies,
+        policy_groups,
+        policy_sets,
+        group_policies,
+        set_groups,
+        tasks,
+    )
+    .await?;
+    tx.commit().await.map_err(|error| error.to_string())
+}
+
+/// 在给定事务中清空脚本原有的任务与策略，保留脚本本身，随后由 `batch_insert_script_related` 重新写入
+pub(crate) async fn clear_script_related_in_transaction(
+    tx: &mut Transaction<'_, Sqlite>,
+    script_id: ScriptId,
+) -> Result<(), String> {
+    let script_id = script_id.to_string();
 
     sqlx::query(
         "DELETE FROM group_policies WHERE group_id IN (SELECT id FROM policy_groups WHERE script_id = ?)",
     )
     .bind(&script_id)
-    .execute(&mut *tx)
+    .execute(&mut **tx)
     .await
     .map_err(|error| format!("清理 group_policies 失败: {error}"))?;
 
@@ -231,30 +251,18 @@ pub async fn save_script_editor_graph(
         "DELETE FROM set_groups WHERE set_id IN (SELECT id FROM policy_sets WHERE script_id = ?)",
     )
     .bind(&script_id)
-    .execute(&mut *tx)
+    .execute(&mut **tx)
     .await
     .map_err(|error| format!("清理 set_groups 失败: {error}"))?;
 
     for table in ["script_tasks", "policies", "policy_groups", "policy_sets"] {
         sqlx::query(&format!("DELETE FROM {table} WHERE script_id = ?"))
             .bind(&script_id)
-            .execute(&mut *tx)
+            .execute(&mut **tx)
             .await
             .map_err(|error| format!("清理 {table} 失败: {error}"))?;
     }
-
-    batch_insert_script_related(
-        &mut tx,
-        script,
-        policies,
-        policy_groups,
-        policy_sets,
-        group_policies,
-        set_groups,
-        tasks,
-    )
-    .await?;
-    tx.commit().await.map_err(|error| error.to_string())
+    Ok(())
 }
 
 pub async fn save_cloned_script_graph(
@@ -0,0 +1,123 @@
+use super::graph_write_repository::{
+    batch_insert_script_related, clear_script_related_in_transaction,
+};
+use super::snapshot_repository::{decode_snapshot, encode_snapshot};
+use ad_kernel::ids::ScriptId;
+use domain_script::ScriptGraphSnapshot;
+use sqlx::{FromRow, Sqlite};
+
+/// 克隆脚本的三方合并基线：上次同步时的上游脚本图（本地 ID 空间）
+#[derive(Debug)]
+pub struct ScriptMergeBase {
+    pub script_id: ScriptId,
+    pub cloud_id: ScriptId,
+    pub base_ver_num: u32,
+    pub snapshot: ScriptGraphSnapshot,
+    pub updated_at: String,
+}
+
+#[derive(FromRow)]
+struct ScriptMergeBaseRow {
+    cloud_id: String,
+    base_ver_num: i64,
+    payload: Vec<u8>,
+    updated_at: String,
+}
+
+pub async fn save_script_merge_base(
+    script_id: ScriptId,
+    cloud_id: ScriptId,
+    base_ver_num: u32,
+    snapshot: &ScriptGraphSnapshot,
+    updated_at: String,
+) -> Result<(), String> {
+    upsert_script_merge_base(
+        crate::bootstrap::get_pool(),
+        script_id,
+        cloud_id,
+        base_ver_num,
+        encode_snapshot(snapshot)?,
+        updated_at,
+    )
+    .await
+}
+
+/// 合并上游后的脚本图与新的合并基线在同一事务中写入，任一步失败都不会只留下一半
+pub async fn save_script_upstream_merge(
+    merged: &ScriptGraphSnapshot,
+    base: &ScriptMergeBase,
+) -> Result<(), String> {
+    let payload = encode_snapshot(&base.snapshot)?;
+    let mut tx = crate::bootstrap::get_pool()
+        .begin()
+        .await
+        .map_err(|error| error.to_string())?;
+    clear_script_related_in_transaction(&mut tx, merged.script.id).await?;
+    batch_insert_script_related(
+        &mut tx,
+        &merged.script,
+        &merged.policies,
+        &merged.policy_groups,
+        &merged.policy_sets,
+        &merged.group_policies,
+        &merged.set_groups,
+        &merged.tasks,
+    )
+    .await?;
+    upsert_script_merge_base(
+        &mut *tx,
+        base.script_id,
+        base.cloud_id,
+        base.base_ver_num,
+        payload,
+        base.updated_at.clone(),
+    )
+    .await?;
+    tx.commit().await.map_err(|error| error.to_string())
+}
+
+async fn upsert_script_merge_base<'e>(
+    executor: impl sqlx::Executor<'e, Database = Sqlite>,
+    script_id: ScriptId,
+    cloud_id: ScriptId,
+    base_ver_num: u32,
+    payload: Vec<u8>,
+    updated_at: String,
+) -> Result<(), String> {
+    sqlx::query(
+        "INSERT INTO script_merge_bases (script_id, cloud_id, base_ver_num, payload, updated_at) VALUES (?, ?, ?, ?, ?)
+         ON CONFLICT(script_id) DO UPDATE SET cloud_id = excluded.cloud_id, base_ver_num = excluded.base_ver_num, payload = excluded.payload, updated_at = excluded.updated_at",
+    )
+    .bind(script_id.to_string())
+    .bind(cloud_id.to_string())
+    .bind(i64::from(base_ver_num))
+    .bind(payload)
+    .bind(updated_at)
+    .execute(executor)
+    .await
+    .map_err(|error| format!("写入合并基线失败: {error}"))?;
+    Ok(())
+}
+
+pub async fn get_script_merge_base(script_id: ScriptId) -> Result<Option<ScriptMergeBase>, String> {
+    let Some(row) = sqlx::query_as::<_, ScriptMergeBaseRow>(
+        "SELECT cloud_id, base_ver_num, payload, updated_at FROM script_merge_bases WHERE script_id = ?",
+    )
+    .bind(script_id.to_string())
+    .fetch_optional(crate::bootstrap::get_pool())
+    .await
+    .map_err(|error| error.to_string())?
+    else {
+        return Ok(None);
+    };
+    let cloud_id = uuid::Uuid::parse_str(&row.cloud_id)
+        .map(ScriptId::from)
+        .map_err(|error| format!("合并基线的云端 ID 无效: {error}"))?;
+    Ok(Some(ScriptMergeBase {
+        script_id,
+        cloud_id,
+        base_ver_num: u32::try_from(row.base_ver_num).unwrap_or_default(),
+        snapshot: decode_snapshot(&row.payload)?,
+        updated_at: row.updated_at,
+    }))
+}
@@ -0,0 +1,191 @@
+use super::{
+    get_script, list_group_policy_links, list_policies, list_policy_groups, list_policy_sets,
+    list_script_tasks, list_set_group_links,
+};
+use ad_kernel::ids::ScriptId;
+use domain_script::{ScriptGraphSnapshot, ScriptSnapshotSummary};
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use sqlx::FromRow;
+use std::io::{Read, Write};
+
+/// 每个脚本保留的快照数量，超出后删除最旧的快照
+pub const SCRIPT_SNAPSHOT_RETENTION: i64 = 50;
+
+#[derive(Debug, Clone)]
+pub struct CreateScriptSnapshotInput {
+    pub id: String,
+    pub script_id: ScriptId,
+    pub author: Option<String>,
+    pub message: String,
+    pub created_at: String,
+}
+
+#[derive(FromRow)]
+struct ScriptSnapshotRow {
+    id: String,
+    script_id: String,
+    script_name: String,
+    ver_name: String,
+    author: Option<String>,
+    message: String,
+    size_bytes: i64,
+    created_at: String,
+}
+
+impl From<ScriptSnapshotRow> for ScriptSnapshotSummary {
+    fn from(row: ScriptSnapshotRow) -> Self {
+        Self {
+            id: row.id,
+            script_id: row.script_id,
+            script_name: row.script_name,
+            ver_name: row.ver_name,
+            author: row.author,
+            message: row.message,
+            size_bytes: row.size_bytes,
+            created_at: row.created_at,
+        }
+    }
+}
+
+pub(crate) fn encode_snapshot(snapshot: &ScriptGraphSnapshot) -> Result<Vec<u8>, String> {
+    let json =
+        serde_json::to_vec(snapshot).map_err(|error| format!("序列化脚本快照失败: {error}"))?;
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(&json)
+        .and_then(|_| encoder.finish())
+        .map_err(|error| format!("压缩脚本快照失败: {error}"))
+}
+
+pub(crate) fn decode_snapshot(payload: &[u8]) -> Result<ScriptGraphSnapshot, String> {
+    let mut json = Vec::new();
+    GzDecoder::new(payload)
+        .read_to_end(&mut json)
+        .map_err(|error| format!("解压脚本快照失败: {error}"))?;
+    serde_json::from_slice(&json).map_err(|error| format!("解析脚本快照失败: {error}"))
+}
+
+/// 从当前数据库读取脚本完整图
+pub async fn load_script_graph_snapshot(
+    script_id: ScriptId,
+) -> Result<Option<ScriptGraphSnapshot>, String> {
+    let Some(script) = get_script(script_id).await? else {
+        return Ok(None);
+    };
+    Ok(Some(ScriptGraphSnapshot {
+        script,
+        tasks: list_script_tasks(script_id).await?,
+        policies: list_policies(script_id).await?,
+        policy_groups: list_policy_groups(script_id).await?,
+        policy_sets: list_policy_sets(script_id).await?,
+        group_policies: list_group_policy_links(script_id).await?,
+        set_groups: list_set_group_links(script_id).await?,
+    }))
+}
+
+/// 写入快照并按保留数量清理旧快照
+pub async fn insert_script_snapshot(
+    input: CreateScriptSnapshotInput,
+    snapshot: &ScriptGraphSnapshot,
+) -> Result<ScriptSnapshotSummary, String> {
+    let payload = encode_snapshot(snapshot)?;
+    let script_id = input.script_id.to_string();
+    let summary = ScriptSnapshotSummary {
+        id: input.id,
+        script_id: script_id.clone(),
+        script_name: snapshot.script.info.name.clone(),
+        ver_name: snapshot.script.info.ver_name.clone(),
+        author: input.author,
+        message: input.message,
+        size_bytes: payload.len() as i64,
+        created_at: input.created_at,
+    };
+    let mut tx = crate::bootstrap::get_pool()
+        .begin()
+        .await
+        .map_err(|error| error.to_string())?;
+    sqlx::query("INSERT INTO script_snapshots (id, script_id, script_name, ver_name, author, message, payload, size_bytes, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)")
+        .bind(&summary.id).bind(&script_id).bind(&summary.script_name).bind(&summary.ver_name).bind(&summary.author).bind(&summary.message).bind(payload).bind(summary.size_bytes).bind(&summary.created_at)
+        .execute(&mut *tx).await.map_err(|error| format!("写入脚本快照失败: {error}"))?;
+    sqlx::query("DELETE FROM script_snapshots WHERE script_id = ? AND id NOT IN (SELECT id FROM script_snapshots WHERE script_id = ? ORDER BY created_at DESC, id DESC LIMIT ?)")
+        .bind(&script_id).bind(&script_id).bind(SCRIPT_SNAPSHOT_RETENTION)
+        .execute(&mut *tx).await.map_err(|error| format!("清理旧脚本快照失败: {error}"))?;
+    tx.commit().await.map_err(|error| error.to_string())?;
+    Ok(summary)
+}
+
+pub async fn list_script_snapshots(
+    script_id: ScriptId,
+) -> Result<Vec<ScriptSnapshotSummary>, String> {
+    sqlx::query_as::<_, ScriptSnapshotRow>("SELECT id, script_id, script_name, ver_name, author, message, size_bytes, created_at FROM script_snapshots WHERE script_id = ? ORDER BY created_at DESC, id DESC")
+        .bind(script_id.to_string())
+        .fetch_all(crate::bootstrap::get_pool())
+        .await
+        .map(|rows| rows.into_iter().map(Into::into).collect())
+        .map_err(|error| error.to_string())
+}
+
+pub async fn get_script_snapshot(id: &str) -> Result<Option<ScriptGraphSnapshot>, String> {
+    let payload =
+        sqlx::query_scalar::<_, Vec<u8>>("SELECT payload FROM script_snapshots WHERE id = ?")
+            .bind(id)
+            .fetch_optional(crate::bootstrap::get_pool())
+            .await
+            .map_err(|error| error.to_string())?;
+    payload.as_deref().map(decode_snapshot).transpose()
+}
+
+/// 读取脚本最近一次快照
+pub async fn get_latest_script_snapshot(
+    script_id: ScriptId,
+) -> Result<Option<ScriptGraphSnapshot>, String> {
+    let payload = sqlx::query_scalar::<_, Vec<u8>>(
+        "SELECT payload FROM script_snapshots WHERE script_id = ? ORDER BY created_at DESC, id DESC LIMIT 1",
+    )
+    .bind(script_id.to_string())
+    .fetch_optional(crate::bootstrap::get_pool())
+    .await
+    .map_err(|error| error.to_string())?;
+    payload.as_deref().map(decode_snapshot).transpose()
+}
+
+pub async fn delete_script_snapshot(id: &str) -> Result<(), String> {
+    sqlx::query("DELETE FROM script_snapshots WHERE id = ?")
+        .bind(id)
+        .execute(crate::bootstrap::get_pool())
+        .await
+        .map_err(|error| error.to_string())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_snapshot, encode_snapshot};
+    use domain_script::{ScriptGraphSnapshot, ScriptProfile};
+
+    #[test]
+    fn snapshot_payload_roundtrips_through_compression() {
+        let mut script = ScriptProfile::default();
+        script.info.name = "日常脚本".repeat(64);
+        let snapshot = ScriptGraphSnapshot {
+            script,
+            tasks: Vec::new(),
+            policies: Vec::new(),
+            policy_groups: Vec::new(),
+            policy_sets: Vec::new(),
+            group_policies: Vec::new(),
+            set_groups: Vec::new(),
+        };
+        let json_len = serde_json::to_vec(&snapshot).unwrap().len();
+
+        let payload = encode_snapshot(&snapshot).unwrap();
+        let decoded = decode_snapshot(&payload).unwrap();
+
+        assert!(payload.len() < json_len);
+        assert_eq!(decoded.script.id, snapshot.script.id);
+        assert_eq!(decoded.script.info.name, snapshot.script.info.name);
+        assert!(decode_snapshot(b"not gzip").is_err());
+    }
+}
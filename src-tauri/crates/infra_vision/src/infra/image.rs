@@ -1,3 +1,4 @@
 pub(crate) mod crop_image;
 pub(crate) mod img_error;
 pub(crate) mod load_image;
+pub(crate) mod template_match;
@@ -5,6 +5,12 @@ pub enum ImageError {
 
     #[error("从{path}加载图像失败：{e}")]
     LoadFromLocalFailed { path: String, e: String },
+
+    #[error("解码base64图像失败：{e}")]
+    DecodeBase64Failed { e: String },
+
+    #[error("模板匹配失败：{e}")]
+    TemplateMatchErr { e: String },
 }
 
 pub type ImageResult<T> = Result<T, ImageError>;
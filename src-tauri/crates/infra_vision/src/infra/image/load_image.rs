@@ -15,6 +15,19 @@ pub fn load_img_from_path(img_path: &str) -> ImageResult<DynamicImage> {
     )
 }
 
+/// 解码 base64 图像，兼容 `data:image/png;base64,` 前缀
+pub fn load_img_from_base64(data: &str) -> ImageResult<DynamicImage> {
+    let payload = data
+        .trim()
+        .split_once("base64,")
+        .map(|(_, payload)| payload)
+        .unwrap_or(data.trim());
+    let bytes = general_purpose::STANDARD
+        .decode(payload)
+        .map_err(|e| ImageError::DecodeBase64Failed { e: e.to_string() })?;
+    image::load_from_memory(&bytes).map_err(|e| ImageError::DecodeBase64Failed { e: e.to_string() })
+}
+
 pub fn dynamic_image_to_base64(img: &DynamicImage) -> Result<String, String> {
     let mut cursor = Cursor::new(Vec::new());
     match DynamicImage::ImageRgba8(img.to_rgba8()).write_to(&mut cursor, image::ImageFormat::Png) {
@@ -0,0 +1,607 @@
+use crate::infra::image::img_error::{ImageError, ImageResult};
+use domain_vision::{BoundingBox, DetResult};
+use image::RgbaImage;
+use image::imageops::FilterType;
+use rayon::prelude::*;
+
+/// 粗搜索阶段模板短边的目标尺寸，用于决定金字塔下采样倍数
+const COARSE_TEMPLATE_SIDE: u32 = 12;
+/// 粗搜索最大下采样倍数
+const MAX_PYRAMID_FACTOR: u32 = 4;
+/// 粗搜索阶段相对最终阈值的放宽量，避免下采样损失导致漏检
+const COARSE_THRESHOLD_SLACK: f32 = 0.3;
+/// 每个尺度最多进入精修阶段的候选数
+const MAX_CANDIDATES_PER_SCALE: usize = 64;
+/// 最终结果去重使用的 IoU 阈值
+const NMS_IOU_THRESHOLD: f32 = 0.3;
+/// 模板缩放后允许的最小边长
+const MIN_TEMPLATE_SIDE: u32 = 4;
+
+/// 模板匹配参数
+#[derive(Debug, Clone)]
+pub struct TemplateMatchOptions {
+    /// 归一化互相关得分阈值（0~1）
+    pub threshold: f32,
+    /// 模板缩放比例，空列表等价于 `[1.0]`
+    pub scales: Vec<f32>,
+    /// 最多返回的结果数，0 表示不限制
+    pub max_results: usize,
+    /// 输出到 DetResult 的标签序号
+    pub index: i32,
+    /// 输出到 DetResult 的标签名
+    pub label: String,
+    /// 稳定坐标网格大小
+    pub grid_size: u16,
+}
+
+/// 以像素为单位的搜索区域（左上角闭区间，右下角开区间）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TemplateSearchRegion {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+struct GrayPlane {
+    width: u32,
+    height: u32,
+    data: Vec<f32>,
+}
+
+impl GrayPlane {
+    fn from_rgba(image: &RgbaImage) -> Self {
+        let data = image
+            .pixels()
+            .map(|pixel| {
+                let [r, g, b, _] = pixel.0;
+                0.299 * f32::from(r) + 0.587 * f32::from(g) + 0.114 * f32::from(b)
+            })
+            .collect();
+        Self {
+            width: image.width(),
+            height: image.height(),
+            data,
+        }
+    }
+
+    fn at(&self, x: u32, y: u32) -> f32 {
+        self.data[(y * self.width + x) as usize]
+    }
+
+    /// 以 factor×factor 方块取均值下采样
+    fn downsample(&self, factor: u32) -> Self {
+        if factor <= 1 {
+            return Self {
+                width: self.width,
+                height: self.height,
+                data: self.data.clone(),
+            };
+        }
+        let width = (self.width / factor).max(1);
+        let height = (self.height / factor).max(1);
+        let area = (factor * factor) as f32;
+        let mut data = Vec::with_capacity((width * height) as usize);
+        for y in 0..height {
+            for x in 0..width {
+                let mut sum = 0.0;
+                for dy in 0..factor {
+                    for dx in 0..factor {
+                        let sx = (x * factor + dx).min(self.width - 1);
+                        let sy = (y * factor + dy).min(self.height - 1);
+                        sum += self.at(sx, sy);
+                    }
+                }
+                data.push(sum / area);
+            }
+        }
+        Self {
+            width,
+            height,
+            data,
+        }
+    }
+}
+
+/// 图像积分图（含平方和），用于 O(1) 计算窗口均值与方差
+struct IntegralPlane {
+    stride: usize,
+    sum: Vec<f64>,
+    sq_sum: Vec<f64>,
+}
+
+impl IntegralPlane {
+    fn new(plane: &GrayPlane) -> Self {
+        let stride = plane.width as usize + 1;
+        let rows = plane.height as usize + 1;
+        let mut sum = vec![0.0; stride * rows];
+        let mut sq_sum = vec![0.0; stride * rows];
+        for y in 0..plane.height as usize {
+            let mut row_sum = 0.0;
+            let mut row_sq_sum = 0.0;
+            for x in 0..plane.width as usize {
+                let value = f64::from(plane.data[y * plane.width as usize + x]);
+                row_sum += value;
+                row_sq_sum += value * value;
+                let index = (y + 1) * stride + x + 1;
+                sum[index] = sum[index - stride] + row_sum;
+                sq_sum[index] = sq_sum[index - stride] + row_sq_sum;
+            }
+        }
+        Self {
+            stride,
+            sum,
+            sq_sum,
+        }
+    }
+
+    fn window(&self, x: u32, y: u32, width: u32, height: u32) -> (f64, f64) {
+        let (x1, y1) = (x as usize, y as usize);
+        let (x2, y2) = (x1 + width as usize, y1 + height as usize);
+        let pick = |table: &[f64]| {
+            table[y2 * self.stride + x2]
+                - table[y1 * self.stride + x2]
+                - table[y2 * self.stride + x1]
+                + table[y1 * self.stride + x1]
+        };
+        (pick(&self.sum), pick(&self.sq_sum))
+    }
+}
+
+/// 去均值后的模板，配合积分图计算零均值归一化互相关（ZNCC）
+struct CenteredTemplate {
+    width: u32,
+    height: u32,
+    data: Vec<f32>,
+    norm: f64,
+}
+
+impl CenteredTemplate {
+    fn new(plane: &GrayPlane) -> Option<Self> {
+        let count = plane.data.len() as f64;
+        let mean = plane
+            .data
+            .iter()
+            .map(|value| f64::from(*value))
+            .sum::<f64>()
+            / count;
+        let data: Vec<f32> = plane
+            .data
+            .iter()
+            .map(|value| (f64::from(*value) - mean) as f32)
+            .collect();
+        let norm = data
+            .iter()
+            .map(|value| f64::from(*value) * f64::from(*value))
+            .sum::<f64>()
+            .sqrt();
+        (norm > 1e-3).then_some(Self {
+            width: plane.width,
+            height: plane.height,
+            data,
+            norm,
+        })
+    }
+
+    fn score_at(&self, image: &GrayPlane, integral: &IntegralPlane, x: u32, y: u32) -> f32 {
+        let count = f64::from(self.width * self.height);
+        let (sum, sq_sum) = integral.window(x, y, self.width, self.height);
+        let variance_sum = sq_sum - sum * sum / count;
+        if variance_sum <= 1e-6 {
+            return 0.0;
+        }
+        let mut cross = 0.0f64;
+        for ty in 0..self.height {
+            let image_row = ((y + ty) * image.width + x) as usize;
+            let template_row = (ty * self.width) as usize;
+            let mut row = 0.0f32;
+            for tx in 0..self.width as usize {
+                row += image.data[image_row + tx] * self.data[template_row + tx];
+            }
+            cross += f64::from(row);
+        }
+        (cross / (variance_sum.sqrt() * self.norm)).clamp(-1.0, 1.0) as f32
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct MatchCandidate {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+    score: f32,
+}
+
+/// 在图像（可选区域）中做多尺度模板匹配，输出与目标检测兼容的 DetResult
+pub fn match_template_rgba(
+    image: &RgbaImage,
+    template: &RgbaImage,
+    region: Option<TemplateSearchRegion>,
+    options: &TemplateMatchOptions,
+) -> ImageResult<Vec<DetResult>> {
+    if template.width() == 0 || template.height() == 0 {
+        return Err(ImageError::TemplateMatchErr {
+            e: "模板图像为空".to_string(),
+        });
+    }
+    let region = clamp_region(image, region)?;
+    let source = if region.x == 0
+        && region.y == 0
+        && region.width == image.width()
+        && region.height == image.height()
+    {
+        GrayPlane::from_rgba(image)
+    } else {
+        GrayPlane::from_rgba(
+            &image::imageops::crop_imm(image, region.x, region.y, region.width, region.height)
+                .to_image(),
+        )
+    };
+    let scales: Vec<f32> = if options.scales.is_empty() {
+        vec![1.0]
+    } else {
+        options.scales.clone()
+    };
+
+    let mut candidates = Vec::new();
+    for scale in scales {
+        if !scale.is_finite() || scale <= 0.0 {
+            return Err(ImageError::TemplateMatchErr {
+                e: format!("模板缩放比例[{}]无效", scale),
+            });
+        }
+        let width = (template.width() as f32 * scale).round() as u32;
+        let height = (template.height() as f32 * scale).round() as u32;
+        if width < MIN_TEMPLATE_SIDE
+            || height < MIN_TEMPLATE_SIDE
+            || width > source.width
+            || height > source.height
+        {
+            continue;
+        }
+        let scaled = if width == template.width() && height == template.height() {
+            template.clone()
+        } else {
+            image::imageops::resize(template, width, height, FilterType::Triangle)
+        };
+        let template_plane = GrayPlane::from_rgba(&scaled);
+        candidates.extend(match_single_scale(
+            &source,
+            &template_plane,
+            options.threshold,
+        )?);
+    }
+
+    let mut kept = suppress_overlaps(candidates);
+    if options.max_results > 0 {
+        kept.truncate(options.max_results);
+    }
+    Ok(kept
+        .into_iter()
+        .map(|candidate| {
+            let x1 = (region.x + candidate.x) as i32;
+            let y1 = (region.y + candidate.y) as i32;
+            DetResult::new(
+                BoundingBox::new(
+                    x1,
+                    y1,
+                    x1 + candidate.width as i32,
+                    y1 + candidate.height as i32,
+                ),
+                options.index,
+                options.label.clone(),
+                candidate.score,
+                options.grid_size,
+            )
+        })
+        .collect())
+}
+
+fn clamp_region(
+    image: &RgbaImage,
+    region: Option<TemplateSearchRegion>,
+) -> ImageResult<TemplateSearchRegion> {
+    let full = TemplateSearchRegion {
+        x: 0,
+        y: 0,
+        width: image.width(),
+        height: image.height(),
+    };
+    let Some(region) = region else {
+        return Ok(full);
+    };
+    let x = region.x.min(image.width());
+    let y = region.y.min(image.height());
+    let width = region.width.min(image.width() - x);
+    let height = region.height.min(image.height() - y);
+    if width == 0 || height == 0 {
+        return Err(ImageError::TemplateMatchErr {
+            e: format!(
+                "搜索区域({}, {}, {}x{})超出图像范围 {}x{}",
+                region.x,
+                region.y,
+                region.width,
+                region.height,
+                image.width(),
+                image.height()
+            ),
+        });
+    }
+    Ok(TemplateSearchRegion {
+        x,
+        y,
+        width,
+        height,
+    })
+}
+
+/// 单一尺度：先在下采样图上粗搜，再回到原分辨率邻域精修
+fn match_single_scale(
+    source: &GrayPlane,
+    template: &GrayPlane,
+    threshold: f32,
+) -> ImageResult<Vec<MatchCandidate>> {
+    let full_template =
+        CenteredTemplate::new(template).ok_or_else(|| ImageError::TemplateMatchErr {
+            e: "模板图像缺少纹理（像素近乎纯色），无法计算归一化相关".to_string(),
+        })?;
+    let full_integral = IntegralPlane::new(source);
+    let mut factor =
+        (template.width.min(template.height) / COARSE_TEMPLATE_SIDE).clamp(1, MAX_PYRAMID_FACTOR);
+
+    let mut coarse_hits = None;
+    if factor > 1 {
+        let coarse_source = source.downsample(factor);
+        // 下采样后纹理被抹平时退回原分辨率全图搜索
+        if let Some(coarse_template) = CenteredTemplate::new(&template.downsample(factor))
+            && coarse_template.width <= coarse_source.width
+            && coarse_template.height <= coarse_source.height
+        {
+            let coarse_integral = IntegralPlane::new(&coarse_source);
+            coarse_hits = Some(scan_scores(
+                &coarse_source,
+                &coarse_integral,
+                &coarse_template,
+                threshold - COARSE_THRESHOLD_SLACK,
+            ));
+        } else {
+            factor = 1;
+        }
+    }
+    let coarse_hits = match coarse_hits {
+        Some(hits) => hits,
+        None => scan_scores(source, &full_integral, &full_template, threshold),
+    };
+
+    let peaks = pick_peaks(
+        coarse_hits,
+        (template.width / factor / 2).max(1),
+        (template.height / factor / 2).max(1),
+    );
+    let max_x = source.width - template.width;
+    let max_y = source.height - template.height;
+    let mut refined = Vec::new();
+    for (cx, cy, coarse_score) in peaks {
+        if factor == 1 {
+            refined.push(MatchCandidate {
+                x: cx,
+                y: cy,
+                width: template.width,
+                height: template.height,
+                score: coarse_score,
+            });
+            continue;
+        }
+        let mut best: Option<MatchCandidate> = None;
+        let base_x = cx * factor;
+        let base_y = cy * factor;
+        for y in base_y.saturating_sub(factor)..=(base_y + factor).min(max_y) {
+            for x in base_x.saturating_sub(factor)..=(base_x + factor).min(max_x) {
+                let score = full_template.score_at(source, &full_integral, x, y);
+                if best.is_none_or(|current| score > current.score) {
+                    best = Some(MatchCandidate {
+                        x,
+                        y,
+                        width: template.width,
+                        height: template.height,
+                        score,
+                    });
+                }
+            }
+        }
+        if let Some(best) = best.filter(|best| best.score >= threshold) {
+            refined.push(best);
+        }
+    }
+    Ok(refined)
+}
+
+/// 计算全图得分，返回不低于阈值的位置
+fn scan_scores(
+    source: &GrayPlane,
+    integral: &IntegralPlane,
+    template: &CenteredTemplate,
+    threshold: f32,
+) -> Vec<(u32, u32, f32)> {
+    let max_x = source.width - template.width;
+    let max_y = source.height - template.height;
+    (0..=max_y)
+        .into_par_iter()
+        .flat_map_iter(|y| {
+            (0..=max_x).filter_map(move |x| {
+                let score = template.score_at(source, integral, x, y);
+                (score >= threshold).then_some((x, y, score))
+            })
+        })
+        .collect()
+}
+
+/// 按得分降序贪心挑选峰值，半个模板尺寸内的次优点视为同一目标
+fn pick_peaks(
+    mut hits: Vec<(u32, u32, f32)>,
+    radius_x: u32,
+    radius_y: u32,
+) -> Vec<(u32, u32, f32)> {
+    hits.sort_by(|left, right| right.2.total_cmp(&left.2));
+    let mut peaks: Vec<(u32, u32, f32)> = Vec::new();
+    for hit in hits {
+        if peaks.len() >= MAX_CANDIDATES_PER_SCALE {
+            break;
+        }
+        let duplicated = peaks
+            .iter()
+            .any(|peak| peak.0.abs_diff(hit.0) <= radius_x && peak.1.abs_diff(hit.1) <= radius_y);
+        if !duplicated {
+            peaks.push(hit);
+        }
+    }
+    peaks
+}
+
+fn suppress_overlaps(mut candidates: Vec<MatchCandidate>) -> Vec<MatchCandidate> {
+    candidates.sort_by(|left, right| right.score.total_cmp(&left.score));
+    let mut kept: Vec<MatchCandidate> = Vec::new();
+    for candidate in candidates {
+        if kept
+            .iter()
+            .all(|existing| candidate_iou(existing, &candidate) <= NMS_IOU_THRESHOLD)
+        {
+            kept.push(candidate);
+        }
+    }
+    kept
+}
+
+fn candidate_iou(left: &MatchCandidate, right: &MatchCandidate) -> f32 {
+    let x1 = left.x.max(right.x);
+    let y1 = left.y.max(right.y);
+    let x2 = (left.x + left.width).min(right.x + right.width);
+    let y2 = (left.y + left.height).min(right.y + right.height);
+    if x2 <= x1 || y2 <= y1 {
+        return 0.0;
+    }
+    let intersection = ((x2 - x1) * (y2 - y1)) as f32;
+    let union = (left.width * left.height + right.width * right.height) as f32 - intersection;
+    intersection / union
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    /// 生成类似 UI 图标的模板：渐变底色叠加色块与斜线，避免纯色导致相关系数退化
+    fn build_pattern(width: u32, height: u32) -> RgbaImage {
+        RgbaImage::from_fn(width, height, |x, y| {
+            let mut value = 60 + (x * 80 / width) + (y * 40 / height);
+            if x > width / 5 && x < width / 2 && y > height / 4 && y < height * 3 / 4 {
+                value = 230;
+            }
+            if x > width * 3 / 5 && y > height / 2 {
+                value = 20;
+            }
+            if x.abs_diff(y) <= 1 {
+                value = 160;
+            }
+            let value = value.min(255) as u8;
+            Rgba([value, value / 2 + 60, 255 - value, 255])
+        })
+    }
+
+    fn paste(canvas: &mut RgbaImage, patch: &RgbaImage, x: u32, y: u32) {
+        image::imageops::replace(canvas, patch, i64::from(x), i64::from(y));
+    }
+
+    fn options(threshold: f32, scales: Vec<f32>) -> TemplateMatchOptions {
+        TemplateMatchOptions {
+            threshold,
+            scales,
+            max_results: 0,
+            index: 3,
+            label: "icon".to_string(),
+            grid_size: 8,
+        }
+    }
+
+    #[test]
+    fn finds_exact_template_position() {
+        let template = build_pattern(24, 20);
+        let mut canvas = RgbaImage::from_pixel(160, 120, Rgba([90, 90, 90, 255]));
+        paste(&mut canvas, &template, 70, 45);
+
+        let results = match_template_rgba(&canvas, &template, None, &options(0.9, vec![])).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].bounding_box, BoundingBox::new(70, 45, 94, 65));
+        assert_eq!(results[0].index, 3);
+        assert_eq!(results[0].label, "icon");
+        assert!(results[0].score > 0.99);
+    }
+
+    #[test]
+    fn coarse_to_fine_search_keeps_pixel_accuracy() {
+        let template = build_pattern(48, 40);
+        let mut canvas = RgbaImage::from_pixel(320, 240, Rgba([30, 60, 90, 255]));
+        paste(&mut canvas, &template, 131, 77);
+        paste(&mut canvas, &template, 13, 150);
+
+        let results =
+            match_template_rgba(&canvas, &template, None, &options(0.9, vec![1.0])).unwrap();
+        let mut origins: Vec<_> = results
+            .iter()
+            .map(|item| (item.bounding_box.x1, item.bounding_box.y1))
+            .collect();
+        origins.sort();
+        assert_eq!(origins, vec![(13, 150), (131, 77)]);
+    }
+
+    #[test]
+    fn matches_scaled_template_and_translates_region() {
+        let template = build_pattern(32, 32);
+        let scaled = image::imageops::resize(&template, 48, 48, FilterType::Triangle);
+        let mut canvas = RgbaImage::from_pixel(300, 200, Rgba([120, 120, 120, 255]));
+        paste(&mut canvas, &scaled, 200, 100);
+
+        let region = TemplateSearchRegion {
+            x: 150,
+            y: 50,
+            width: 150,
+            height: 150,
+        };
+        let results = match_template_rgba(
+            &canvas,
+            &template,
+            Some(region),
+            &options(0.85, vec![1.0, 1.5]),
+        )
+        .unwrap();
+        assert_eq!(results.len(), 1);
+        let bbox = &results[0].bounding_box;
+        assert!((bbox.x1 - 200).abs() <= 1 && (bbox.y1 - 100).abs() <= 1);
+        assert!((bbox.x2 - bbox.x1 - 48).abs() <= 1);
+    }
+
+    #[test]
+    fn region_excludes_matches_outside() {
+        let template = build_pattern(20, 20);
+        let mut canvas = RgbaImage::from_pixel(200, 100, Rgba([0, 0, 0, 255]));
+        paste(&mut canvas, &template, 10, 10);
+        let region = TemplateSearchRegion {
+            x: 100,
+            y: 0,
+            width: 100,
+            height: 100,
+        };
+
+        let results =
+            match_template_rgba(&canvas, &template, Some(region), &options(0.8, vec![])).unwrap();
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn flat_template_is_rejected() {
+        let template = RgbaImage::from_pixel(16, 16, Rgba([10, 10, 10, 255]));
+        let canvas = build_pattern(64, 64);
+        assert!(match_template_rgba(&canvas, &template, None, &options(0.8, vec![])).is_err());
+    }
+}
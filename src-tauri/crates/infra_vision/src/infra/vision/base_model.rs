@@ -97,6 +97,12 @@ impl BaseModel {
                     e: "当前不提供内置目标检测/文字检测模型，请切换为自定义路径".to_string(),
                 });
             }
+            ModelType::PaddleSvtr | ModelType::Parseq => {
+                return Err(VisionError::IoError {
+                    path: "[built-in-recognizer]".to_string(),
+                    e: format!("当前不提供内置{:?}识别模型，请切换为自定义路径", model_type),
+                });
+            }
         };
 
         let mut candidates = vec![
@@ -155,7 +161,7 @@ impl BaseModel {
     pub(crate) fn use_parallel_cpu_sessions(&self, session_intra_threads: usize) -> bool {
         matches!(
             self.model_type,
-            ModelType::PaddleCrnn5 | ModelType::PaddleCrnn6
+            ModelType::PaddleCrnn5 | ModelType::PaddleCrnn6 | ModelType::PaddleSvtr
         ) && self.execution_provider == InferenceBackend::CPU
             && session_intra_threads > 0
             && self.intra_thread_num > session_intra_threads
@@ -1,7 +1,7 @@
 use crate::infra::vision::base_model;
 use crate::infra::vision::rec::paddle_crnn;
-use crate::infra::vision::vision_error::VisionResult;
-use domain_vision::{BaseModel, PaddleRecCrnn};
+use crate::infra::vision::vision_error::{VisionError, VisionResult};
+use domain_vision::{BaseModel, RecognizerType};
 use std::path::PathBuf;
 
 /// Resolves the model file selected by a persisted vision configuration.
@@ -10,6 +10,14 @@ pub fn resolve_model_path(config: &BaseModel) -> VisionResult<PathBuf> {
 }
 
 /// Resolves the recognition dictionary selected by a persisted vision configuration.
-pub fn resolve_recognizer_dict_path(config: &PaddleRecCrnn) -> VisionResult<PathBuf> {
-    paddle_crnn::resolve_recognizer_dict_config_path(config)
+pub fn resolve_recognizer_dict_path(config: &RecognizerType) -> VisionResult<PathBuf> {
+    match config {
+        RecognizerType::PaddleCrnn(cfg) | RecognizerType::PaddleSvtr(cfg) => {
+            paddle_crnn::resolve_recognizer_dict_config_path(cfg)
+        }
+        RecognizerType::Parseq(cfg) => cfg.dict_path.clone().ok_or_else(|| VisionError::IoError {
+            path: "".to_string(),
+            e: "字典路径为空".to_string(),
+        }),
+    }
 }
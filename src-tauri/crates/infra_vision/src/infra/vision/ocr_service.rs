@@ -1,6 +1,6 @@
 use crate::infra::vision::base_traits::{ModelHandler, TextDetector, TextRecognizer};
 use crate::infra::vision::det::RuntimeDetector;
-use crate::infra::vision::rec::RuntimeRecognizer;
+use crate::infra::vision::rec::load_recognizer;
 use crate::infra::vision::vision_error::{VisionError, VisionResult};
 use domain_vision::{DetResult, DetectorType, ModelType, OcrResult, RecognizerType};
 use image::{DynamicImage, RgbaImage};
@@ -57,21 +57,10 @@ impl OcrService {
 
     /// 使用配置初始化识别器。
     ///
-    /// 初始化顺序固定为：加载字典 -> 加载 ONNX 模型 -> 缓存 trait 对象实例。
+    /// 具体识别模型由注册表按配置类型加载，初始化顺序固定为：加载字典 -> 加载 ONNX 模型 -> 缓存 trait 对象实例。
     pub async fn init_recognizer(&mut self, config: RecognizerType) -> VisionResult<()> {
-        Log::info("初始化文字识别模型...");
-        let recognizer: Arc<dyn TextRecognizer + Send + Sync> =
-            match RuntimeRecognizer::from(config) {
-                RuntimeRecognizer::PaddleCrnn(mut crnn) => {
-                    // 加载字典
-                    Log::debug("加载字典文件...");
-                    crnn.load_dict().await?;
-                    Log::debug("加载CRNN识别模型...");
-                    crnn.load_model()?;
-                    Arc::new(crnn)
-                }
-            };
-        self.recognizer = Some(recognizer);
+        Log::info(&format!("初始化文字识别模型: {}", config.kind_name()));
+        self.recognizer = Some(load_recognizer(config).await?);
         Ok(())
     }
 
@@ -270,13 +259,9 @@ mod tests {
     }
 
     fn recognizer_model_signature(config: &RecognizerType) -> String {
-        match config {
-            RecognizerType::PaddleCrnn(cfg) => {
-                crate::infra::vision::base_model::resolve_model_config_path(&cfg.base_model)
-                    .map(|path| file_asset_signature(&path))
-                    .unwrap_or_else(|error| format!("resolve-error:{}", error))
-            }
-        }
+        crate::infra::vision::base_model::resolve_model_config_path(config.base_model())
+            .map(|path| format!("{}:{}", config.kind_name(), file_asset_signature(&path)))
+            .unwrap_or_else(|error| format!("resolve-error:{}", error))
     }
 
     fn test_cache_key(image: &DynamicImage, rec_model_signature: &str) -> String {
@@ -588,18 +573,22 @@ mod tests {
                 cfg.base_model.input_height,
             ),
         };
-        let rec_conf = match config_print.recognizer {
-            RecognizerType::PaddleCrnn(ref cfg) => (
-                cfg.base_model.intra_thread_num,
-                cfg.base_model.intra_spinning,
-                cfg.base_model.inter_thread_num,
-                cfg.base_model.inter_spinning,
-                cfg.parallel_cpu_session_intra_threads,
-                cfg.base_model.execution_provider.as_str(),
-                cfg.base_model.input_width,
-                cfg.base_model.input_height,
-            ),
-        };
+        let rec_base = config_print.recognizer.base_model();
+        let rec_conf = (
+            rec_base.intra_thread_num,
+            rec_base.intra_spinning,
+            rec_base.inter_thread_num,
+            rec_base.inter_spinning,
+            match config_print.recognizer {
+                RecognizerType::PaddleCrnn(ref cfg) | RecognizerType::PaddleSvtr(ref cfg) => {
+                    cfg.parallel_cpu_session_intra_threads
+                }
+                RecognizerType::Parseq(_) => rec_base.intra_thread_num,
+            },
+            rec_base.execution_provider.as_str(),
+            rec_base.input_width,
+            rec_base.input_height,
+        );
 
         println!(
             "det: intraThreadNum: {}, intraSpinning:{}, interThreadNum: {}, interSpinning:{}, provider: {}, inputWidth: {}, inputHeight: {}",
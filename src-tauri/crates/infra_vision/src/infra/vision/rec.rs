@@ -1,16 +1,87 @@
-use crate::infra::vision::rec::paddle_crnn::PaddleRecCrnn;
-use domain_vision::RecognizerType;
+use crate::infra::vision::base_traits::TextRecognizer;
+use crate::infra::vision::rec::paddle_crnn::{CtcDecodeMode, PaddleRecCrnn};
+use crate::infra::vision::rec::parseq::ParseqRec;
+use crate::infra::vision::vision_error::{VisionError, VisionResult};
+use domain_vision::{RecResizeFilter, RecognizerType};
+use image::imageops::FilterType;
+use ndarray::ArrayView1;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::fs::read_to_string;
 
 pub(crate) mod paddle_crnn;
-#[derive(Debug)]
-pub(crate) enum RuntimeRecognizer {
-    PaddleCrnn(PaddleRecCrnn),
+pub(crate) mod parseq;
+
+/// 识别器加载入口：各识别模型自行完成字典、ONNX 会话等运行期资源的准备
+pub(crate) trait RecognizerLoader: TextRecognizer + Sized + 'static {
+    async fn load(self) -> VisionResult<Self>;
+}
+
+async fn load_boxed<R: RecognizerLoader>(
+    recognizer: R,
+) -> VisionResult<Arc<dyn TextRecognizer + Send + Sync>> {
+    Ok(Arc::new(recognizer.load().await?))
 }
 
-impl From<RecognizerType> for RuntimeRecognizer {
-    fn from(config: RecognizerType) -> Self {
-        match config {
-            RecognizerType::PaddleCrnn(config) => Self::PaddleCrnn(config.into()),
+/// 识别器注册表：新增识别模型只需实现 `RecognizerLoader` 并在这里登记，调用方只持有 trait 对象
+pub(crate) async fn load_recognizer(
+    config: RecognizerType,
+) -> VisionResult<Arc<dyn TextRecognizer + Send + Sync>> {
+    match config {
+        RecognizerType::PaddleCrnn(config) => load_boxed(PaddleRecCrnn::from(config)).await,
+        RecognizerType::PaddleSvtr(config) => {
+            load_boxed(PaddleRecCrnn::from(config).with_decode_mode(CtcDecodeMode::Svtr)).await
         }
+        RecognizerType::Parseq(config) => load_boxed(ParseqRec::from(config)).await,
+    }
+}
+
+/// 单个时间步上概率（或 logits）最大的类别及其取值
+pub(crate) fn argmax(row: ArrayView1<f32>) -> (usize, f32) {
+    row.iter().copied().enumerate().fold(
+        (0, f32::MIN),
+        |best, item| if item.1 > best.1 { item } else { best },
+    )
+}
+
+pub(crate) fn resize_filter_type(filter: RecResizeFilter) -> FilterType {
+    match filter {
+        RecResizeFilter::Nearest => FilterType::Nearest,
+        RecResizeFilter::Triangle => FilterType::Triangle,
+        RecResizeFilter::Gaussian => FilterType::Gaussian,
+        RecResizeFilter::CatmullRom => FilterType::CatmullRom,
+        RecResizeFilter::Lanczos3 => FilterType::Lanczos3,
+    }
+}
+
+/// 读取识别字典。
+///
+/// 会保留原始行内容，只在首行移除 BOM，避免把空格字符误删。
+pub(crate) async fn read_dict_lines(dict_path: &Path) -> VisionResult<Vec<String>> {
+    let content = read_to_string(dict_path)
+        .await
+        .map_err(|e| VisionError::IoError {
+            path: dict_path.to_string_lossy().to_string(),
+            e: e.to_string(),
+        })?;
+
+    let dict: Vec<String> = content
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| {
+            if idx == 0 {
+                line.trim_start_matches('\u{feff}').to_string()
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+
+    if dict.is_empty() {
+        return Err(VisionError::IoError {
+            path: dict_path.to_string_lossy().to_string(),
+            e: "字典文件为空".to_string(),
+        });
     }
+    Ok(dict)
 }
@@ -1,5 +1,6 @@
 use crate::infra::vision::base_model::{BaseModel, ModelSpatialInput};
 use crate::infra::vision::base_traits::{ModelHandler, TextRecognizer};
+use crate::infra::vision::rec::{RecognizerLoader, argmax, read_dict_lines, resize_filter_type};
 use crate::infra::vision::tensor_view::select_batch_and_squeeze_to_2d;
 use crate::infra::vision::vision_error::{VisionError, VisionResult};
 use domain_vision::{
@@ -14,7 +15,6 @@ use std::path::PathBuf;
 
 use ndarray::{Array3, Array4, ArrayD, ArrayView2, ArrayViewD, ArrayViewMut3, Axis};
 use rayon::prelude::*;
-use tokio::fs::read_to_string;
 
 const REC_SCALE: f32 = 2.0 / 255.0;
 
@@ -33,6 +33,15 @@ struct RecInputGeometry {
     height: u32,
 }
 
+/// CTC 输出的解码约定
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CtcDecodeMode {
+    /// 首尾两个类别都视为空白，逐帧输出
+    Crnn,
+    /// 只有首类是空白，合并相邻重复字符；末尾多出的一类是空格
+    Svtr,
+}
+
 #[derive(Debug)]
 pub(crate) struct PaddleRecCrnn {
     pub base_model: BaseModel,
@@ -43,6 +52,7 @@ pub(crate) struct PaddleRecCrnn {
     pub width_bucket_step: u32,
     pub parallel_cpu_session_intra_threads: usize,
     pub dict: Vec<String>,
+    decode_mode: CtcDecodeMode,
 }
 
 impl From<PaddleRecCrnnConfig> for PaddleRecCrnn {
@@ -56,19 +66,19 @@ impl From<PaddleRecCrnnConfig> for PaddleRecCrnn {
             width_bucket_step: config.width_bucket_step,
             parallel_cpu_session_intra_threads: config.parallel_cpu_session_intra_threads,
             dict: Vec::new(),
+            decode_mode: CtcDecodeMode::Crnn,
         }
     }
 }
 
 impl PaddleRecCrnn {
+    pub(crate) fn with_decode_mode(mut self, decode_mode: CtcDecodeMode) -> Self {
+        self.decode_mode = decode_mode;
+        self
+    }
+
     fn active_filter(&self) -> FilterType {
-        match self.resize_filter {
-            RecResizeFilter::Nearest => FilterType::Nearest,
-            RecResizeFilter::Triangle => FilterType::Triangle,
-            RecResizeFilter::Gaussian => FilterType::Gaussian,
-            RecResizeFilter::CatmullRom => FilterType::CatmullRom,
-            RecResizeFilter::Lanczos3 => FilterType::Lanczos3,
-        }
+        resize_filter_type(self.resize_filter)
     }
 
     fn align_width(width: u32) -> u32 {
@@ -580,36 +590,53 @@ impl PaddleRecCrnn {
     /// 会保留原始行内容，只在首行移除 BOM，避免把空格字符误删。
     pub(crate) async fn load_dict(&mut self) -> VisionResult<()> {
         let dict_path = self.resolve_dict_path()?;
+        self.dict = read_dict_lines(&dict_path).await?;
+        self.dict_path = Some(dict_path);
+        Ok(())
+    }
+}
 
-        let content =
-            read_to_string(dict_path.clone())
-                .await
-                .map_err(|e| VisionError::IoError {
-                    path: dict_path.to_string_lossy().to_string(),
-                    e: e.to_string(),
-                })?;
-
-        let mut dict = Vec::new();
-        for (idx, line) in content.lines().enumerate() {
-            let value = if idx == 0 {
-                line.trim_start_matches('\u{feff}').to_string()
-            } else {
-                line.to_string()
-            };
-            dict.push(value);
-        }
+/// SVTR 导出的输出可能是 `[C, T]`，按与字典大小吻合的那一维判断类别轴
+fn ctc_time_major<'a>(sequence: ArrayView2<'a, f32>, dict_len: usize) -> ArrayView2<'a, f32> {
+    let class_counts = [dict_len + 1, dict_len + 2];
+    if !class_counts.contains(&sequence.shape()[1]) && class_counts.contains(&sequence.shape()[0]) {
+        sequence.reversed_axes()
+    } else {
+        sequence
+    }
+}
 
-        if dict.is_empty() {
-            return Err(VisionError::IoError {
-                path: dict_path.to_string_lossy().to_string(),
-                e: "字典文件为空".to_string(),
-            });
-        }
-        self.dict_path = Some(dict_path);
-        self.dict = dict;
+/// 标准 CTC 贪心解码：类别 0 为空白，相邻相同的类别只输出一次
+fn decode_ctc_collapsed(
+    sequence: ArrayView2<f32>,
+    dict: &[String],
+) -> VisionResult<(String, Vec<f32>, Vec<usize>)> {
+    let sequence = ctc_time_major(sequence, dict.len());
+    let class_num = sequence.shape()[1];
+    if class_num != dict.len() + 1 && class_num != dict.len() + 2 {
+        return Err(VisionError::DictSizeErr {
+            out: class_num,
+            dict: dict.len(),
+        });
+    }
 
-        Ok(())
+    let mut text = String::new();
+    let mut scores = Vec::new();
+    let mut indexes = Vec::new();
+    let mut previous = 0;
+    for row in sequence.rows() {
+        let (max_idx, max_prob) = argmax(row);
+        if max_idx != 0 && max_idx != previous {
+            match dict.get(max_idx - 1) {
+                Some(char) => text.push_str(char),
+                None => text.push(' '),
+            }
+            scores.push(max_prob);
+            indexes.push(max_idx - 1);
+        }
+        previous = max_idx;
     }
+    Ok((text, scores, indexes))
 }
 
 pub(crate) fn resolve_recognizer_dict_config_path(
@@ -618,6 +645,16 @@ pub(crate) fn resolve_recognizer_dict_config_path(
     PaddleRecCrnn::resolve_dict_config_path(config)
 }
 
+impl RecognizerLoader for PaddleRecCrnn {
+    async fn load(mut self) -> VisionResult<Self> {
+        Log::debug("加载字典文件...");
+        self.load_dict().await?;
+        Log::debug(&format!("加载{:?}识别模型...", self.decode_mode));
+        self.load_model()?;
+        Ok(self)
+    }
+}
+
 impl ModelHandler for PaddleRecCrnn {
     fn load_model(&mut self) -> VisionResult<()> {
         self.base_model
@@ -741,6 +778,17 @@ impl TextRecognizer for PaddleRecCrnn {
     ) -> VisionResult<OcrResult> {
         // 识别后处理只关心 [T, C]，上面先把 [B, T, C] / [B, 1, T, C] 等布局规整成 2D。
         let sequence = self.extract_sequence_view(output, batch_size)?;
+        if self.decode_mode == CtcDecodeMode::Svtr {
+            let (txt, score, index) = decode_ctc_collapsed(sequence, &self.dict)?;
+            return Ok(OcrResult {
+                bounding_box: det_result.bounding_box.clone(),
+                stable_box: det_result.stable_box.clone(),
+                stable_center: det_result.stable_center.clone(),
+                txt,
+                score,
+                index,
+            });
+        }
         let seq_len = sequence.shape()[0];
         let class_num = sequence.shape()[1];
 
@@ -872,4 +920,24 @@ mod tests {
         assert_eq!(input[[1, 0, 1]], 0.0);
         assert_eq!(input[[2, 0, 1]], 0.0);
     }
+
+    #[test]
+    fn svtr_decode_collapses_repeats_and_accepts_class_major_output() {
+        let dict = vec!["a".to_string(), "b".to_string()];
+        // 类别: [blank, a, b, space]，按 [C, T] 排列
+        let output = ndarray::array![
+            [0.1, 0.0, 0.9, 0.0, 0.0, 0.0],
+            [0.9, 0.9, 0.0, 0.9, 0.0, 0.0],
+            [0.0, 0.1, 0.1, 0.1, 0.0, 0.8],
+            [0.0, 0.0, 0.0, 0.0, 0.9, 0.2],
+        ];
+
+        let (text, scores, indexes) = decode_ctc_collapsed(output.view(), &dict).unwrap();
+
+        assert_eq!(text, "aa b");
+        assert_eq!(indexes, vec![0, 0, 2, 1]);
+        assert_eq!(scores.len(), 4);
+        let oversized_dict = vec!["x".to_string(); 10];
+        assert!(decode_ctc_collapsed(output.view(), &oversized_dict).is_err());
+    }
 }
@@ -0,0 +1,250 @@
+use crate::infra::vision::base_model::{BaseModel, ModelSpatialInput};
+use crate::infra::vision::base_traits::{ModelHandler, TextRecognizer};
+use crate::infra::vision::rec::{RecognizerLoader, argmax, read_dict_lines, resize_filter_type};
+use crate::infra::vision::tensor_view::select_batch_and_squeeze_to_2d;
+use crate::infra::vision::vision_error::{VisionError, VisionResult};
+use domain_vision::{AttnRec, DetResult, OcrResult, RecResizeFilter};
+use image::{DynamicImage, RgbaImage};
+use infra_logging::Log;
+use ndarray::{Array3, ArrayD, ArrayView2, ArrayViewD, Axis};
+use std::path::PathBuf;
+
+const PARSEQ_SCALE: f32 = 2.0 / 255.0;
+
+/// PARSeq 一类的注意力解码识别器：整图缩放到固定尺寸，输出 `[T, C]` 的 logits，类别 0 为结束符
+#[derive(Debug)]
+pub(crate) struct ParseqRec {
+    pub base_model: BaseModel,
+    pub dict_path: Option<PathBuf>,
+    pub resize_filter: RecResizeFilter,
+    pub dict: Vec<String>,
+}
+
+impl From<AttnRec> for ParseqRec {
+    fn from(config: AttnRec) -> Self {
+        Self {
+            base_model: config.base_model.into(),
+            dict_path: config.dict_path,
+            resize_filter: config.resize_filter,
+            dict: Vec::new(),
+        }
+    }
+}
+
+impl ParseqRec {
+    fn input_size(&self) -> (u32, u32) {
+        let ModelSpatialInput { width, height } =
+            self.base_model
+                .model_spatial_input()
+                .unwrap_or(ModelSpatialInput {
+                    width: None,
+                    height: None,
+                });
+        (
+            width.unwrap_or(self.base_model.input_width).max(1),
+            height.unwrap_or(self.base_model.input_height).max(1),
+        )
+    }
+
+    fn preprocess_rgba(&self, img: &RgbaImage) -> VisionResult<(ArrayD<f32>, [f32; 2], [u32; 2])> {
+        let (origin_w, origin_h) = img.dimensions();
+        if origin_w == 0 || origin_h == 0 {
+            return Err(VisionError::DataProcessingErr {
+                method: "parseq_preprocess".to_string(),
+                e: "输入图像宽高不能为 0".to_string(),
+            });
+        }
+        let (width, height) = self.input_size();
+        let resized =
+            image::imageops::resize(img, width, height, resize_filter_type(self.resize_filter));
+        let mut input = Array3::<f32>::zeros((3, height as usize, width as usize));
+        for (x, y, pixel) in resized.enumerate_pixels() {
+            for channel in 0..3 {
+                input[[channel, y as usize, x as usize]] =
+                    pixel[channel] as f32 * PARSEQ_SCALE - 1.0;
+            }
+        }
+        Ok((
+            input.insert_axis(Axis(0)).into_dyn(),
+            [
+                origin_w as f32 / width as f32,
+                origin_h as f32 / height as f32,
+            ],
+            [origin_h, origin_w],
+        ))
+    }
+}
+
+/// 逐位取最大类别直到结束符；输出为未归一化的 logits，分数取 softmax 后的概率
+fn decode_attention(
+    sequence: ArrayView2<f32>,
+    dict: &[String],
+) -> VisionResult<(String, Vec<f32>, Vec<usize>)> {
+    let class_num = sequence.shape()[1];
+    if class_num <= dict.len() {
+        return Err(VisionError::DictSizeErr {
+            out: class_num,
+            dict: dict.len(),
+        });
+    }
+
+    let mut text = String::new();
+    let mut scores = Vec::new();
+    let mut indexes = Vec::new();
+    for row in sequence.rows() {
+        let (max_idx, max_logit) = argmax(row);
+        if max_idx == 0 {
+            break;
+        }
+        // BOS/PAD 等字典之外的类别不参与输出
+        let Some(char) = dict.get(max_idx - 1) else {
+            continue;
+        };
+        let exp_sum: f32 = row.iter().map(|logit| (logit - max_logit).exp()).sum();
+        text.push_str(char);
+        scores.push(1.0 / exp_sum);
+        indexes.push(max_idx - 1);
+    }
+    Ok((text, scores, indexes))
+}
+
+impl RecognizerLoader for ParseqRec {
+    async fn load(mut self) -> VisionResult<Self> {
+        let dict_path = self.dict_path.clone().ok_or_else(|| VisionError::IoError {
+            path: "".to_string(),
+            e: "字典路径为空".to_string(),
+        })?;
+        Log::debug("加载字典文件...");
+        self.dict = read_dict_lines(&dict_path).await?;
+        Log::debug("加载PARSeq识别模型...");
+        self.load_model()?;
+        Ok(self)
+    }
+}
+
+impl ModelHandler for ParseqRec {
+    fn load_model(&mut self) -> VisionResult<()> {
+        self.base_model.load_model_base::<Self>("parseq_rec")
+    }
+
+    fn preprocess(&self, img: &DynamicImage) -> VisionResult<(ArrayD<f32>, [f32; 2], [u32; 2])> {
+        self.preprocess_rgba(&img.to_rgba8())
+    }
+
+    fn inference(&self, input: ArrayViewD<f32>) -> VisionResult<ArrayD<f32>> {
+        self.base_model.inference_base(
+            input,
+            self.get_input_node_name(),
+            self.get_output_node_name(),
+        )
+    }
+
+    fn get_input_node_name(&self) -> &'static str {
+        "input"
+    }
+
+    fn get_output_node_name(&self) -> &'static str {
+        "output"
+    }
+
+    fn get_target_height(&self) -> u32 {
+        self.input_size().1
+    }
+}
+
+impl TextRecognizer for ParseqRec {
+    fn recognize_crops(
+        &self,
+        cropped_images: Vec<DynamicImage>,
+        det_results: &[DetResult],
+    ) -> VisionResult<Vec<OcrResult>> {
+        self.recognize_crops_rgba(
+            cropped_images
+                .into_iter()
+                .map(|image| image.to_rgba8())
+                .collect(),
+            det_results,
+        )
+    }
+
+    fn recognize_crops_rgba(
+        &self,
+        cropped_images: Vec<RgbaImage>,
+        det_results: &[DetResult],
+    ) -> VisionResult<Vec<OcrResult>> {
+        let mut results = Vec::with_capacity(cropped_images.len());
+        for (idx, (image, det_res)) in cropped_images.iter().zip(det_results).enumerate() {
+            let outcome = self.preprocess_rgba(image).and_then(|(input, _, _)| {
+                self.base_model.inference_with_output_view(
+                    input.view(),
+                    self.get_input_node_name(),
+                    self.get_output_node_name(),
+                    |output| self.postprocess(output, det_res, 0),
+                )
+            });
+            match outcome {
+                Ok(ocr) => results.push(ocr),
+                Err(e) => {
+                    Log::warn(format!("文字识别-推理：第 {} 项推理失败: {:?}", idx, e).as_str());
+                }
+            }
+        }
+        if results.len() != det_results.len() {
+            Log::warn(
+                format!(
+                    "文字识别-后处理：部分结果处理失败！(总数: {}, 成功: {})",
+                    det_results.len(),
+                    results.len()
+                )
+                .as_str(),
+            );
+        }
+        Ok(results)
+    }
+
+    fn postprocess(
+        &self,
+        output: ArrayViewD<f32>,
+        det_result: &DetResult,
+        batch_size: usize,
+    ) -> VisionResult<OcrResult> {
+        let sequence =
+            select_batch_and_squeeze_to_2d(output, batch_size, "parseq_extract_sequence_view")?;
+        let (txt, score, index) = decode_attention(sequence, &self.dict)?;
+        Ok(OcrResult {
+            bounding_box: det_result.bounding_box.clone(),
+            stable_box: det_result.stable_box.clone(),
+            stable_center: det_result.stable_center.clone(),
+            txt,
+            score,
+            index,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn attention_decode_stops_at_eos_and_skips_special_tokens() {
+        let dict = vec!["a".to_string(), "b".to_string()];
+        // 类别: [EOS, a, b, BOS]
+        let logits = array![
+            [0.0, 5.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 9.0],
+            [0.0, 1.0, 6.0, 0.0],
+            [7.0, 0.0, 0.0, 0.0],
+            [0.0, 8.0, 0.0, 0.0],
+        ];
+
+        let (text, scores, indexes) = decode_attention(logits.view(), &dict).unwrap();
+
+        assert_eq!(text, "ab");
+        assert_eq!(indexes, vec![0, 1]);
+        assert!(scores.iter().all(|score| *score > 0.9 && *score <= 1.0));
+        let oversized_dict = vec!["x".to_string(); 4];
+        assert!(decode_attention(logits.view(), &oversized_dict).is_err());
+    }
+}
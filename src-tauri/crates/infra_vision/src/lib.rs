@@ -2,7 +2,12 @@ mod infra;
 
 pub use infra::image::crop_image::get_crop_image_rgba;
 pub use infra::image::img_error::{ImageError, ImageResult};
-pub use infra::image::load_image::{dynamic_image_to_base64, load_img_from_path};
+pub use infra::image::load_image::{
+    dynamic_image_to_base64, load_img_from_base64, load_img_from_path,
+};
+pub use infra::image::template_match::{
+    TemplateMatchOptions, TemplateSearchRegion, match_template_rgba,
+};
 pub use infra::vision::model_paths::{resolve_model_path, resolve_recognizer_dict_path};
 pub use infra::vision::ocr_service::OcrService;
 pub use infra::vision::vision_error::{VisionError, VisionResult};
@@ -2,6 +2,7 @@
 pub enum CaptureMethod {
     Window = 1,
     Adb = 2,
+    AdbStream = 3,
 }
 
 impl From<u8> for CaptureMethod {
@@ -9,6 +10,7 @@ impl From<u8> for CaptureMethod {
         match v {
             1 => CaptureMethod::Window,
             2 => CaptureMethod::Adb,
+            3 => CaptureMethod::AdbStream,
             _ => {
                 tracing::warn!("不支持的截图方式！将设置为窗口截图！");
                 CaptureMethod::Window
@@ -22,6 +24,7 @@ impl std::fmt::Display for CaptureMethod {
         match self {
             CaptureMethod::Window => write!(f, "窗口截图"),
             CaptureMethod::Adb => write!(f, "ADB截图"),
+            CaptureMethod::AdbStream => write!(f, "ADB截图流"),
         }
     }
 }
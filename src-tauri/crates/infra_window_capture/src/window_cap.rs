@@ -67,6 +67,15 @@ impl WindowInfo {
         found
     }
 
+    /// 窗口内容区的屏幕坐标与尺寸，扣除配置的边框偏移
+    pub async fn content_bounds(&self) -> Result<(i32, i32, u32, u32), String> {
+        let window = self
+            .ensure_window()
+            .await
+            .ok_or_else(|| format!("未找到目标窗口: {}", self.config.title))?;
+        Self::window_content_bounds(&window, self.config.offsets)
+    }
+
     pub async fn valid_capture(&self) -> bool {
         if self.window.read().await.is_some() {
             return true;
@@ -5,7 +5,8 @@ use ad_kernel::ids::{
     AccountId, AssignmentId, DeviceId, DispatchId, ExecutionId, MessageId, PolicyGroupId, PolicyId,
     PolicySetId, ScriptId, SessionId, StepId, TaskId, TemplateId,
 };
-use domain_device::{TimeoutAction, TimeoutNotifyChannel};
+use domain_device::{HumanizeProfile, TimeoutAction, TimeoutNotifyChannel};
+use domain_schedule::TaskRetryPolicy;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Encode, Decode, Deserialize, PartialEq)]
@@ -177,6 +178,8 @@ pub struct RuntimeExecutionPolicy {
     pub progress_timeout_ms: u64,
     pub timeout_action: TimeoutAction,
     pub timeout_notify_channels: Vec<TimeoutNotifyChannel>,
+    pub retry_policy: TaskRetryPolicy,
+    pub humanize: HumanizeProfile,
 }
 
 #[derive(Debug, Clone, Encode, Decode, Serialize, Deserialize, PartialEq, Eq)]
@@ -189,11 +192,41 @@ pub struct RuntimeQueueItem {
     pub script_id: ScriptId,
     pub time_template_id: Option<TemplateId>,
     pub account_id: Option<AccountId>,
+    pub account_name: Option<String>,
     pub account_data_json: Option<String>,
     pub order_index: u32,
     pub window_start_at: Option<String>,
     pub template_values_json: Option<String>,
     pub dedup_scope_base_hash: String,
+    /// 非空时同一 dispatch 内按顺序为每个账号运行一次脚本
+    #[serde(default)]
+    pub account_runs: Vec<RuntimeAccountRun>,
+}
+
+impl RuntimeQueueItem {
+    /// 展开为单个账号的运行项，账号相关的模板值与去重作用域覆盖原值
+    pub fn for_account(&self, run: &RuntimeAccountRun) -> Self {
+        Self {
+            account_id: Some(run.account_id.clone()),
+            account_name: Some(run.account_name.clone()),
+            account_data_json: run.account_data_json.clone(),
+            template_values_json: run.template_values_json.clone(),
+            dedup_scope_base_hash: run.dedup_scope_base_hash.clone(),
+            account_runs: Vec::new(),
+            ..self.clone()
+        }
+    }
+}
+
+/// 多账号轮换中的单个账号
+#[derive(Debug, Clone, Encode, Decode, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeAccountRun {
+    pub account_id: AccountId,
+    pub account_name: String,
+    pub account_data_json: Option<String>,
+    pub template_values_json: Option<String>,
+    pub dedup_scope_base_hash: String,
 }
 
 #[derive(Debug, Clone, Encode, Decode, Serialize, Deserialize, PartialEq, Eq)]
@@ -224,9 +257,46 @@ pub struct RuntimeSessionSnapshot {
 #[derive(Debug, Clone, Encode, Decode, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub enum SessionControlMessage {
-    LoadSession { session: RuntimeSessionSnapshot },
-    ReloadSession { session: RuntimeSessionSnapshot },
+    LoadSession {
+        session: RuntimeSessionSnapshot,
+    },
+    ReloadSession {
+        session: RuntimeSessionSnapshot,
+    },
     ClearSession,
+    /// 覆盖当前调试断点，执行到这些步骤前暂停
+    SetBreakpoints {
+        step_ids: Vec<StepId>,
+    },
+    /// 在下一个步骤入口暂停
+    DebugPause,
+    /// 从调试暂停处继续
+    DebugResume {
+        mode: DebugResumeMode,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Encode, Decode, Serialize, Deserialize, PartialEq, Eq, ts_rs::TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub enum DebugResumeMode {
+    /// 继续运行到下一个断点
+    Continue,
+    /// 进入 Sequence / FlowControl 的第一个子步骤
+    StepInto,
+    /// 执行完当前步骤（含子步骤）后在同层或外层暂停
+    StepOver,
+    /// 执行完当前所在的 Sequence / FlowControl 后暂停
+    StepOut,
+}
+
+#[derive(Debug, Clone, Copy, Encode, Decode, Serialize, Deserialize, PartialEq, Eq, ts_rs::TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub enum DebugPauseReason {
+    Breakpoint,
+    Step,
+    Pause,
 }
 
 #[derive(Debug, Clone, Encode, Decode, Serialize, Deserialize, PartialEq, Eq, ts_rs::TS)]
@@ -351,6 +421,24 @@ pub struct RuntimeDispatchEvent {
     pub at: String,
 }
 
+/// 调试暂停时的现场，变量与视觉结果以 JSON 文本传输
+#[derive(Debug, Clone, Encode, Decode, Serialize, Deserialize, PartialEq, Eq, ts_rs::TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct RuntimeDebugPausedEvent {
+    pub session_id: Option<SessionId>,
+    pub script_id: Option<ScriptId>,
+    pub task_id: Option<TaskId>,
+    pub step_id: Option<StepId>,
+    pub step_name: Option<String>,
+    /// 步骤嵌套深度，最外层为 1
+    pub depth: u32,
+    pub reason: DebugPauseReason,
+    pub variables_json: String,
+    pub vision_json: Option<String>,
+    pub at: String,
+}
+
 #[derive(Debug, Clone, Encode, Decode, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub enum RuntimeEventMessage {
@@ -360,6 +448,7 @@ pub enum RuntimeEventMessage {
     Connection(ConnectionStatusEvent),
     Capture(CaptureResultEvent),
     Dispatch(RuntimeDispatchEvent),
+    DebugPaused(RuntimeDebugPausedEvent),
 }
 
 #[derive(Debug, Clone, Encode, Decode, Deserialize, PartialEq)]
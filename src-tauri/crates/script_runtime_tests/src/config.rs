@@ -37,4 +37,15 @@ pub struct TestScenarioConfig {
     pub task_id: Option<TaskId>,
     #[serde(default)]
     pub template_values: Option<Value>,
+    /// 配置后以回放模式运行：截图依次取自这些 PNG，设备操作只记录不下发
+    #[serde(default)]
+    pub screenshots: Option<ScreenshotSource>,
+}
+
+/// 回放截图来源；目录按文件名排序读取其中的 PNG，列表按给定顺序读取
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum ScreenshotSource {
+    Directory(PathBuf),
+    Files(Vec<PathBuf>),
 }
@@ -0,0 +1,291 @@
+use serde::Serialize;
+use serde_json::Value;
+
+/// 超过该规模的数组不做 LCS 对齐，退化为按下标比较
+const MAX_ALIGN_CELLS: usize = 4_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DiffKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// 基准与本次结果之间的一处差异。
+///
+/// `removed` 的路径指向基准中的位置，`added` 与 `changed` 的路径指向本次结果中的位置。
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffEntry {
+    pub section: String,
+    pub kind: DiffKind,
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual: Option<Value>,
+}
+
+impl DiffEntry {
+    fn new(path: String, kind: DiffKind, expected: Option<&Value>, actual: Option<&Value>) -> Self {
+        Self {
+            section: section_of(&path),
+            kind,
+            path,
+            expected: expected.cloned(),
+            actual: actual.cloned(),
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self.kind {
+            DiffKind::Added => format!("出现额外结果字段: {}", self.path),
+            DiffKind::Removed => format!("缺少结果字段: {}", self.path),
+            DiffKind::Changed => format!(
+                "结果不一致[{}]: expected={}, actual={}",
+                self.path,
+                self.expected.as_ref().unwrap_or(&Value::Null),
+                self.actual.as_ref().unwrap_or(&Value::Null)
+            ),
+        }
+    }
+}
+
+pub fn diff_json(expected: &Value, actual: &Value) -> Vec<DiffEntry> {
+    let mut differences = Vec::new();
+    diff_values("$", expected, actual, &mut differences);
+    differences
+}
+
+fn diff_values(path: &str, expected: &Value, actual: &Value, differences: &mut Vec<DiffEntry>) {
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            for (key, expected_value) in expected {
+                let child_path = key_path(path, key);
+                match actual.get(key) {
+                    Some(actual_value) => {
+                        diff_values(&child_path, expected_value, actual_value, differences)
+                    }
+                    None => differences.push(DiffEntry::new(
+                        child_path,
+                        DiffKind::Removed,
+                        Some(expected_value),
+                        None,
+                    )),
+                }
+            }
+            for (key, actual_value) in actual {
+                if !expected.contains_key(key) {
+                    differences.push(DiffEntry::new(
+                        key_path(path, key),
+                        DiffKind::Added,
+                        None,
+                        Some(actual_value),
+                    ));
+                }
+            }
+        }
+        (Value::Array(expected), Value::Array(actual)) => {
+            diff_arrays(path, expected, actual, differences)
+        }
+        _ if expected == actual => {}
+        _ => differences.push(DiffEntry::new(
+            path.to_string(),
+            DiffKind::Changed,
+            Some(expected),
+            Some(actual),
+        )),
+    }
+}
+
+/// 普通标识符用 `.key`，其余键（如 `runtime.result`）用 `['key']`，避免路径产生歧义
+fn key_path(path: &str, key: &str) -> String {
+    let is_identifier = key
+        .chars()
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && key
+            .chars()
+            .all(|char| char.is_ascii_alphanumeric() || char == '_');
+    if is_identifier {
+        format!("{path}.{key}")
+    } else {
+        format!(
+            "{path}['{}']",
+            key.replace('\\', "\\\\").replace('\'', "\\'")
+        )
+    }
+}
+
+/// 按最长公共子序列对齐数组元素，使轨迹中间插入或删除一步时只报告这一步，
+/// 而不是其后所有元素都错位；两侧同一位置未对齐的元素视为修改并继续向下比较。
+fn diff_arrays(path: &str, expected: &[Value], actual: &[Value], differences: &mut Vec<DiffEntry>) {
+    let pairs = align(expected, actual);
+    let (mut expected_index, mut actual_index) = (0, 0);
+    for (next_expected, next_actual) in pairs
+        .into_iter()
+        .chain(std::iter::once((expected.len(), actual.len())))
+    {
+        let removed = next_expected - expected_index;
+        let added = next_actual - actual_index;
+        let changed = removed.min(added);
+        for offset in 0..changed {
+            diff_values(
+                &format!("{path}[{}]", actual_index + offset),
+                &expected[expected_index + offset],
+                &actual[actual_index + offset],
+                differences,
+            );
+        }
+        let removed_from = expected_index + changed;
+        for (index, value) in expected[removed_from..next_expected].iter().enumerate() {
+            differences.push(DiffEntry::new(
+                format!("{path}[{}]", removed_from + index),
+                DiffKind::Removed,
+                Some(value),
+                None,
+            ));
+        }
+        let added_from = actual_index + changed;
+        for (index, value) in actual[added_from..next_actual].iter().enumerate() {
+            differences.push(DiffEntry::new(
+                format!("{path}[{}]", added_from + index),
+                DiffKind::Added,
+                None,
+                Some(value),
+            ));
+        }
+        expected_index = next_expected + 1;
+        actual_index = next_actual + 1;
+    }
+}
+
+/// 返回两侧相等元素的下标对；数组过大时只按下标对齐相等元素
+fn align(expected: &[Value], actual: &[Value]) -> Vec<(usize, usize)> {
+    let (rows, cols) = (expected.len(), actual.len());
+    if rows.saturating_mul(cols) > MAX_ALIGN_CELLS {
+        return (0..rows.min(cols))
+            .filter(|&index| expected[index] == actual[index])
+            .map(|index| (index, index))
+            .collect();
+    }
+    let mut lengths = vec![0u32; (rows + 1) * (cols + 1)];
+    let at = |row: usize, col: usize| row * (cols + 1) + col;
+    for row in (0..rows).rev() {
+        for col in (0..cols).rev() {
+            lengths[at(row, col)] = if expected[row] == actual[col] {
+                lengths[at(row + 1, col + 1)] + 1
+            } else {
+                lengths[at(row + 1, col)].max(lengths[at(row, col + 1)])
+            };
+        }
+    }
+    let mut pairs = Vec::new();
+    let (mut row, mut col) = (0, 0);
+    while row < rows && col < cols {
+        if expected[row] == actual[col] {
+            pairs.push((row, col));
+            row += 1;
+            col += 1;
+        } else if lengths[at(row + 1, col)] >= lengths[at(row, col + 1)] {
+            row += 1;
+        } else {
+            col += 1;
+        }
+    }
+    pairs
+}
+
+fn section_of(path: &str) -> String {
+    path.strip_prefix("$.")
+        .and_then(|rest| rest.split(['.', '[']).next())
+        .filter(|section| !section.is_empty())
+        .unwrap_or("$")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DiffKind, diff_json};
+    use serde_json::json;
+
+    #[test]
+    fn object_diff_reports_changed_and_added_fields() {
+        let expected = json!({ "variables": { "runtime.result": "matched" } });
+        let actual = json!({
+            "variables": {
+                "runtime.result": "missed",
+                "runtime.extra": true
+            }
+        });
+
+        let differences = diff_json(&expected, &actual);
+
+        assert_eq!(differences.len(), 2);
+        assert_eq!(differences[0].kind, DiffKind::Changed);
+        assert_eq!(differences[0].path, "$.variables['runtime.result']");
+        assert_eq!(differences[0].section, "variables");
+        assert_eq!(differences[1].kind, DiffKind::Added);
+        assert_eq!(differences[1].path, "$.variables['runtime.extra']");
+        assert!(differences[1].describe().contains("runtime.extra"));
+    }
+
+    #[test]
+    fn inserted_trace_entry_does_not_shift_later_entries() {
+        let expected = json!({ "operations": [
+            { "type": "click", "x": 1 },
+            { "type": "back" },
+            { "type": "home" }
+        ]});
+        let actual = json!({ "operations": [
+            { "type": "click", "x": 1 },
+            { "type": "delay", "ms": 300 },
+            { "type": "back" },
+            { "type": "home" }
+        ]});
+
+        let differences = diff_json(&expected, &actual);
+
+        assert_eq!(differences.len(), 1);
+        assert_eq!(differences[0].kind, DiffKind::Added);
+        assert_eq!(differences[0].path, "$.operations[1]");
+        assert_eq!(differences[0].section, "operations");
+    }
+
+    #[test]
+    fn replaced_trace_entry_reports_nested_field_changes() {
+        let expected =
+            json!({ "prints": [{ "level": "Info", "message": "a" }, { "message": "end" }] });
+        let actual =
+            json!({ "prints": [{ "level": "Info", "message": "b" }, { "message": "end" }] });
+
+        let differences = diff_json(&expected, &actual);
+
+        assert_eq!(differences.len(), 1);
+        assert_eq!(differences[0].kind, DiffKind::Changed);
+        assert_eq!(differences[0].path, "$.prints[0].message");
+        assert_eq!(differences[0].expected, Some(json!("a")));
+        assert_eq!(differences[0].actual, Some(json!("b")));
+    }
+
+    #[test]
+    fn removed_trace_entries_point_at_baseline_indexes() {
+        let expected = json!({ "stepTrace": [1, 2, 3, 4] });
+        let actual = json!({ "stepTrace": [1, 4] });
+
+        let differences = diff_json(&expected, &actual);
+
+        let paths = differences
+            .iter()
+            .map(|entry| (entry.kind, entry.path.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            vec![
+                (DiffKind::Removed, "$.stepTrace[1]"),
+                (DiffKind::Removed, "$.stepTrace[2]")
+            ]
+        );
+    }
+}
@@ -1,33 +1,81 @@
 pub mod config;
+pub mod diff;
+pub mod report;
 pub mod runner;
 
 #[cfg(test)]
 mod tests {
     use ad_kernel::LogLevel;
     use ad_kernel::ids::{DeviceId, ScriptId, TaskId};
-    use child_runner::testkit::{TestScriptRunRequest, run_script_test};
+    use child_runner::testkit::{TestScriptRunRequest, run_device_queue_test, run_script_test};
+    use domain_device::DeviceProfile;
+    use domain_schedule::{RetryGiveUpAction, TaskRetryPolicy};
     use domain_script::{
-        DataHanding, PrintSource, ScriptInfo, ScriptProfile, ScriptTask, ScriptTaskProfile, Step,
-        StepKind, TaskCycle, TaskRowType, TaskTone, TaskTriggerMode,
+        Action, DataHanding, PrintSource, ScriptInfo, ScriptProfile, ScriptTask, ScriptTaskProfile,
+        Step, StepKind, StoreExpiry, TaskCycle, TaskRowType, TaskTone, TaskTriggerMode, VarValue,
     };
     use runner_protocol::message::ScriptBundleSnapshot;
     use serde_json::{Value, json};
+    use tokio::sync::OnceCell;
 
-    #[tokio::test]
-    async fn complete_bundle_script_runs_through_the_real_scheduler() {
+    static TEST_DATABASE: OnceCell<()> = OnceCell::const_new();
+
+    /// 任务开启 record_schedule 时会写调度记录，测试进程共用一个临时库
+    async fn ensure_test_database() {
+        TEST_DATABASE
+            .get_or_init(|| async {
+                let directory = std::env::temp_dir().join(format!(
+                    "autodaily-script-runtime-db-{}-{}",
+                    std::process::id(),
+                    chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+                ));
+                infra_sqlite::init_db_and_migrate_with_path(&directory)
+                    .await
+                    .unwrap();
+            })
+            .await;
+    }
+
+    fn build_bundle(task_id: TaskId, steps: Vec<Step>) -> ScriptBundleSnapshot {
         let script_id = ScriptId::new_v7();
-        let task_id = TaskId::new_v7();
-        let mut script_info = ScriptInfo::default();
+        let task = build_task(script_id, task_id, "记录正式步骤输出", 0, steps);
+        bundle_of(script_id, ScriptInfo::default(), vec![task])
+    }
+
+    fn bundle_of(
+        script_id: ScriptId,
+        mut script_info: ScriptInfo,
+        tasks: Vec<ScriptTaskProfile>,
+    ) -> ScriptBundleSnapshot {
         script_info.name = "runtime-test".to_string();
         let script = ScriptProfile {
             id: script_id,
             info: script_info,
         };
+        ScriptBundleSnapshot {
+            script_id,
+            script_json: serde_json::to_string(&script).unwrap(),
+            tasks_json: serde_json::to_string(&tasks).unwrap(),
+            policies_json: "[]".to_string(),
+            policy_groups_json: "[]".to_string(),
+            policy_sets_json: "[]".to_string(),
+            group_policies_json: "[]".to_string(),
+            set_groups_json: "[]".to_string(),
+        }
+    }
+
+    fn build_task(
+        script_id: ScriptId,
+        task_id: TaskId,
+        name: &str,
+        index: u32,
+        steps: Vec<Step>,
+    ) -> ScriptTaskProfile {
         let now = chrono::Utc::now();
-        let task = ScriptTaskProfile {
+        ScriptTaskProfile {
             id: task_id,
             script_id,
-            name: "记录正式步骤输出".to_string(),
+            name: name.to_string(),
             description: String::new(),
             row_type: TaskRowType::Task,
             trigger_mode: TaskTriggerMode::RootOnly,
@@ -43,37 +91,44 @@ mod tests {
             task: ScriptTask {
                 ui_data: Value::Null,
                 variables: Value::Null,
-                steps: vec![Step {
-                    id: None,
-                    source_id: None,
-                    target_id: None,
-                    label: Some("输出".to_string()),
-                    skip_flag: false,
-                    kind: StepKind::DataHanding {
-                        a: DataHanding::Print {
-                            source: PrintSource::Text,
-                            value: "full script completed".to_string(),
-                            level: LogLevel::Debug,
-                        },
-                    },
-                }],
+                steps,
+                retry_policy: None,
             },
             created_at: now,
             updated_at: now,
             deleted_at: None,
             is_deleted: false,
-            index: 0,
-        };
-        let bundle = ScriptBundleSnapshot {
-            script_id,
-            script_json: serde_json::to_string(&script).unwrap(),
-            tasks_json: serde_json::to_string(&vec![task]).unwrap(),
-            policies_json: "[]".to_string(),
-            policy_groups_json: "[]".to_string(),
-            policy_sets_json: "[]".to_string(),
-            group_policies_json: "[]".to_string(),
-            set_groups_json: "[]".to_string(),
-        };
+            index,
+        }
+    }
+
+    fn step(label: &str, kind: StepKind) -> Step {
+        Step {
+            id: None,
+            source_id: None,
+            target_id: None,
+            label: Some(label.to_string()),
+            skip_flag: false,
+            kind,
+        }
+    }
+
+    #[tokio::test]
+    async fn complete_bundle_script_runs_through_the_real_scheduler() {
+        let task_id = TaskId::new_v7();
+        let bundle = build_bundle(
+            task_id,
+            vec![step(
+                "输出",
+                StepKind::DataHanding {
+                    a: DataHanding::Print {
+                        source: PrintSource::Text,
+                        value: "full script completed".to_string(),
+                        level: LogLevel::Debug,
+                    },
+                },
+            )],
+        );
 
         let result = run_script_test(TestScriptRunRequest {
             bundle,
@@ -81,11 +136,13 @@ mod tests {
             device_id: DeviceId::new_v7(),
             device_config: None,
             template_values_json: None,
+            replay_screenshots: Vec::new(),
         })
         .await
         .unwrap();
 
         assert_eq!(result["execution"]["outcome"], json!("completed"));
+        assert!(result["execution"].get("remainingQueue").is_none());
         assert_eq!(
             result["prints"][0]["message"],
             json!("full script completed")
@@ -94,4 +151,374 @@ mod tests {
         assert_eq!(result["stepTrace"][1]["outcome"], json!("next"));
         assert_eq!(result["taskStates"][task_id.to_string()]["done"], true);
     }
+
+    #[tokio::test]
+    async fn replay_feeds_screenshots_and_records_operations_without_device() {
+        let directory = std::env::temp_dir().join(format!(
+            "autodaily-script-runtime-replay-run-{}-{}",
+            std::process::id(),
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        std::fs::create_dir_all(&directory).unwrap();
+        let frames = [(32, 24), (48, 36)]
+            .iter()
+            .enumerate()
+            .map(|(index, (width, height))| {
+                let path = directory.join(format!("{index:03}.png"));
+                image::RgbaImage::from_pixel(
+                    *width,
+                    *height,
+                    image::Rgba([index as u8, 0, 0, 255]),
+                )
+                .save(&path)
+                .unwrap();
+                path
+            })
+            .collect::<Vec<_>>();
+        let task_id = TaskId::new_v7();
+        let capture = |label: &str| {
+            step(
+                label,
+                StepKind::Action {
+                    exec_max: 0,
+                    a: Action::Capture {
+                        output_var: format!("runtime.{label}"),
+                    },
+                },
+            )
+        };
+        let bundle = build_bundle(
+            task_id,
+            vec![
+                capture("first"),
+                step(
+                    "返回",
+                    StepKind::Action {
+                        exec_max: 0,
+                        a: Action::Back,
+                    },
+                ),
+                capture("second"),
+                capture("third"),
+            ],
+        );
+
+        let result = run_script_test(TestScriptRunRequest {
+            bundle,
+            task_id: None,
+            device_id: DeviceId::new_v7(),
+            device_config: None,
+            template_values_json: None,
+            replay_screenshots: frames,
+        })
+        .await
+        .unwrap();
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(result["execution"]["outcome"], json!("completed"));
+        let operations = result["operations"].as_array().unwrap();
+        let kinds = operations
+            .iter()
+            .map(|operation| operation["type"].as_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(kinds, vec!["capture", "back", "capture", "capture"]);
+        assert_eq!(operations[0]["file"], json!("000.png"));
+        assert_eq!(operations[2]["frame"], json!(1));
+        assert_eq!(operations[3]["frame"], json!(1));
+    }
+
+    fn print_step(message: &str) -> Step {
+        step(
+            message,
+            StepKind::DataHanding {
+                a: DataHanding::Print {
+                    source: PrintSource::Text,
+                    value: message.to_string(),
+                    level: LogLevel::Info,
+                },
+            },
+        )
+    }
+
+    /// 前 `failures` 次执行抛错，之后成功；执行次数记在跨运行存储里，失败后也不会丢失
+    fn flaky_steps(failures: u32) -> Vec<Step> {
+        vec![
+            step(
+                "读取次数",
+                StepKind::DataHanding {
+                    a: DataHanding::StoreGet {
+                        key: "attempts".to_string(),
+                        out_var: "runtime.attempts".to_string(),
+                        default_val: Some(VarValue::Int { value: 0 }),
+                    },
+                },
+            ),
+            step(
+                "记录次数",
+                StepKind::DataHanding {
+                    a: DataHanding::StoreSet {
+                        key: "attempts".to_string(),
+                        json_val: None,
+                        expr: Some("runtime.attempts + 1".to_string()),
+                        expiry: StoreExpiry::Never,
+                    },
+                },
+            ),
+            step(
+                "不稳定步骤",
+                StepKind::DataHanding {
+                    a: DataHanding::Rhai {
+                        code: format!(
+                            r#"if runtime.attempts < {failures} {{ throw "第 " + (runtime.attempts + 1) + " 次执行失败"; }}"#
+                        ),
+                        out_var: None,
+                    },
+                },
+            ),
+        ]
+    }
+
+    fn retrying_task(
+        script_id: ScriptId,
+        task_id: TaskId,
+        index: u32,
+        steps: Vec<Step>,
+        retry_policy: TaskRetryPolicy,
+    ) -> ScriptTaskProfile {
+        let mut task = build_task(script_id, task_id, "重试任务", index, steps);
+        task.record_schedule = true;
+        task.task.retry_policy = Some(retry_policy);
+        task
+    }
+
+    fn retry_policy(max_attempts: u32, give_up_action: RetryGiveUpAction) -> TaskRetryPolicy {
+        TaskRetryPolicy {
+            max_attempts,
+            backoff_ms: 10,
+            backoff_factor: 1,
+            max_backoff_ms: 10,
+            run_recovery_task: false,
+            give_up_action,
+        }
+    }
+
+    async fn run_retry_script(bundle: ScriptBundleSnapshot, device_id: DeviceId) -> Value {
+        ensure_test_database().await;
+        // 调度记录对设备与脚本有外键约束，先落库两者
+        infra_sqlite::save_device(&DeviceProfile {
+            id: device_id,
+            ..DeviceProfile::default()
+        })
+        .await
+        .unwrap();
+        let script: ScriptProfile = serde_json::from_str(&bundle.script_json).unwrap();
+        infra_sqlite::save_script(&script).await.unwrap();
+        run_device_queue_test(
+            TestScriptRunRequest {
+                bundle,
+                task_id: None,
+                device_id,
+                device_config: None,
+                template_values_json: None,
+                replay_screenshots: Vec::new(),
+            },
+            1,
+        )
+        .await
+        .unwrap()
+    }
+
+    /// 按写入顺序返回任务的调度记录 (状态, 消息)
+    async fn journal_rows(device_id: DeviceId, task_id: TaskId) -> Vec<(String, Option<String>)> {
+        let mut records = infra_sqlite::list_execution_schedules(device_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|record| record.task_id == task_id)
+            .collect::<Vec<_>>();
+        records.sort_by_key(|record| record.id);
+        records
+            .into_iter()
+            .map(|record| (record.status, record.message))
+            .collect()
+    }
+
+    fn print_messages(result: &Value) -> Vec<&str> {
+        result["prints"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|print| print["message"].as_str().unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn failing_task_is_retried_until_it_succeeds() {
+        let script_id = ScriptId::new_v7();
+        let task_id = TaskId::new_v7();
+        let device_id = DeviceId::new_v7();
+        let bundle = bundle_of(
+            script_id,
+            ScriptInfo::default(),
+            vec![retrying_task(
+                script_id,
+                task_id,
+                0,
+                [flaky_steps(2), vec![print_step("flaky done")]].concat(),
+                retry_policy(3, RetryGiveUpAction::FailScript),
+            )],
+        );
+
+        let result = run_retry_script(bundle, device_id).await;
+
+        assert_eq!(result["execution"]["outcome"], json!("completed"));
+        assert_eq!(print_messages(&result), vec!["flaky done"]);
+        assert_eq!(result["taskStates"][task_id.to_string()]["done"], true);
+        let rows = journal_rows(device_id, task_id).await;
+        let statuses = rows
+            .iter()
+            .map(|(status, _)| status.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(statuses, vec!["Retrying", "Retrying", "Success"]);
+        assert!(
+            rows[0]
+                .1
+                .as_deref()
+                .unwrap()
+                .starts_with("第 1/3 次尝试失败")
+        );
+        assert!(
+            rows[1]
+                .1
+                .as_deref()
+                .unwrap()
+                .starts_with("第 2/3 次尝试失败")
+        );
+        assert_eq!(rows[2].1.as_deref(), Some("第 3 次尝试成功"));
+    }
+
+    #[tokio::test]
+    async fn exhausted_retries_with_fail_script_stop_the_script_only() {
+        let script_id = ScriptId::new_v7();
+        let task_id = TaskId::new_v7();
+        let device_id = DeviceId::new_v7();
+        let bundle = bundle_of(
+            script_id,
+            ScriptInfo::default(),
+            vec![
+                retrying_task(
+                    script_id,
+                    task_id,
+                    0,
+                    flaky_steps(u32::MAX),
+                    retry_policy(2, RetryGiveUpAction::FailScript),
+                ),
+                build_task(
+                    script_id,
+                    TaskId::new_v7(),
+                    "后续任务",
+                    1,
+                    vec![print_step("next task")],
+                ),
+            ],
+        );
+
+        let result = run_retry_script(bundle, device_id).await;
+
+        assert_eq!(result["execution"]["outcome"], json!("failed"));
+        assert!(print_messages(&result).is_empty());
+        assert_eq!(result["execution"]["remainingQueue"], json!(1));
+        let statuses = journal_rows(device_id, task_id)
+            .await
+            .into_iter()
+            .map(|(status, _)| status)
+            .collect::<Vec<_>>();
+        assert_eq!(statuses, vec!["Retrying", "Failed"]);
+    }
+
+    #[tokio::test]
+    async fn exhausted_retries_with_skip_task_continue_with_the_next_task() {
+        let script_id = ScriptId::new_v7();
+        let task_id = TaskId::new_v7();
+        let device_id = DeviceId::new_v7();
+        let bundle = bundle_of(
+            script_id,
+            ScriptInfo::default(),
+            vec![
+                retrying_task(
+                    script_id,
+                    task_id,
+                    0,
+                    flaky_steps(u32::MAX),
+                    retry_policy(2, RetryGiveUpAction::SkipTask),
+                ),
+                build_task(
+                    script_id,
+                    TaskId::new_v7(),
+                    "后续任务",
+                    1,
+                    vec![print_step("next task")],
+                ),
+            ],
+        );
+
+        let result = run_retry_script(bundle, device_id).await;
+
+        assert_eq!(result["execution"]["outcome"], json!("completed"));
+        assert_eq!(print_messages(&result), vec!["next task"]);
+        assert_eq!(result["execution"]["remainingQueue"], json!(1));
+        let statuses = journal_rows(device_id, task_id)
+            .await
+            .into_iter()
+            .map(|(status, _)| status)
+            .collect::<Vec<_>>();
+        assert_eq!(statuses, vec!["Retrying", "Failed"]);
+    }
+
+    #[tokio::test]
+    async fn exhausted_retries_with_stop_queue_run_recovery_and_clear_the_queue() {
+        let script_id = ScriptId::new_v7();
+        let task_id = TaskId::new_v7();
+        let recovery_task_id = TaskId::new_v7();
+        let device_id = DeviceId::new_v7();
+        let mut script_info = ScriptInfo::default();
+        script_info.runtime_settings.recovery_task_id = Some(recovery_task_id);
+        let mut recovery_task = build_task(
+            script_id,
+            recovery_task_id,
+            "恢复任务",
+            1,
+            vec![print_step("recovery")],
+        );
+        recovery_task.trigger_mode = TaskTriggerMode::LinkOnly;
+        let bundle = bundle_of(
+            script_id,
+            script_info,
+            vec![
+                retrying_task(
+                    script_id,
+                    task_id,
+                    0,
+                    flaky_steps(u32::MAX),
+                    TaskRetryPolicy {
+                        run_recovery_task: true,
+                        ..retry_policy(3, RetryGiveUpAction::StopQueue)
+                    },
+                ),
+                recovery_task,
+            ],
+        );
+
+        let result = run_retry_script(bundle, device_id).await;
+
+        assert_eq!(result["execution"]["outcome"], json!("failed"));
+        assert_eq!(print_messages(&result), vec!["recovery", "recovery"]);
+        assert_eq!(result["execution"]["remainingQueue"], json!(0));
+        let statuses = journal_rows(device_id, task_id)
+            .await
+            .into_iter()
+            .map(|(status, _)| status)
+            .collect::<Vec<_>>();
+        assert_eq!(statuses, vec!["Retrying", "Retrying", "Failed"]);
+    }
 }
@@ -1,6 +1,9 @@
+use script_runtime_tests::report::{write_junit, write_markdown};
 use script_runtime_tests::runner::{RunMode, run_config_path};
 use std::path::PathBuf;
 
+const USAGE: &str = "用法: cargo run -p script-runtime-tests -- <record|verify> [--junit <report.xml>] [--markdown <report.md>] <config.json> [more.json]";
+
 #[tokio::main]
 async fn main() {
     let mut args = std::env::args_os().skip(1);
@@ -8,21 +11,35 @@ async fn main() {
         Some(value) if value.eq_ignore_ascii_case("record") => RunMode::Record,
         Some(value) if value.eq_ignore_ascii_case("verify") => RunMode::Verify,
         _ => {
-            eprintln!(
-                "用法: cargo run -p script-runtime-tests -- <record|verify> <config.json> [more.json]"
-            );
+            eprintln!("{USAGE}");
             std::process::exit(2);
         }
     };
-    let paths = args.map(PathBuf::from).collect::<Vec<_>>();
+    let mut paths = Vec::new();
+    let mut junit_path = None;
+    let mut markdown_path = None;
+    while let Some(arg) = args.next() {
+        let target = match arg.to_str() {
+            Some("--junit") => &mut junit_path,
+            Some("--markdown") => &mut markdown_path,
+            _ => {
+                paths.push(PathBuf::from(arg));
+                continue;
+            }
+        };
+        let Some(value) = args.next() else {
+            eprintln!("{USAGE}");
+            std::process::exit(2);
+        };
+        *target = Some(PathBuf::from(value));
+    }
     if paths.is_empty() {
-        eprintln!(
-            "用法: cargo run -p script-runtime-tests -- <record|verify> <config.json> [more.json]"
-        );
+        eprintln!("{USAGE}");
         std::process::exit(2);
     }
 
     let mut passed = true;
+    let mut reports = Vec::new();
     for path in paths {
         match run_config_path(&path, mode).await {
             Ok(report) => {
@@ -31,6 +48,7 @@ async fn main() {
                     "{}",
                     serde_json::to_string_pretty(&report).expect("serialize suite report")
                 );
+                reports.push(report);
             }
             Err(error) => {
                 passed = false;
@@ -39,6 +57,19 @@ async fn main() {
         }
     }
 
+    if let Some(path) = junit_path
+        && let Err(error) = write_junit(&path, &reports)
+    {
+        passed = false;
+        eprintln!("{error}");
+    }
+    if let Some(path) = markdown_path
+        && let Err(error) = write_markdown(&path, &reports)
+    {
+        passed = false;
+        eprintln!("{error}");
+    }
+
     if !passed {
         std::process::exit(1);
     }
@@ -0,0 +1,294 @@
+use crate::diff::{DiffEntry, DiffKind};
+use crate::runner::{ScenarioReport, SuiteReport};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+
+/// Markdown 中单个值的最大展示长度
+const MARKDOWN_VALUE_MAX_CHARS: usize = 120;
+/// Markdown 中每个分区最多展示的差异条数
+const MARKDOWN_ENTRIES_PER_SECTION: usize = 50;
+
+pub fn write_junit(path: &Path, suites: &[SuiteReport]) -> Result<(), String> {
+    write_report(path, &render_junit(suites), "JUnit")
+}
+
+pub fn write_markdown(path: &Path, suites: &[SuiteReport]) -> Result<(), String> {
+    write_report(path, &render_markdown(suites), "Markdown")
+}
+
+fn write_report(path: &Path, content: &str, label: &str) -> Result<(), String> {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        std::fs::create_dir_all(parent)
+            .map_err(|error| format!("创建{label}报告目录失败[{}]: {error}", parent.display()))?;
+    }
+    std::fs::write(path, content)
+        .map_err(|error| format!("写入{label}报告失败[{}]: {error}", path.display()))
+}
+
+pub fn render_junit(suites: &[SuiteReport]) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites>\n");
+    for suite in suites {
+        let failures = suite
+            .scenarios
+            .iter()
+            .filter(|scenario| !scenario.passed)
+            .count()
+            + usize::from(!suite.coverage.passed);
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{failures}\">",
+            escape_xml(&suite.name),
+            suite.scenarios.len() + 1
+        );
+        let _ = write!(
+            xml,
+            "    <testcase classname=\"{0}\" name=\"coverage\"",
+            escape_xml(&suite.name)
+        );
+        if suite.coverage.passed {
+            xml.push_str("/>\n");
+        } else {
+            let _ = writeln!(
+                xml,
+                ">\n      <failure message=\"缺少能力覆盖\">{}</failure>\n    </testcase>",
+                escape_xml(&suite.coverage.missing.join("\n"))
+            );
+        }
+        for scenario in &suite.scenarios {
+            render_junit_case(&mut xml, &suite.name, scenario);
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+fn render_junit_case(xml: &mut String, suite_name: &str, scenario: &ScenarioReport) {
+    let _ = write!(
+        xml,
+        "    <testcase classname=\"{}\" name=\"{}\"",
+        escape_xml(suite_name),
+        escape_xml(&scenario.name)
+    );
+    if scenario.passed {
+        xml.push_str("/>\n");
+        return;
+    }
+    let message = if scenario.diff.is_empty() {
+        scenario.failures.first().cloned().unwrap_or_default()
+    } else {
+        format!("与基准存在 {} 处差异", scenario.diff.len())
+    };
+    let _ = writeln!(
+        xml,
+        ">\n      <failure message=\"{}\">{}</failure>\n    </testcase>",
+        escape_xml(&message),
+        escape_xml(&scenario.failures.join("\n"))
+    );
+}
+
+pub fn render_markdown(suites: &[SuiteReport]) -> String {
+    let mut markdown = String::new();
+    for suite in suites {
+        let _ = writeln!(
+            markdown,
+            "## {} {}\n",
+            status_icon(suite.passed),
+            escape_markdown(&suite.name)
+        );
+        if !suite.coverage.passed {
+            let _ = writeln!(
+                markdown,
+                "缺少能力覆盖：{}\n",
+                suite
+                    .coverage
+                    .missing
+                    .iter()
+                    .map(|capability| format!("`{capability}`"))
+                    .collect::<Vec<_>>()
+                    .join("、")
+            );
+        }
+        markdown.push_str("| 场景 | 结果 | 差异 |\n| --- | --- | --- |\n");
+        for scenario in &suite.scenarios {
+            let _ = writeln!(
+                markdown,
+                "| {} | {} | {} |",
+                escape_markdown(&scenario.name),
+                status_icon(scenario.passed),
+                scenario.diff.len()
+            );
+        }
+        markdown.push('\n');
+        for scenario in suite.scenarios.iter().filter(|scenario| !scenario.passed) {
+            render_markdown_scenario(&mut markdown, scenario);
+        }
+    }
+    markdown
+}
+
+fn render_markdown_scenario(markdown: &mut String, scenario: &ScenarioReport) {
+    let _ = writeln!(
+        markdown,
+        "### {}\n\n基准：`{}`\n",
+        escape_markdown(&scenario.name),
+        scenario.baseline_path.display()
+    );
+    if scenario.diff.is_empty() {
+        for failure in &scenario.failures {
+            let _ = writeln!(markdown, "- {}", escape_markdown(failure));
+        }
+        markdown.push('\n');
+        return;
+    }
+    let mut sections = BTreeMap::<&str, Vec<&DiffEntry>>::new();
+    for entry in &scenario.diff {
+        sections.entry(&entry.section).or_default().push(entry);
+    }
+    for (section, entries) in sections {
+        let _ = writeln!(
+            markdown,
+            "<details><summary><code>{}</code>：{} 处差异</summary>\n",
+            escape_markdown(section),
+            entries.len()
+        );
+        markdown.push_str("| 类型 | 路径 | 基准 | 本次 |\n| --- | --- | --- | --- |\n");
+        for entry in entries.iter().take(MARKDOWN_ENTRIES_PER_SECTION) {
+            let _ = writeln!(
+                markdown,
+                "| {} | `{}` | {} | {} |",
+                kind_label(entry.kind),
+                escape_markdown(&entry.path),
+                markdown_value(entry.expected.as_ref()),
+                markdown_value(entry.actual.as_ref())
+            );
+        }
+        if entries.len() > MARKDOWN_ENTRIES_PER_SECTION {
+            let _ = writeln!(
+                markdown,
+                "\n另有 {} 处差异未展示，请查看 JSON 报告。",
+                entries.len() - MARKDOWN_ENTRIES_PER_SECTION
+            );
+        }
+        markdown.push_str("\n</details>\n\n");
+    }
+}
+
+fn kind_label(kind: DiffKind) -> &'static str {
+    match kind {
+        DiffKind::Added => "新增",
+        DiffKind::Removed => "删除",
+        DiffKind::Changed => "修改",
+    }
+}
+
+fn status_icon(passed: bool) -> &'static str {
+    if passed { "✅" } else { "❌" }
+}
+
+fn markdown_value(value: Option<&Value>) -> String {
+    let Some(value) = value else {
+        return String::new();
+    };
+    let text = value.to_string();
+    let text = if text.chars().count() > MARKDOWN_VALUE_MAX_CHARS {
+        let truncated = text
+            .chars()
+            .take(MARKDOWN_VALUE_MAX_CHARS)
+            .collect::<String>();
+        format!("{truncated}…")
+    } else {
+        text
+    };
+    format!("`{}`", escape_markdown(&text).replace('`', "'"))
+}
+
+fn escape_markdown(value: &str) -> String {
+    value.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for char in value.chars() {
+        match char {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(char),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render_junit, render_markdown};
+    use crate::diff::diff_json;
+    use crate::runner::{CoverageReport, RunMode, ScenarioReport, SuiteReport};
+    use serde_json::json;
+    use std::path::PathBuf;
+
+    fn suite() -> SuiteReport {
+        let diff = diff_json(
+            &json!({ "operations": [{ "type": "back" }], "prints": [] }),
+            &json!({ "operations": [{ "type": "home" }], "prints": [{ "message": "a|b" }] }),
+        );
+        SuiteReport {
+            name: "能力<套件>".to_string(),
+            mode: RunMode::Verify,
+            passed: false,
+            coverage: CoverageReport {
+                passed: true,
+                discovered: Vec::new(),
+                missing: Vec::new(),
+            },
+            scenarios: vec![
+                ScenarioReport {
+                    name: "通过".to_string(),
+                    task_id: None,
+                    passed: true,
+                    failures: Vec::new(),
+                    diff: Vec::new(),
+                    baseline_path: PathBuf::from("baselines/pass.json"),
+                    result: None,
+                },
+                ScenarioReport {
+                    name: "变化".to_string(),
+                    task_id: None,
+                    passed: false,
+                    failures: diff.iter().map(|entry| entry.describe()).collect(),
+                    diff,
+                    baseline_path: PathBuf::from("baselines/changed.json"),
+                    result: None,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn junit_report_counts_failures_and_escapes_names() {
+        let xml = render_junit(&[suite()]);
+
+        assert!(xml.contains("<testsuite name=\"能力&lt;套件&gt;\" tests=\"3\" failures=\"1\">"));
+        assert!(xml.contains("name=\"通过\"/>"));
+        assert!(xml.contains("<failure message=\"与基准存在 2 处差异\">"));
+    }
+
+    #[test]
+    fn markdown_report_groups_differences_by_section() {
+        let markdown = render_markdown(&[suite()]);
+
+        assert!(markdown.contains("| 通过 | ✅ | 0 |"));
+        assert!(markdown.contains("<code>operations</code>：1 处差异"));
+        assert!(markdown.contains("| 修改 | `$.operations[0].type` | `\"back\"` | `\"home\"` |"));
+        assert!(markdown.contains("<code>prints</code>：1 处差异"));
+        assert!(markdown.contains("a\\|b"));
+    }
+}
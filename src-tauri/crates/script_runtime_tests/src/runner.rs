@@ -1,4 +1,6 @@
-use crate::config::{TestScenarioConfig, TestSuiteConfig};
+use crate::config::{ScreenshotSource, TestScenarioConfig, TestSuiteConfig};
+use crate::diff::{DiffEntry, diff_json};
+use ad_kernel::ids::DeviceId;
 use child_runner::testkit::{TestScriptRunRequest, run_script_test};
 use domain_device::DeviceProfile;
 use domain_script::{ScriptTaskProfile, Step};
@@ -47,6 +49,9 @@ pub struct ScenarioReport {
     pub task_id: Option<ad_kernel::ids::TaskId>,
     pub passed: bool,
     pub failures: Vec<String>,
+    /// Verify 模式下基准与本次结果的结构化差异
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub diff: Vec<DiffEntry>,
     pub baseline_path: PathBuf,
     pub result: Option<Value>,
 }
@@ -70,7 +75,7 @@ pub async fn run_config(
     }
     let database_dir = resolve_database_dir(config_dir, config.database_dir.as_deref())?;
     let bundle = load_bundle(&database_dir, config.script_id).await?;
-    let device = resolve_device(config.device_id).await?;
+    let device = resolve_device(config.device_id).await;
     let tasks: Vec<ScriptTaskProfile> = serde_json::from_str(&bundle.tasks_json)
         .map_err(|error| format!("解析运行时 tasks_json 失败: {error}"))?;
     let coverage = build_coverage_report(&tasks, &config.required_capabilities)?;
@@ -87,7 +92,8 @@ pub async fn run_config(
                 baseline_path.display()
             ));
         }
-        reports.push(run_scenario(&bundle, &device, scenario, baseline_path, mode).await);
+        reports
+            .push(run_scenario(&bundle, &device, config_dir, scenario, baseline_path, mode).await);
     }
 
     let passed = coverage.passed && reports.iter().all(|report| report.passed);
@@ -242,12 +248,13 @@ fn tagged_capability(value: Option<&Value>, tag: &str, prefix: &str) -> Option<S
 
 async fn run_scenario(
     bundle: &ScriptBundleSnapshot,
-    device: &DeviceProfile,
+    device: &Result<DeviceProfile, String>,
+    config_dir: &Path,
     scenario: &TestScenarioConfig,
     baseline_path: PathBuf,
     mode: RunMode,
 ) -> ScenarioReport {
-    let request = match build_request(bundle.clone(), device, scenario) {
+    let request = match build_request(bundle.clone(), device, config_dir, scenario) {
         Ok(request) => request,
         Err(error) => return failed_scenario(scenario, baseline_path, error),
     };
@@ -265,23 +272,28 @@ async fn run_scenario(
             task_id: scenario.task_id,
             passed: false,
             failures: vec![format!("正式执行失败: {error}")],
+            diff: Vec::new(),
             baseline_path,
             result: Some(result),
         };
     }
 
-    let failures = match mode {
+    let (failures, diff) = match mode {
         RunMode::Record => match write_baseline(&baseline_path, &result) {
-            Ok(()) => Vec::new(),
-            Err(error) => vec![error],
+            Ok(()) => (Vec::new(), Vec::new()),
+            Err(error) => (vec![error], Vec::new()),
+        },
+        RunMode::Verify => match verify_baseline(&baseline_path, &result) {
+            Ok(diff) => (diff.iter().map(DiffEntry::describe).collect(), diff),
+            Err(error) => (vec![error], Vec::new()),
         },
-        RunMode::Verify => verify_baseline(&baseline_path, &result),
     };
     ScenarioReport {
         name: scenario.name.clone(),
         task_id: scenario.task_id,
         passed: failures.is_empty(),
         failures,
+        diff,
         baseline_path,
         result: Some(result),
     }
@@ -289,9 +301,22 @@ async fn run_scenario(
 
 fn build_request(
     bundle: ScriptBundleSnapshot,
-    device: &DeviceProfile,
+    device: &Result<DeviceProfile, String>,
+    config_dir: &Path,
     scenario: &TestScenarioConfig,
 ) -> Result<TestScriptRunRequest, String> {
+    let replay_screenshots = scenario
+        .screenshots
+        .as_ref()
+        .map(|source| resolve_screenshots(config_dir, source))
+        .transpose()?
+        .unwrap_or_default();
+    // 回放模式不连接设备，数据库中没有设备时使用临时设备 ID
+    let (device_id, device_config) = match device {
+        Ok(device) => (device.id, Some(device.config.clone())),
+        Err(_) if !replay_screenshots.is_empty() => (DeviceId::new_v7(), None),
+        Err(error) => return Err(error.clone()),
+    };
     let template_values_json = scenario
         .template_values
         .as_ref()
@@ -301,12 +326,52 @@ fn build_request(
     Ok(TestScriptRunRequest {
         bundle,
         task_id: scenario.task_id,
-        device_id: device.id,
-        device_config: Some(device.config.clone()),
+        device_id,
+        device_config,
         template_values_json,
+        replay_screenshots,
     })
 }
 
+fn resolve_screenshots(
+    config_dir: &Path,
+    source: &ScreenshotSource,
+) -> Result<Vec<PathBuf>, String> {
+    let paths = match source {
+        ScreenshotSource::Files(files) => files
+            .iter()
+            .map(|path| resolve_path(config_dir, path))
+            .collect::<Vec<_>>(),
+        ScreenshotSource::Directory(directory) => {
+            let directory = resolve_path(config_dir, directory);
+            let entries = std::fs::read_dir(&directory).map_err(|error| {
+                format!("读取回放截图目录失败[{}]: {error}", directory.display())
+            })?;
+            let mut paths = Vec::new();
+            for entry in entries {
+                let path = entry
+                    .map_err(|error| {
+                        format!("读取回放截图目录失败[{}]: {error}", directory.display())
+                    })?
+                    .path();
+                let is_png = path
+                    .extension()
+                    .and_then(|extension| extension.to_str())
+                    .is_some_and(|extension| extension.eq_ignore_ascii_case("png"));
+                if path.is_file() && is_png {
+                    paths.push(path);
+                }
+            }
+            paths.sort();
+            paths
+        }
+    };
+    if paths.is_empty() {
+        return Err("回放截图列表为空".to_string());
+    }
+    Ok(paths)
+}
+
 fn scenario_baseline_path(baseline_dir: &Path, scenario: &TestScenarioConfig) -> PathBuf {
     let file_name = scenario
         .task_id
@@ -327,25 +392,16 @@ fn write_baseline(path: &Path, result: &Value) -> Result<(), String> {
         .map_err(|error| format!("写入基准结果失败[{}]: {error}", path.display()))
 }
 
-fn verify_baseline(path: &Path, actual: &Value) -> Vec<String> {
-    let content = match std::fs::read_to_string(path) {
-        Ok(content) => content,
-        Err(error) => {
-            return vec![format!(
-                "读取基准结果失败[{}]: {error}；请先运行 record",
-                path.display()
-            )];
-        }
-    };
-    let expected = match serde_json::from_str::<Value>(&content) {
-        Ok(value) => value,
-        Err(error) => {
-            return vec![format!("解析基准结果失败[{}]: {error}", path.display())];
-        }
-    };
-    let mut differences = Vec::new();
-    diff_values("$", &expected, actual, &mut differences);
-    differences
+fn verify_baseline(path: &Path, actual: &Value) -> Result<Vec<DiffEntry>, String> {
+    let content = std::fs::read_to_string(path).map_err(|error| {
+        format!(
+            "读取基准结果失败[{}]: {error}；请先运行 record",
+            path.display()
+        )
+    })?;
+    let expected = serde_json::from_str::<Value>(&content)
+        .map_err(|error| format!("解析基准结果失败[{}]: {error}", path.display()))?;
+    Ok(diff_json(&expected, actual))
 }
 
 fn normalize_value(value: Value) -> Value {
@@ -374,43 +430,6 @@ fn normalize_value(value: Value) -> Value {
     }
 }
 
-fn diff_values(path: &str, expected: &Value, actual: &Value, differences: &mut Vec<String>) {
-    match (expected, actual) {
-        (Value::Object(expected), Value::Object(actual)) => {
-            for (key, expected_value) in expected {
-                let child_path = format!("{path}.{key}");
-                match actual.get(key) {
-                    Some(actual_value) => {
-                        diff_values(&child_path, expected_value, actual_value, differences)
-                    }
-                    None => differences.push(format!("缺少结果字段: {child_path}")),
-                }
-            }
-            for key in actual.keys() {
-                if !expected.contains_key(key) {
-                    differences.push(format!("出现额外结果字段: {path}.{key}"));
-                }
-            }
-        }
-        (Value::Array(expected), Value::Array(actual)) => {
-            if expected.len() != actual.len() {
-                differences.push(format!(
-                    "数组长度不同[{path}]: expected={}, actual={}",
-                    expected.len(),
-                    actual.len()
-                ));
-            }
-            for (index, (expected, actual)) in expected.iter().zip(actual.iter()).enumerate() {
-                diff_values(&format!("{path}[{index}]"), expected, actual, differences);
-            }
-        }
-        _ if expected == actual => {}
-        _ => differences.push(format!(
-            "结果不一致[{path}]: expected={expected}, actual={actual}"
-        )),
-    }
-}
-
 fn resolve_path(config_dir: &Path, path: &Path) -> PathBuf {
     if path.is_absolute() {
         path.to_path_buf()
@@ -439,6 +458,7 @@ fn failed_scenario(
         task_id: scenario.task_id,
         passed: false,
         failures: vec![error],
+        diff: Vec::new(),
         baseline_path,
         result: None,
     }
@@ -447,11 +467,14 @@ fn failed_scenario(
 #[cfg(test)]
 mod tests {
     use super::{
-        collect_step_capabilities, diff_values, normalize_value, verify_baseline, write_baseline,
+        collect_step_capabilities, normalize_value, resolve_screenshots, verify_baseline,
+        write_baseline,
     };
+    use crate::config::ScreenshotSource;
     use domain_script::{Action, Step, StepKind};
     use serde_json::json;
     use std::collections::BTreeSet;
+    use std::path::PathBuf;
     use std::time::{SystemTime, UNIX_EPOCH};
 
     fn step(kind: StepKind) -> Step {
@@ -496,50 +519,58 @@ mod tests {
     }
 
     #[test]
-    fn exact_diff_reports_changed_and_added_fields() {
-        let expected = json!({ "variables": { "runtime.result": "matched" } });
-        let actual = json!({
-            "variables": {
-                "runtime.result": "missed",
-                "runtime.extra": true
-            }
-        });
-        let mut differences = Vec::new();
+    fn recorded_baseline_verifies_until_the_result_changes() {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let directory = std::env::temp_dir().join(format!(
+            "autodaily-script-runtime-baseline-{}-{unique}",
+            std::process::id()
+        ));
+        let path = directory.join("task.json");
+        let recorded = json!({ "execution": { "outcome": "completed" } });
 
-        diff_values("$", &expected, &actual, &mut differences);
+        write_baseline(&path, &recorded).unwrap();
+        assert!(verify_baseline(&path, &recorded).unwrap().is_empty());
 
+        let differences =
+            verify_baseline(&path, &json!({ "execution": { "outcome": "stopped" } })).unwrap();
         assert!(
             differences
                 .iter()
-                .any(|item| item.contains("runtime.result"))
-        );
-        assert!(
-            differences
-                .iter()
-                .any(|item| item.contains("runtime.extra"))
+                .any(|item| item.path == "$.execution.outcome")
         );
+
+        std::fs::remove_file(&path).unwrap();
+        std::fs::remove_dir(&directory).unwrap();
     }
 
     #[test]
-    fn recorded_baseline_verifies_until_the_result_changes() {
+    fn screenshot_directory_resolves_sorted_png_files() {
         let unique = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap()
             .as_nanos();
         let directory = std::env::temp_dir().join(format!(
-            "autodaily-script-runtime-baseline-{}-{unique}",
+            "autodaily-script-runtime-replay-{}-{unique}",
             std::process::id()
         ));
-        let path = directory.join("task.json");
-        let recorded = json!({ "execution": { "outcome": "completed" } });
+        std::fs::create_dir_all(&directory).unwrap();
+        for name in ["002.png", "001.PNG", "notes.txt"] {
+            std::fs::write(directory.join(name), b"").unwrap();
+        }
 
-        write_baseline(&path, &recorded).unwrap();
-        assert!(verify_baseline(&path, &recorded).is_empty());
+        let paths =
+            resolve_screenshots(&directory, &ScreenshotSource::Directory(PathBuf::from(".")))
+                .unwrap();
 
-        let differences = verify_baseline(&path, &json!({ "execution": { "outcome": "stopped" } }));
-        assert!(differences.iter().any(|item| item.contains("outcome")));
+        let names = paths
+            .iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["001.PNG", "002.png"]);
 
-        std::fs::remove_file(&path).unwrap();
-        std::fs::remove_dir(&directory).unwrap();
+        std::fs::remove_dir_all(&directory).unwrap();
     }
 }
@@ -3,7 +3,7 @@ mod target_validation;
 
 use ad_kernel::ids::{ScriptId, TaskId};
 use domain_schedule::PlannerQueueItem;
-use domain_script::ScriptType;
+use domain_script::{ScriptDiagnostic, ScriptType};
 use runner_protocol::message::{RunTarget, RuntimeQueueItem, ScriptBundleSnapshot};
 use std::collections::HashSet;
 
@@ -15,6 +15,7 @@ pub(super) struct LoadedScriptBundle {
     pub(super) policy_ids: HashSet<ad_kernel::ids::PolicyId>,
     pub(super) policy_group_ids: HashSet<ad_kernel::ids::PolicyGroupId>,
     pub(super) policy_set_ids: HashSet<ad_kernel::ids::PolicySetId>,
+    pub(super) diagnostics: Vec<ScriptDiagnostic>,
     pub(super) snapshot: ScriptBundleSnapshot,
 }
 
@@ -58,3 +59,7 @@ pub(super) fn validate_run_target_support(
 ) -> Result<(), String> {
     target_validation::validate_run_target_support(run_target, bundles)
 }
+
+pub(super) fn validate_script_diagnostics(bundles: &[LoadedScriptBundle]) -> Result<(), String> {
+    target_validation::validate_script_diagnostics(bundles)
+}
@@ -1,17 +1,21 @@
 use super::LoadedScriptBundle;
+use crate::api::local::script::lint::{ScriptLintGraph, lint_script_graph};
 use ad_kernel::ids::{AccountId, DeviceId, DispatchId, ScriptId, TemplateId};
-use chrono::{Days, TimeZone, Timelike};
-use domain_schedule::{AssignmentProfile, TemplateValueProfile, TimeTemplateProfile};
-use domain_schedule::{TimeOfDay, TimeWindow};
+use domain_schedule::{
+    AssignmentProfile, TemplateSchedule, TemplateValueProfile, TimeTemplateProfile,
+};
 use domain_script::{
     PolicyGroupPolicyLink, PolicyGroupProfile, PolicyProfile, PolicySetGroupLink, PolicySetProfile,
     ScriptTaskProfile, TaskRowType,
 };
 use infra_sqlite::{
     find_template_value, get_script, get_time_template, list_assignments, list_group_policy_links,
-    list_policies, list_policy_groups, list_policy_sets, list_script_tasks, list_set_group_links,
+    list_policies, list_policy_groups, list_policy_sets, list_script_accounts, list_script_tasks,
+    list_set_group_links,
+};
+use runner_protocol::message::{
+    DispatchKind, DispatchSource, RunTarget, RuntimeAccountRun, RuntimeQueueItem,
 };
-use runner_protocol::message::{DispatchKind, DispatchSource, RunTarget, RuntimeQueueItem};
 use serde::Serialize;
 use serde_json::{Map, Value};
 use sha2::{Digest, Sha256};
@@ -21,41 +25,10 @@ fn compute_window_start_at(
     template: &TimeTemplateProfile,
     now: chrono::DateTime<chrono::Local>,
 ) -> Result<Option<String>, String> {
-    let window = TimeWindow::parse(template.start_time.as_deref(), template.end_time.as_deref())
-        .map_err(|error| error.to_string())?;
-    if window.is_unbounded() {
-        return Ok(None);
-    }
-
-    let today = now.date_naive();
-    let now_time = TimeOfDay::from_hour_minute(now.hour() as u8, now.minute() as u8)
-        .ok_or_else(|| "构造当前时间失败".to_string())?;
-    if !window.contains(now_time) {
-        return Ok(None);
-    }
-
-    let window_start = match window.start() {
-        None => today
-            .and_hms_opt(0, 0, 0)
-            .ok_or_else(|| "构造时间窗口起点失败".to_string())?,
-        Some(start) => {
-            let date = if window.starts_previous_day(now_time) {
-                today - Days::new(1)
-            } else {
-                today
-            };
-            date.and_hms_opt(start.hour() as u32, start.minute() as u32, 0)
-                .ok_or_else(|| "构造时间窗口起点失败".to_string())?
-        }
-    };
-
-    Ok(Some(
-        chrono::Local
-            .from_local_datetime(&window_start)
-            .single()
-            .ok_or_else(|| "构造时间窗口实例失败".to_string())?
-            .to_rfc3339(),
-    ))
+    let schedule = TemplateSchedule::parse(template).map_err(|error| error.to_string())?;
+    Ok(schedule
+        .current_window_start(&now)
+        .map(|window_start| window_start.to_rfc3339()))
 }
 
 fn normalize_account_id(account_id: Option<AccountId>) -> Option<AccountId> {
@@ -127,6 +100,9 @@ struct RuntimeDedupScope {
     account_id: Option<String>,
     start_time: Option<String>,
     end_time: Option<String>,
+    /// 只在设置了 cron 时参与哈希，保持旧模板的去重键不变
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cron: Option<String>,
     task_settings: Value,
 }
 
@@ -145,6 +121,9 @@ fn compute_dedup_scope_base_hash(
         account_id: account_id.map(str::to_string),
         start_time: template.and_then(|item| item.start_time.clone()),
         end_time: template.and_then(|item| item.end_time.clone()),
+        cron: template
+            .and_then(|item| item.calendar.cron_expr())
+            .map(str::to_string),
         task_settings: extract_task_settings_scope(template_values_json)?,
     };
     let json = serde_json::to_vec(&scope).map_err(|error| error.to_string())?;
@@ -185,6 +164,15 @@ async fn load_script_bundle(script_id: ScriptId) -> Result<LoadedScriptBundle, S
     let policy_ids = policies.iter().map(|policy| policy.id).collect();
     let policy_group_ids = policy_groups.iter().map(|group| group.id).collect();
     let policy_set_ids = policy_sets.iter().map(|set| set.id).collect();
+    let diagnostics = lint_script_graph(&ScriptLintGraph {
+        script: &script,
+        tasks: &tasks,
+        policies: &policies,
+        policy_groups: &policy_groups,
+        policy_sets: &policy_sets,
+        group_policies: &group_policies,
+        set_groups: &set_groups,
+    });
 
     Ok(LoadedScriptBundle {
         script_id,
@@ -194,6 +182,7 @@ async fn load_script_bundle(script_id: ScriptId) -> Result<LoadedScriptBundle, S
         policy_ids,
         policy_group_ids,
         policy_set_ids,
+        diagnostics,
         snapshot: runner_protocol::message::ScriptBundleSnapshot {
             script_id,
             script_json: serialize_to_json_string(&script)?,
@@ -246,6 +235,58 @@ pub(super) async fn load_runtime_queue_for_current_window(
     Ok(queue)
 }
 
+async fn load_template_values_json(
+    device_id: DeviceId,
+    assignment: &AssignmentProfile,
+    account_id: Option<AccountId>,
+) -> Result<Option<String>, String> {
+    let Some(time_template_id) = assignment.time_template_id else {
+        return Ok(None);
+    };
+    find_template_values_with_fallback(
+        device_id,
+        assignment.script_id,
+        time_template_id,
+        account_id,
+    )
+    .await?
+    .map(|record| serde_json::to_string(&record.values).map_err(|e| e.to_string()))
+    .transpose()
+}
+
+/// 脚本登记了账号时，按账号顺序展开为逐个账号的运行参数，去重键随账号隔离
+async fn build_account_runs(
+    device_id: DeviceId,
+    assignment: &AssignmentProfile,
+    time_template: Option<&TimeTemplateProfile>,
+) -> Result<Vec<RuntimeAccountRun>, String> {
+    let accounts = list_script_accounts(assignment.script_id).await?;
+    let mut runs = Vec::new();
+    for account in accounts
+        .into_iter()
+        .filter(|account| account.runs_on(device_id))
+    {
+        let template_values_json =
+            load_template_values_json(device_id, assignment, Some(account.id.clone())).await?;
+        let dedup_scope_base_hash = compute_dedup_scope_base_hash(
+            device_id,
+            assignment.script_id,
+            assignment.time_template_id,
+            Some(account.id.as_str()),
+            time_template,
+            template_values_json.as_deref(),
+        )?;
+        runs.push(RuntimeAccountRun {
+            account_data_json: Some(serialize_to_json_string(&account.account_data)?),
+            account_id: account.id,
+            account_name: account.name,
+            template_values_json,
+            dedup_scope_base_hash,
+        });
+    }
+    Ok(runs)
+}
+
 async fn build_runtime_queue_item(
     device_id: DeviceId,
     assignment: AssignmentProfile,
@@ -258,18 +299,8 @@ async fn build_runtime_queue_item(
         Some(time_template_id) => load_time_template(time_template_id).await?,
         None => None,
     };
-    let template_values_json = match assignment.time_template_id {
-        Some(time_template_id) => find_template_values_with_fallback(
-            device_id,
-            assignment.script_id,
-            time_template_id,
-            account_id.clone(),
-        )
-        .await?
-        .map(|record| serde_json::to_string(&record.values).map_err(|e| e.to_string()))
-        .transpose()?,
-        None => None,
-    };
+    let template_values_json =
+        load_template_values_json(device_id, &assignment, account_id.clone()).await?;
     let dedup_scope_base_hash = compute_dedup_scope_base_hash(
         device_id,
         assignment.script_id,
@@ -278,6 +309,7 @@ async fn build_runtime_queue_item(
         time_template.as_ref(),
         template_values_json.as_deref(),
     )?;
+    let account_runs = build_account_runs(device_id, &assignment, time_template.as_ref()).await?;
 
     Ok(RuntimeQueueItem {
         dispatch_id: DispatchId::new_v7(),
@@ -287,11 +319,13 @@ async fn build_runtime_queue_item(
         script_id: assignment.script_id,
         time_template_id: assignment.time_template_id,
         account_id,
+        account_name: None,
         account_data_json: Some(account_data_json),
         order_index: assignment.index,
         window_start_at,
         template_values_json,
         dedup_scope_base_hash,
+        account_runs,
     })
 }
 
@@ -328,6 +362,7 @@ mod tests {
             name: "sample".to_string(),
             start_time: start_time.map(str::to_string),
             end_time: end_time.map(str::to_string),
+            calendar: Default::default(),
         }
     }
 
@@ -392,4 +427,22 @@ mod tests {
         assert_ne!(base, changed_window);
         assert_ne!(base, changed_task_setting);
     }
+
+    #[test]
+    fn dedup_scope_base_hash_is_scoped_by_account() {
+        let hash_for = |account_id: Option<&str>| {
+            compute_dedup_scope_base_hash(
+                UuidV7(1),
+                UuidV7(2),
+                Some(UuidV7(3)),
+                account_id,
+                Some(&sample_template(Some("09:00"), Some("18:00"))),
+                None,
+            )
+            .expect("hash")
+        };
+
+        assert_ne!(hash_for(Some("main")), hash_for(Some("alt")));
+        assert_ne!(hash_for(None), hash_for(Some("main")));
+    }
 }
@@ -1,7 +1,11 @@
 use super::LoadedScriptBundle;
+use crate::infra::logging::log_trait::Log;
 use ad_kernel::ids::ScriptId;
 use runner_protocol::message::RunTarget;
 
+/// 启动失败提示中最多列出的错误条数
+const MAX_REPORTED_DIAGNOSTICS: usize = 3;
+
 pub(super) fn validate_run_target_support(
     run_target: &RunTarget,
     bundles: &[LoadedScriptBundle],
@@ -68,3 +72,35 @@ pub(super) fn validate_run_target_support(
         }
     }
 }
+
+/// 拒绝带有静态检查错误的脚本，警告只写日志
+pub(super) fn validate_script_diagnostics(bundles: &[LoadedScriptBundle]) -> Result<(), String> {
+    for bundle in bundles {
+        let (errors, warnings): (Vec<_>, Vec<_>) = bundle
+            .diagnostics
+            .iter()
+            .partition(|diagnostic| diagnostic.is_error());
+        for warning in &warnings {
+            Log::warn(&format!(
+                "[ lint ] 脚本[{}]: {}",
+                bundle.script_name, warning.message
+            ));
+        }
+        if errors.is_empty() {
+            continue;
+        }
+        let preview = errors
+            .iter()
+            .take(MAX_REPORTED_DIAGNOSTICS)
+            .map(|diagnostic| diagnostic.message.as_str())
+            .collect::<Vec<_>>()
+            .join("；");
+        return Err(format!(
+            "脚本[{}]静态检查发现 {} 个错误: {}",
+            bundle.script_name,
+            errors.len(),
+            preview
+        ));
+    }
+    Ok(())
+}
@@ -1,7 +1,8 @@
 use ad_kernel::ids::{AssignmentId, DeviceId, ExecutionId, ScriptId, SessionId, StepId, TaskId};
 use domain_device::TimeoutAction;
 use runner_protocol::message::{
-    ConnectionStatusKind, RuntimeLifecyclePhase, RuntimeProgressPhase, RuntimeScheduleStatus,
+    ConnectionStatusKind, RuntimeDebugPausedEvent, RuntimeLifecyclePhase, RuntimeProgressPhase,
+    RuntimeScheduleStatus,
 };
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq, ts_rs::TS)]
@@ -157,6 +158,14 @@ pub struct DeviceTimeoutEventPayload {
     pub at: String,
 }
 
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq, ts_rs::TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct DeviceDebugPausedEventPayload {
+    pub device_id: DeviceId,
+    pub paused: RuntimeDebugPausedEvent,
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq, ts_rs::TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
@@ -5,8 +5,8 @@ use super::runtime::{
     dispatch_session_to_child, emit_queue_finished_progress, ensure_device_capture_ready,
     ensure_device_ready_for_manual, request_child_connection_action,
     restart_device_runtime_internal, send_capture_control, send_process_control,
-    set_connection_status, shutdown_device_runtime_internal, spawn_device_runtime_internal,
-    wait_for_capture_result, wait_for_ipc_client,
+    send_session_control, set_connection_status, shutdown_device_runtime_internal,
+    spawn_device_runtime_internal, wait_for_capture_result, wait_for_ipc_client,
 };
 use super::scheduler::{
     dispatch_next_scheduled_queue_item, dispatch_priority, ensure_planner_batch_for_device,
@@ -23,7 +23,7 @@ use crate::api::local::execution::{
 use crate::infra::context::child_process_manager::get_process_manager;
 use crate::infra::context::main_process::MainProcessCtx;
 use crate::infra::logging::log_trait::Log;
-use ad_kernel::ids::{BatchId, DeviceId, now_millis_string};
+use ad_kernel::ids::{BatchId, DeviceId, StepId, now_millis_string};
 use chrono::Local;
 use domain_device::DeviceProfile;
 use domain_schedule::{AssignmentScheduleStatus, AssignmentTriggerSource};
@@ -34,7 +34,8 @@ use infra_sqlite::{
     update_assignment_schedule_status,
 };
 use runner_protocol::message::{
-    ConnectionAction, ConnectionStatusKind, DispatchSource, ProcessAction, RunTarget,
+    ConnectionAction, ConnectionStatusKind, DebugResumeMode, DispatchSource, ProcessAction,
+    RunTarget, SessionControlMessage,
 };
 use tauri::{Manager, command};
 
@@ -235,6 +236,51 @@ pub async fn cmd_device_pause(
     ))
 }
 
+#[command]
+pub async fn cmd_device_debug_set_breakpoints(
+    app_handle: tauri::AppHandle,
+    device_id: DeviceId,
+    step_ids: Vec<StepId>,
+) -> Result<String, String> {
+    let count = step_ids.len();
+    send_session_control(
+        device_id,
+        SessionControlMessage::SetBreakpoints { step_ids },
+    )
+    .await;
+    Ok(format!(
+        "已向设备[{}]同步 {} 个调试断点",
+        device_log_label(&app_handle, device_id),
+        count
+    ))
+}
+
+#[command]
+pub async fn cmd_device_debug_pause(
+    app_handle: tauri::AppHandle,
+    device_id: DeviceId,
+) -> Result<String, String> {
+    send_session_control(device_id, SessionControlMessage::DebugPause).await;
+    Ok(format!(
+        "已向设备[{}]发送调试暂停命令",
+        device_log_label(&app_handle, device_id)
+    ))
+}
+
+#[command]
+pub async fn cmd_device_debug_resume(
+    app_handle: tauri::AppHandle,
+    device_id: DeviceId,
+    mode: DebugResumeMode,
+) -> Result<String, String> {
+    send_session_control(device_id, SessionControlMessage::DebugResume { mode }).await;
+    Ok(format!(
+        "已向设备[{}]发送调试继续命令: {:?}",
+        device_log_label(&app_handle, device_id),
+        mode
+    ))
+}
+
 #[command]
 pub async fn cmd_sync_device_runtime_session(
     app_handle: tauri::AppHandle,
@@ -13,8 +13,8 @@ use super::state::{
 use crate::api::local::execution::DeviceRuntimeProgressPhase;
 use crate::infra::logging::log_trait::Log;
 use ad_kernel::ids::{DeviceId, TemplateId};
-use chrono::{Days, Local, TimeZone};
-use domain_schedule::TimeWindow;
+use chrono::Local;
+use domain_schedule::TemplateSchedule;
 use domain_schedule::{AssignmentScheduleProfile, TimeTemplateProfile};
 use domain_schedule::{AssignmentScheduleStatus, AssignmentTriggerSource};
 use infra_sqlite::{get_all_devices, get_time_template, list_assignments};
@@ -30,33 +30,8 @@ fn compute_next_due_from_template(
     template: &TimeTemplateProfile,
     now: chrono::DateTime<Local>,
 ) -> Result<Option<chrono::DateTime<Local>>, String> {
-    let window = TimeWindow::parse(template.start_time.as_deref(), template.end_time.as_deref())
-        .map_err(|error| error.to_string())?;
-    let Some(start) = window.start() else {
-        return Ok(None);
-    };
-    let today = now.date_naive();
-    let today_due = Local
-        .from_local_datetime(
-            &today
-                .and_hms_opt(start.hour() as u32, start.minute() as u32, 0)
-                .ok_or_else(|| "构造下一次调度时间失败".to_string())?,
-        )
-        .single()
-        .ok_or_else(|| "构造下一次调度时间失败".to_string())?;
-    if today_due > now {
-        return Ok(Some(today_due));
-    }
-    Ok(Some(
-        Local
-            .from_local_datetime(
-                &(today + Days::new(1))
-                    .and_hms_opt(start.hour() as u32, start.minute() as u32, 0)
-                    .ok_or_else(|| "构造下一次调度时间失败".to_string())?,
-            )
-            .single()
-            .ok_or_else(|| "构造下一次调度时间失败".to_string())?,
-    ))
+    let schedule = TemplateSchedule::parse(template).map_err(|error| error.to_string())?;
+    Ok(schedule.next_due_after(&now))
 }
 
 pub(super) fn dispatch_priority(source: &DispatchSource) -> u8 {
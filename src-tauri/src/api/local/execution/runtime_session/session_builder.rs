@@ -1,6 +1,7 @@
 use super::access_control::validate_published_script_runtime_access;
 use crate::api::local::execution::bundle_loader::{
     load_runtime_queue, load_script_bundles, validate_run_target_support,
+    validate_script_diagnostics,
 };
 use crate::app::config::vision_cache_conf::get_vision_text_cache_runtime_config_app;
 use ad_kernel::ids::{AssignmentId, DeviceId, SessionId};
@@ -39,6 +40,8 @@ fn to_runtime_policy(device_profile: &DeviceProfile) -> RuntimeExecutionPolicy {
         progress_timeout_ms: u64::from(execution_policy.progress_timeout_ms),
         timeout_action: execution_policy.timeout_action.clone(),
         timeout_notify_channels: execution_policy.timeout_notify_channels.clone(),
+        retry_policy: execution_policy.retry_policy.clone(),
+        humanize: execution_policy.humanize.clone(),
     }
 }
 
@@ -63,6 +66,7 @@ pub(super) async fn load_runtime_session_for_queue_item(
     let loaded_script_bundles = load_script_bundles(&run_target, &queue).await?;
     validate_published_script_runtime_access(app_handle, &loaded_script_bundles).await?;
     validate_run_target_support(&run_target, &loaded_script_bundles)?;
+    validate_script_diagnostics(&loaded_script_bundles)?;
     let script_bundles = loaded_script_bundles
         .into_iter()
         .map(|bundle| bundle.snapshot)
@@ -98,11 +102,13 @@ pub(super) async fn build_runtime_session_snapshot(
                     script_id,
                     time_template_id: None,
                     account_id: None,
+                    account_name: None,
                     account_data_json: None,
                     order_index: 0,
                     window_start_at: None,
                     template_values_json: None,
                     dedup_scope_base_hash: String::new(),
+                    account_runs: Vec::new(),
                 });
             }
             queue
@@ -112,6 +118,7 @@ pub(super) async fn build_runtime_session_snapshot(
     let loaded_script_bundles = load_script_bundles(&run_target, &queue).await?;
     validate_published_script_runtime_access(app_handle, &loaded_script_bundles).await?;
     validate_run_target_support(&run_target, &loaded_script_bundles)?;
+    validate_script_diagnostics(&loaded_script_bundles)?;
     let script_bundles = loaded_script_bundles
         .into_iter()
         .map(|bundle| bundle.snapshot)
@@ -4,21 +4,27 @@ use crate::api::local::execution::{
     load_runtime_queue_for_current_window, notify_auto_dispatch_reschedule, planner_queue_items,
 };
 use crate::infra::logging::log_trait::Log;
+use crate::infra::mail::local_day_range;
 use ad_kernel::ids::{AccountId, AssignmentId, DeviceId, ScriptId, TemplateId};
-use domain_schedule::TimeWindow;
+use domain_schedule::TemplateSchedule;
 use domain_schedule::{
-    AssignmentProfile, AssignmentScheduleProfile, ExecutionScheduleProfile, TemplateValueProfile,
-    TimeTemplateProfile,
+    AssignmentProfile, AssignmentScheduleProfile, ExecutionScheduleProfile, RunSummaryReport,
+    ScriptAccountProfile, TemplateValueProfile, TimeTemplateProfile,
 };
 use infra_sqlite::{
-    clear_schedules_by_device, clear_schedules_by_script, delete_assignment, delete_template_value,
-    delete_time_template, find_template_value_exact, list_assignments, list_execution_schedules,
-    list_time_templates, save_template_value, save_time_template,
+    build_run_summary_report, clear_schedules_by_device, clear_schedules_by_script,
+    delete_assignment, delete_template_value, delete_time_template, find_template_value_exact,
+    list_assignments, list_execution_schedules, list_time_templates, save_template_value,
+    save_time_template,
 };
 use infra_sqlite::{
     compact_assignment_indices, load_assignment_schedules_by_device, reorder_assignment_indices,
     sync_active_planner_schedule_order_indices, sync_active_planner_schedules_from_queue,
 };
+use infra_sqlite::{
+    delete_script_account, list_account_execution_schedules, list_script_accounts,
+    reorder_script_accounts, save_script_account,
+};
 use tauri::command;
 
 fn assignment_scope_label(assignment: &AssignmentProfile) -> String {
@@ -188,6 +194,20 @@ pub async fn get_assignment_schedules_by_device_cmd(
     load_assignment_schedules_by_device(device_id).await
 }
 
+/// 汇总时间区间内的运行结果，未指定时默认统计今天
+#[command]
+pub async fn get_run_summary_report_cmd(
+    range_start: Option<String>,
+    range_end: Option<String>,
+) -> Result<RunSummaryReport, String> {
+    let (today_start, today_end) = local_day_range(chrono::Local::now().date_naive());
+    build_run_summary_report(
+        range_start.as_deref().unwrap_or(&today_start),
+        range_end.as_deref().unwrap_or(&today_end),
+    )
+    .await
+}
+
 /// 清除指定设备的所有调度记录
 #[command]
 pub async fn clear_schedules_cmd(device_id: DeviceId) -> Result<(), String> {
@@ -214,8 +234,7 @@ pub async fn save_time_template_cmd(
     _app_handle: tauri::AppHandle,
     template: TimeTemplateProfile,
 ) -> Result<(), String> {
-    TimeWindow::parse(template.start_time.as_deref(), template.end_time.as_deref())
-        .map_err(|error| error.to_string())?;
+    TemplateSchedule::parse(&template).map_err(|error| error.to_string())?;
     save_time_template(&template).await?;
     notify_auto_dispatch_reschedule();
     Ok(())
@@ -313,3 +332,69 @@ pub async fn delete_script_time_template_values_cmd(
     let account_id = normalize_account_id(account_id);
     delete_template_value(device_id, script_id, time_template_id, account_id).await
 }
+
+// ========== 脚本账号 ==========
+
+/// 获取脚本登记的全部账号（按轮换顺序）
+#[command]
+pub async fn get_script_accounts_cmd(
+    script_id: ScriptId,
+) -> Result<Vec<ScriptAccountProfile>, String> {
+    list_script_accounts(script_id).await
+}
+
+/// 保存（新增或更新）脚本账号，未指定 id 时自动生成
+#[command]
+pub async fn save_script_account_cmd(mut account: ScriptAccountProfile) -> Result<(), String> {
+    account.name = account.name.trim().to_string();
+    if account.name.is_empty() {
+        return Err("账号名称不能为空".to_string());
+    }
+    account.id = normalize_account_id(Some(account.id))
+        .unwrap_or_else(|| ad_kernel::ids::UuidV7::new_v7().to_string());
+
+    let now = chrono::Local::now().to_rfc3339();
+    let existing = list_script_accounts(account.script_id)
+        .await?
+        .into_iter()
+        .find(|item| item.id == account.id);
+    account.created_at = existing
+        .map(|item| item.created_at)
+        .unwrap_or_else(|| now.clone());
+    account.updated_at = now;
+    save_script_account(&account).await?;
+    notify_auto_dispatch_reschedule();
+    Ok(())
+}
+
+/// 删除脚本账号，同时清理该账号的模板覆盖值与持久化存储
+#[command]
+pub async fn delete_script_account_cmd(
+    script_id: ScriptId,
+    account_id: AccountId,
+) -> Result<(), String> {
+    delete_script_account(script_id, &account_id).await?;
+    notify_auto_dispatch_reschedule();
+    Ok(())
+}
+
+/// 批量更新账号轮换顺序
+#[command]
+pub async fn reorder_script_accounts_cmd(
+    script_id: ScriptId,
+    account_ids: Vec<AccountId>,
+) -> Result<(), String> {
+    reorder_script_accounts(script_id, &account_ids).await?;
+    notify_auto_dispatch_reschedule();
+    Ok(())
+}
+
+/// 获取某账号最近的任务运行记录
+#[command]
+pub async fn get_script_account_schedules_cmd(
+    script_id: ScriptId,
+    account_id: AccountId,
+    limit: Option<u32>,
+) -> Result<Vec<ExecutionScheduleProfile>, String> {
+    list_account_execution_schedules(script_id, &account_id, limit.unwrap_or(100)).await
+}
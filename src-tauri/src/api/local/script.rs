@@ -1,6 +1,10 @@
 pub(crate) mod commands;
 pub(crate) mod dto;
+pub(crate) mod lint;
+pub(crate) mod merge;
 pub(crate) mod policies;
+pub(crate) mod snapshots;
 pub(crate) mod transfer_records;
 
 pub(crate) use commands::*;
+pub(crate) use lint::{lint_script_cmd, lint_script_editor_cmd};
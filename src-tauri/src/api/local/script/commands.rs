@@ -3,26 +3,30 @@ use crate::api::local::execution::{
     notify_auto_dispatch_planner,
 };
 use crate::api::local::script::dto::{ScriptEditorSaveRequest, ScriptTable, ScriptTaskTable};
+use crate::api::local::script::snapshots::{
+    record_baseline_script_snapshot, record_script_snapshot,
+};
 use crate::api::response::ApiResponse;
 use crate::api::server::dto::apply_current_client_capability;
 use crate::api::server::local_scripts_dir;
 use crate::api::server::profile_cache::load_current_authenticated_user;
+use crate::infra::logging::log_trait::Log;
 use ad_kernel::ids::{PolicyGroupId, PolicyId, PolicySetId, ScriptId};
 use domain_script::{
     PolicyGroupPolicyLink, PolicyGroupProfile, PolicyProfile, PolicySetGroupLink, PolicySetProfile,
-    ScriptProfile, ScriptTaskProfile,
+    ScriptGraphSnapshot, ScriptProfile, ScriptTaskProfile,
 };
 use domain_script::{clone_cloud_id, ensure_clone_allowed, ensure_editable};
 use infra_sqlite::{
     delete_script, ensure_existing_script_editable, ensure_stored_script_editable,
-    save_cloned_script_graph, save_script_editor_graph,
+    save_cloned_script_graph, save_script_editor_graph, save_script_merge_base,
 };
 use infra_sqlite::{
     find_dev_script_by_cloud_id, get_script, list_group_policy_links, list_policies,
     list_policy_groups, list_policy_sets, list_script_tasks, list_scripts, list_set_group_links,
     save_script,
 };
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use tauri::command;
 
 /// 获取所有脚本配置
@@ -75,32 +79,14 @@ fn parse_relation_ids(values: &[String], valid_ids: &HashSet<String>) -> Vec<Str
         .collect()
 }
 
-#[command]
-pub async fn save_script_editor_cmd(
-    app_handle: tauri::AppHandle,
-    payload: ScriptEditorSaveRequest,
-) -> Result<(), String> {
-    let ScriptEditorSaveRequest {
-        script,
-        tasks,
-        policies,
-        policy_groups,
-        policy_sets,
-        group_policy_ids_by_group_id,
-        set_group_ids_by_set_id,
-    } = payload;
-    let mut script: ScriptProfile = script.into();
-    let tasks: Vec<ScriptTaskProfile> = tasks.into_iter().map(Into::into).collect();
-    let policies: Vec<PolicyProfile> = policies.into_iter().map(Into::into).collect();
-    let policy_groups: Vec<PolicyGroupProfile> =
-        policy_groups.into_iter().map(Into::into).collect();
-    let policy_sets: Vec<PolicySetProfile> = policy_sets.into_iter().map(Into::into).collect();
-
-    ensure_editable(&script.info.script_type).map_err(|error| error.to_string())?;
-
-    ensure_existing_script_editable(script.id).await?;
-    apply_current_client_capability(&mut script.info);
-
+/// 把编辑器提交的关联 ID 表转换为关联记录，丢弃指向不存在对象的 ID
+pub(crate) fn editor_relation_links(
+    group_policy_ids_by_group_id: &HashMap<String, Vec<String>>,
+    set_group_ids_by_set_id: &HashMap<String, Vec<String>>,
+    policies: &[PolicyProfile],
+    policy_groups: &[PolicyGroupProfile],
+    policy_sets: &[PolicySetProfile],
+) -> (Vec<PolicyGroupPolicyLink>, Vec<PolicySetGroupLink>) {
     let policy_ids = policies
         .iter()
         .map(|policy| policy.id.to_string())
@@ -146,8 +132,46 @@ pub async fn save_script_editor_cmd(
                 })
         })
         .collect::<Vec<_>>();
+    (group_policies, set_groups)
+}
+
+#[command]
+pub async fn save_script_editor_cmd(
+    app_handle: tauri::AppHandle,
+    payload: ScriptEditorSaveRequest,
+) -> Result<(), String> {
+    let ScriptEditorSaveRequest {
+        script,
+        tasks,
+        policies,
+        policy_groups,
+        policy_sets,
+        group_policy_ids_by_group_id,
+        set_group_ids_by_set_id,
+        snapshot_message,
+    } = payload;
+    let mut script: ScriptProfile = script.into();
+    let tasks: Vec<ScriptTaskProfile> = tasks.into_iter().map(Into::into).collect();
+    let policies: Vec<PolicyProfile> = policies.into_iter().map(Into::into).collect();
+    let policy_groups: Vec<PolicyGroupProfile> =
+        policy_groups.into_iter().map(Into::into).collect();
+    let policy_sets: Vec<PolicySetProfile> = policy_sets.into_iter().map(Into::into).collect();
+
+    ensure_editable(&script.info.script_type).map_err(|error| error.to_string())?;
+
+    ensure_existing_script_editable(script.id).await?;
+    apply_current_client_capability(&mut script.info);
+
+    let (group_policies, set_groups) = editor_relation_links(
+        &group_policy_ids_by_group_id,
+        &set_group_ids_by_set_id,
+        &policies,
+        &policy_groups,
+        &policy_sets,
+    );
 
     let affected_device_ids = load_assigned_device_ids_by_script(script.id).await?;
+    record_baseline_script_snapshot(&app_handle, script.id).await;
     save_script_editor_graph(
         &script,
         &policies,
@@ -158,6 +182,13 @@ pub async fn save_script_editor_cmd(
         &tasks,
     )
     .await?;
+    let message = snapshot_message
+        .map(|message| message.trim().to_string())
+        .filter(|message| !message.is_empty())
+        .unwrap_or_else(|| "编辑器保存".to_string());
+    if let Err(error) = record_script_snapshot(&app_handle, script.id, message).await {
+        Log::warn(&format!("创建脚本快照失败: {}", error));
+    }
     notify_auto_dispatch_planner();
     enqueue_device_runtime_session_refresh_jobs(
         &app_handle,
@@ -368,6 +399,31 @@ pub async fn clone_local_script_cmd(
         return ApiResponse::error(Some(e));
     }
 
+    // 从云端下载版本克隆时记录合并基线，供上游更新后三方合并
+    if let Some(cloud_id) = cloud_id {
+        let base_ver_num = script.info.ver_num;
+        let base = ScriptGraphSnapshot {
+            script,
+            tasks,
+            policies,
+            policy_groups,
+            policy_sets,
+            group_policies,
+            set_groups,
+        };
+        if let Err(error) = save_script_merge_base(
+            new_script_id,
+            cloud_id,
+            base_ver_num,
+            &base,
+            chrono::Utc::now().to_rfc3339(),
+        )
+        .await
+        {
+            Log::warn(&format!("记录克隆合并基线失败: {}", error));
+        }
+    }
+
     notify_auto_dispatch_planner();
     if let Err(error) = enqueue_device_runtime_session_refresh_jobs(
         &app_handle,
@@ -1,8 +1,8 @@
 use ad_kernel::ids::{PolicyGroupId, PolicyId, PolicySetId, ScriptId, TaskId};
 use domain_script::{
     PolicyGroupInfo, PolicyGroupProfile, PolicyInfo, PolicyProfile, PolicySetInfo,
-    PolicySetProfile, ScriptInfo, ScriptProfile, ScriptTask, ScriptTaskProfile, TaskCycle,
-    TaskRowType, TaskTone, TaskTriggerMode,
+    PolicySetProfile, ScriptInfo, ScriptMergeConflict, ScriptProfile, ScriptSnapshotDiff,
+    ScriptTask, ScriptTaskProfile, TaskCycle, TaskRowType, TaskTone, TaskTriggerMode,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -175,6 +175,27 @@ pub struct ScriptEditorSaveRequest {
     pub policy_sets: Vec<PolicySetTable>,
     pub group_policy_ids_by_group_id: HashMap<String, Vec<String>>,
     pub set_group_ids_by_set_id: HashMap<String, Vec<String>>,
+    /// 本次保存的快照说明
+    #[serde(default)]
+    pub snapshot_message: Option<String>,
+}
+
+/// 克隆脚本与上游新版本的三方合并预览
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptUpstreamMergePreview {
+    pub cloud_id: ScriptId,
+    pub base_ver_num: u32,
+    pub upstream_ver_num: u32,
+    pub upstream_ver_name: String,
+    /// 克隆后本地的改动
+    pub local_changes: ScriptSnapshotDiff,
+    /// 上游新版本的改动
+    pub upstream_changes: ScriptSnapshotDiff,
+    /// 应用合并后本地脚本将发生的变化
+    pub merged_changes: ScriptSnapshotDiff,
+    pub conflicts: Vec<ScriptMergeConflict>,
 }
 
 #[cfg(test)]
@@ -0,0 +1,98 @@
+use crate::api::local::script::commands::editor_relation_links;
+use crate::api::local::script::dto::ScriptEditorSaveRequest;
+use ad_kernel::ids::ScriptId;
+use domain_script::{
+    PolicyGroupPolicyLink, PolicyGroupProfile, PolicyProfile, PolicySetGroupLink, PolicySetProfile,
+    RhaiSnippetKind, ScriptDiagnostic, ScriptLintInput, ScriptProfile, ScriptTaskProfile,
+    lint_script_bundle,
+};
+use infra_sqlite::{
+    get_script, list_group_policy_links, list_policies, list_policy_groups, list_policy_sets,
+    list_script_tasks, list_set_group_links,
+};
+use tauri::command;
+
+/// 一次运行或编辑器检查所需的完整脚本图
+pub(crate) struct ScriptLintGraph<'a> {
+    pub(crate) script: &'a ScriptProfile,
+    pub(crate) tasks: &'a [ScriptTaskProfile],
+    pub(crate) policies: &'a [PolicyProfile],
+    pub(crate) policy_groups: &'a [PolicyGroupProfile],
+    pub(crate) policy_sets: &'a [PolicySetProfile],
+    pub(crate) group_policies: &'a [PolicyGroupPolicyLink],
+    pub(crate) set_groups: &'a [PolicySetGroupLink],
+}
+
+/// 静态检查脚本；Rhai 只做语法编译，模块导入与函数调用留到运行时解析
+pub(crate) fn lint_script_graph(graph: &ScriptLintGraph<'_>) -> Vec<ScriptDiagnostic> {
+    let engine = rhai::Engine::new();
+    let input = ScriptLintInput {
+        info: &graph.script.info,
+        tasks: graph.tasks,
+        policies: graph.policies,
+        policy_groups: graph.policy_groups,
+        policy_sets: graph.policy_sets,
+        group_policies: graph.group_policies,
+        set_groups: graph.set_groups,
+    };
+    lint_script_bundle(&input, |kind, code| {
+        match kind {
+            RhaiSnippetKind::Script => engine.compile(code).map(|_| ()),
+            RhaiSnippetKind::Expression => engine.compile_expression(code).map(|_| ()),
+        }
+        .map_err(|error| error.to_string())
+    })
+}
+
+/// 获取脚本的静态检查结果，供编辑器展示
+#[command]
+pub async fn lint_script_cmd(script_id: ScriptId) -> Result<Vec<ScriptDiagnostic>, String> {
+    let script = get_script(script_id)
+        .await?
+        .ok_or_else(|| format!("脚本[{}]不存在", script_id))?;
+    let tasks = list_script_tasks(script_id).await?;
+    let policies = list_policies(script_id).await?;
+    let policy_groups = list_policy_groups(script_id).await?;
+    let policy_sets = list_policy_sets(script_id).await?;
+    let group_policies = list_group_policy_links(script_id).await?;
+    let set_groups = list_set_group_links(script_id).await?;
+    Ok(lint_script_graph(&ScriptLintGraph {
+        script: &script,
+        tasks: &tasks,
+        policies: &policies,
+        policy_groups: &policy_groups,
+        policy_sets: &policy_sets,
+        group_policies: &group_policies,
+        set_groups: &set_groups,
+    }))
+}
+
+/// 检查编辑器中尚未保存的脚本图，编辑过程中即可定位问题步骤
+#[command]
+pub async fn lint_script_editor_cmd(
+    payload: ScriptEditorSaveRequest,
+) -> Result<Vec<ScriptDiagnostic>, String> {
+    let script: ScriptProfile = payload.script.into();
+    let tasks: Vec<ScriptTaskProfile> = payload.tasks.into_iter().map(Into::into).collect();
+    let policies: Vec<PolicyProfile> = payload.policies.into_iter().map(Into::into).collect();
+    let policy_groups: Vec<PolicyGroupProfile> =
+        payload.policy_groups.into_iter().map(Into::into).collect();
+    let policy_sets: Vec<PolicySetProfile> =
+        payload.policy_sets.into_iter().map(Into::into).collect();
+    let (group_policies, set_groups) = editor_relation_links(
+        &payload.group_policy_ids_by_group_id,
+        &payload.set_group_ids_by_set_id,
+        &policies,
+        &policy_groups,
+        &policy_sets,
+    );
+    Ok(lint_script_graph(&ScriptLintGraph {
+        script: &script,
+        tasks: &tasks,
+        policies: &policies,
+        policy_groups: &policy_groups,
+        policy_sets: &policy_sets,
+        group_policies: &group_policies,
+        set_groups: &set_groups,
+    }))
+}
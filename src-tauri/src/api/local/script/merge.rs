@@ -0,0 +1,120 @@
+use crate::api::local::execution::{
+    enqueue_device_runtime_session_refresh_jobs, load_assigned_device_ids_by_script,
+    notify_auto_dispatch_planner,
+};
+use crate::api::local::script::dto::ScriptUpstreamMergePreview;
+use crate::api::local::script::snapshots::record_script_snapshot;
+use crate::api::server::dto::apply_current_client_capability;
+use crate::api::server::find_replaceable_local_published_script;
+use ad_kernel::ids::ScriptId;
+use chrono::Utc;
+use domain_script::{
+    MergeResolution, ScriptGraphSnapshot, ScriptMergeOutcome, diff_script_snapshots,
+    ensure_editable, merge_script_graphs,
+};
+use infra_sqlite::{
+    ScriptMergeBase, ensure_existing_script_editable, get_script_merge_base,
+    load_script_graph_snapshot, save_script_upstream_merge,
+};
+use std::collections::HashMap;
+use tauri::{AppHandle, command};
+
+struct UpstreamMergeInputs {
+    base: ScriptMergeBase,
+    ours: ScriptGraphSnapshot,
+    theirs: ScriptGraphSnapshot,
+}
+
+impl UpstreamMergeInputs {
+    fn merge(
+        &self,
+        resolutions: &HashMap<String, MergeResolution>,
+    ) -> Result<ScriptMergeOutcome, String> {
+        merge_script_graphs(&self.base.snapshot, &self.ours, &self.theirs, resolutions)
+    }
+}
+
+async fn load_upstream_merge_inputs(script_id: ScriptId) -> Result<UpstreamMergeInputs, String> {
+    let ours = load_script_graph_snapshot(script_id)
+        .await?
+        .ok_or_else(|| "脚本不存在".to_string())?;
+    ensure_editable(&ours.script.info.script_type).map_err(|error| error.to_string())?;
+    let base = get_script_merge_base(script_id)
+        .await?
+        .ok_or_else(|| "该脚本没有克隆基线，无法与上游合并".to_string())?;
+    let upstream = find_replaceable_local_published_script(&base.cloud_id.to_string())
+        .await?
+        .ok_or_else(|| "未找到该脚本的云端下载版本，请先在脚本市场下载最新版本".to_string())?;
+    let theirs = load_script_graph_snapshot(upstream.id)
+        .await?
+        .ok_or_else(|| "读取云端下载版本失败".to_string())?;
+    Ok(UpstreamMergeInputs { base, ours, theirs })
+}
+
+/// 预览克隆脚本与上游新版本的三方合并结果
+#[command]
+pub async fn preview_script_upstream_merge_cmd(
+    script_id: ScriptId,
+    resolutions: Option<HashMap<String, MergeResolution>>,
+) -> Result<ScriptUpstreamMergePreview, String> {
+    let inputs = load_upstream_merge_inputs(script_id).await?;
+    let outcome = inputs.merge(&resolutions.unwrap_or_default())?;
+    Ok(ScriptUpstreamMergePreview {
+        cloud_id: inputs.base.cloud_id,
+        base_ver_num: inputs.base.base_ver_num,
+        upstream_ver_num: inputs.theirs.script.info.ver_num,
+        upstream_ver_name: inputs.theirs.script.info.ver_name.clone(),
+        local_changes: diff_script_snapshots(&inputs.base.snapshot, &inputs.ours),
+        upstream_changes: diff_script_snapshots(&inputs.base.snapshot, &outcome.upstream_base),
+        merged_changes: diff_script_snapshots(&inputs.ours, &outcome.merged),
+        conflicts: outcome.conflicts,
+    })
+}
+
+/// 应用三方合并，未指定处理方式的冲突保留本地
+#[command]
+pub async fn apply_script_upstream_merge_cmd(
+    app_handle: AppHandle,
+    script_id: ScriptId,
+    resolutions: HashMap<String, MergeResolution>,
+) -> Result<(), String> {
+    ensure_existing_script_editable(script_id).await?;
+    let inputs = load_upstream_merge_inputs(script_id).await?;
+    let ScriptMergeOutcome {
+        mut merged,
+        upstream_base,
+        ..
+    } = inputs.merge(&resolutions)?;
+    apply_current_client_capability(&mut merged.script.info);
+    let upstream_ver_num = inputs.theirs.script.info.ver_num;
+    let upstream_ver_name = inputs.theirs.script.info.ver_name.clone();
+
+    record_script_snapshot(&app_handle, script_id, "合并上游前自动保存".to_string()).await?;
+    let affected_device_ids = load_assigned_device_ids_by_script(script_id).await?;
+    save_script_upstream_merge(
+        &merged,
+        &ScriptMergeBase {
+            script_id,
+            cloud_id: inputs.base.cloud_id,
+            base_ver_num: upstream_ver_num,
+            snapshot: upstream_base,
+            updated_at: Utc::now().to_rfc3339(),
+        },
+    )
+    .await?;
+    record_script_snapshot(
+        &app_handle,
+        script_id,
+        format!("合并上游 {upstream_ver_name}"),
+    )
+    .await?;
+    notify_auto_dispatch_planner();
+    enqueue_device_runtime_session_refresh_jobs(
+        &app_handle,
+        affected_device_ids,
+        true,
+        false,
+        "apply_script_upstream_merge",
+    )?;
+    Ok(())
+}
@@ -0,0 +1,154 @@
+use crate::api::local::execution::{
+    enqueue_device_runtime_session_refresh_jobs, load_assigned_device_ids_by_script,
+    notify_auto_dispatch_planner,
+};
+use crate::api::server::dto::apply_current_client_capability;
+use crate::api::server::profile_cache::load_current_authenticated_user;
+use crate::infra::logging::log_trait::Log;
+use ad_kernel::ids::ScriptId;
+use chrono::Utc;
+use domain_script::{
+    ScriptGraphSnapshot, ScriptSnapshotDiff, ScriptSnapshotSummary, diff_script_snapshots,
+    ensure_editable,
+};
+use infra_sqlite::{
+    CreateScriptSnapshotInput, delete_script_snapshot, ensure_existing_script_editable,
+    get_latest_script_snapshot, get_script_snapshot, insert_script_snapshot, list_script_snapshots,
+    load_script_graph_snapshot, save_script_editor_graph,
+};
+use tauri::{AppHandle, command};
+
+/// 为脚本当前状态创建快照，与最近一次快照相同时跳过
+pub(crate) async fn record_script_snapshot(
+    app_handle: &AppHandle,
+    script_id: ScriptId,
+    message: String,
+) -> Result<Option<ScriptSnapshotSummary>, String> {
+    let Some(current) = load_script_graph_snapshot(script_id).await? else {
+        return Ok(None);
+    };
+    if let Some(latest) = get_latest_script_snapshot(script_id).await?
+        && diff_script_snapshots(&latest, &current).is_empty()
+    {
+        return Ok(None);
+    }
+    let author = load_current_authenticated_user(app_handle).map(|user| user.username);
+    insert_script_snapshot(
+        CreateScriptSnapshotInput {
+            id: uuid::Uuid::now_v7().to_string(),
+            script_id,
+            author,
+            message,
+            created_at: Utc::now().to_rfc3339(),
+        },
+        &current,
+    )
+    .await
+    .map(Some)
+}
+
+/// 脚本还没有任何快照时，先为写入前的状态留存一份基线
+pub(crate) async fn record_baseline_script_snapshot(app_handle: &AppHandle, script_id: ScriptId) {
+    let result = match get_latest_script_snapshot(script_id).await {
+        Ok(Some(_)) => return,
+        Ok(None) => {
+            record_script_snapshot(app_handle, script_id, "保存前自动保存".to_string()).await
+        }
+        Err(error) => Err(error),
+    };
+    if let Err(error) = result {
+        Log::warn(&format!("创建脚本基线快照失败: {}", error));
+    }
+}
+
+async fn load_owned_snapshot(
+    script_id: ScriptId,
+    snapshot_id: &str,
+) -> Result<ScriptGraphSnapshot, String> {
+    let snapshot = get_script_snapshot(snapshot_id)
+        .await?
+        .ok_or_else(|| format!("脚本快照不存在: {snapshot_id}"))?;
+    if snapshot.script.id != script_id {
+        return Err("快照不属于当前脚本".to_string());
+    }
+    Ok(snapshot)
+}
+
+/// 获取脚本的快照列表（按时间倒序）
+#[command]
+pub async fn list_script_snapshots_cmd(
+    script_id: ScriptId,
+) -> Result<Vec<ScriptSnapshotSummary>, String> {
+    list_script_snapshots(script_id).await
+}
+
+/// 比较两个快照；未指定目标快照时与脚本当前状态比较
+#[command]
+pub async fn diff_script_snapshots_cmd(
+    script_id: ScriptId,
+    base_snapshot_id: String,
+    target_snapshot_id: Option<String>,
+) -> Result<ScriptSnapshotDiff, String> {
+    let base = load_owned_snapshot(script_id, &base_snapshot_id).await?;
+    let target = match target_snapshot_id.filter(|value| !value.trim().is_empty()) {
+        Some(snapshot_id) => load_owned_snapshot(script_id, &snapshot_id).await?,
+        None => load_script_graph_snapshot(script_id)
+            .await?
+            .ok_or_else(|| "脚本不存在".to_string())?,
+    };
+    Ok(diff_script_snapshots(&base, &target))
+}
+
+/// 将脚本回滚到指定快照，回滚前后的状态都会留存快照
+#[command]
+pub async fn restore_script_snapshot_cmd(
+    app_handle: AppHandle,
+    script_id: ScriptId,
+    snapshot_id: String,
+) -> Result<(), String> {
+    let ScriptGraphSnapshot {
+        mut script,
+        tasks,
+        policies,
+        policy_groups,
+        policy_sets,
+        group_policies,
+        set_groups,
+    } = load_owned_snapshot(script_id, &snapshot_id).await?;
+    ensure_editable(&script.info.script_type).map_err(|error| error.to_string())?;
+    ensure_existing_script_editable(script_id).await?;
+    apply_current_client_capability(&mut script.info);
+
+    record_script_snapshot(&app_handle, script_id, "回滚前自动保存".to_string()).await?;
+    let affected_device_ids = load_assigned_device_ids_by_script(script_id).await?;
+    save_script_editor_graph(
+        &script,
+        &policies,
+        &policy_groups,
+        &policy_sets,
+        &group_policies,
+        &set_groups,
+        &tasks,
+    )
+    .await?;
+    record_script_snapshot(&app_handle, script_id, format!("回滚到快照 {snapshot_id}")).await?;
+    notify_auto_dispatch_planner();
+    enqueue_device_runtime_session_refresh_jobs(
+        &app_handle,
+        affected_device_ids,
+        true,
+        false,
+        "restore_script_snapshot",
+    )?;
+    Ok(())
+}
+
+/// 删除单个脚本快照
+#[command]
+pub async fn delete_script_snapshot_cmd(
+    script_id: ScriptId,
+    snapshot_id: String,
+) -> Result<(), String> {
+    load_owned_snapshot(script_id, &snapshot_id).await?;
+    delete_script_snapshot(&snapshot_id).await
+}
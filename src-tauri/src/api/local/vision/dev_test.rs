@@ -74,11 +74,11 @@ fn validate_capture_request(
                     Ok(())
                 }
             }
-            CapMethod::Adb => {
+            CapMethod::Adb | CapMethod::AdbStream { .. } => {
                 Err("当前设备保存的截图方式是 ADB，但本次请求走了窗口截图".to_string())
             }
         },
-        CaptureMethod::Adb => describe_adb_config_issue(adb_conf)
+        CaptureMethod::Adb | CaptureMethod::AdbStream => describe_adb_config_issue(adb_conf)
             .map(|issue| format!("ADB 截图配置无效：{}", issue))
             .map_or(Ok(()), Err),
     }
@@ -144,19 +144,15 @@ fn normalize_detector_conf(app_handle: &AppHandle, detector_conf: DetectorType)
 
 fn normalize_recognizer_conf(
     app_handle: &AppHandle,
-    recognizer_conf: RecognizerType,
+    mut recognizer_conf: RecognizerType,
 ) -> RecognizerType {
     let scripts_dir = resolve_scripts_dir(app_handle);
-    match recognizer_conf {
-        RecognizerType::PaddleCrnn(mut crnn) => {
-            if crnn.base_model.model_source == ModelSource::Custom {
-                crnn.base_model.model_path =
-                    resolve_custom_model_path(&crnn.base_model.model_path, &scripts_dir);
-            }
-            resolve_optional_relative_path(&mut crnn.dict_path, &scripts_dir);
-            RecognizerType::PaddleCrnn(crnn)
-        }
+    let base_model = recognizer_conf.base_model_mut();
+    if base_model.model_source == ModelSource::Custom {
+        base_model.model_path = resolve_custom_model_path(&base_model.model_path, &scripts_dir);
     }
+    resolve_optional_relative_path(recognizer_conf.dict_path_mut(), &scripts_dir);
+    recognizer_conf
 }
 
 #[command]
@@ -170,7 +166,10 @@ pub async fn dev_capture_test(
     let capture_method_for_error = capture_method.clone();
     let device_conf_for_error = device_conf.clone();
     let adb_conf_for_error = adb_conf.clone();
-    if matches!(capture_method, CaptureMethod::Adb) {
+    if matches!(
+        capture_method,
+        CaptureMethod::Adb | CaptureMethod::AdbStream
+    ) {
         ADBCtx::new(adb_conf).await?;
     }
     let device_ctx = DeviceCtx::new(Arc::new(RwLock::new(device_conf))).await;
@@ -182,12 +181,16 @@ pub async fn dev_capture_test(
                     "窗口截图校验失败：未找到标题包含“{}”的可截图窗口，或目标窗口已最小化",
                     title.trim()
                 ),
-                CapMethod::Adb => "窗口截图校验失败：当前设备截图方式配置不一致".to_string(),
-            },
-            CaptureMethod::Adb => match describe_adb_config_issue(&adb_conf_for_error) {
-                Some(issue) => format!("ADB 截图校验失败：{}", issue),
-                None => "ADB 截图校验失败：请检查设备连接状态、ADB 服务和截图通道".to_string(),
+                CapMethod::Adb | CapMethod::AdbStream { .. } => {
+                    "窗口截图校验失败：当前设备截图方式配置不一致".to_string()
+                }
             },
+            CaptureMethod::Adb | CaptureMethod::AdbStream => {
+                match describe_adb_config_issue(&adb_conf_for_error) {
+                    Some(issue) => format!("ADB 截图校验失败：{}", issue),
+                    None => "ADB 截图校验失败：请检查设备连接状态、ADB 服务和截图通道".to_string(),
+                }
+            }
         };
         return Err(reason);
     }
@@ -10,11 +10,12 @@ pub(crate) use auth::{
     backend_send_verification_code, backend_update_username,
 };
 pub(crate) use script::local_scripts_dir;
+pub(crate) use script::preflight::find_replaceable_local_published_script;
 pub(crate) use script::{
     backend_download_model, backend_download_script, backend_get_script_change_logs,
     backend_get_script_cloud_summary, backend_preflight_download_script,
     backend_preflight_upload_script, backend_search_scripts, backend_upload_model,
-    backend_upload_script,
+    backend_upload_script, export_script_package_cmd, import_script_package_cmd,
 };
 pub(crate) use support::{backend_create_feedback, backend_create_script_report};
 
@@ -5,7 +5,7 @@ pub(crate) mod preflight;
 pub(crate) use market::{
     backend_download_script, backend_get_script_change_logs, backend_get_script_cloud_summary,
     backend_preflight_download_script, backend_preflight_upload_script, backend_search_scripts,
-    backend_upload_script,
+    backend_upload_script, export_script_package_cmd, import_script_package_cmd,
 };
 pub(crate) use model::local_scripts_dir;
 pub(crate) use model::{backend_download_model, backend_upload_model};
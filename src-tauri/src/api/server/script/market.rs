@@ -18,7 +18,7 @@ use crate::api::server::{app_error_message, format_backend_message, trans_api_re
 use crate::app::app_error::AppResult;
 use crate::infra::http_client::HttpClient;
 use crate::infra::logging::log_trait::Log;
-use ad_kernel::ids::UserId;
+use ad_kernel::ids::{PolicyGroupId, PolicyId, PolicySetId, ScriptId, TaskId, UserId};
 use domain_script::ScriptType;
 use infra_sqlite::{
     CreateScriptTransferRecordInput, FinishScriptTransferRecordInput, get_script,
@@ -30,8 +30,11 @@ use std::sync::Arc;
 use tauri::{AppHandle, command};
 
 const TRANSFER_DELETED_MESSAGE: &str = "传输已删除";
+#[path = "market/offline_package.rs"]
+mod offline_package;
 #[path = "market/script_dir_swap.rs"]
 mod script_dir_swap;
+pub(crate) use offline_package::{export_script_package_cmd, import_script_package_cmd};
 use script_dir_swap::{
     activate as activate_script_dir_swap, cleanup as cleanup_script_dir_swap_temp,
     prepare as prepare_script_dir_swap, rollback as rollback_script_dir_swap,
@@ -330,6 +333,64 @@ async fn record_immediate_transfer_terminal_state(
     );
 }
 
+/// 将云端/离线包中的脚本图改写为本地副本：脚本 ID 换成本地 ID，策略、分组、集合与任务全部换新 ID 并修正关联表
+fn remap_script_graph_to_local(data: &mut ScriptUploadRequest, local_script_id: &ScriptId) {
+    let old_script_id = data.script.id.clone();
+    let mut policy_map: HashMap<PolicyId, PolicyId> = HashMap::new();
+    let mut group_map: HashMap<PolicyGroupId, PolicyGroupId> = HashMap::new();
+    let mut set_map: HashMap<PolicySetId, PolicySetId> = HashMap::new();
+
+    data.script.id = local_script_id.clone();
+    data.script.info.cloud_id = Some(old_script_id);
+    data.script.info.script_type = ScriptType::Published;
+    rewrite_script_model_paths_for_published(&mut data.script, &local_script_id.to_string());
+
+    for policy in data.policies.iter_mut() {
+        let new_pid = PolicyId::new_v7();
+        policy_map.insert(policy.id.clone(), new_pid.clone());
+        policy.id = new_pid;
+        policy.script_id = local_script_id.clone();
+    }
+
+    for group in data.policy_groups.iter_mut() {
+        let new_gid = PolicyGroupId::new_v7();
+        group_map.insert(group.id.clone(), new_gid.clone());
+        group.id = new_gid;
+        group.script_id = local_script_id.clone();
+    }
+
+    for set in data.policy_sets.iter_mut() {
+        let new_sid = PolicySetId::new_v7();
+        set_map.insert(set.id.clone(), new_sid.clone());
+        set.id = new_sid;
+        set.script_id = local_script_id.clone();
+    }
+
+    for task in data.tasks.iter_mut() {
+        let new_tid = TaskId::new_v7();
+        task.id = new_tid;
+        task.script_id = local_script_id.clone();
+    }
+
+    for gp in data.group_policies.iter_mut() {
+        if let Some(new_gid) = group_map.get(&gp.group_id) {
+            gp.group_id = new_gid.clone();
+        }
+        if let Some(new_pid) = policy_map.get(&gp.policy_id) {
+            gp.policy_id = new_pid.clone();
+        }
+    }
+
+    for sg in data.set_groups.iter_mut() {
+        if let Some(new_sid) = set_map.get(&sg.set_id) {
+            sg.set_id = new_sid.clone();
+        }
+        if let Some(new_gid) = group_map.get(&sg.group_id) {
+            sg.group_id = new_gid.clone();
+        }
+    }
+}
+
 async fn fetch_remote_model_file_index(
     client: &HttpClient,
     script_id: &str,
@@ -546,7 +607,6 @@ pub async fn backend_download_script(
     replace_local_script_id: Option<String>,
     script_name: Option<String>,
 ) -> ApiResponse<String> {
-    use ad_kernel::ids::{PolicyGroupId, PolicyId, PolicySetId, ScriptId, TaskId};
     use infra_sqlite::get_pool;
     let pool = get_pool();
 
@@ -780,66 +840,7 @@ pub async fn backend_download_script(
         }
     }
 
-    let old_script_id = download_data.script.id.clone();
-    let mut policy_map: std::collections::HashMap<PolicyId, PolicyId> =
-        std::collections::HashMap::new();
-    let mut group_map: std::collections::HashMap<PolicyGroupId, PolicyGroupId> =
-        std::collections::HashMap::new();
-    let mut set_map: std::collections::HashMap<PolicySetId, PolicySetId> =
-        std::collections::HashMap::new();
-
-    download_data.script.id = local_script_id.clone();
-    download_data.script.info.cloud_id = Some(old_script_id);
-    download_data.script.info.script_type = ScriptType::Published;
-    rewrite_script_model_paths_for_published(
-        &mut download_data.script,
-        &local_script_id.to_string(),
-    );
-
-    for policy in download_data.policies.iter_mut() {
-        let new_pid = PolicyId::new_v7();
-        policy_map.insert(policy.id.clone(), new_pid.clone());
-        policy.id = new_pid;
-        policy.script_id = local_script_id.clone();
-    }
-
-    for group in download_data.policy_groups.iter_mut() {
-        let new_gid = PolicyGroupId::new_v7();
-        group_map.insert(group.id.clone(), new_gid.clone());
-        group.id = new_gid;
-        group.script_id = local_script_id.clone();
-    }
-
-    for set in download_data.policy_sets.iter_mut() {
-        let new_sid = PolicySetId::new_v7();
-        set_map.insert(set.id.clone(), new_sid.clone());
-        set.id = new_sid;
-        set.script_id = local_script_id.clone();
-    }
-
-    for task in download_data.tasks.iter_mut() {
-        let new_tid = TaskId::new_v7();
-        task.id = new_tid;
-        task.script_id = local_script_id.clone();
-    }
-
-    for gp in download_data.group_policies.iter_mut() {
-        if let Some(new_gid) = group_map.get(&gp.group_id) {
-            gp.group_id = new_gid.clone();
-        }
-        if let Some(new_pid) = policy_map.get(&gp.policy_id) {
-            gp.policy_id = new_pid.clone();
-        }
-    }
-
-    for sg in download_data.set_groups.iter_mut() {
-        if let Some(new_sid) = set_map.get(&sg.set_id) {
-            sg.set_id = new_sid.clone();
-        }
-        if let Some(new_gid) = group_map.get(&sg.group_id) {
-            sg.group_id = new_gid.clone();
-        }
-    }
+    remap_script_graph_to_local(&mut download_data, &local_script_id);
 
     let transfer_total_bytes = download_data
         .model_files
@@ -0,0 +1,528 @@
+//! 离线脚本包 (.adpkg)：单个 zip 文件，内含 manifest.json、script.json 与 models/ 下的模型/字典文件。
+//! manifest 记录每个文件的大小与 SHA-256，导入时逐个校验后再写库。
+
+use super::super::model::{
+    LocalModelUpload, build_model_file_payload, collect_model_uploads, local_scripts_dir,
+    normalize_model_type, rewrite_script_model_paths_for_published, runtime_type_param,
+};
+use super::{
+    activate_script_dir_swap, cleanup_script_dir_swap_temp, prepare_script_dir_swap,
+    remap_script_graph_to_local, rollback_script_dir_swap,
+};
+use crate::api::local::script::transfer_records::now_rfc3339;
+use crate::api::response::ApiResponse;
+use crate::api::server::dto::ScriptUploadRequest;
+use crate::api::server::script::preflight::{
+    find_replaceable_local_published_script, format_version_label, validate_script_compatibility,
+};
+use ad_kernel::ids::ScriptId;
+use domain_script::ScriptType;
+use infra_sqlite::{
+    get_script, list_group_policy_links, list_policies, list_policy_groups, list_policy_sets,
+    list_script_tasks, list_set_group_links,
+};
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::{Read, Seek, Write};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, command};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+const PACKAGE_FORMAT_VERSION: u32 = 1;
+const MANIFEST_ENTRY: &str = "manifest.json";
+const SCRIPT_ENTRY: &str = "script.json";
+const MODEL_ENTRY_DIR: &str = "models";
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptPackageManifest {
+    pub format_version: u32,
+    pub script_id: String,
+    pub script_name: String,
+    pub ver_name: String,
+    pub ver_num: u32,
+    pub exported_at: String,
+    pub files: Vec<ScriptPackageFile>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptPackageFile {
+    pub path: String,
+    pub size_bytes: u64,
+    pub sha256: String,
+}
+
+impl ScriptPackageManifest {
+    fn file(&self, path: &str) -> Option<&ScriptPackageFile> {
+        self.files.iter().find(|file| file.path == path)
+    }
+}
+
+/// 导出本地脚本为离线脚本包
+#[command]
+pub async fn export_script_package_cmd(
+    app_handle: AppHandle,
+    script_id: String,
+    target_path: String,
+) -> ApiResponse<ScriptPackageManifest> {
+    let script_id_value = match uuid::Uuid::parse_str(&script_id) {
+        Ok(id) => ScriptId::from(id),
+        Err(_) => return ApiResponse::error(Some("脚本 ID 格式无效".to_string())),
+    };
+    let mut script = match get_script(script_id_value).await {
+        Ok(Some(script)) => script,
+        Ok(None) => return ApiResponse::error(Some("脚本不存在".to_string())),
+        Err(error) => return ApiResponse::error(Some(format!("读取本地脚本失败: {}", error))),
+    };
+    let runtime_type = match runtime_type_param(&script.info.runtime_type) {
+        Ok(value) => value,
+        Err(error) => return ApiResponse::error(Some(error)),
+    };
+
+    let scripts_root = local_scripts_dir(&app_handle);
+    let model_uploads = match collect_model_uploads(&script, &scripts_root) {
+        Ok(value) => value,
+        Err(error) => return ApiResponse::error(Some(error)),
+    };
+    rewrite_script_model_paths_for_published(&mut script, &script_id);
+    // 云端脚本的本地副本以云端 ID 作为包内身份，导入方据此覆盖同源副本
+    if script.info.script_type == ScriptType::Published
+        && let Some(cloud_id) = script.info.cloud_id
+    {
+        script.id = cloud_id;
+    }
+
+    let script_version = script.info.ver_num;
+    let mut model_files = build_model_file_payload(
+        &script_id,
+        script_version,
+        runtime_type.as_str(),
+        &model_uploads,
+    );
+    for model in model_files.iter_mut() {
+        model.download_path = model_entry_path(&model.file_name);
+    }
+    let payload = ScriptUploadRequest {
+        policies: list_policies(script_id_value).await.unwrap_or_default(),
+        tasks: list_script_tasks(script_id_value)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|task| !task.is_deleted)
+            .collect(),
+        policy_groups: list_policy_groups(script_id_value)
+            .await
+            .unwrap_or_default(),
+        policy_sets: list_policy_sets(script_id_value).await.unwrap_or_default(),
+        group_policies: list_group_policy_links(script_id_value)
+            .await
+            .unwrap_or_default(),
+        set_groups: list_set_group_links(script_id_value)
+            .await
+            .unwrap_or_default(),
+        model_files,
+        script,
+    };
+
+    match write_script_package(Path::new(&target_path), &payload, &model_uploads) {
+        Ok(manifest) => ApiResponse::success(Some(manifest), Some("脚本包导出成功".to_string())),
+        Err(error) => ApiResponse::error(Some(error)),
+    }
+}
+
+/// 导入离线脚本包，作为本地脚本副本写入；已有同源副本时按版本覆盖
+#[command]
+pub async fn import_script_package_cmd(
+    app_handle: AppHandle,
+    package_path: String,
+) -> ApiResponse<String> {
+    use infra_sqlite::get_pool;
+
+    let mut archive = match open_script_package(Path::new(&package_path)) {
+        Ok(archive) => archive,
+        Err(error) => return ApiResponse::error(Some(error)),
+    };
+    let manifest = match read_package_manifest(&mut archive) {
+        Ok(manifest) => manifest,
+        Err(error) => return ApiResponse::error(Some(error)),
+    };
+    let mut package_data = match read_package_payload(&mut archive, &manifest) {
+        Ok(data) => data,
+        Err(error) => return ApiResponse::error(Some(error)),
+    };
+    if let Some(error) = validate_script_compatibility(&package_data.script.info) {
+        return ApiResponse::error(Some(error));
+    }
+
+    let source_script_id = package_data.script.id.to_string();
+    let replacement_target = match find_replaceable_local_published_script(&source_script_id).await
+    {
+        Ok(script) => script,
+        Err(error) => return ApiResponse::error(Some(error)),
+    };
+    if let Some(existing) = replacement_target.as_ref()
+        && package_data.script.info.ver_num < existing.info.ver_num
+    {
+        return ApiResponse::error(Some(format!(
+            "本地已有 {}，脚本包仅为 {}。不允许用较旧的脚本包覆盖本地副本。",
+            format_version_label(
+                Some(existing.info.ver_name.as_str()),
+                Some(existing.info.ver_num)
+            ),
+            format_version_label(
+                Some(package_data.script.info.ver_name.as_str()),
+                Some(package_data.script.info.ver_num),
+            ),
+        )));
+    }
+
+    let local_script_id = replacement_target
+        .as_ref()
+        .map(|script| script.id)
+        .unwrap_or_else(ScriptId::new_v7);
+    remap_script_graph_to_local(&mut package_data, &local_script_id);
+
+    let scripts_root = local_scripts_dir(&app_handle);
+    let import_id = uuid::Uuid::now_v7().to_string();
+    let mut dir_swap = match prepare_script_dir_swap(
+        &scripts_root,
+        &local_script_id.to_string(),
+        import_id.as_str(),
+    ) {
+        Ok(state) => state,
+        Err(error) => return ApiResponse::error(Some(error)),
+    };
+    if let Err(error) = extract_package_models(
+        &mut archive,
+        &manifest,
+        &package_data,
+        &dir_swap.staging_dir,
+    ) {
+        cleanup_script_dir_swap_temp(&dir_swap);
+        return ApiResponse::error(Some(error));
+    }
+
+    let mut tx = match get_pool().begin().await {
+        Ok(transaction) => transaction,
+        Err(error) => {
+            cleanup_script_dir_swap_temp(&dir_swap);
+            return ApiResponse::error(Some(format!("开启事务失败: {}", error)));
+        }
+    };
+    if let Some(existing_script) = replacement_target.as_ref()
+        && let Err(error) =
+            infra_sqlite::delete_script_graph_in_transaction(&mut tx, existing_script.id).await
+    {
+        cleanup_script_dir_swap_temp(&dir_swap);
+        return ApiResponse::error(Some(error));
+    }
+    if let Err(error) = infra_sqlite::batch_insert_script_related(
+        &mut tx,
+        &package_data.script,
+        &package_data.policies,
+        &package_data.policy_groups,
+        &package_data.policy_sets,
+        &package_data.group_policies,
+        &package_data.set_groups,
+        &package_data.tasks,
+    )
+    .await
+    {
+        cleanup_script_dir_swap_temp(&dir_swap);
+        return ApiResponse::error(Some(error));
+    }
+    if let Err(error) = activate_script_dir_swap(&mut dir_swap) {
+        cleanup_script_dir_swap_temp(&dir_swap);
+        return ApiResponse::error(Some(error));
+    }
+    if let Err(error) = tx.commit().await {
+        if let Err(rollback_error) = rollback_script_dir_swap(&mut dir_swap) {
+            crate::infra::logging::log_trait::Log::error(&format!(
+                "提交失败后恢复脚本模型目录失败: {}, final_dir={}",
+                rollback_error,
+                dir_swap.final_dir.display()
+            ));
+        }
+        return ApiResponse::error(Some(format!("提交事务失败: {}", error)));
+    }
+    cleanup_script_dir_swap_temp(&dir_swap);
+
+    let success_message = match replacement_target.as_ref() {
+        Some(existing) if existing.info.ver_num == package_data.script.info.ver_num => {
+            "相同版本已覆盖到本地库".to_string()
+        }
+        Some(_) => "脚本包已更新到本地库".to_string(),
+        None => "脚本包导入成功".to_string(),
+    };
+    ApiResponse::success(Some(local_script_id.to_string()), Some(success_message))
+}
+
+fn model_entry_path(file_name: &str) -> String {
+    format!("{}/{}", MODEL_ENTRY_DIR, file_name)
+}
+
+fn write_script_package(
+    target_path: &Path,
+    payload: &ScriptUploadRequest,
+    model_uploads: &[LocalModelUpload],
+) -> Result<ScriptPackageManifest, String> {
+    let script_bytes = serde_json::to_vec_pretty(payload)
+        .map_err(|error| format!("序列化脚本数据失败: {}", error))?;
+    let mut files = vec![ScriptPackageFile {
+        path: SCRIPT_ENTRY.to_string(),
+        size_bytes: script_bytes.len() as u64,
+        sha256: format!("{:x}", Sha256::digest(&script_bytes)),
+    }];
+    files.extend(model_uploads.iter().map(|model| ScriptPackageFile {
+        path: model_entry_path(model.file_name),
+        size_bytes: model.size_bytes,
+        sha256: model.sha256.clone(),
+    }));
+    let manifest = ScriptPackageManifest {
+        format_version: PACKAGE_FORMAT_VERSION,
+        script_id: payload.script.id.to_string(),
+        script_name: payload.script.info.name.clone(),
+        ver_name: payload.script.info.ver_name.clone(),
+        ver_num: payload.script.info.ver_num,
+        exported_at: now_rfc3339(),
+        files,
+    };
+    let manifest_bytes = serde_json::to_vec_pretty(&manifest)
+        .map_err(|error| format!("序列化脚本包清单失败: {}", error))?;
+
+    // 先写临时文件，完整写完再替换目标，避免半个包覆盖旧文件
+    let partial_path = PathBuf::from(format!("{}.part", target_path.display()));
+    let write_result = (|| -> Result<(), String> {
+        let file = File::create(&partial_path)
+            .map_err(|error| format!("创建脚本包 {} 失败: {}", partial_path.display(), error))?;
+        let mut writer = ZipWriter::new(file);
+        let deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        let stored = SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Stored)
+            .large_file(true);
+        let zip_error = |error: zip::result::ZipError| format!("写入脚本包失败: {}", error);
+
+        writer
+            .start_file(MANIFEST_ENTRY, deflated)
+            .map_err(zip_error)?;
+        writer
+            .write_all(&manifest_bytes)
+            .map_err(|error| format!("写入脚本包失败: {}", error))?;
+        writer
+            .start_file(SCRIPT_ENTRY, deflated)
+            .map_err(zip_error)?;
+        writer
+            .write_all(&script_bytes)
+            .map_err(|error| format!("写入脚本包失败: {}", error))?;
+        for model in model_uploads {
+            let mut source = File::open(&model.local_path).map_err(|error| {
+                format!(
+                    "打开模型文件 {} 失败: {}",
+                    model.local_path.display(),
+                    error
+                )
+            })?;
+            writer
+                .start_file(model_entry_path(model.file_name), stored)
+                .map_err(zip_error)?;
+            std::io::copy(&mut source, &mut writer).map_err(|error| {
+                format!(
+                    "写入模型文件 {} 失败: {}",
+                    model.local_path.display(),
+                    error
+                )
+            })?;
+        }
+        writer.finish().map_err(zip_error)?;
+        Ok(())
+    })();
+    if let Err(error) = write_result {
+        let _ = std::fs::remove_file(&partial_path);
+        return Err(error);
+    }
+    std::fs::rename(&partial_path, target_path).map_err(|error| {
+        let _ = std::fs::remove_file(&partial_path);
+        format!("保存脚本包 {} 失败: {}", target_path.display(), error)
+    })?;
+    Ok(manifest)
+}
+
+fn open_script_package(path: &Path) -> Result<ZipArchive<File>, String> {
+    let file = File::open(path)
+        .map_err(|error| format!("打开脚本包 {} 失败: {}", path.display(), error))?;
+    ZipArchive::new(file).map_err(|error| format!("脚本包格式无效: {}", error))
+}
+
+fn read_package_manifest<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+) -> Result<ScriptPackageManifest, String> {
+    let entry = archive
+        .by_name(MANIFEST_ENTRY)
+        .map_err(|_| "脚本包缺少 manifest.json".to_string())?;
+    let manifest: ScriptPackageManifest =
+        serde_json::from_reader(entry).map_err(|error| format!("解析脚本包清单失败: {}", error))?;
+    if manifest.format_version > PACKAGE_FORMAT_VERSION {
+        return Err(format!(
+            "脚本包格式版本 {} 高于当前支持的 {}，请先升级客户端",
+            manifest.format_version, PACKAGE_FORMAT_VERSION
+        ));
+    }
+    Ok(manifest)
+}
+
+fn read_package_payload<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    manifest: &ScriptPackageManifest,
+) -> Result<ScriptUploadRequest, String> {
+    let mut bytes = Vec::new();
+    copy_verified_entry(archive, manifest, SCRIPT_ENTRY, &mut bytes)?;
+    let payload: ScriptUploadRequest =
+        serde_json::from_slice(&bytes).map_err(|error| format!("解析脚本包数据失败: {}", error))?;
+    Ok(payload)
+}
+
+/// 按 script.json 中的模型清单解出模型文件到暂存目录；清单之外的文件一律不落盘
+fn extract_package_models<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    manifest: &ScriptPackageManifest,
+    payload: &ScriptUploadRequest,
+    staging_dir: &Path,
+) -> Result<(), String> {
+    std::fs::create_dir_all(staging_dir).map_err(|error| {
+        format!(
+            "创建脚本模型暂存目录 {} 失败: {}",
+            staging_dir.display(),
+            error
+        )
+    })?;
+    for model in &payload.model_files {
+        let spec = normalize_model_type(model.r#type.as_str())?;
+        let target = staging_dir.join(spec.file_name);
+        let mut file = File::create(&target)
+            .map_err(|error| format!("创建模型文件 {} 失败: {}", target.display(), error))?;
+        copy_verified_entry(archive, manifest, &model.download_path, &mut file)?;
+    }
+    Ok(())
+}
+
+/// 读取包内文件并写入 `writer`，同时校验 manifest 中登记的大小与 SHA-256
+fn copy_verified_entry<R: Read + Seek, W: Write>(
+    archive: &mut ZipArchive<R>,
+    manifest: &ScriptPackageManifest,
+    path: &str,
+    writer: &mut W,
+) -> Result<(), String> {
+    let expected = manifest
+        .file(path)
+        .ok_or_else(|| format!("脚本包清单缺少文件 {}", path))?;
+    let mut entry = archive
+        .by_name(path)
+        .map_err(|_| format!("脚本包缺少文件 {}", path))?;
+    let mut hasher = Sha256::new();
+    let mut size = 0_u64;
+    let mut buffer = [0_u8; 8192];
+    loop {
+        let read = entry
+            .read(&mut buffer)
+            .map_err(|error| format!("读取脚本包文件 {} 失败: {}", path, error))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        writer
+            .write_all(&buffer[..read])
+            .map_err(|error| format!("写出脚本包文件 {} 失败: {}", path, error))?;
+        size += read as u64;
+    }
+    if size != expected.size_bytes
+        || !format!("{:x}", hasher.finalize()).eq_ignore_ascii_case(&expected.sha256)
+    {
+        return Err(format!(
+            "脚本包文件 {} 校验失败，文件可能已损坏或被篡改",
+            path
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use domain_script::ScriptProfile;
+
+    fn sample_payload(model_path: &str) -> ScriptUploadRequest {
+        ScriptUploadRequest {
+            script: ScriptProfile::default(),
+            policies: Vec::new(),
+            tasks: Vec::new(),
+            policy_groups: Vec::new(),
+            policy_sets: Vec::new(),
+            group_policies: Vec::new(),
+            set_groups: Vec::new(),
+            model_files: vec![crate::api::server::dto::ScriptModelFileDto {
+                r#type: "txt_rec_dict".to_string(),
+                file_name: "ch_v5_dict.txt".to_string(),
+                download_path: model_path.to_string(),
+                ..Default::default()
+            }],
+        }
+    }
+
+    fn sample_upload(dir: &Path) -> LocalModelUpload {
+        let local_path = dir.join("dict.txt");
+        std::fs::write(&local_path, "a\nb\n").unwrap();
+        LocalModelUpload {
+            type_name: "txt_rec_dict",
+            file_name: "ch_v5_dict.txt",
+            local_path,
+            size_bytes: 4,
+            sha256: format!("{:x}", Sha256::digest(b"a\nb\n")),
+        }
+    }
+
+    #[test]
+    fn script_package_round_trips_and_extracts_models() {
+        let dir = std::env::temp_dir().join(format!("adpkg-test-{}", uuid::Uuid::now_v7()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let package_path = dir.join("demo.adpkg");
+        let payload = sample_payload(&model_entry_path("ch_v5_dict.txt"));
+
+        let manifest =
+            write_script_package(&package_path, &payload, &[sample_upload(&dir)]).unwrap();
+        assert_eq!(manifest.files.len(), 2);
+
+        let mut archive = open_script_package(&package_path).unwrap();
+        let manifest = read_package_manifest(&mut archive).unwrap();
+        let restored = read_package_payload(&mut archive, &manifest).unwrap();
+        let staging_dir = dir.join("staging");
+        extract_package_models(&mut archive, &manifest, &restored, &staging_dir).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(staging_dir.join("ch_v5_dict.txt")).unwrap(),
+            "a\nb\n"
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn script_package_rejects_tampered_files() {
+        let dir = std::env::temp_dir().join(format!("adpkg-test-{}", uuid::Uuid::now_v7()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let package_path = dir.join("demo.adpkg");
+        let payload = sample_payload(&model_entry_path("ch_v5_dict.txt"));
+        write_script_package(&package_path, &payload, &[sample_upload(&dir)]).unwrap();
+
+        let mut archive = open_script_package(&package_path).unwrap();
+        let mut manifest = read_package_manifest(&mut archive).unwrap();
+        manifest.files[1].sha256 = "0".repeat(64);
+        let restored = read_package_payload(&mut archive, &manifest).unwrap();
+        let error =
+            extract_package_models(&mut archive, &manifest, &restored, &dir.join("staging"))
+                .unwrap_err();
+        assert!(error.contains("校验失败"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
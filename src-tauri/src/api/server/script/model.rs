@@ -394,27 +394,19 @@ fn detector_base_model_mut(model: &mut DetectorType) -> &mut BaseModel {
 }
 
 fn recognizer_base_model(model: &RecognizerType) -> &BaseModel {
-    match model {
-        RecognizerType::PaddleCrnn(rec) => &rec.base_model,
-    }
+    model.base_model()
 }
 
 fn recognizer_dict_path(model: &RecognizerType) -> Option<&PathBuf> {
-    match model {
-        RecognizerType::PaddleCrnn(rec) => rec.dict_path.as_ref(),
-    }
+    model.dict_path()
 }
 
 fn recognizer_base_model_mut(model: &mut RecognizerType) -> &mut BaseModel {
-    match model {
-        RecognizerType::PaddleCrnn(rec) => &mut rec.base_model,
-    }
+    model.base_model_mut()
 }
 
 fn recognizer_dict_path_mut(model: &mut RecognizerType) -> Option<&mut PathBuf> {
-    match model {
-        RecognizerType::PaddleCrnn(rec) => rec.dict_path.as_mut(),
-    }
+    model.dict_path_mut().as_mut()
 }
 
 #[command]
@@ -5,6 +5,7 @@
 // 日志服务
 pub(crate) mod logging;
 pub(crate) mod mail;
+pub(crate) mod notify;
 
 // 共享内存模型管理器
 //pub mod shared;
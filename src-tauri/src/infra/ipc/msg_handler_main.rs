@@ -1,8 +1,8 @@
 //! Root-process child message handler.
 use crate::api::local::execution::{
-    DeviceConnectionEventPayload, DeviceLifecycleStatus, DeviceProgressEventPayload,
-    DeviceRuntimeProgressPhase, DeviceScheduleEventPayload, DeviceStatusEventPayload,
-    DeviceTimeoutEventPayload,
+    DeviceConnectionEventPayload, DeviceDebugPausedEventPayload, DeviceLifecycleStatus,
+    DeviceProgressEventPayload, DeviceRuntimeProgressPhase, DeviceScheduleEventPayload,
+    DeviceStatusEventPayload, DeviceTimeoutEventPayload,
 };
 use crate::app::constants::MAIN_WINDOW;
 use crate::infra::app_handle::get_app_handle;
@@ -14,8 +14,12 @@ use crate::infra::logging::main_process_log_handler::get_child_log_receiver;
 use crate::infra::mail::{
     EmailMessagePayload, load_email_config, send_timeout_email_in_background,
 };
+use crate::infra::notify::dispatch_device_notification_in_background;
 use domain_device::TimeoutAction;
-use runner_protocol::message::{IpcMessage, MessagePayload, RuntimeEventMessage};
+use domain_notification::NotificationEventKind;
+use runner_protocol::message::{
+    IpcMessage, MessagePayload, RuntimeEventMessage, RuntimeScheduleStatus,
+};
 use tauri::{Emitter, Manager};
 use tauri_plugin_notification::NotificationExt;
 
@@ -292,6 +296,15 @@ fn handle_runtime_event(device_id: ad_kernel::ids::DeviceId, event: &RuntimeEven
                             at: progress.at.clone(),
                         };
                         let _ = main_window.emit("device-timeout", emit_data);
+                        dispatch_device_notification_in_background(
+                            device_id,
+                            device_label.clone(),
+                            NotificationEventKind::Timeout,
+                            progress.script_id,
+                            progress.task_id,
+                            body.trim().to_string(),
+                            progress.at.clone(),
+                        );
                     }
                 }
             }
@@ -309,6 +322,31 @@ fn handle_runtime_event(device_id: ad_kernel::ids::DeviceId, event: &RuntimeEven
                     at: schedule.at.clone(),
                 };
                 let _ = main_window.emit("device-schedule", emit_data);
+
+                // 任务级事件带 task_id；脚本整体失败或完成的事件不带 task_id
+                let notification_kind = match (&schedule.status, schedule.task_id) {
+                    (RuntimeScheduleStatus::Failed, Some(_)) => {
+                        Some(NotificationEventKind::TaskFailed)
+                    }
+                    (RuntimeScheduleStatus::Failed, None) => {
+                        Some(NotificationEventKind::ScriptFailed)
+                    }
+                    (RuntimeScheduleStatus::Success, None) => {
+                        Some(NotificationEventKind::ScriptCompleted)
+                    }
+                    _ => None,
+                };
+                if let Some(kind) = notification_kind {
+                    dispatch_device_notification_in_background(
+                        device_id,
+                        device_label.clone(),
+                        kind,
+                        schedule.script_id,
+                        schedule.task_id,
+                        schedule.message.clone().unwrap_or_default(),
+                        schedule.at.clone(),
+                    );
+                }
             }
             RuntimeEventMessage::Connection(connection) => {
                 let _ = get_app_handle()
@@ -450,6 +488,20 @@ fn handle_runtime_event(device_id: ad_kernel::ids::DeviceId, event: &RuntimeEven
                 });
                 let _ = main_window.emit("device-dispatch", emit_data);
             }
+            RuntimeEventMessage::DebugPaused(paused) => {
+                let log_line = format!(
+                    "[ ipc ] 设备[{}]调试暂停: {:?} step={}",
+                    device_label,
+                    paused.reason,
+                    paused.step_name.as_deref().unwrap_or("<unknown>")
+                );
+                forward_child_runtime_log(device_id, LogLevel::Info, log_line);
+                let emit_data = DeviceDebugPausedEventPayload {
+                    device_id,
+                    paused: paused.clone(),
+                };
+                let _ = main_window.emit("device-debug-paused", emit_data);
+            }
         }
     }
 }
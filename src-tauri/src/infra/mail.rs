@@ -1,5 +1,6 @@
 pub(crate) use infra_mail::{EmailMessagePayload, send_email};
 
+use chrono::{Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
 use domain_notification::EmailConfig;
 use tauri::AppHandle;
 use tauri_plugin_store::StoreExt;
@@ -35,3 +36,159 @@ pub fn send_timeout_email_in_background(app_handle: AppHandle, payload: EmailMes
         }
     });
 }
+
+/// 本地自然日 `[date 00:00, date+1 00:00)` 的 rfc3339 区间
+pub(crate) fn local_day_range(date: NaiveDate) -> (String, String) {
+    let start_of = |date: NaiveDate| {
+        Local
+            .from_local_datetime(&date.and_time(NaiveTime::MIN))
+            .earliest()
+            .map(|time| time.to_rfc3339())
+            .unwrap_or_else(|| format!("{date}T00:00:00"))
+    };
+    (start_of(date), start_of(date + Duration::days(1)))
+}
+
+async fn send_daily_summary_email(config: &EmailConfig, date: NaiveDate) -> Result<(), String> {
+    let (range_start, range_end) = local_day_range(date);
+    let report = infra_sqlite::build_run_summary_report(&range_start, &range_end).await?;
+    let payload = EmailMessagePayload {
+        subject: format!(
+            "AutoDaily 运行汇总 {date} - 成功 {} / 失败 {}",
+            report.runs.success + report.tasks.success,
+            report.runs.failed + report.tasks.failed
+        ),
+        body: report.to_plain_text(),
+    };
+    send_email(config, &payload).await
+}
+
+fn daily_summary_due_at(config: &EmailConfig) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(config.daily_summary_time.trim(), "%H:%M").ok()
+}
+
+/// 到达发送时间且当天尚未成功发送时发送汇总；发送失败不记为已发送，下一轮继续重试
+async fn send_daily_summary_if_due<F, Fut>(
+    config: &EmailConfig,
+    now: NaiveDateTime,
+    last_sent_date: &mut Option<NaiveDate>,
+    send: F,
+) -> Option<(NaiveDate, Result<(), String>)>
+where
+    F: FnOnce(NaiveDate) -> Fut,
+    Fut: Future<Output = Result<(), String>>,
+{
+    let today = now.date();
+    let due_at = daily_summary_due_at(config)?;
+    if !config.email_notification
+        || !config.daily_summary_email
+        || now.time() < due_at
+        || last_sent_date.is_some_and(|date| date >= today)
+    {
+        return None;
+    }
+    let result = send(today).await;
+    if result.is_ok() {
+        *last_sent_date = Some(today);
+    }
+    Some((today, result))
+}
+
+/// 每分钟检查一次，到达配置时间后发送当日汇总；每天最多成功发送一次
+pub fn spawn_daily_summary_email_loop(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        // 启动时已过发送时间的当天不补发
+        let now = Local::now();
+        let mut last_sent_date = load_email_config(&app_handle)
+            .ok()
+            .and_then(|config| daily_summary_due_at(&config))
+            .filter(|due_at| now.time() >= *due_at)
+            .map(|_| now.date_naive());
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
+        loop {
+            interval.tick().await;
+            let config = match load_email_config(&app_handle) {
+                Ok(config) => config,
+                Err(error) => {
+                    Log::warn(&format!("[ email ] {}", error));
+                    continue;
+                }
+            };
+            match send_daily_summary_if_due(
+                &config,
+                Local::now().naive_local(),
+                &mut last_sent_date,
+                |date| send_daily_summary_email(&config, date),
+            )
+            .await
+            {
+                Some((date, Ok(()))) => {
+                    Log::info(&format!("[ email ] 已发送 {} 运行汇总邮件", date))
+                }
+                Some((_, Err(error))) => {
+                    Log::warn(&format!("[ email ] 运行汇总邮件发送失败: {}", error))
+                }
+                None => {}
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn failed_daily_summary_is_retried_on_the_next_tick() {
+        let config = EmailConfig {
+            email_notification: true,
+            daily_summary_email: true,
+            daily_summary_time: "23:30".to_string(),
+            ..EmailConfig::default()
+        };
+        let today = NaiveDate::from_ymd_opt(2026, 10, 17).unwrap();
+        let now = today.and_hms_opt(23, 31, 0).unwrap();
+        let mut last_sent_date = None;
+
+        let failed = send_daily_summary_if_due(&config, now, &mut last_sent_date, |_| async {
+            Err("SMTP 连接失败".to_string())
+        })
+        .await;
+        assert!(matches!(failed, Some((date, Err(_))) if date == today));
+        assert_eq!(last_sent_date, None);
+
+        let retried =
+            send_daily_summary_if_due(&config, now, &mut last_sent_date, |_| async { Ok(()) })
+                .await;
+        assert_eq!(retried, Some((today, Ok(()))));
+        assert_eq!(last_sent_date, Some(today));
+
+        let repeated = send_daily_summary_if_due(&config, now, &mut last_sent_date, |_| async {
+            panic!("当天已发送的汇总不应再次发送")
+        })
+        .await;
+        assert_eq!(repeated, None);
+    }
+
+    #[tokio::test]
+    async fn daily_summary_is_skipped_when_email_notification_is_off() {
+        let config = EmailConfig {
+            email_notification: false,
+            daily_summary_email: true,
+            daily_summary_time: "23:30".to_string(),
+            ..EmailConfig::default()
+        };
+        let now = NaiveDate::from_ymd_opt(2026, 10, 17)
+            .unwrap()
+            .and_hms_opt(23, 31, 0)
+            .unwrap();
+        let mut last_sent_date = None;
+
+        let skipped = send_daily_summary_if_due(&config, now, &mut last_sent_date, |_| async {
+            panic!("关闭邮件通知时不应发送汇总")
+        })
+        .await;
+        assert_eq!(skipped, None);
+        assert_eq!(last_sent_date, None);
+    }
+}
@@ -0,0 +1,59 @@
+use ad_kernel::ids::{DeviceId, ScriptId, TaskId};
+use domain_notification::{NotificationChannelConfig, NotificationEvent, NotificationEventKind};
+use infra_notify::dispatch_notification;
+use infra_sqlite::get_device;
+
+use crate::infra::app_handle::get_app_handle;
+use crate::infra::logging::log_trait::Log;
+use crate::infra::mail::load_email_config;
+
+/// 按设备配置的通知目标推送运行事件，在后台执行且不阻塞 IPC 处理
+pub fn dispatch_device_notification_in_background(
+    device_id: DeviceId,
+    device_name: String,
+    kind: NotificationEventKind,
+    script_id: Option<ScriptId>,
+    task_id: Option<TaskId>,
+    message: String,
+    at: String,
+) {
+    tauri::async_runtime::spawn(async move {
+        let targets = match get_device(device_id).await {
+            Ok(Some(device)) => device.config.execution_policy.notification_targets,
+            Ok(None) => return,
+            Err(error) => {
+                Log::warn(&format!(
+                    "[ notify ] 读取设备[{}]通知配置失败: {}",
+                    device_name, error
+                ));
+                return;
+            }
+        };
+        if !targets.iter().any(|target| target.accepts(kind)) {
+            return;
+        }
+        let uses_email = targets.iter().any(|target| {
+            target.accepts(kind) && matches!(target.channel, NotificationChannelConfig::Email)
+        });
+        let email_config = if uses_email {
+            load_email_config(get_app_handle())
+                .map_err(|error| Log::warn(&format!("[ notify ] {}", error)))
+                .ok()
+        } else {
+            None
+        };
+        let event = NotificationEvent {
+            kind,
+            title: kind.title().to_string(),
+            device_id: device_id.to_string(),
+            device_name,
+            script_id: script_id.map(|id| id.to_string()),
+            task_id: task_id.map(|id| id.to_string()),
+            message,
+            at,
+        };
+        for error in dispatch_notification(&targets, email_config.as_ref(), &event).await {
+            Log::warn(&format!("[ notify ] {}", error));
+        }
+    });
+}
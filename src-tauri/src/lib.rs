@@ -12,22 +12,33 @@ use crate::api::local::device::{
     save_device_cmd,
 };
 use crate::api::local::execution::{
-    cmd_bootstrap_enabled_devices, cmd_capture_device_image, cmd_device_pause, cmd_device_shutdown,
-    cmd_device_start, cmd_device_stop, cmd_get_device_runtime_snapshots, cmd_get_running_devices,
-    cmd_is_device_running, cmd_prepare_device_capture, cmd_probe_device_connections,
-    cmd_restart_device_runtime, cmd_run_script_target, cmd_run_user_script_target,
-    cmd_spawn_device, cmd_sync_device_runtime_session, register_child_process_exit_handler,
-    spawn_auto_dispatch_planner_loop, spawn_dispatch_signal_loop, spawn_runtime_reconcile_loop,
+    cmd_bootstrap_enabled_devices, cmd_capture_device_image, cmd_device_debug_pause,
+    cmd_device_debug_resume, cmd_device_debug_set_breakpoints, cmd_device_pause,
+    cmd_device_shutdown, cmd_device_start, cmd_device_stop, cmd_get_device_runtime_snapshots,
+    cmd_get_running_devices, cmd_is_device_running, cmd_prepare_device_capture,
+    cmd_probe_device_connections, cmd_restart_device_runtime, cmd_run_script_target,
+    cmd_run_user_script_target, cmd_spawn_device, cmd_sync_device_runtime_session,
+    register_child_process_exit_handler, spawn_auto_dispatch_planner_loop,
+    spawn_dispatch_signal_loop, spawn_runtime_reconcile_loop,
 };
 use crate::api::local::get_uuid_v7;
 use crate::api::local::schedule::{
     clear_schedules_by_script_cmd, clear_schedules_cmd, delete_assignment_cmd,
-    delete_script_time_template_values_cmd, delete_time_template_cmd, get_all_time_templates_cmd,
-    get_assignment_schedules_by_device_cmd, get_assignments_by_device_cmd,
-    get_schedules_by_device_cmd, get_script_time_template_values_cmd, reorder_assignments_cmd,
-    save_assignment_cmd, save_script_time_template_values_cmd, save_time_template_cmd,
+    delete_script_account_cmd, delete_script_time_template_values_cmd, delete_time_template_cmd,
+    get_all_time_templates_cmd, get_assignment_schedules_by_device_cmd,
+    get_assignments_by_device_cmd, get_run_summary_report_cmd, get_schedules_by_device_cmd,
+    get_script_account_schedules_cmd, get_script_accounts_cmd, get_script_time_template_values_cmd,
+    reorder_assignments_cmd, reorder_script_accounts_cmd, save_assignment_cmd,
+    save_script_account_cmd, save_script_time_template_values_cmd, save_time_template_cmd,
+};
+use crate::api::local::script::merge::{
+    apply_script_upstream_merge_cmd, preview_script_upstream_merge_cmd,
 };
 use crate::api::local::script::policies::*;
+use crate::api::local::script::snapshots::{
+    delete_script_snapshot_cmd, diff_script_snapshots_cmd, list_script_snapshots_cmd,
+    restore_script_snapshot_cmd,
+};
 use crate::api::local::script::transfer_records::{
     clear_script_transfer_records_cmd, delete_script_transfer_record_cmd,
     list_script_transfer_records_cmd, pause_script_transfer_record_cmd,
@@ -35,7 +46,8 @@ use crate::api::local::script::transfer_records::{
 };
 use crate::api::local::script::{
     clone_local_script_cmd, delete_script_cmd, get_all_scripts_cmd, get_script_by_id_cmd,
-    get_script_tasks_cmd, get_yolo_labels_cmd, save_script_cmd, save_script_editor_cmd,
+    get_script_tasks_cmd, get_yolo_labels_cmd, lint_script_cmd, lint_script_editor_cmd,
+    save_script_cmd, save_script_editor_cmd,
 };
 use crate::api::local::settings::email::{
     get_email_config_cmd, send_test_email_cmd, set_email_config_cmd,
@@ -66,10 +78,12 @@ use crate::api::server::{
     backend_preflight_upload_script, backend_redeem_sponsor_code, backend_register,
     backend_reset_password, backend_search_scripts, backend_send_verification_code,
     backend_update_username, backend_upload_model, backend_upload_script,
+    export_script_package_cmd, import_script_package_cmd,
 };
 use crate::app::before_exit::before_exit;
 use crate::app::init_start::init_at_start;
 use crate::infra::context::main_process::MainProcessCtx;
+use crate::infra::mail::spawn_daily_summary_email_loop;
 use std::sync::atomic::{AtomicBool, Ordering};
 use tauri::{App, Emitter, Manager};
 
@@ -106,6 +120,8 @@ pub fn run() {
             spawn_auto_dispatch_planner_loop(app_handle.clone());
             //运行时工作job协调
             spawn_runtime_reconcile_loop(app_handle.clone(), runtime_reconcile_rx);
+            //每日运行汇总邮件
+            spawn_daily_summary_email_loop(app_handle.clone());
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -154,6 +170,8 @@ pub fn run() {
             save_script_cmd,
             save_script_editor_cmd,
             delete_script_cmd,
+            lint_script_cmd,
+            lint_script_editor_cmd,
             // 图像转换
             convert_img_to_base64_cmd,
             // 脚本任务
@@ -165,6 +183,14 @@ pub fn run() {
             clear_script_transfer_records_cmd,
             pause_script_transfer_record_cmd,
             resume_script_transfer_record_cmd,
+            // 脚本快照
+            list_script_snapshots_cmd,
+            diff_script_snapshots_cmd,
+            restore_script_snapshot_cmd,
+            delete_script_snapshot_cmd,
+            // 上游三方合并
+            preview_script_upstream_merge_cmd,
+            apply_script_upstream_merge_cmd,
             // 策略管理
             get_all_policies_cmd,
             save_policy_cmd,
@@ -185,6 +211,9 @@ pub fn run() {
             cmd_device_start,
             cmd_device_stop,
             cmd_device_pause,
+            cmd_device_debug_set_breakpoints,
+            cmd_device_debug_pause,
+            cmd_device_debug_resume,
             cmd_sync_device_runtime_session,
             cmd_run_script_target,
             cmd_run_user_script_target,
@@ -205,6 +234,7 @@ pub fn run() {
             delete_assignment_cmd,
             reorder_assignments_cmd,
             get_schedules_by_device_cmd,
+            get_run_summary_report_cmd,
             clear_schedules_cmd,
             clear_schedules_by_script_cmd,
             get_all_time_templates_cmd,
@@ -213,6 +243,11 @@ pub fn run() {
             get_script_time_template_values_cmd,
             save_script_time_template_values_cmd,
             delete_script_time_template_values_cmd,
+            get_script_accounts_cmd,
+            save_script_account_cmd,
+            delete_script_account_cmd,
+            reorder_script_accounts_cmd,
+            get_script_account_schedules_cmd,
             // 远端服务器相关
             backend_send_verification_code,
             backend_register,
@@ -230,6 +265,8 @@ pub fn run() {
             backend_preflight_upload_script,
             backend_redeem_sponsor_code,
             backend_download_script,
+            export_script_package_cmd,
+            import_script_package_cmd,
             backend_upload_script,
             backend_upload_model,
             backend_download_model,
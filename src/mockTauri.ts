@@ -825,6 +825,27 @@ if (isBrowserMockTarget && !(window as { __TAURI_INTERNALS__?: unknown }).__TAUR
             }),
           }));
           return null;
+        case 'export_script_package_cmd':
+        case 'import_script_package_cmd':
+          return { success: false, data: null, message: 'Script packages unavailable in browser mock' };
+        case 'list_script_snapshots_cmd':
+          return [];
+        case 'diff_script_snapshots_cmd':
+          return {
+            scriptChanged: false,
+            tasks: [],
+            steps: [],
+            policies: [],
+            policyGroups: [],
+            policySets: [],
+            linksChanged: false,
+          };
+        case 'restore_script_snapshot_cmd':
+        case 'delete_script_snapshot_cmd':
+          return null;
+        case 'preview_script_upstream_merge_cmd':
+        case 'apply_script_upstream_merge_cmd':
+          throw new Error('浏览器模拟环境不支持合并上游');
         case 'backend_download_script':
           {
             const replaceLocalScriptId =
@@ -1026,6 +1047,12 @@ if (isBrowserMockTarget && !(window as { __TAURI_INTERNALS__?: unknown }).__TAUR
           return `已向设备[${String(args.deviceId)}]发送启动命令`;
         case 'cmd_device_pause':
           return `已向设备[${String(args.deviceId)}]发送暂停命令`;
+        case 'cmd_device_debug_set_breakpoints':
+          return `已向设备[${String(args.deviceId)}]同步 ${Array.isArray(args.stepIds) ? args.stepIds.length : 0} 个调试断点`;
+        case 'cmd_device_debug_pause':
+          return `已向设备[${String(args.deviceId)}]发送调试暂停命令`;
+        case 'cmd_device_debug_resume':
+          return `已向设备[${String(args.deviceId)}]发送调试继续命令: ${String(args.mode)}`;
         case 'cmd_device_stop':
           return `已向设备[${String(args.deviceId)}]发送停止命令`;
         case 'cmd_sync_device_runtime_session':
@@ -1200,6 +1227,13 @@ if (isBrowserMockTarget && !(window as { __TAURI_INTERNALS__?: unknown }).__TAUR
             };
           });
           return null;
+        case 'get_script_accounts_cmd':
+        case 'get_script_account_schedules_cmd':
+          return [];
+        case 'save_script_account_cmd':
+        case 'delete_script_account_cmd':
+        case 'reorder_script_accounts_cmd':
+          throw new Error('浏览器模拟环境不支持多账号轮换');
         case 'get_all_scripts_cmd':
           return readState().scripts;
         case 'save_device_cmd':
@@ -1238,6 +1272,9 @@ if (isBrowserMockTarget && !(window as { __TAURI_INTERNALS__?: unknown }).__TAUR
           const state = readState();
           return state.scriptTasks[String(args.scriptId)] ?? [];
         }
+        case 'lint_script_cmd':
+        case 'lint_script_editor_cmd':
+          return [];
         case 'get_all_policies_cmd': {
           const state = readState();
           return state.policies
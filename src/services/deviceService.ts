@@ -1,5 +1,6 @@
 import { invoke } from '@/utils/api';
 import type { DeviceRuntimeSnapshot } from '@/types/app/domain';
+import type { DebugResumeMode } from '@/types/bindings/DebugResumeMode';
 import type { DeviceTable } from '@/types/bindings/DeviceTable';
 import type { LogLevel } from '@/types/bindings/LogLevel';
 
@@ -22,6 +23,11 @@ export const deviceService = {
     start: (deviceId: string) => invoke('cmd_device_start', { deviceId }) as Promise<string>,
     pause: (deviceId: string) => invoke('cmd_device_pause', { deviceId }) as Promise<string>,
     stop: (deviceId: string) => invoke('cmd_device_stop', { deviceId }) as Promise<string>,
+    setDebugBreakpoints: (deviceId: string, stepIds: string[]) =>
+        invoke('cmd_device_debug_set_breakpoints', { deviceId, stepIds }) as Promise<string>,
+    debugPause: (deviceId: string) => invoke('cmd_device_debug_pause', { deviceId }) as Promise<string>,
+    debugResume: (deviceId: string, mode: DebugResumeMode) =>
+        invoke('cmd_device_debug_resume', { deviceId, mode }) as Promise<string>,
     restartRuntime: (deviceId: string) =>
         invoke('cmd_restart_device_runtime', { deviceId }) as Promise<string>,
     updateChildLogLevel: (deviceId: string, logLevel: LogLevel) =>
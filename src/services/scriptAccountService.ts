@@ -0,0 +1,20 @@
+import { invoke } from '@/utils/api';
+import type { ExecutionScheduleProfile } from '@/types/bindings/ExecutionScheduleProfile';
+import type { ScriptAccountProfile } from '@/types/bindings/ScriptAccountProfile';
+
+export const scriptAccountService = {
+    list: (scriptId: string) =>
+        invoke('get_script_accounts_cmd', { scriptId }) as Promise<ScriptAccountProfile[]>,
+    save: (account: ScriptAccountProfile) =>
+        invoke('save_script_account_cmd', { account }) as Promise<void>,
+    remove: (scriptId: string, accountId: string) =>
+        invoke('delete_script_account_cmd', { scriptId, accountId }) as Promise<void>,
+    reorder: (scriptId: string, accountIds: string[]) =>
+        invoke('reorder_script_accounts_cmd', { scriptId, accountIds }) as Promise<void>,
+    listSchedules: (scriptId: string, accountId: string, limit: number | null = null) =>
+        invoke('get_script_account_schedules_cmd', {
+            scriptId,
+            accountId,
+            limit,
+        }) as Promise<ExecutionScheduleProfile[]>,
+};
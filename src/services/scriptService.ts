@@ -2,8 +2,13 @@ import { ApiEnvelope, createServerResponseError, invoke } from '@/utils/api';
 import type { PolicyGroupTable } from '@/types/bindings/PolicyGroupTable';
 import type { PolicySetTable } from '@/types/bindings/PolicySetTable';
 import type { PolicyTable } from '@/types/bindings/PolicyTable';
+import type { MergeResolution } from '@/types/bindings/MergeResolution';
+import type { ScriptDiagnostic } from '@/types/bindings/ScriptDiagnostic';
+import type { ScriptSnapshotDiff } from '@/types/bindings/ScriptSnapshotDiff';
+import type { ScriptSnapshotSummary } from '@/types/bindings/ScriptSnapshotSummary';
 import type { ScriptTaskTable } from '@/types/bindings/ScriptTaskTable';
 import type { ScriptTable } from '@/types/bindings/ScriptTable';
+import type { ScriptUpstreamMergePreview } from '@/types/bindings/ScriptUpstreamMergePreview';
 import type { ScriptType } from '@/types/bindings/ScriptType';
 import type { ScriptVariableCatalog } from '@/types/bindings/ScriptVariableCatalog';
 import type {
@@ -12,6 +17,7 @@ import type {
     ScriptCloudSummary,
     MarketPage,
     MarketScriptRecord,
+    ScriptPackageManifest,
     ScriptSearchInput,
     ScriptVersionPreflight,
     ScriptTableRecord,
@@ -35,6 +41,7 @@ type ScriptEditorSavePayload = {
     policySets: PolicySetTable[];
     groupPolicyIdsByGroupId: Record<string, string[]>;
     setGroupIdsBySetId: Record<string, string[]>;
+    snapshotMessage?: string | null;
 };
 
 const emptyMarketPage = (query: ScriptSearchInput): MarketPage<MarketScriptRecord> => ({
@@ -89,9 +96,12 @@ export const normalizeScriptTable = (script: ScriptTable | ScriptTableRecord): S
                 ? raw.data.requiredFeatures
                 : [...DEFAULT_SCRIPT_REQUIRED_FEATURES],
             variableCatalog: raw.data.variableCatalog ?? createEmptyVariableCatalog(),
+            rhaiModules: Array.isArray(raw.data.rhaiModules) ? raw.data.rhaiModules : [],
             runtimeSettings: {
                 recoveryTaskId: raw.data.runtimeSettings?.recoveryTaskId ?? null,
+                switchAccountTaskId: raw.data.runtimeSettings?.switchAccountTaskId ?? null,
                 clickRandomOffset: Math.max(0, toSafeNumber(raw.data.runtimeSettings?.clickRandomOffset, 0)),
+                humanize: raw.data.runtimeSettings?.humanize ?? null,
             },
             verNum: toSafeNumber(raw.data.verNum, 1),
             latestVer: toSafeNumber(raw.data.latestVer, 1),
@@ -133,10 +143,13 @@ export const createBlankScript = (
         minRuntimeSchema: null,
         requiredFeatures: [...DEFAULT_SCRIPT_REQUIRED_FEATURES],
         variableCatalog: createEmptyVariableCatalog(),
+        rhaiModules: [],
         cloudId: null,
         runtimeSettings: {
             recoveryTaskId: null,
+            switchAccountTaskId: null,
             clickRandomOffset: 0,
+            humanize: null,
         },
     },
 });
@@ -163,6 +176,7 @@ export const scriptService = {
         return scripts.map(normalizeScriptTable);
     },
     listTasks: (scriptId: string) => invoke('get_script_tasks_cmd', { scriptId }) as Promise<ScriptTaskTable[]>,
+    lint: (scriptId: string) => invoke('lint_script_cmd', { scriptId }) as Promise<ScriptDiagnostic[]>,
     listPolicies: (scriptId: string) => invoke('get_all_policies_cmd', { scriptId }) as Promise<PolicyTable[]>,
     savePolicy: (policy: PolicyTable) => invoke('save_policy_cmd', { policy }) as Promise<void>,
     removePolicy: (id: string) => invoke('delete_policy_cmd', { id }) as Promise<void>,
@@ -180,6 +194,24 @@ export const scriptService = {
         invoke('update_set_groups_cmd', { setId, groupIds }) as Promise<void>,
     saveEditorBundle: (payload: ScriptEditorSavePayload) =>
         invoke('save_script_editor_cmd', { payload }) as Promise<void>,
+    lintEditorBundle: (payload: ScriptEditorSavePayload) =>
+        invoke('lint_script_editor_cmd', { payload }) as Promise<ScriptDiagnostic[]>,
+    listSnapshots: (scriptId: string) =>
+        invoke('list_script_snapshots_cmd', { scriptId }) as Promise<ScriptSnapshotSummary[]>,
+    diffSnapshots: (scriptId: string, baseSnapshotId: string, targetSnapshotId?: string | null) =>
+        invoke('diff_script_snapshots_cmd', {
+            scriptId,
+            baseSnapshotId,
+            targetSnapshotId: targetSnapshotId ?? null,
+        }) as Promise<ScriptSnapshotDiff>,
+    restoreSnapshot: (scriptId: string, snapshotId: string) =>
+        invoke('restore_script_snapshot_cmd', { scriptId, snapshotId }) as Promise<void>,
+    removeSnapshot: (scriptId: string, snapshotId: string) =>
+        invoke('delete_script_snapshot_cmd', { scriptId, snapshotId }) as Promise<void>,
+    previewUpstreamMerge: (scriptId: string, resolutions: Record<string, MergeResolution>) =>
+        invoke('preview_script_upstream_merge_cmd', { scriptId, resolutions }) as Promise<ScriptUpstreamMergePreview>,
+    applyUpstreamMerge: (scriptId: string, resolutions: Record<string, MergeResolution>) =>
+        invoke('apply_script_upstream_merge_cmd', { scriptId, resolutions }) as Promise<void>,
     saveLocal: async (script: ScriptTableRecord): Promise<void> => {
         await invoke('save_script_cmd', { script });
     },
@@ -193,6 +225,13 @@ export const scriptService = {
             sourceScriptId,
             overwriteCloudId,
         })) as ApiEnvelope<string>,
+    exportPackage: async (scriptId: string, targetPath: string) =>
+        (await invoke('export_script_package_cmd', {
+            scriptId,
+            targetPath,
+        })) as ApiEnvelope<ScriptPackageManifest>,
+    importPackage: async (packagePath: string) =>
+        (await invoke('import_script_package_cmd', { packagePath })) as ApiEnvelope<string>,
     searchMarket: async (query: ScriptSearchInput): Promise<MarketPage<MarketScriptRecord>> => {
         const response = (await invoke('backend_search_scripts', { req: query })) as ApiEnvelope<MarketPage<MarketScriptRecord>>;
         if (!response.success) {
@@ -1,6 +1,7 @@
 import { invoke } from '@/utils/api';
 import type { AssignmentSchedule, DeviceScriptSchedule } from '@/types/app/domain';
 import type { DeviceScriptAssignment } from '@/types/bindings/DeviceScriptAssignment';
+import type { RunSummaryReport } from '@/types/bindings/RunSummaryReport';
 import type { TimeTemplate } from '@/types/bindings/TimeTemplate';
 
 export const taskService = {
@@ -20,6 +21,8 @@ export const taskService = {
         invoke('clear_schedules_cmd', { deviceId }) as Promise<void>,
     clearSchedulesByScript: (scriptId: string) =>
         invoke('clear_schedules_by_script_cmd', { scriptId }) as Promise<void>,
+    getRunSummaryReport: (rangeStart?: string, rangeEnd?: string) =>
+        invoke('get_run_summary_report_cmd', { rangeStart, rangeEnd }) as Promise<RunSummaryReport>,
     listTimeTemplates: () => invoke('get_all_time_templates_cmd') as Promise<TimeTemplate[]>,
     saveTimeTemplate: (template: TimeTemplate) =>
         invoke('save_time_template_cmd', { template }) as Promise<void>,
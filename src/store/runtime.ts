@@ -1,7 +1,7 @@
 import { defineStore } from 'pinia';
 import { ref } from 'vue';
 import { listen } from '@tauri-apps/api/event';
-import type { DeviceRuntimeSnapshot, RuntimeProgressEvent, RuntimeResultProjection, RuntimeScheduleEvent, RuntimeTimeoutEvent } from '@/types/app/domain';
+import type { DeviceRuntimeSnapshot, RuntimeDebugPause, RuntimeProgressEvent, RuntimeResultProjection, RuntimeScheduleEvent, RuntimeTimeoutEvent } from '@/types/app/domain';
 
 const MAX_SCHEDULE_EVENTS = 50;
 
@@ -113,6 +113,40 @@ const normalizeTimeoutEvent = (payload: unknown): RuntimeTimeoutEvent | null =>
     };
 };
 
+const normalizeDebugPausedEvent = (payload: unknown): RuntimeDebugPause | null => {
+    if (!payload || typeof payload !== 'object') {
+        return null;
+    }
+
+    const record = payload as Record<string, unknown>;
+    const paused = record.paused as Record<string, unknown> | null | undefined;
+    if (
+        typeof record.deviceId !== 'string' ||
+        !paused ||
+        typeof paused !== 'object' ||
+        (paused.reason !== 'breakpoint' && paused.reason !== 'step' && paused.reason !== 'pause') ||
+        typeof paused.at !== 'string'
+    ) {
+        return null;
+    }
+
+    return {
+        deviceId: record.deviceId,
+        paused: {
+            sessionId: typeof paused.sessionId === 'string' ? paused.sessionId : null,
+            scriptId: typeof paused.scriptId === 'string' ? paused.scriptId : null,
+            taskId: typeof paused.taskId === 'string' ? paused.taskId : null,
+            stepId: typeof paused.stepId === 'string' ? paused.stepId : null,
+            stepName: typeof paused.stepName === 'string' ? paused.stepName : null,
+            depth: typeof paused.depth === 'number' ? paused.depth : 0,
+            reason: paused.reason,
+            variablesJson: typeof paused.variablesJson === 'string' ? paused.variablesJson : '{}',
+            visionJson: typeof paused.visionJson === 'string' ? paused.visionJson : null,
+            at: paused.at,
+        },
+    };
+};
+
 const parseRuntimeTime = (value: string | null | undefined) => {
     if (!value) {
         return 0;
@@ -184,6 +218,7 @@ export const useRuntimeStore = defineStore('runtime', () => {
     const latestProgressByDevice = ref<Record<string, RuntimeProgressEvent | null>>({});
     const scheduleEventsByDevice = ref<Record<string, RuntimeScheduleEvent[]>>({});
     const latestTimeoutByDevice = ref<Record<string, RuntimeTimeoutEvent | null>>({});
+    const debugPauseByDevice = ref<Record<string, RuntimeDebugPause | null>>({});
 
     const appendScheduleEvent = (entry: RuntimeScheduleEvent) => {
         const current = scheduleEventsByDevice.value[entry.deviceId] ?? [];
@@ -209,6 +244,10 @@ export const useRuntimeStore = defineStore('runtime', () => {
                 ...latestProgressByDevice.value,
                 [payload.deviceId]: payload,
             };
+            // 暂停后收到其他阶段的进度，说明已继续执行或已停止
+            if (payload.phase !== 'paused' && debugPauseByDevice.value[payload.deviceId]) {
+                clearDebugPause(payload.deviceId);
+            }
         });
 
         await listen('device-schedule', (event) => {
@@ -232,12 +271,25 @@ export const useRuntimeStore = defineStore('runtime', () => {
             };
         });
 
+        await listen('device-debug-paused', (event) => {
+            const payload = normalizeDebugPausedEvent(event.payload);
+            if (!payload) {
+                return;
+            }
+
+            debugPauseByDevice.value = {
+                ...debugPauseByDevice.value,
+                [payload.deviceId]: payload,
+            };
+        });
+
         initialized.value = true;
     };
 
     const getLatestProgress = (deviceId: string) => latestProgressByDevice.value[deviceId] ?? null;
     const getScheduleEvents = (deviceId: string) => scheduleEventsByDevice.value[deviceId] ?? [];
     const getLatestTimeout = (deviceId: string) => latestTimeoutByDevice.value[deviceId] ?? null;
+    const getDebugPause = (deviceId: string) => debugPauseByDevice.value[deviceId] ?? null;
     const hydrateRuntimeSnapshots = (snapshots: DeviceRuntimeSnapshot[]) => {
         const nextProgress = { ...latestProgressByDevice.value };
 
@@ -260,11 +312,19 @@ export const useRuntimeStore = defineStore('runtime', () => {
             latestProgress,
             latestSchedule,
             latestTimeout,
+            debugPause: getDebugPause(deviceId),
             timeoutActionResult: resolveTimeoutActionResult(latestTimeout, schedules, latestProgress),
             updatedAt: latestRuntimeTimestamp(latestProgress, latestSchedule, latestTimeout),
         };
     };
 
+    const clearDebugPause = (deviceId: string) => {
+        debugPauseByDevice.value = {
+            ...debugPauseByDevice.value,
+            [deviceId]: null,
+        };
+    };
+
     const clearTimeoutState = (deviceId?: string) => {
         if (deviceId) {
             latestTimeoutByDevice.value = {
@@ -288,26 +348,31 @@ export const useRuntimeStore = defineStore('runtime', () => {
                 [deviceId]: [],
             };
             clearTimeoutState(deviceId);
+            clearDebugPause(deviceId);
             return;
         }
 
         latestProgressByDevice.value = {};
         scheduleEventsByDevice.value = {};
+        debugPauseByDevice.value = {};
         clearTimeoutState();
     };
 
     return {
+        clearDebugPause,
         clearTimeoutState,
         clearRuntimeState,
         getLatestProgress,
         getRuntimeResult,
         getScheduleEvents,
         getLatestTimeout,
+        getDebugPause,
         hydrateRuntimeSnapshots,
         initIpcListeners,
         initialized,
         latestProgressByDevice,
         scheduleEventsByDevice,
         latestTimeoutByDevice,
+        debugPauseByDevice,
     };
 });
@@ -2,6 +2,7 @@ import { defineStore } from 'pinia';
 import { computed, ref, shallowRef } from 'vue';
 import { createEditableScript, scriptService } from '@/services/scriptService';
 import { taskService } from '@/services/taskService';
+import type { MergeResolution } from '@/types/bindings/MergeResolution';
 import type { ScriptTaskTable } from '@/types/bindings/ScriptTaskTable';
 import type {
     MarketPage,
@@ -129,6 +130,32 @@ export const useScriptStore = defineStore('script', () => {
     const cloneScript = (sourceScriptId: string, overwriteCloudId: boolean) =>
         scriptService.cloneLocal(sourceScriptId, overwriteCloudId);
 
+    const exportScriptPackage = (scriptId: string, targetPath: string) =>
+        scriptService.exportPackage(scriptId, targetPath);
+
+    const importScriptPackage = (packagePath: string) => scriptService.importPackage(packagePath);
+
+    const listScriptSnapshots = (scriptId: string) => scriptService.listSnapshots(scriptId);
+
+    const diffScriptSnapshots = (scriptId: string, baseSnapshotId: string, targetSnapshotId?: string | null) =>
+        scriptService.diffSnapshots(scriptId, baseSnapshotId, targetSnapshotId);
+
+    const restoreScriptSnapshot = async (scriptId: string, snapshotId: string) => {
+        await scriptService.restoreSnapshot(scriptId, snapshotId);
+        await loadScripts();
+    };
+
+    const removeScriptSnapshot = (scriptId: string, snapshotId: string) =>
+        scriptService.removeSnapshot(scriptId, snapshotId);
+
+    const previewUpstreamMerge = (scriptId: string, resolutions: Record<string, MergeResolution> = {}) =>
+        scriptService.previewUpstreamMerge(scriptId, resolutions);
+
+    const applyUpstreamMerge = async (scriptId: string, resolutions: Record<string, MergeResolution>) => {
+        await scriptService.applyUpstreamMerge(scriptId, resolutions);
+        await loadScripts();
+    };
+
     const searchMarket = async (
         partial?: Partial<ScriptSearchInput>,
         options?: {
@@ -180,6 +207,14 @@ export const useScriptStore = defineStore('script', () => {
         cloneScript,
         deleteScript,
         downloadMarketScript,
+        exportScriptPackage,
+        importScriptPackage,
+        listScriptSnapshots,
+        diffScriptSnapshots,
+        restoreScriptSnapshot,
+        removeScriptSnapshot,
+        previewUpstreamMerge,
+        applyUpstreamMerge,
         loadScripts,
         loadScriptTasks,
         loadMoreMarket,
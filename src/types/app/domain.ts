@@ -2,6 +2,7 @@ import type { AssignmentScheduleStatus } from '@/types/bindings/AssignmentSchedu
 import type { AssignmentTriggerSource } from '@/types/bindings/AssignmentTriggerSource';
 import type { ConnectionStatusKind } from '@/types/bindings/ConnectionStatusKind';
 import type { DeviceConnectionEventPayload } from '@/types/bindings/DeviceConnectionEventPayload';
+import type { DeviceDebugPausedEventPayload } from '@/types/bindings/DeviceDebugPausedEventPayload';
 import type { EmulatorConnectMode } from '@/types/bindings/EmulatorConnectMode';
 import type { DeviceLifecycleStatus } from '@/types/bindings/DeviceLifecycleStatus';
 import type { DeviceProgressEventPayload } from '@/types/bindings/DeviceProgressEventPayload';
@@ -23,6 +24,9 @@ import type { ScriptVariableCatalog } from '@/types/bindings/ScriptVariableCatal
 import type { ScriptPlatform } from '@/types/bindings/ScriptPlatform';
 import type { ScriptType } from '@/types/bindings/ScriptType';
 import type { TimeoutAction } from '@/types/bindings/TimeoutAction';
+import type { NotificationTarget } from '@/types/bindings/NotificationTarget';
+import type { TaskRetryPolicy } from '@/types/bindings/TaskRetryPolicy';
+import type { HumanizeProfile } from '@/types/bindings/HumanizeProfile';
 import type { TimeoutNotifyChannel } from '@/types/bindings/TimeoutNotifyChannel';
 import type { JsonValue as StoreJsonValue } from '@/types/bindings/serde_json/JsonValue';
 export type { RunTarget } from '@/types/bindings/RunTarget';
@@ -96,6 +100,8 @@ export interface EmailConfig {
     senderEmail: string;
     recipient: string;
     timeoutSeconds: number;
+    dailySummaryEmail: boolean;
+    dailySummaryTime: string;
 }
 
 export interface VisionTextCacheConfig {
@@ -177,13 +183,21 @@ export interface ScriptInfoRecord {
     minRuntimeSchema: number | null;
     requiredFeatures: string[];
     variableCatalog: ScriptVariableCatalog;
+    rhaiModules: ScriptRhaiModule[];
     cloudId: string | null;
     runtimeSettings: {
         recoveryTaskId: string | null;
+        switchAccountTaskId: string | null;
         clickRandomOffset: number;
+        humanize: HumanizeProfile | null;
     };
 }
 
+export interface ScriptRhaiModule {
+    name: string;
+    code: string;
+}
+
 export interface ScriptTableRecord {
     id: string;
     data: ScriptInfoRecord;
@@ -293,12 +307,14 @@ export type RuntimeLifecycleEvent = DeviceStatusEventPayload;
 export type RuntimeProgressEvent = DeviceProgressEventPayload;
 export type RuntimeScheduleEvent = DeviceScheduleEventPayload;
 export type RuntimeTimeoutEvent = DeviceTimeoutEventPayload;
+export type RuntimeDebugPause = DeviceDebugPausedEventPayload;
 
 export interface RuntimeResultProjection {
     deviceId: string;
     latestProgress: RuntimeProgressEvent | null;
     latestSchedule: RuntimeScheduleEvent | null;
     latestTimeout: RuntimeTimeoutEvent | null;
+    debugPause: RuntimeDebugPause | null;
     timeoutActionResult: 'none' | 'pending' | 'skipped' | 'recovered' | 'stopped' | 'failed';
     updatedAt: string | null;
 }
@@ -403,6 +419,22 @@ export interface ScriptVersionPreflight {
     remoteVerNum: number | null;
 }
 
+export interface ScriptPackageFile {
+    path: string;
+    sizeBytes: number;
+    sha256: string;
+}
+
+export interface ScriptPackageManifest {
+    formatVersion: number;
+    scriptId: string;
+    scriptName: string;
+    verName: string;
+    verNum: number;
+    exportedAt: string;
+    files: ScriptPackageFile[];
+}
+
 export type ScriptUploadActivityStatus = 'waitingAuth' | 'success' | 'error';
 
 export interface ScriptUploadActivity {
@@ -478,8 +510,9 @@ export interface DeviceFormState {
     cores: number[];
     logLevel: LogLevel;
     logToFile: boolean;
-    capMethodType: 'window' | 'adb';
+    capMethodType: 'window' | 'adb' | 'adbStream';
     capMethodValue: string;
+    streamMaxFrameAgeMs: number;
     windowCaptureInterface: 'dxgi' | 'gdi' | 'dwmGetDxSharedSurface' | 'wgc';
     frameTimeoutSecs: number;
     windowOffsets: string;
@@ -494,6 +527,10 @@ export interface DeviceFormState {
     progressTimeoutMs: number;
     timeoutAction: TimeoutAction;
     timeoutNotifyChannels: TimeoutNotifyChannel[];
+    notificationTargets: NotificationTarget[];
+    retryPolicy: TaskRetryPolicy;
+    humanize: HumanizeProfile;
+    humanizeSeed: string;
 }
 
 export interface DeviceSummary {
@@ -553,4 +590,6 @@ export const DEFAULT_EMAIL_CONFIG: EmailConfig = {
     senderEmail: '',
     recipient: '',
     timeoutSeconds: 60,
+    dailySummaryEmail: false,
+    dailySummaryTime: '23:30',
 };
@@ -0,0 +1,22 @@
+import type { HumanizeProfile } from '@/types/bindings/HumanizeProfile';
+
+// 与 Rust 侧 HumanizeProfile::default() 保持一致
+export const createDefaultHumanizeProfile = (): HumanizeProfile => ({
+    enabled: false,
+    clickSigmaPermille: 350,
+    clickRadiusPx: 6,
+    swipeCurvePermille: 120,
+    swipeDurationJitterPermille: 150,
+    pressMinMs: 40,
+    pressMaxMs: 120,
+    delaySigmaMs: 120,
+    seed: null,
+});
+
+export const parseHumanizeSeed = (value: string): HumanizeProfile['seed'] => {
+    const trimmed = value.trim();
+    if (!/^\d+$/.test(trimmed)) {
+        return null;
+    }
+    return Number(trimmed) as never;
+};
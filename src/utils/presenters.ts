@@ -134,10 +134,11 @@ const formatWindowCaptureInterface = (value: WindowCaptureInterface) => {
     return value === 'gdi' ? 'GDI' : 'DXGI';
 };
 
-export const formatCaptureMethod = (method: CapMethod) =>
-    method.type === 'adb'
-        ? 'ADB 截图'
-        : `窗口截取 · ${formatWindowCaptureInterface(method.interface)} · ${method.title}`;
+export const formatCaptureMethod = (method: CapMethod) => {
+    if (method.type === 'adb') return 'ADB 截图';
+    if (method.type === 'adbStream') return 'ADB 截图流 · H.264';
+    return `窗口截取 · ${formatWindowCaptureInterface(method.interface)} · ${method.title}`;
+};
 
 export const formatStatusTone = (status: DeviceRuntimeStatus['kind'] | LogLevel) => {
     if (status === 'running' || status === 'Info') return 'success';
@@ -217,8 +218,16 @@ export const formatTemplateWindow = (template: TimeTemplate | null | undefined)
         return '全天';
     }
 
+    const calendar = template.calendar;
+    if (calendar?.cron) {
+        return `cron ${calendar.cron}`;
+    }
+
     if (template.startTime && template.endTime) {
-        return `${template.startTime} - ${template.endTime}`;
+        const weekdays = calendar?.weekdays?.length
+            ? ` · 周${calendar.weekdays.map((day) => '一二三四五六日'[day - 1]).join('')}`
+            : '';
+        return `${template.startTime} - ${template.endTime}${weekdays}`;
     }
 
     return template.name;
@@ -1,4 +1,5 @@
 import type { ScriptTableRecord } from '@/types/app/domain';
+import { recognizerConfig } from '@/utils/visionModelPresets';
 
 export interface ScriptInfoValidationIssue {
   field: string;
@@ -41,10 +42,7 @@ const getRecognizerBaseModelPath = (model: ScriptTableRecord['data']['txtRecMode
   if (!model) {
     return null;
   }
-  if ('PaddleCrnn' in model) {
-    return model.PaddleCrnn.baseModel.modelPath;
-  }
-  return null;
+  return recognizerConfig(model).baseModel.modelPath;
 };
 
 export const validateScriptInfo = (script: ScriptTableRecord | null | undefined): ScriptInfoValidationIssue[] => {
@@ -100,7 +98,7 @@ export const validateScriptInfo = (script: ScriptTableRecord | null | undefined)
 
     if (!script.data.txtRecModel) {
       issues.push({ field: 'txtRecModel', label: '文字识别模型', message: 'Rhai 运行时必须配置文字识别模型。' });
-    } else if (isBlank(getRecognizerBaseModelPath(script.data.txtRecModel)) && script.data.txtRecModel.PaddleCrnn.baseModel.modelSource !== 'BuiltIn') {
+    } else if (isBlank(getRecognizerBaseModelPath(script.data.txtRecModel)) && recognizerConfig(script.data.txtRecModel).baseModel.modelSource !== 'BuiltIn') {
       issues.push({ field: 'txtRecModel.modelPath', label: '文字识别模型路径', message: 'Rhai 运行时必须填写文字识别模型路径。' });
     }
   }
@@ -1,3 +1,4 @@
+import type { AttnRec } from '@/types/bindings/AttnRec';
 import type { BaseModel } from '@/types/bindings/BaseModel';
 import type { DetectorType } from '@/types/bindings/DetectorType';
 import type { PaddleDetDbNet } from '@/types/bindings/PaddleDetDbNet';
@@ -7,7 +8,7 @@ import type { YoloDet } from '@/types/bindings/YoloDet';
 import type { YoloPostprocessKind } from '@/types/bindings/YoloPostprocessKind';
 
 export type DetectorKind = 'none' | 'Yolo11' | 'PaddleDbNet5' | 'PaddleDbNet6' | 'Yolo26';
-export type RecognizerKind = 'none' | 'PaddleCrnn5' | 'PaddleCrnn6';
+export type RecognizerKind = 'none' | 'PaddleCrnn5' | 'PaddleCrnn6' | 'PaddleSvtr' | 'Parseq';
 export const YOLO_LEGACY_CONFIDENCE_DEFAULT = 0.25;
 export const YOLO_LEGACY_IOU_DEFAULT = 0.45;
 export const CUSTOM_CRNN_DICT_FILE_NAME = 'ch_v5_dict.txt';
@@ -71,6 +72,21 @@ export function createCrnn(version: 'v5' | 'v6' = 'v5'): PaddleRecCrnn {
     };
 }
 
+export function createSvtr(): PaddleRecCrnn {
+    return {
+        ...createCrnn(),
+        baseModel: createBaseModel('PaddleSvtr', 320, 48, 'Custom'),
+    };
+}
+
+export function createAttnRec(): AttnRec {
+    return {
+        baseModel: createBaseModel('Parseq', 128, 32, 'Custom'),
+        dictPath: null,
+        resizeFilter: 'Triangle',
+    };
+}
+
 export function defaultYoloPostprocessKind(kind: 'Yolo11' | 'Yolo26'): YoloPostprocessKind {
     return kind === 'Yolo26' ? 'EndToEnd' : 'LegacyNms';
 }
@@ -107,6 +123,8 @@ export function resolveRecognizerKind(model: RecognizerType | null): RecognizerK
     if ('PaddleCrnn' in model) {
         return model.PaddleCrnn.baseModel.modelType === 'PaddleCrnn6' ? 'PaddleCrnn6' : 'PaddleCrnn5';
     }
+    if ('PaddleSvtr' in model) return 'PaddleSvtr';
+    if ('Parseq' in model) return 'Parseq';
     return 'none';
 }
 
@@ -139,10 +157,23 @@ export function extractDbNet(model: DetectorType | null): PaddleDetDbNet | null
 }
 
 export function extractCrnn(model: RecognizerType | null): PaddleRecCrnn | null {
-    if (!model || !('PaddleCrnn' in model)) {
+    if (!model) return null;
+    if ('PaddleCrnn' in model) return model.PaddleCrnn;
+    if ('PaddleSvtr' in model) return model.PaddleSvtr;
+    return null;
+}
+
+export function extractAttnRec(model: RecognizerType | null): AttnRec | null {
+    if (!model || !('Parseq' in model)) {
         return null;
     }
-    return model.PaddleCrnn;
+    return model.Parseq;
+}
+
+export function recognizerConfig(model: RecognizerType): PaddleRecCrnn | AttnRec {
+    if ('PaddleCrnn' in model) return model.PaddleCrnn;
+    if ('PaddleSvtr' in model) return model.PaddleSvtr;
+    return model.Parseq;
 }
 
 export function createDetectorByKind(kind: DetectorKind, textMode: boolean): DetectorType | null {
@@ -155,6 +186,8 @@ export function createDetectorByKind(kind: DetectorKind, textMode: boolean): Det
 
 export function createRecognizerByKind(kind: RecognizerKind): RecognizerType | null {
     if (kind === 'none') return null;
+    if (kind === 'PaddleSvtr') return { PaddleSvtr: createSvtr() };
+    if (kind === 'Parseq') return { Parseq: createAttnRec() };
     return { PaddleCrnn: createCrnn(kind === 'PaddleCrnn6' ? 'v6' : 'v5') };
 }
 
@@ -193,13 +226,12 @@ export function rewritePublishedRecognizerModelPath(
         return null;
     }
     const next = clone(model);
-    if ('PaddleCrnn' in next) {
-        if (next.PaddleCrnn.baseModel.modelSource === 'Custom') {
-            next.PaddleCrnn.baseModel.modelPath = `${scriptId}/txt_rec_model.onnx`;
-        }
-        if (next.PaddleCrnn.dictPath?.trim()) {
-            next.PaddleCrnn.dictPath = `${scriptId}/${CUSTOM_CRNN_DICT_FILE_NAME}`;
-        }
+    const config = recognizerConfig(next);
+    if (config.baseModel.modelSource === 'Custom') {
+        config.baseModel.modelPath = `${scriptId}/txt_rec_model.onnx`;
+    }
+    if (config.dictPath?.trim()) {
+        config.dictPath = `${scriptId}/${CUSTOM_CRNN_DICT_FILE_NAME}`;
     }
     return next;
 }
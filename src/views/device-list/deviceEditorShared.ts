@@ -2,13 +2,18 @@ import { taskService } from '@/services/taskService';
 import { validateDeviceForm } from '@/views/device-list/deviceFormValidation';
 import type { DeviceFormState, SystemPreferences } from '@/types/app/domain';
 import type { DeviceTable } from '@/types/bindings/DeviceTable';
+import type { NotificationChannelConfig } from '@/types/bindings/NotificationChannelConfig';
+import type { NotificationTarget } from '@/types/bindings/NotificationTarget';
+import type { TaskRetryPolicy } from '@/types/bindings/TaskRetryPolicy';
+import { parseHumanizeSeed } from '@/utils/humanizeProfile';
 
 export const buildDeviceTableFromForm = async (
   form: DeviceFormState,
   preferences: SystemPreferences,
 ): Promise<DeviceTable> => {
   const normalized = validateDeviceForm(form);
-  const captureMethodType = form.transportKind === 'emulatorTcp' ? form.capMethodType : 'adb';
+  const captureMethodType =
+    form.transportKind !== 'emulatorTcp' && form.capMethodType === 'window' ? 'adb' : form.capMethodType;
 
   return {
     id: form.id ?? (await taskService.requestUuid()),
@@ -42,7 +47,12 @@ export const buildDeviceTableFromForm = async (
       capMethod:
         captureMethodType === 'adb'
           ? { type: 'adb' }
-          : {
+          : captureMethodType === 'adbStream'
+            ? {
+                type: 'adbStream',
+                maxFrameAgeMs: Math.max(0, Math.floor(Number(form.streamMaxFrameAgeMs) || 0)),
+              }
+            : {
               type: 'window',
               title: normalized.capMethodValue || form.deviceName,
               interface: form.windowCaptureInterface,
@@ -52,7 +62,7 @@ export const buildDeviceTableFromForm = async (
               offsetRightPx: normalized.windowOffsets.right,
               offsetBottomPx: normalized.windowOffsets.bottom,
             },
-      imageCompression: captureMethodType === 'adb' ? 'AdbOriginal' : 'WindowOriginal',
+      imageCompression: captureMethodType === 'window' ? 'WindowOriginal' : 'AdbOriginal',
       enable: form.enable,
       autoStart: form.autoStart,
       executionPolicy: {
@@ -61,7 +71,67 @@ export const buildDeviceTableFromForm = async (
         progressTimeoutMs: Math.max(1000, Math.floor(Number(form.progressTimeoutMs) || 30000)),
         timeoutAction: form.timeoutAction,
         timeoutNotifyChannels: [...form.timeoutNotifyChannels],
+        notificationTargets: form.notificationTargets.map(normalizeNotificationTarget),
+        retryPolicy: {
+          ...form.retryPolicy,
+          maxAttempts: Math.max(1, Math.floor(Number(form.retryPolicy.maxAttempts) || 1)),
+          backoffMs: Math.max(0, Math.floor(Number(form.retryPolicy.backoffMs) || 0)),
+        },
+        humanize: {
+          ...form.humanize,
+          pressMinMs: Math.max(0, Math.floor(Number(form.humanize.pressMinMs) || 0)),
+          pressMaxMs: Math.max(0, Math.floor(Number(form.humanize.pressMaxMs) || 0)),
+          delaySigmaMs: Math.max(0, Math.floor(Number(form.humanize.delaySigmaMs) || 0)),
+          seed: parseHumanizeSeed(form.humanizeSeed),
+        },
       },
     },
   };
 };
+
+export const createDefaultRetryPolicy = (): TaskRetryPolicy => ({
+  maxAttempts: 1,
+  backoffMs: 5000,
+  backoffFactor: 2,
+  maxBackoffMs: 60000,
+  runRecoveryTask: true,
+  giveUpAction: 'failScript',
+});
+
+// 与 Rust 侧 NotificationChannelConfig 的默认值保持一致
+export const createNotificationChannel = (type: NotificationChannelConfig['type']): NotificationChannelConfig => {
+  switch (type) {
+    case 'webhook':
+      return { type, url: '', method: 'POST', headers: {}, bodyTemplate: '', timeoutSeconds: 10 as never };
+    case 'file':
+      return { type, path: '' };
+    case 'command':
+      return { type, program: '', args: [], timeoutSeconds: 10 as never };
+    case 'email':
+      return { type };
+  }
+};
+
+export const createDefaultNotificationTarget = (): NotificationTarget => ({
+  name: '',
+  enabled: true,
+  events: ['taskFailed', 'scriptFailed', 'timeout'],
+  channel: createNotificationChannel('webhook'),
+});
+
+const normalizeNotificationTarget = (target: NotificationTarget): NotificationTarget => {
+  const channel = { ...target.channel };
+  if (channel.type === 'webhook') {
+    channel.url = channel.url.trim();
+    channel.method = channel.method.trim().toUpperCase() || 'POST';
+  } else if (channel.type === 'file') {
+    channel.path = channel.path.trim();
+  } else if (channel.type === 'command') {
+    channel.program = channel.program.trim();
+    channel.args = channel.args.filter((arg) => arg.trim().length > 0);
+  }
+  if (channel.type === 'webhook' || channel.type === 'command') {
+    channel.timeoutSeconds = Math.max(1, Math.floor(Number(channel.timeoutSeconds) || 10)) as never;
+  }
+  return { ...target, name: target.name.trim(), events: [...target.events], channel };
+};
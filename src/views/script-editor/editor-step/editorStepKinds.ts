@@ -17,6 +17,7 @@ import type { TaskRowType } from '@/types/bindings/TaskRowType';
 import type { TaskTone } from '@/types/bindings/TaskTone';
 import type { TaskTriggerMode } from '@/types/bindings/TaskTriggerMode';
 import type { VisionNode } from '@/types/bindings/VisionNode';
+import type { WaitUntilTarget } from '@/types/bindings/WaitUntilTarget';
 
 type ActionMode = Extract<Action, { ac: 'click' }>['mode'] | Extract<Action, { ac: 'swipe' }>['mode'];
 type PolicySetResultField = Extract<ConditionNode, { type: 'policySetResult' }>['field'];
@@ -35,7 +36,7 @@ export const STEP_OP = {
   vision: STEP_OPS[5],
 } as const;
 
-export const ACTION_TYPES = ['click', 'swipe', 'capture', 'reboot', 'back', 'posAdd', 'posMinus', 'dropSetNext', 'launchApp', 'stopApp'] as const satisfies readonly Action['ac'][];
+export const ACTION_TYPES = ['click', 'swipe', 'capture', 'reboot', 'back', 'posAdd', 'posMinus', 'dropSetNext', 'launchApp', 'stopApp', 'keyEvent', 'path', 'pinch'] as const satisfies readonly Action['ac'][];
 export const ACTION_TYPE = {
   click: ACTION_TYPES[0],
   swipe: ACTION_TYPES[1],
@@ -47,6 +48,9 @@ export const ACTION_TYPE = {
   dropSetNext: ACTION_TYPES[7],
   launchApp: ACTION_TYPES[8],
   stopApp: ACTION_TYPES[9],
+  keyEvent: ACTION_TYPES[10],
+  path: ACTION_TYPES[11],
+  pinch: ACTION_TYPES[12],
 } as const;
 
 export const ACTION_MODES = ['point', 'percent', 'txt', 'labelIdx', 'mixed'] as const satisfies readonly ActionMode[];
@@ -79,6 +83,7 @@ export const FLOW_TYPES = [
   'searchPolicySetText',
   'handlePolicySet',
   'handlePolicy',
+  'waitUntil',
 ] as const satisfies readonly FlowControl['type'][];
 export const FLOW_TYPE = {
   if: FLOW_TYPES[0],
@@ -101,9 +106,17 @@ export const FLOW_TYPE = {
   searchPolicySetText: FLOW_TYPES[17],
   handlePolicySet: FLOW_TYPES[18],
   handlePolicy: FLOW_TYPES[19],
+  waitUntil: FLOW_TYPES[20],
 } as const;
 
-export const DATA_TYPES = ['setVar', 'clearVars', 'getVar', 'print', 'filter', 'colorCompare', 'relativeFilter', 'rhai'] as const satisfies readonly DataHanding['type'][];
+export const WAIT_UNTIL_TARGET_TYPES = ['stable', 'changed', 'condition'] as const satisfies readonly WaitUntilTarget['type'][];
+export const WAIT_UNTIL_TARGET_TYPE = {
+  stable: WAIT_UNTIL_TARGET_TYPES[0],
+  changed: WAIT_UNTIL_TARGET_TYPES[1],
+  condition: WAIT_UNTIL_TARGET_TYPES[2],
+} as const;
+
+export const DATA_TYPES = ['setVar', 'clearVars', 'getVar', 'print', 'filter', 'colorCompare', 'relativeFilter', 'rhai', 'extractNumber', 'storeGet', 'storeSet', 'storeRemove'] as const satisfies readonly DataHanding['type'][];
 export const DATA_TYPE = {
   setVar: DATA_TYPES[0],
   clearVars: DATA_TYPES[1],
@@ -113,6 +126,10 @@ export const DATA_TYPE = {
   colorCompare: DATA_TYPES[5],
   relativeFilter: DATA_TYPES[6],
   rhai: DATA_TYPES[7],
+  extractNumber: DATA_TYPES[8],
+  storeGet: DATA_TYPES[9],
+  storeSet: DATA_TYPES[10],
+  storeRemove: DATA_TYPES[11],
 } as const;
 
 export const PRINT_SOURCES = ['text', 'variable'] as const satisfies readonly PrintSource[];
@@ -128,11 +145,12 @@ export const COLOR_COMPARE_METHOD_TYPE = {
   oklabDistance: COLOR_COMPARE_METHOD_TYPES[0],
 } as const;
 
-export const VISION_TYPES = ['detect', 'ocr', 'visionSearch'] as const satisfies readonly VisionNode['type'][];
+export const VISION_TYPES = ['detect', 'ocr', 'visionSearch', 'templateMatch'] as const satisfies readonly VisionNode['type'][];
 export const VISION_TYPE = {
   detect: VISION_TYPES[0],
   ocr: VISION_TYPES[1],
   visionSearch: VISION_TYPES[2],
+  templateMatch: VISION_TYPES[3],
 } as const;
 
 export const TASK_CONTROL_TYPES = ['setState'] as const satisfies readonly TaskControl['type'][];
@@ -209,7 +227,7 @@ export const TASK_CYCLE_VALUE = {
   monthDay: 'monthDay',
 } as const;
 
-export const CONDITION_TYPES = ['rawExpr', 'group', 'execNumCompare', 'taskStatus', 'currentTaskIn', 'varCompare', 'visionCountCompare', 'policySetResult', 'colorCompare'] as const satisfies readonly ConditionNode['type'][];
+export const CONDITION_TYPES = ['rawExpr', 'group', 'execNumCompare', 'taskStatus', 'currentTaskIn', 'varCompare', 'visionCountCompare', 'policySetResult', 'colorCompare', 'ocrNumberCompare'] as const satisfies readonly ConditionNode['type'][];
 export const CONDITION_TYPE = {
   rawExpr: CONDITION_TYPES[0],
   group: CONDITION_TYPES[1],
@@ -220,6 +238,7 @@ export const CONDITION_TYPE = {
   visionCountCompare: CONDITION_TYPES[6],
   policySetResult: CONDITION_TYPES[7],
   colorCompare: CONDITION_TYPES[8],
+  ocrNumberCompare: CONDITION_TYPES[9],
 } as const;
 
 export const POLICY_SET_RESULT_FIELDS = ['matched', 'policySetId', 'policyGroupId', 'policyId'] as const satisfies readonly PolicySetResultField[];
@@ -261,6 +280,18 @@ export const createRegionPoint = (mode: 'point' | 'percent' = ACTION_MODE.point,
   mode,
   p: { x, y },
 });
+export const createWaitUntilTarget = (type: WaitUntilTarget['type'] = WAIT_UNTIL_TARGET_TYPE.stable): WaitUntilTarget => {
+  if (type === WAIT_UNTIL_TARGET_TYPE.condition) {
+    return { type, con: { type: CONDITION_TYPE.rawExpr, expr: 'true' } };
+  }
+  const region = {
+    diff_threshold: 0.02,
+    region_top_left: createRegionPoint(),
+    region_bottom_right: createRegionPoint(),
+  };
+  const target = type === WAIT_UNTIL_TARGET_TYPE.changed ? { type, reference_var: null, ...region } : { type, stable_ms: 1000, ...region };
+  return target as unknown as WaitUntilTarget;
+};
 export const createColorCompareMethod = (
   type: ColorCompareMethod['type'] = COLOR_COMPARE_METHOD_TYPE.oklabDistance,
   threshold = 0,
@@ -1,5 +1,7 @@
 import type { Step } from '@/types/bindings';
 import type { VisionNode } from '@/types/bindings/VisionNode';
+import type { StoreExpiry } from '@/types/bindings/StoreExpiry';
+import type { WaitUntilTarget } from '@/types/bindings/WaitUntilTarget';
 import {
   ACTION_MODE,
   ACTION_TYPE,
@@ -14,6 +16,7 @@ import {
   createStateTargetList,
   createStepList,
   createStringList,
+  createWaitUntilTarget,
   DATA_TYPE,
   FLOW_TYPE,
   LOGIC_OP,
@@ -24,8 +27,10 @@ import {
   STEP_OP,
   TASK_CONTROL_TYPE,
   VISION_TYPE,
+  WAIT_UNTIL_TARGET_TYPE,
 } from '@/views/script-editor/editor-step/editorStepKinds';
 import { buildVarValue } from '@/views/script-editor/editorVarValue';
+import { createOcrNumberSource, describeOcrNumberSource } from '@/views/script-editor/editorNumberSource';
 
 export interface EditorStepTemplate {
   id: string;
@@ -68,10 +73,14 @@ const SVG_ICONS = {
   swipeLabel: '<rect x="4" y="4" width="12" height="12" rx="2" stroke-dasharray="3 2"/><path d="M10 20h10"/><path d="M17 17l3 3-3 3"/>',
   swipeMixed: '<rect x="3" y="4" width="7" height="7" rx="1" stroke-dasharray="2 1"/><path d="M13 5h8"/><path d="M13 10h5"/><path d="M6 15v5h14"/><path d="M17 17l3 3-3 3"/>',
   back: '<path d="M15 18l-6-6 6-6"/>',
+  path: '<circle cx="5" cy="5" r="1.5"/><circle cx="19" cy="5" r="1.5"/><circle cx="5" cy="19" r="1.5"/><circle cx="19" cy="19" r="1.5"/><path d="M5 5l14 0-14 14 14 0"/>',
+  pinch: '<path d="M4 4l6 6"/><path d="M4 9V4h5"/><path d="M20 20l-6-6"/><path d="M20 15v5h-5"/>',
+  key: '<rect x="2" y="6" width="20" height="12" rx="2"/><path d="M6 10h.01"/><path d="M10 10h.01"/><path d="M14 10h.01"/><path d="M18 10h.01"/><path d="M8 14h8"/>',
   add: '<circle cx="12" cy="12" r="10"/><path d="M12 8v8"/><path d="M8 12h8"/>',
   minus: '<circle cx="12" cy="12" r="10"/><path d="M8 12h8"/>',
   next: '<path d="M6 9l6 6 6-6"/>',
   wait: '<circle cx="12" cy="12" r="10"/><path d="M12 6v6l4 2"/>',
+  waitUntil: '<path d="M5 22h14"/><path d="M5 2h14"/><path d="M17 22v-4.17a2 2 0 0 0-.59-1.42L12 12l-4.41 4.41A2 2 0 0 0 7 17.83V22"/><path d="M7 2v4.17a2 2 0 0 0 .59 1.42L12 12l4.41-4.41A2 2 0 0 0 17 6.17V2"/>',
   branch: '<path d="M6 3v12"/><circle cx="18" cy="6" r="3"/><circle cx="6" cy="18" r="3"/><path d="M18 9a9 9 0 0 1-9 9"/>',
   loop: '<path d="M21 12a9 9 0 1 1-9-9c2.5 0 4.8 1 6.5 2.5L21 8"/><path d="M21 2v6h-6"/>',
   forEach: '<rect x="3" y="3" width="7" height="7" rx="1"/><rect x="14" y="3" width="7" height="7" rx="1"/><rect x="14" y="14" width="7" height="7" rx="1"/><rect x="3" y="14" width="7" height="7" rx="1"/>',
@@ -92,6 +101,7 @@ const SVG_ICONS = {
   policyRun: '<path d="M14 2H6a2 2 0 0 0-2 2v16a2 2 0 0 0 2-2h12a2 2 0 0 0 2-2V8z"/><path d="M14 2v6h6"/><path d="M10 12l5 3-5 3z"/>',
   setVar: '<path d="M12 20h9"/><path d="M16.5 3.5a2.121 2.121 0 0 1 3 3L7 19l-4 1 1-4L16.5 3.5z"/>',
   getVar: '<path d="M21 15v4a2 2 0 0 1-2 2H5a2 2 0 0 1-2-2v-4"/><path d="M7 10l5 5 5-5"/><path d="M12 15V3"/>',
+  store: '<ellipse cx="12" cy="5" rx="9" ry="3"/><path d="M3 5v14c0 1.66 4 3 9 3s9-1.34 9-3V5"/><path d="M3 12c0 1.66 4 3 9 3s9-1.34 9-3"/>',
   clearVars: '<path d="M3 6h18"/><path d="M8 6V4h8v2"/><path d="M19 6l-1 15H6L5 6"/><path d="M10 11v5"/><path d="M14 11v5"/>',
   code: '<path d="M16 18l6-6-6-6"/><path d="M8 6l-6 6 6 6"/><path d="M14 4l-4 16"/>',
   print: '<path d="M6 9V3h12v6"/><rect x="6" y="14" width="12" height="7"/><path d="M6 17H4a2 2 0 0 1-2-2v-4a2 2 0 0 1 2-2h16a2 2 0 0 1 2 2v4a2 2 0 0 1-2 2h-2"/><path d="M18 12h.01"/>',
@@ -101,6 +111,7 @@ const SVG_ICONS = {
   ocr: '<path d="M4 6h16"/><path d="M4 12h10"/><path d="M4 18h7"/><path d="M18 12l2 2-4 4"/>',
   count: '<path d="M9 7h11"/><path d="M9 12h11"/><path d="M9 17h11"/><path d="M4 7h.01"/><path d="M4 12h.01"/><path d="M4 17h.01"/>',
   search: '<circle cx="11" cy="11" r="8"/><path d="M21 21l-4.35-4.35"/>',
+  templateMatch: '<rect x="3" y="3" width="18" height="18" rx="2"/><circle cx="9" cy="9" r="2"/><path d="M21 15l-3.1-3.1a2 2 0 0 0-2.8 0L6 21"/>',
   scanSearch: '<path d="M3 7V5a2 2 0 0 1 2-2h2"/><path d="M17 3h2a2 2 0 0 1 2 2v2"/><path d="M21 17v2a2 2 0 0 1-2 2h-2"/><path d="M7 21H5a2 2 0 0 1-2-2v-2"/><circle cx="11" cy="11" r="4"/><path d="m15 15 3 3"/>',
   state: '<path d="M22 11.08V12a10 10 0 1 1-5.93-9.14"/><path d="M22 4L12 14.01l-3-3"/>',
   link: '<path d="M5 5v14"/><path d="M5 12h14"/><path d="m15 7 5 5-5 5"/>',
@@ -383,6 +394,66 @@ export const editorStepTemplates: EditorStepTemplate[] = [
         },
       }),
   },
+  {
+    id: 'key-event',
+    icon: genSvg(SVG_ICONS.key),
+    label: '按键',
+    description: '发送按键事件，支持 ENTER、ESC、VOLUME_UP 等按键名或 keycode:66 形式的 Android 键码。',
+    group: '动作',
+    create: () =>
+      createBaseStep({
+        label: '按键',
+        op: STEP_OP.action,
+        exec_max: 0,
+        a: {
+          ac: ACTION_TYPE.keyEvent,
+          code: 'ENTER',
+          long_press: false,
+          repeat: 1,
+        },
+      }),
+  },
+  {
+    id: 'path',
+    icon: genSvg(SVG_ICONS.path),
+    label: '轨迹滑动',
+    description: '按住后沿折线依次经过多个点，每段可设置耗时，适合图案解锁与连线。',
+    group: '动作',
+    create: () =>
+      createBaseStep({
+        label: '轨迹滑动',
+        op: STEP_OP.action,
+        exec_max: 0,
+        a: {
+          ac: ACTION_TYPE.path,
+          points: [
+            { point: { mode: ACTION_MODE.point, p: { x: 320, y: 360 } }, duration: 0 as never },
+            { point: { mode: ACTION_MODE.point, p: { x: 640, y: 360 } }, duration: 200 as never },
+            { point: { mode: ACTION_MODE.point, p: { x: 640, y: 600 } }, duration: 200 as never },
+          ],
+        },
+      }),
+  },
+  {
+    id: 'pinch',
+    icon: genSvg(SVG_ICONS.pinch),
+    label: '双指缩放',
+    description: '以中心点为中点水平移动两根手指，终止距离大于起始距离时放大。',
+    group: '动作',
+    create: () =>
+      createBaseStep({
+        label: '双指缩放',
+        op: STEP_OP.action,
+        exec_max: 0,
+        a: {
+          ac: ACTION_TYPE.pinch,
+          center: { mode: ACTION_MODE.percent, p: { x: 0.5, y: 0.5 } },
+          start_distance: 200,
+          end_distance: 500,
+          duration: 400 as never,
+        },
+      }),
+  },
   {
     id: 'pos-add',
     icon: genSvg(SVG_ICONS.add),
@@ -458,6 +529,25 @@ export const editorStepTemplates: EditorStepTemplate[] = [
         },
       }),
   },
+  {
+    id: 'wait-until',
+    icon: genSvg(SVG_ICONS.waitUntil),
+    label: '等待画面',
+    description: '反复截图直到画面稳定、发生变化或条件成立，超时后执行兜底步骤。',
+    group: '流程',
+    create: () =>
+      createBaseStep({
+        label: '等待画面',
+        op: STEP_OP.flowControl,
+        a: {
+          type: FLOW_TYPE.waitUntil,
+          until: createWaitUntilTarget(),
+          timeout_ms: 10000,
+          interval_ms: 300,
+          else_steps: createStepList(),
+        },
+      }),
+  },
   {
     id: 'if',
     icon: genSvg(SVG_ICONS.branch),
@@ -921,6 +1011,77 @@ export const editorStepTemplates: EditorStepTemplate[] = [
         },
       }),
   },
+  {
+    id: 'extract-number',
+    icon: genSvg(SVG_ICONS.getVar),
+    label: '提取数值',
+    description: '从 OCR 文本中提取整数、小数、分数、百分比或时长，写入变量。',
+    group: '数据',
+    create: () =>
+      createBaseStep({
+        label: '提取数值',
+        op: STEP_OP.dataHanding,
+        a: {
+          type: DATA_TYPE.extractNumber,
+          ...createOcrNumberSource(),
+          field: 'value',
+          out_var: 'runtime.number',
+        },
+      }),
+  },
+  {
+    id: 'store-get',
+    icon: genSvg(SVG_ICONS.store),
+    label: '读取存储',
+    description: '读取跨运行持久化的键值，按设备、账号与脚本隔离。',
+    group: '数据',
+    create: () =>
+      createBaseStep({
+        label: '读取存储',
+        op: STEP_OP.dataHanding,
+        a: {
+          type: DATA_TYPE.storeGet,
+          key: '',
+          out_var: 'runtime.stored',
+          default_val: null,
+        },
+      }),
+  },
+  {
+    id: 'store-set',
+    icon: genSvg(SVG_ICONS.store),
+    label: '写入存储',
+    description: '将表达式结果写入持久化存储，可设置有效期或每日重置。',
+    group: '数据',
+    create: () =>
+      createBaseStep({
+        label: '写入存储',
+        op: STEP_OP.dataHanding,
+        a: {
+          type: DATA_TYPE.storeSet,
+          key: '',
+          json_val: null,
+          expr: '',
+          expiry: { type: 'never' },
+        },
+      }),
+  },
+  {
+    id: 'store-remove',
+    icon: genSvg(SVG_ICONS.store),
+    label: '删除存储',
+    description: '删除持久化存储中的一个或多个键。',
+    group: '数据',
+    create: () =>
+      createBaseStep({
+        label: '删除存储',
+        op: STEP_OP.dataHanding,
+        a: {
+          type: DATA_TYPE.storeRemove,
+          keys: createStringList(),
+        },
+      }),
+  },
   {
     id: 'vision-detect',
     icon: genSvg(SVG_ICONS.detect),
@@ -935,6 +1096,8 @@ export const editorStepTemplates: EditorStepTemplate[] = [
           type: VISION_TYPE.detect,
           input_var: 'runtime.captureResult',
           out_var: 'runtime.detResults',
+          region_top_left: createRegionPoint(),
+          region_bottom_right: createRegionPoint(),
         },
       }),
   },
@@ -952,6 +1115,33 @@ export const editorStepTemplates: EditorStepTemplate[] = [
           type: VISION_TYPE.ocr,
           input_var: 'runtime.captureResult',
           out_var: 'runtime.ocrResults',
+          region_top_left: createRegionPoint(),
+          region_bottom_right: createRegionPoint(),
+        },
+      }),
+  },
+  {
+    id: 'vision-template-match',
+    icon: genSvg(SVG_ICONS.templateMatch),
+    label: '模板匹配',
+    description: '按参考图在截图中查找目标，输出与目标检测兼容的结果。',
+    group: '视觉',
+    create: () =>
+      createBaseStep({
+        label: '模板匹配',
+        op: STEP_OP.vision,
+        a: {
+          type: VISION_TYPE.templateMatch,
+          input_var: 'runtime.captureResult',
+          template: '',
+          label: '',
+          idx: 0,
+          threshold: 0.8,
+          scales: [1],
+          max_results: 0,
+          region_top_left: createRegionPoint(),
+          region_bottom_right: createRegionPoint(),
+          out_var: 'runtime.detResults',
         },
       }),
   },
@@ -1061,6 +1251,9 @@ export const describeStepTitle = (step: Step) => {
     if (step.a.ac === ACTION_TYPE.stopApp) return '停止应用';
     if (step.a.ac === ACTION_TYPE.reboot) return '重启应用';
     if (step.a.ac === ACTION_TYPE.back) return '返回';
+    if (step.a.ac === ACTION_TYPE.keyEvent) return '按键';
+    if (step.a.ac === ACTION_TYPE.path) return '轨迹滑动';
+    if (step.a.ac === ACTION_TYPE.pinch) return '双指缩放';
     if (step.a.ac === ACTION_TYPE.posAdd) return '点击索引加一';
     if (step.a.ac === ACTION_TYPE.posMinus) return '点击索引减一';
     if (step.a.ac === ACTION_TYPE.dropSetNext) return 'UI 变量下一个';
@@ -1082,6 +1275,7 @@ export const describeStepTitle = (step: Step) => {
 
   if (step.op === STEP_OP.flowControl) {
     if (step.a.type === FLOW_TYPE.waitMs) return '等待';
+    if (step.a.type === FLOW_TYPE.waitUntil) return '等待画面';
     if (step.a.type === FLOW_TYPE.link) return '跳转任务';
     if (step.a.type === FLOW_TYPE.addPolicies) return '追加策略集';
     if (step.a.type === FLOW_TYPE.removePolicies) return '移除策略集';
@@ -1113,6 +1307,10 @@ export const describeStepTitle = (step: Step) => {
     if (step.a.type === DATA_TYPE.filter) return '过滤变量';
     if (step.a.type === DATA_TYPE.colorCompare) return '颜色筛选';
     if (step.a.type === DATA_TYPE.relativeFilter) return '相对位置筛选';
+    if (step.a.type === DATA_TYPE.extractNumber) return '提取数值';
+    if (step.a.type === DATA_TYPE.storeGet) return '读取存储';
+    if (step.a.type === DATA_TYPE.storeSet) return '写入存储';
+    if (step.a.type === DATA_TYPE.storeRemove) return '删除存储';
     return '数据处理';
   }
 
@@ -1125,12 +1323,25 @@ export const describeStepTitle = (step: Step) => {
     if (visionStep.type === VISION_TYPE.detect) return '目标检测';
     if (visionStep.type === VISION_TYPE.ocr) return 'OCR';
     if (visionStep.type === VISION_TYPE.visionSearch) return '视觉搜索';
+    if (visionStep.type === VISION_TYPE.templateMatch) return '模板匹配';
     return '视觉步骤';
   }
 
   return '步骤';
 };
 
+const describeWaitUntilTarget = (until: WaitUntilTarget) => {
+  if (until.type === WAIT_UNTIL_TARGET_TYPE.stable) return `等待画面稳定 ${String(until.stable_ms)} ms`;
+  if (until.type === WAIT_UNTIL_TARGET_TYPE.changed) return until.reference_var ? `等待画面不同于 ${until.reference_var}` : '等待画面变化';
+  return '等待条件成立';
+};
+
+const describeStoreExpiry = (expiry: StoreExpiry) => {
+  if (expiry.type === 'ttl') return `${String(expiry.seconds)} 秒后过期`;
+  if (expiry.type === 'daily') return `每日 ${expiry.reset_hour} 点重置`;
+  return '永不过期';
+};
+
 export const describeStepMeta = (step: Step) => {
   const visionStep = step.op === STEP_OP.vision ? (step.a as VisionNode) : null;
 
@@ -1148,6 +1359,18 @@ export const describeStepMeta = (step: Step) => {
     if (step.a.ac === ACTION_TYPE.stopApp) return `停止 ${step.a.pkg_name_expr || step.a.pkg_name || '未指定包名'}`;
     if (step.a.ac === ACTION_TYPE.reboot) return '旧版重启应用动作，请改用停止应用 + 启动应用';
     if (step.a.ac === ACTION_TYPE.back) return 'Android 返回键';
+    if (step.a.ac === ACTION_TYPE.keyEvent) {
+      const press = step.a.long_press ? '长按' : '按下';
+      return `${press} ${step.a.code || '未指定按键'}${step.a.repeat > 1 ? ` × ${step.a.repeat}` : ''}`;
+    }
+    if (step.a.ac === ACTION_TYPE.path) {
+      const total = step.a.points.slice(1).reduce((sum, waypoint) => sum + Number(waypoint.duration), 0);
+      return `${step.a.points.length} 个点 · ${total}ms`;
+    }
+    if (step.a.ac === ACTION_TYPE.pinch) {
+      const zoom = step.a.end_distance > step.a.start_distance ? '放大' : '缩小';
+      return `${zoom} ${step.a.start_distance} → ${step.a.end_distance} · ${step.a.duration}ms`;
+    }
     if (step.a.ac === ACTION_TYPE.posAdd) return `调整策略 ${step.a.target || '未指定'} · +1`;
     if (step.a.ac === ACTION_TYPE.posMinus) return `调整策略 ${step.a.target || '未指定'} · -1`;
     if (step.a.ac === ACTION_TYPE.dropSetNext) return `任务 ${step.a.task || '未指定'} · 变量 ${step.a.variable_id || '未指定'}`;
@@ -1174,6 +1397,8 @@ export const describeStepMeta = (step: Step) => {
           return `等待输入变量 · ${step.a.input_var} · 兜底 ${String(step.a.ms)} ms`;
         }
         return `等待 ${String(step.a.ms)} ms`;
+      case FLOW_TYPE.waitUntil:
+        return `${describeWaitUntilTarget(step.a.until)} · 超时 ${String(step.a.timeout_ms)} ms${step.a.else_steps.length ? ` · 兜底 ${step.a.else_steps.length} 步` : ''}`;
       case FLOW_TYPE.link:
         return `跳转➡️[${step.a.target || '未指定'}]`;
       case FLOW_TYPE.addPolicies:
@@ -1231,6 +1456,14 @@ export const describeStepMeta = (step: Step) => {
         return `颜色筛选 ${step.a.input_var || '未命名输入'} -> ${step.a.out_var || '未命名输出'}`;
       case DATA_TYPE.relativeFilter:
         return `相对位置 ${step.a.input_var || '未命名输入'} -> ${step.a.out_var || '未命名输出'}`;
+      case DATA_TYPE.extractNumber:
+        return `提取数值 ${describeOcrNumberSource(step.a)} -> ${step.a.out_var || '未命名输出'}`;
+      case DATA_TYPE.storeGet:
+        return `读取存储 ${step.a.key || '未指定键'} -> ${step.a.out_var || '未命名输出'}`;
+      case DATA_TYPE.storeSet:
+        return `写入存储 ${step.a.key || '未指定键'} · ${describeStoreExpiry(step.a.expiry)}`;
+      case DATA_TYPE.storeRemove:
+        return step.a.keys.length ? `删除 ${step.a.keys.length} 个存储键` : '删除存储键列表';
       default:
         return '数据处理';
     }
@@ -1243,6 +1476,9 @@ export const describeStepMeta = (step: Step) => {
     if (visionStep.type === VISION_TYPE.ocr) {
       return `OCR ${visionStep.input_var || '未命名输入'} -> ${visionStep.out_var || '未命名输出'}`;
     }
+    if (visionStep.type === VISION_TYPE.templateMatch) {
+      return `模板匹配${visionStep.template ? '' : '（未设置参考图）'} ${visionStep.input_var || '未命名输入'} -> ${visionStep.out_var || '未命名输出'}`;
+    }
     return `视觉搜索 -> ${visionStep.out_var || '未命名输出'}`;
   }
 
@@ -100,6 +100,14 @@ export const getParentBranchPath = (path: StepPath | null): StepBranchPath => {
   };
 };
 
+const NESTED_BRANCH_KINDS: StepBranchKind[] = ['sequence', 'then', 'else', 'flow', 'visionThen', 'filterThen'];
+
+/** 收集步骤自身及全部嵌套子步骤的 id */
+export const collectStepIds = (step: Step): string[] => [
+  ...(step.id ? [step.id] : []),
+  ...NESTED_BRANCH_KINDS.flatMap((branch) => getBranchStepsFromStep(step, branch).flatMap(collectStepIds)),
+];
+
 const getBranchStepsFromStep = (step: Step, branch: StepBranchKind): Step[] => {
   switch (branch) {
     case 'sequence':
@@ -107,7 +115,9 @@ const getBranchStepsFromStep = (step: Step, branch: StepBranchKind): Step[] => {
     case 'then':
       return step.op === STEP_OP.flowControl && step.a.type === FLOW_TYPE.if ? step.a.then : [];
     case 'else':
-      return step.op === STEP_OP.flowControl && step.a.type === FLOW_TYPE.if ? (step.a.else_steps ?? []) : [];
+      if (step.op !== STEP_OP.flowControl) return [];
+      if (step.a.type === FLOW_TYPE.if) return step.a.else_steps ?? [];
+      return step.a.type === FLOW_TYPE.waitUntil ? step.a.else_steps : [];
     case 'flow':
       return step.op === STEP_OP.flowControl && (step.a.type === FLOW_TYPE.while || step.a.type === FLOW_TYPE.forEach || step.a.type === FLOW_TYPE.repeat) ? step.a.flow : [];
     case 'visionThen':
@@ -129,7 +139,9 @@ const setBranchStepsOnStep = (step: Step, branch: StepBranchKind, steps: Step[])
     case 'then':
       return step.op === STEP_OP.flowControl && step.a.type === FLOW_TYPE.if ? { ...step, a: { ...step.a, then: steps } } : step;
     case 'else':
-      return step.op === STEP_OP.flowControl && step.a.type === FLOW_TYPE.if ? { ...step, a: { ...step.a, else_steps: steps } } : step;
+      return step.op === STEP_OP.flowControl && (step.a.type === FLOW_TYPE.if || step.a.type === FLOW_TYPE.waitUntil)
+        ? { ...step, a: { ...step.a, else_steps: steps } }
+        : step;
     case 'flow':
       return step.op === STEP_OP.flowControl && (step.a.type === FLOW_TYPE.while || step.a.type === FLOW_TYPE.forEach || step.a.type === FLOW_TYPE.repeat)
         ? { ...step, a: { ...step.a, flow: steps } }
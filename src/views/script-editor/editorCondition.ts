@@ -22,6 +22,7 @@ import {
   STATE_STATUS_TYPE,
   TASK_CONTROL_TYPE,
 } from '@/views/script-editor/editor-step/editorStepKinds';
+import { createOcrNumberSource, describeOcrNumberSource } from '@/views/script-editor/editorNumberSource';
 
 const castCondition = (value: unknown) => value as ConditionNode;
 
@@ -34,6 +35,7 @@ export const conditionTypeOptions = [
   { label: '变量比较', value: CONDITION_TYPE.varCompare, description: '比较运行时变量或输入变量。' },
   { label: '判断数量大小', value: CONDITION_TYPE.visionCountCompare, description: '统计检测标签或 OCR 文字的匹配数量，并和指定数量比较。' },
   { label: '策略集结果', value: CONDITION_TYPE.policySetResult, description: '按策略集处理步骤输出的结果对象判断。' },
+  { label: 'OCR 数值比较', value: CONDITION_TYPE.ocrNumberCompare, description: '从 OCR 文本中提取数值、分数、百分比或时长，并和指定值比较。' },
 ];
 
 export const logicOpOptions = [
@@ -168,6 +170,14 @@ export const createConditionNode = (type: ConditionNode['type'] = CONDITION_TYPE
         g: 255,
         b: 255,
       });
+    case CONDITION_TYPE.ocrNumberCompare:
+      return castCondition({
+        type: CONDITION_TYPE.ocrNumberCompare,
+        ...createOcrNumberSource(),
+        field: 'value',
+        op: COMPARE_OP.ge satisfies CompareOp,
+        value: 0,
+      });
     default:
       return castCondition({
         type: CONDITION_TYPE.rawExpr,
@@ -202,6 +212,8 @@ export const describeConditionNode = (node: ConditionNode) => {
       return `数量比较 · ${node.input_var || '未绑定变量'} · ${node.op} ${node.expected_count}`;
     case 'policySetResult':
       return `策略集结果 · ${node.field}`;
+    case 'ocrNumberCompare':
+      return `数值比较 · ${describeOcrNumberSource(node)} · ${node.op} ${node.value}`;
     case 'colorCompare':
       return `${node.is_font ? '字体色' : '背景色'} · ${node.txt_target || '未指定目标'}`;
     default:
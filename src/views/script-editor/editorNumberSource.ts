@@ -0,0 +1,44 @@
+import type { NumberField } from '@/types/bindings/NumberField';
+import type { NumberKind } from '@/types/bindings/NumberKind';
+import type { OcrNumberSource } from '@/types/bindings/OcrNumberSource';
+import type { RelativeDirection } from '@/types/bindings/RelativeDirection';
+import { createRegionPoint } from '@/views/script-editor/editor-step/editorStepKinds';
+
+export const numberKindOptions = [
+  { label: '自动识别', value: 'auto' satisfies NumberKind, description: '取文本中最先出现的数值，时长与分数优先。' },
+  { label: '整数', value: 'integer' satisfies NumberKind, description: '支持千分位，如 1,234。' },
+  { label: '小数', value: 'decimal' satisfies NumberKind, description: '如 12.5。' },
+  { label: '分数', value: 'fraction' satisfies NumberKind, description: '如 体力 45/120。' },
+  { label: '百分比', value: 'percent' satisfies NumberKind, description: '如 85%。' },
+  { label: '时长', value: 'duration' satisfies NumberKind, description: '如 01:20:05、2小时30分，单位毫秒。' },
+];
+
+export const numberFieldOptions = [
+  { label: '数值', value: 'value' satisfies NumberField, description: '整数/小数/百分比取数值本身，分数取分子，时长取毫秒。' },
+  { label: '分母', value: 'denominator' satisfies NumberField, description: '仅分数可用。' },
+  { label: '比值', value: 'ratio' satisfies NumberField, description: '分数取分子/分母，百分比取除以 100 后的值。' },
+];
+
+export const anchorDirectionOptions = [
+  { label: '同一文本', value: '', description: '锚点与数值在同一条 OCR 文本中。' },
+  { label: '右侧', value: 'right' satisfies RelativeDirection, description: '取锚点右侧最近的文本。' },
+  { label: '下方', value: 'below' satisfies RelativeDirection, description: '取锚点下方最近的文本。' },
+  { label: '左侧', value: 'left' satisfies RelativeDirection, description: '取锚点左侧最近的文本。' },
+  { label: '上方', value: 'above' satisfies RelativeDirection, description: '取锚点上方最近的文本。' },
+  { label: '附近', value: 'near' satisfies RelativeDirection, description: '取距离锚点最近的文本。' },
+];
+
+export const createOcrNumberSource = (inputVar = 'runtime.ocrResults'): OcrNumberSource => ({
+  input_var: inputVar,
+  region_top_left: createRegionPoint(),
+  region_bottom_right: createRegionPoint(),
+  anchor_text: '',
+  anchor_direction: null,
+  kind: 'auto',
+});
+
+export const describeOcrNumberSource = (source: OcrNumberSource) => {
+  const input = source.input_var.trim() || '当前截图';
+  const anchor = source.anchor_text.trim();
+  return anchor ? `${input} · 锚点 ${anchor}` : input;
+};
@@ -1,4 +1,6 @@
 import type { SearchRule } from '@/types/bindings/SearchRule';
+import type { TextMatchMode } from '@/types/bindings/TextMatchMode';
+import type { TextNormalize } from '@/types/bindings/TextNormalize';
 import {
   createSearchRuleList,
   LOGIC_OP,
@@ -23,6 +25,41 @@ export const searchScopeOptions = [
   { label: '条目', value: SEARCH_SCOPE.Item, description: '在单个条目内匹配。' },
 ];
 
+export const textMatchModeOptions = [
+  { label: '包含', value: 'contains', description: 'OCR 文本包含匹配内容。' },
+  { label: '正则', value: 'regex', description: '匹配内容作为正则表达式，在归一化后的文本上查找；正则本身不做归一化。' },
+  { label: '模糊', value: 'fuzzy', description: '允许少量误识别，按相似度判断。' },
+];
+
+export const textNormalizeOptions: Array<{ key: keyof TextNormalize; label: string; description: string }> = [
+  { key: 'nfkc', label: 'NFKC', description: 'Unicode 兼容归一化。' },
+  { key: 'foldWidth', label: '全半角', description: '全角字母数字折叠为半角。' },
+  { key: 'foldCase', label: '大小写', description: '忽略大小写。' },
+  { key: 'removeWhitespace', label: '去空白', description: '移除所有空白字符。' },
+  { key: 'foldConfusables', label: '易混字', description: '折叠 0/O、1/l/I 与常见繁体字。' },
+];
+
+export const DEFAULT_FUZZY_SIMILARITY = 0.8;
+
+export const createTextNormalize = (): TextNormalize => ({
+  nfkc: false,
+  foldWidth: false,
+  foldCase: false,
+  removeWhitespace: false,
+  foldConfusables: false,
+});
+
+export const createTextMatchMode = (type: string): TextMatchMode => {
+  switch (type) {
+    case 'regex':
+      return { type: 'regex' };
+    case 'fuzzy':
+      return { type: 'fuzzy', similarity: DEFAULT_FUZZY_SIMILARITY };
+    default:
+      return { type: 'contains' };
+  }
+};
+
 export const createSearchRule = (type: SearchRule['type']): SearchRule => {
   switch (type) {
     case SEARCH_RULE_TYPE.detLabel:
@@ -35,7 +72,12 @@ export const createSearchRule = (type: SearchRule['type']): SearchRule => {
         items: createSearchRuleList(),
       };
     default:
-      return { type: SEARCH_RULE_TYPE.txt, pattern: '' };
+      return {
+        type: SEARCH_RULE_TYPE.txt,
+        pattern: '',
+        mode: createTextMatchMode('contains'),
+        normalize: createTextNormalize(),
+      };
   }
 };
 
@@ -55,7 +97,7 @@ export const ensureRootGroupRule = (rule: SearchRule): SearchRule => {
 export const describeSearchRule = (rule: SearchRule): string => {
   switch (rule.type) {
     case SEARCH_RULE_TYPE.txt:
-      return `文本: ${rule.pattern || '未填写'}`;
+      return `${textMatchModeOptions.find((option) => option.value === rule.mode?.type)?.label ?? '包含'}文本: ${rule.pattern || '未填写'}`;
     case SEARCH_RULE_TYPE.detLabel:
       return `标签索引: ${String(rule.idx)}`;
     case SEARCH_RULE_TYPE.group:
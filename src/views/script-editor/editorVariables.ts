@@ -315,6 +315,22 @@ const collectDerivedRuntimeVariables = (
       continue;
     }
 
+    if (step.op === 'dataHanding' && step.a.type === 'extractNumber') {
+      if (step.a.out_var?.trim()) {
+        const key = step.a.out_var.trim();
+        bucket.set(key, createDerivedRuntimeVariable(key, step.a.kind === 'integer' ? 'int' : 'float', ownerTaskId, step.id));
+      }
+      continue;
+    }
+
+    if (step.op === 'dataHanding' && step.a.type === 'storeGet') {
+      if (step.a.out_var?.trim()) {
+        const key = step.a.out_var.trim();
+        bucket.set(key, createDerivedRuntimeVariable(key, 'json', ownerTaskId, step.id));
+      }
+      continue;
+    }
+
     if (step.op === 'dataHanding' && step.a.type === 'relativeFilter') {
       if (step.a.out_var?.trim()) {
         const key = step.a.out_var.trim();
@@ -336,6 +352,11 @@ const collectDerivedRuntimeVariables = (
         continue;
       }
 
+      if (step.a.type === 'waitUntil') {
+        collectDerivedRuntimeVariables(step.a.else_steps, ownerTaskId, bucket);
+        continue;
+      }
+
       if (step.a.type === 'while' || step.a.type === 'forEach' || step.a.type === 'repeat') {
         collectDerivedRuntimeVariables(step.a.flow, ownerTaskId, bucket);
       }
@@ -243,6 +243,14 @@ export const findStepPathById = (steps: Step[], stepId: string, branch: StepBran
         continue;
       }
 
+      if (step.a.type === 'waitUntil') {
+        const elsePath = findStepPathById(step.a.else_steps, stepId, { parentStepPath: path, branch: 'else' });
+        if (elsePath) {
+          return elsePath;
+        }
+        continue;
+      }
+
       if (step.a.type === 'while' || step.a.type === 'forEach' || step.a.type === 'repeat') {
         const flowPath = findStepPathById(step.a.flow, stepId, { parentStepPath: path, branch: 'flow' });
         if (flowPath) {
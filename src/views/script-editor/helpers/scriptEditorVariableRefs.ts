@@ -25,7 +25,7 @@ const collectConditionVariableReferences = (condition: ConditionNode, bucket: Se
     return;
   }
 
-  if (condition.type === 'visionCountCompare' && condition.input_var?.trim()) {
+  if ((condition.type === 'visionCountCompare' || condition.type === 'ocrNumberCompare') && condition.input_var?.trim()) {
     bucket.add(condition.input_var.trim());
     return;
   }
@@ -103,7 +103,14 @@ export const collectVariableReferencesFromSteps = (steps: Step[], bucket = new S
         continue;
       }
 
-      if (step.a.type === 'colorCompare') {
+      if (step.a.type === 'storeGet') {
+        if (step.a.out_var?.trim()) {
+          bucket.add(step.a.out_var.trim());
+        }
+        continue;
+      }
+
+      if (step.a.type === 'colorCompare' || step.a.type === 'extractNumber') {
         if (step.a.input_var?.trim()) {
           bucket.add(step.a.input_var.trim());
         }
@@ -144,6 +151,16 @@ export const collectVariableReferencesFromSteps = (steps: Step[], bucket = new S
         }
         continue;
       }
+      if (step.a.type === 'waitUntil') {
+        if (step.a.until.type === 'changed' && step.a.until.reference_var?.trim()) {
+          bucket.add(step.a.until.reference_var.trim());
+        }
+        if (step.a.until.type === 'condition') {
+          collectConditionVariableReferences(step.a.until.con, bucket);
+        }
+        collectVariableReferencesFromSteps(step.a.else_steps, bucket);
+        continue;
+      }
       if (step.a.type === 'searchPolicySetText') {
         if (step.a.ocr_input_var?.trim()) {
           bucket.add(step.a.ocr_input_var.trim());
@@ -219,7 +236,7 @@ const collectVariableUsagesFromCondition = (condition: ConditionNode, scopeLabel
     pushVariableUsage(bucket, condition.value.var_name, `${scopeLabel}的执行次数条件`);
     return;
   }
-  if (condition.type === 'visionCountCompare') {
+  if (condition.type === 'visionCountCompare' || condition.type === 'ocrNumberCompare') {
     pushVariableUsage(bucket, condition.input_var, `${scopeLabel}的条件`);
     return;
   }
@@ -279,7 +296,11 @@ export const collectVariableUsagesFromSteps = (steps: Step[], scopeLabel: string
         collectVariableUsagesFromSteps(step.a.then_steps, scopeLabel, bucket);
         continue;
       }
-      if (step.a.type === 'colorCompare') {
+      if (step.a.type === 'storeGet') {
+        pushVariableUsage(bucket, step.a.out_var, stepLabel);
+        continue;
+      }
+      if (step.a.type === 'colorCompare' || step.a.type === 'extractNumber') {
         pushVariableUsage(bucket, step.a.input_var, stepLabel);
         pushVariableUsage(bucket, step.a.out_var, stepLabel);
         continue;
@@ -302,6 +323,16 @@ export const collectVariableUsagesFromSteps = (steps: Step[], scopeLabel: string
         pushVariableUsage(bucket, step.a.runtime_var, stepLabel);
         continue;
       }
+      if (step.a.type === 'waitUntil') {
+        if (step.a.until.type === 'changed') {
+          pushVariableUsage(bucket, step.a.until.reference_var, `${stepLabel}的参考图`);
+        }
+        if (step.a.until.type === 'condition') {
+          collectVariableUsagesFromCondition(step.a.until.con, stepLabel, bucket);
+        }
+        collectVariableUsagesFromSteps(step.a.else_steps, scopeLabel, bucket);
+        continue;
+      }
       if (step.a.type === 'searchPolicySetText') {
         pushVariableUsage(bucket, step.a.ocr_input_var, `${stepLabel}的OCR输入`);
         pushVariableUsage(bucket, step.a.out_var, stepLabel);
@@ -396,7 +427,7 @@ const renameConditionVariableReferences = (condition: ConditionNode, previousKey
     return nextCondition;
   }
 
-  if (nextCondition.type === 'visionCountCompare' && nextCondition.input_var === previousKey) {
+  if ((nextCondition.type === 'visionCountCompare' || nextCondition.type === 'ocrNumberCompare') && nextCondition.input_var === previousKey) {
     nextCondition.input_var = nextKey;
     return nextCondition;
   }
@@ -440,7 +471,14 @@ export const renameVariableReferencesInSteps = (steps: Step[], previousKey: stri
         return nextStep;
       }
 
-      if (nextStep.a.type === 'colorCompare') {
+      if (nextStep.a.type === 'storeGet') {
+        if (nextStep.a.out_var === previousKey) {
+          nextStep.a.out_var = nextKey;
+        }
+        return nextStep;
+      }
+
+      if (nextStep.a.type === 'colorCompare' || nextStep.a.type === 'extractNumber') {
         if (nextStep.a.input_var === previousKey) {
           nextStep.a.input_var = nextKey;
         }
@@ -458,6 +496,17 @@ export const renameVariableReferencesInSteps = (steps: Step[], previousKey: stri
         nextStep.a.con = renameConditionVariableReferences(nextStep.a.con, previousKey, nextKey);
       }
 
+      if (nextStep.a.type === 'waitUntil') {
+        if (nextStep.a.until.type === 'changed' && nextStep.a.until.reference_var === previousKey) {
+          nextStep.a.until.reference_var = nextKey;
+        }
+        if (nextStep.a.until.type === 'condition') {
+          nextStep.a.until.con = renameConditionVariableReferences(nextStep.a.until.con, previousKey, nextKey);
+        }
+        nextStep.a.else_steps = renameVariableReferencesInSteps(nextStep.a.else_steps, previousKey, nextKey);
+        return nextStep;
+      }
+
       if (nextStep.a.type === 'handlePolicySet') {
         if (nextStep.a.det_input_var === previousKey) {
           nextStep.a.det_input_var = nextKey;
@@ -111,6 +111,7 @@ const seedPublishedScript = async (
             minRuntimeSchema: 1,
             requiredFeatures: ['onnxInference', 'runtime:rhai', 'device:android'],
             variableCatalog: emptyVariableCatalog,
+            rhaiModules: [],
             cloudId: 'cloud-script-1',
             runtimeSettings: {
               recoveryTaskId: null,